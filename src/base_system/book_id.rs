@@ -7,22 +7,23 @@ static RE_URL: OnceLock<Regex> = OnceLock::new();
 static RE_QS: OnceLock<Regex> = OnceLock::new();
 static RE_PAGE: OnceLock<Regex> = OnceLock::new();
 static RE_SHORT_LINK: OnceLock<Regex> = OnceLock::new();
+static RE_SHARE_ID: OnceLock<Regex> = OnceLock::new();
+static RE_LONG_DIGITS: OnceLock<Regex> = OnceLock::new();
+static RE_SHARE_TITLE: OnceLock<Regex> = OnceLock::new();
 static HTTP_CLIENT: OnceLock<reqwest::blocking::Client> = OnceLock::new();
 
 /// Known domains that issue short-link share URLs of the form `/t/<token>`.
 /// The token is URL-safe and may contain `_` / `-` in addition to letters and digits.
-/// Only these hosts are followed during redirect resolution to prevent SSRF.
-const ALLOWED_SHORT_LINK_HOSTS: &[&str] = &[
-    "changdunovel.com",
-    "www.changdunovel.com",
-    "fanqienovel.com",
-    "www.fanqienovel.com",
-    "fqnovel.com",
-    "www.fqnovel.com",
-];
+/// Only these hosts (and their subdomains, e.g. `m.fanqienovel.com`) are
+/// followed during redirect resolution to prevent SSRF.
+const ALLOWED_SHORT_LINK_HOSTS: &[&str] = &["changdunovel.com", "fanqienovel.com", "fqnovel.com"];
+
+/// 短链接跳转的总超时；解析失败时调用方回退到搜索，不宜等太久。
+const SHORT_LINK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(8);
 
 fn re_url() -> &'static Regex {
-    RE_URL.get_or_init(|| Regex::new(r"https?://\S+").expect("compile RE_URL"))
+    // 只取 ASCII 可见字符，避免口令里紧跟链接的中文被并入 URL。
+    RE_URL.get_or_init(|| Regex::new(r"https?://[!-~]+").expect("compile RE_URL"))
 }
 
 fn re_qs() -> &'static Regex {
@@ -40,10 +41,30 @@ fn re_short_link() -> &'static Regex {
     })
 }
 
+/// 分享口令里显式标注的书号，如 `书号：7423591956359416856`。
+fn re_share_id() -> &'static Regex {
+    RE_SHARE_ID.get_or_init(|| {
+        Regex::new(r"(?i)(?:书号|书籍\s*ID|book_?id)\s*[:：=#]?\s*([0-9]{6,})")
+            .expect("compile RE_SHARE_ID")
+    })
+}
+
+/// 口令中裸露的长数字串（番茄 book_id 为 15~20 位）。
+fn re_long_digits() -> &'static Regex {
+    RE_LONG_DIGITS.get_or_init(|| {
+        Regex::new(r"(?:^|[^0-9])([0-9]{15,20})(?:[^0-9]|$)").expect("compile RE_LONG_DIGITS")
+    })
+}
+
+fn re_share_title() -> &'static Regex {
+    RE_SHARE_TITLE.get_or_init(|| Regex::new(r"《([^《》]+)》").expect("compile RE_SHARE_TITLE"))
+}
+
 fn http_client() -> &'static reqwest::blocking::Client {
     HTTP_CLIENT.get_or_init(|| {
         reqwest::blocking::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
+            .timeout(SHORT_LINK_TIMEOUT)
+            .connect_timeout(std::time::Duration::from_secs(5))
             .build()
             .expect("build HTTP client for short-link resolution")
     })
@@ -85,7 +106,42 @@ pub fn parse_book_id(input: &str) -> Option<String> {
         return caps.get(1).map(|m| m.as_str().to_string());
     }
 
-    None
+    parse_share_code(trimmed)
+}
+
+/// 从 App 分享口令文本中提取 book_id：优先显式书号，其次 URL 之外的长数字串。
+fn parse_share_code(text: &str) -> Option<String> {
+    if let Some(caps) = re_share_id().captures(text) {
+        return caps.get(1).map(|m| m.as_str().to_string());
+    }
+    let without_urls = re_url().replace_all(text, " ");
+    re_long_digits()
+        .captures(&without_urls)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
+}
+
+/// 分享口令无法解析出 book_id 时用于回退搜索的关键词：优先《书名》，
+/// 否则取去掉链接后的剩余文本。
+pub fn share_search_keyword(input: &str) -> Option<String> {
+    if let Some(caps) = re_share_title().captures(input) {
+        let title = caps[1].trim();
+        if !title.is_empty() {
+            return Some(title.to_string());
+        }
+    }
+    let rest = re_url().replace_all(input, " ");
+    let rest = rest.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!rest.is_empty()).then_some(rest)
+}
+
+fn is_allowed_short_link_host(host: &str) -> bool {
+    ALLOWED_SHORT_LINK_HOSTS.iter().any(|allowed| {
+        host == *allowed
+            || host
+                .strip_suffix(allowed)
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    })
 }
 
 /// Returns `true` if `input` contains a short-redirect share link from a
@@ -100,7 +156,7 @@ pub fn is_short_link(input: &str) -> bool {
         return false;
     }
     url_host(target)
-        .map(|h| ALLOWED_SHORT_LINK_HOSTS.contains(&h.as_str()))
+        .map(|h| is_allowed_short_link_host(&h))
         .unwrap_or(false)
 }
 
@@ -126,7 +182,11 @@ pub fn resolve_book_id(input: &str) -> Option<String> {
     let response = match http_client().get(url).send() {
         Ok(r) => r,
         Err(e) => {
-            tracing::warn!(url = %url, error = %e, "短链接跳转失败");
+            if e.is_timeout() {
+                tracing::warn!(url = %url, "短链接跳转超时");
+            } else {
+                tracing::warn!(url = %url, error = %e, "短链接跳转失败");
+            }
             return None;
         }
     };
@@ -141,7 +201,7 @@ pub fn resolve_book_id(input: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{is_short_link, parse_book_id};
+    use super::{is_short_link, parse_book_id, share_search_keyword};
 
     #[test]
     fn parse_plain_numeric_book_id() {
@@ -170,5 +230,29 @@ mod tests {
     #[test]
     fn reject_short_link_from_unknown_host() {
         assert!(!is_short_link("https://example.com/t/E_HDbOHpMJA/"));
+        assert!(!is_short_link("https://evilfanqienovel.com/t/E_HDbOHpMJA/"));
+    }
+
+    #[test]
+    fn recognize_short_link_on_subdomain() {
+        assert!(is_short_link("https://m.fanqienovel.com/t/E_HDbOHpMJA/"));
+    }
+
+    #[test]
+    fn parse_book_id_from_share_code() {
+        let text = "【番茄小说】《我的书》书号：7423591956359416856，复制口令打开番茄小说";
+        assert_eq!(parse_book_id(text), Some("7423591956359416856".into()));
+        let bare = "推荐《我的书》7423591956359416856 快来看";
+        assert_eq!(parse_book_id(bare), Some("7423591956359416856".into()));
+    }
+
+    #[test]
+    fn share_keyword_prefers_book_title() {
+        let text = "快来读《我的书》 https://changdunovel.com/t/E_HDbOHpMJA/";
+        assert_eq!(share_search_keyword(text), Some("我的书".into()));
+        assert_eq!(
+            share_search_keyword("https://changdunovel.com/t/E_HDbOHpMJA/"),
+            None
+        );
     }
 }
@@ -0,0 +1,217 @@
+//! 书库探索：按分类浏览番茄网页榜单。
+//!
+//! 输入 `榜:分类 [页码]`（新书榜用 `新书榜:分类`）即可拉取对应分类的榜单，
+//! 结果复用搜索结果列表与预览/下载流程。
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use super::json_extract;
+use crate::network_parser::network::{FanqieWebConfig, FanqieWebNetwork};
+
+/// 每页条数。
+pub const PAGE_SIZE: usize = 30;
+
+/// TUI 输入框中展示的语法提示。
+pub const BROWSE_SYNTAX_HINT: &str = "榜:分类 [页码]，如 榜:玄幻 2";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RankCategory {
+    pub name: &'static str,
+    /// 1 男频，0 女频
+    pub gender: u8,
+    pub id: u32,
+}
+
+const fn male(name: &'static str, id: u32) -> RankCategory {
+    RankCategory {
+        name,
+        gender: 1,
+        id,
+    }
+}
+
+const fn female(name: &'static str, id: u32) -> RankCategory {
+    RankCategory {
+        name,
+        gender: 0,
+        id,
+    }
+}
+
+/// 分类与榜单 ID，对应 fanqienovel.com/rank 页面的分类。
+pub const RANK_CATEGORIES: &[RankCategory] = &[
+    male("都市日常", 261),
+    male("都市修真", 124),
+    male("都市高武", 1014),
+    male("都市种田", 263),
+    male("都市脑洞", 262),
+    male("传统玄幻", 258),
+    male("玄幻脑洞", 257),
+    male("东方仙侠", 1140),
+    male("西方奇幻", 1141),
+    male("历史古代", 273),
+    male("历史脑洞", 272),
+    male("科幻末世", 8),
+    male("悬疑灵异", 751),
+    male("悬疑脑洞", 539),
+    male("战神赘婿", 27),
+    male("抗战谍战", 504),
+    male("游戏体育", 746),
+    male("动漫衍生", 718),
+    male("男频衍生", 1016),
+    female("古风世情", 1139),
+    female("宫斗宅斗", 246),
+    female("玄幻言情", 248),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BrowseQuery {
+    pub category: RankCategory,
+    /// 从 1 开始
+    pub page: usize,
+    pub new_book: bool,
+}
+
+impl BrowseQuery {
+    pub fn with_page(self, page: usize) -> Self {
+        Self {
+            page: page.max(1),
+            ..self
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let rank = if self.new_book {
+            "新书榜"
+        } else {
+            "阅读榜"
+        };
+        format!("{}·{} 第 {} 页", self.category.name, rank, self.page)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LibraryBook {
+    pub book_id: String,
+    pub title: String,
+    pub author: String,
+    pub raw: Value,
+}
+
+/// 可用分类名（顿号分隔），用于错误提示。
+pub fn category_names() -> String {
+    RANK_CATEGORIES
+        .iter()
+        .map(|c| c.name)
+        .collect::<Vec<_>>()
+        .join("、")
+}
+
+/// 按名称查找分类：先精确匹配，再包含匹配（如 `玄幻` -> `传统玄幻`）。
+pub fn find_category(name: &str) -> Option<RankCategory> {
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    RANK_CATEGORIES
+        .iter()
+        .find(|c| c.name == name)
+        .or_else(|| RANK_CATEGORIES.iter().find(|c| c.name.contains(name)))
+        .copied()
+}
+
+/// 解析浏览语法；不是浏览语法时返回 `None`，分类无效时返回 `Some(Err)`。
+pub fn parse_browse_query(input: &str) -> Option<Result<BrowseQuery, String>> {
+    let input = input.trim();
+    let (rest, new_book) = ["新书榜:", "新书榜：", "newrank:"]
+        .iter()
+        .find_map(|p| strip_prefix_ci(input, p).map(|r| (r, true)))
+        .or_else(|| {
+            ["榜:", "榜：", "rank:"]
+                .iter()
+                .find_map(|p| strip_prefix_ci(input, p).map(|r| (r, false)))
+        })?;
+
+    let mut words = rest.split_whitespace();
+    let name = words.next().unwrap_or("");
+    let page = match words.next().map(str::parse::<usize>) {
+        None => 1,
+        Some(Ok(n)) if n >= 1 => n,
+        Some(_) => return Some(Err("页码需为正整数".to_string())),
+    };
+    Some(match find_category(name) {
+        Some(category) => Ok(BrowseQuery {
+            category,
+            page,
+            new_book,
+        }),
+        None => Err(format!("未知分类“{}”，可选：{}", name, category_names())),
+    })
+}
+
+fn strip_prefix_ci<'a>(input: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = input.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        Some(&input[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+/// 拉取一页榜单。
+pub fn fetch_rank_page(query: &BrowseQuery) -> Result<Vec<LibraryBook>> {
+    let web = FanqieWebNetwork::new(FanqieWebConfig::default()).context("init FanqieWebNetwork")?;
+    let offset = (query.page.max(1) - 1) * PAGE_SIZE;
+    let items = web.fetch_category_rank(
+        query.category.gender,
+        query.category.id,
+        query.new_book,
+        offset,
+        PAGE_SIZE,
+    )?;
+    Ok(items.iter().filter_map(parse_rank_item).collect())
+}
+
+fn parse_rank_item(v: &Value) -> Option<LibraryBook> {
+    let map = v.as_object()?;
+    let book_id = json_extract::pick_string(map, &["bookId", "book_id"])?;
+    let title = json_extract::pick_string(map, &["bookName", "book_name", "title"])
+        .unwrap_or_else(|| book_id.clone());
+    let author = json_extract::pick_string(map, &["author", "authorName", "author_name"])
+        .unwrap_or_default();
+    Some(LibraryBook {
+        book_id,
+        title,
+        author,
+        raw: v.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_browse_query_and_rank_item() {
+        let q = parse_browse_query("榜:玄幻 2").unwrap().unwrap();
+        assert_eq!(q.category.name, "传统玄幻");
+        assert_eq!(q.page, 2);
+        assert!(!q.new_book);
+
+        let q = parse_browse_query("新书榜：都市日常").unwrap().unwrap();
+        assert_eq!(q.category.id, 261);
+        assert!(q.new_book);
+        assert_eq!(q.with_page(0).page, 1);
+
+        assert!(parse_browse_query("RANK:不存在").unwrap().is_err());
+        assert!(parse_browse_query("榜:玄幻 0").unwrap().is_err());
+        assert!(parse_browse_query("诡秘之主").is_none());
+
+        let item = serde_json::json!({"bookId": "7143038691944959011", "bookName": "全球尸化", "author": "某某"});
+        let book = parse_rank_item(&item).unwrap();
+        assert_eq!(book.book_id, "7143038691944959011");
+        assert_eq!(book.title, "全球尸化");
+        assert!(parse_rank_item(&serde_json::json!({"bookName": "x"})).is_none());
+    }
+}
@@ -5,6 +5,8 @@ use std::path::{Path, PathBuf};
 use crate::base_system::context::{Config, safe_fs_name};
 
 pub const COVER_FILE_STEM: &str = "cover";
+/// `prefer_hd_cover` 下保存的高清原图（`cover.*` 为其压缩版）。
+pub const HD_COVER_FILE_STEM: &str = "cover_hd";
 pub const COVER_IMAGE_EXTENSIONS: [&str; 4] = ["jpg", "jpeg", "png", "webp"];
 
 /// 缓存目录名只使用稳定的 `book_id`。
@@ -15,9 +17,15 @@ pub fn book_folder_name(book_id: &str, _book_name: Option<&str>) -> String {
     safe_fs_name(book_id, "_", 120)
 }
 
-pub fn book_folder_path(config: &Config, book_id: &str, book_name: Option<&str>) -> PathBuf {
-    config
-        .default_save_dir()
+/// 书籍缓存目录；`root` 为按 `save_path_rules` 分流得到的保存根目录，缺省时用 `save_path`。
+pub fn book_folder_path(
+    config: &Config,
+    book_id: &str,
+    book_name: Option<&str>,
+    root: Option<&Path>,
+) -> PathBuf {
+    root.map(Path::to_path_buf)
+        .unwrap_or_else(|| config.default_save_dir())
         .join(book_folder_name(book_id, book_name))
 }
 
@@ -32,6 +40,17 @@ pub fn canonical_cover_path(folder: &Path, ext: &str) -> PathBuf {
     folder.join(format!("{COVER_FILE_STEM}.{ext}"))
 }
 
+pub fn hd_cover_path(folder: &Path, ext: &str) -> PathBuf {
+    folder.join(format!("{HD_COVER_FILE_STEM}.{ext}"))
+}
+
+pub fn find_existing_hd_cover_file(folder: &Path) -> Option<PathBuf> {
+    COVER_IMAGE_EXTENSIONS
+        .iter()
+        .map(|ext| hd_cover_path(folder, ext))
+        .find(|p| p.exists())
+}
+
 pub fn cover_file_candidates(folder: &Path, book_name: Option<&str>) -> Vec<PathBuf> {
     let mut candidates = Vec::new();
     for ext in &COVER_IMAGE_EXTENSIONS {
@@ -3,11 +3,27 @@
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use serde_yaml::Value;
 use thiserror::Error;
+use tracing::{info, warn};
+
+/// 环境变量覆盖前缀：`TOMATO_` + 大写字段名，如 `TOMATO_MAX_WORKERS=8`。
+pub const ENV_PREFIX: &str = "TOMATO_";
+
+/// 最近一次加载时被环境变量覆盖的字段，写回文件时用于还原文件中的原值。
+static ENV_OVERRIDES: Mutex<Vec<EnvOverride>> = Mutex::new(Vec::new());
+
+/// 一个被环境变量覆盖的字段：文件（或默认）中的原值与覆盖后的值。
+#[derive(Debug, Clone)]
+struct EnvOverride {
+    field: String,
+    file_value: Value,
+    env_value: Value,
+}
 
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -57,7 +73,7 @@ pub fn load_or_create_with_base<T: ConfigSpec>(
     if !path.exists() {
         let default_config = T::default();
         write_with_comments(&default_config, &path)?;
-        return Ok(default_config);
+        return Ok(apply_env_overrides(default_config));
     }
 
     let raw = fs::read_to_string(&path).map_err(|source| ConfigError::Io {
@@ -81,7 +97,172 @@ pub fn load_or_create_with_base<T: ConfigSpec>(
         write_with_comments(&config, &path)?;
     }
 
-    Ok(config)
+    // 环境覆盖只作用于内存中的配置，不回写文件
+    Ok(apply_env_overrides(config))
+}
+
+/// 用 `TOMATO_<FIELD>` 环境变量覆盖配置字段；非法值记录 warning 后忽略。
+///
+/// 被覆盖的字段会记下文件中的原值，`write_with_comments` 写回时还原，
+/// 避免凭据等覆盖值在 TUI/Web 保存设置时落盘。
+pub fn apply_env_overrides<T: ConfigSpec>(config: T) -> T {
+    let mut overrides = Vec::new();
+    let config = apply_overrides_recording(config, |key| std::env::var(key).ok(), &mut overrides);
+    *ENV_OVERRIDES.lock().unwrap_or_else(|e| e.into_inner()) = overrides;
+    config
+}
+
+#[cfg(test)]
+fn apply_overrides_with<T: ConfigSpec>(config: T, lookup: impl Fn(&str) -> Option<String>) -> T {
+    apply_overrides_recording(config, lookup, &mut Vec::new())
+}
+
+fn apply_overrides_recording<T: ConfigSpec>(
+    config: T,
+    lookup: impl Fn(&str) -> Option<String>,
+    overrides: &mut Vec<EnvOverride>,
+) -> T {
+    let Ok(Value::Mapping(mut map)) = serde_yaml::to_value(&config) else {
+        return config;
+    };
+    let original = map.clone();
+
+    let mut applied = Vec::new();
+    let mut applied_fields = Vec::new();
+    for field in T::fields() {
+        let env_key = format!("{}{}", ENV_PREFIX, field.name.to_ascii_uppercase());
+        let Some(raw) = lookup(&env_key) else {
+            continue;
+        };
+        let key = Value::String(field.name.to_string());
+        let current = map.get(&key).cloned().unwrap_or(Value::Null);
+        let Some(parsed) = parse_env_value(&current, &raw) else {
+            warn!(target: "config", key = %env_key, value = %raw, "环境变量值无法解析，已忽略");
+            continue;
+        };
+
+        // 逐字段校验，确保单个非法值（如超出类型范围）不影响其他覆盖
+        let mut candidate = map.clone();
+        candidate.insert(key, parsed);
+        match serde_yaml::from_value::<T>(Value::Mapping(candidate.clone())) {
+            Ok(_) => {
+                map = candidate;
+                applied.push(env_key);
+                applied_fields.push(field.name);
+            }
+            Err(err) => {
+                warn!(target: "config", key = %env_key, value = %raw, error = %err, "环境变量值类型不匹配，已忽略");
+            }
+        }
+    }
+
+    if applied.is_empty() {
+        return config;
+    }
+    match serde_yaml::from_value::<T>(Value::Mapping(map)) {
+        Ok(overridden) => {
+            info!(target: "config", keys = %applied.join(","), "已应用环境变量配置覆盖");
+            // 以反序列化后再序列化的值为准，写回时才能与内存中的值逐字比较
+            if let Ok(Value::Mapping(final_map)) = serde_yaml::to_value(&overridden) {
+                for name in applied_fields {
+                    let key = Value::String(name.to_string());
+                    overrides.push(EnvOverride {
+                        field: name.to_string(),
+                        file_value: original.get(&key).cloned().unwrap_or(Value::Null),
+                        env_value: final_map.get(&key).cloned().unwrap_or(Value::Null),
+                    });
+                }
+            }
+            overridden
+        }
+        Err(_) => config,
+    }
+}
+
+/// 写回前还原仍保持环境变量覆盖值的字段；用户在界面里改过的字段按新值保存。
+fn restore_env_overridden(mapping: &mut serde_yaml::Mapping, overrides: &[EnvOverride]) {
+    for ov in overrides {
+        let key = Value::String(ov.field.clone());
+        if mapping.get(&key) == Some(&ov.env_value) {
+            mapping.insert(key, ov.file_value.clone());
+        }
+    }
+}
+
+/// 按字段当前值的类型解析环境变量字符串。
+fn parse_env_value(current: &Value, raw: &str) -> Option<Value> {
+    let trimmed = raw.trim();
+    match current {
+        Value::Bool(_) => match trimmed.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => Some(Value::Bool(true)),
+            "0" | "false" | "no" | "off" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        Value::Number(n) if n.is_f64() => trimmed.parse::<f64>().ok().map(Value::from),
+        Value::Number(_) => trimmed
+            .parse::<u64>()
+            .map(Value::from)
+            .or_else(|_| trimmed.parse::<i64>().map(Value::from))
+            .ok(),
+        Value::String(_) => Some(Value::String(raw.to_string())),
+        Value::Sequence(_) if trimmed.starts_with('[') => serde_yaml::from_str(trimmed)
+            .ok()
+            .filter(Value::is_sequence),
+        // 列表字段支持逗号分隔的简写
+        Value::Sequence(_) => Some(Value::Sequence(
+            trimmed
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| Value::String(s.to_string()))
+                .collect(),
+        )),
+        _ => serde_yaml::from_str(trimmed).ok(),
+    }
+}
+
+/// 将单个字段恢复为 `T::default()` 中的值。
+pub fn reset_field<T: ConfigSpec>(config: &T, name: &str) -> Result<T, ConfigError> {
+    if !T::fields().iter().any(|f| f.name == name) {
+        return Err(ConfigError::Validation(format!("未知配置项: {name}")));
+    }
+    let (mut current, defaults) = config_mappings(config)?;
+    let key = Value::String(name.to_string());
+    match defaults.get(&key) {
+        Some(v) => current.insert(key, v.clone()),
+        None => current.remove(&key),
+    };
+    serde_yaml::from_value(Value::Mapping(current))
+        .map_err(|err| ConfigError::Validation(err.to_string()))
+}
+
+/// 全部字段恢复默认，`keep` 中的字段保留当前值。
+pub fn reset_all<T: ConfigSpec>(config: &T, keep: &[&str]) -> Result<T, ConfigError> {
+    let (current, mut defaults) = config_mappings(config)?;
+    for name in keep {
+        let key = Value::String((*name).to_string());
+        if let Some(v) = current.get(&key) {
+            defaults.insert(key, v.clone());
+        }
+    }
+    serde_yaml::from_value(Value::Mapping(defaults))
+        .map_err(|err| ConfigError::Validation(err.to_string()))
+}
+
+fn config_mappings<T: ConfigSpec>(
+    config: &T,
+) -> Result<(serde_yaml::Mapping, serde_yaml::Mapping), ConfigError> {
+    let to_mapping = |value: Result<Value, serde_yaml::Error>| match value {
+        Ok(Value::Mapping(map)) => Ok(map),
+        Ok(_) => Err(ConfigError::Validation(
+            "config must serialize to a mapping".to_string(),
+        )),
+        Err(err) => Err(ConfigError::Validation(err.to_string())),
+    };
+    Ok((
+        to_mapping(serde_yaml::to_value(config))?,
+        to_mapping(serde_yaml::to_value(T::default()))?,
+    ))
 }
 
 pub fn write_with_comments<T: ConfigSpec>(config: &T, path: &Path) -> Result<(), ConfigError> {
@@ -96,7 +277,7 @@ pub fn write_with_comments<T: ConfigSpec>(config: &T, path: &Path) -> Result<(),
 pub fn generate_yaml_with_comments<T: ConfigSpec>(config: &T) -> Result<String, ConfigError> {
     let value =
         serde_yaml::to_value(config).map_err(|err| ConfigError::Validation(err.to_string()))?;
-    let mapping = match value {
+    let mut mapping = match value {
         Value::Mapping(map) => map,
         _ => {
             return Err(ConfigError::Validation(
@@ -104,6 +285,10 @@ pub fn generate_yaml_with_comments<T: ConfigSpec>(config: &T) -> Result<String,
             ));
         }
     };
+    restore_env_overridden(
+        &mut mapping,
+        &ENV_OVERRIDES.lock().unwrap_or_else(|e| e.into_inner()),
+    );
 
     let mut lines = Vec::new();
     for field in T::fields() {
@@ -182,3 +367,125 @@ fn ensure_parent(path: &Path) -> Result<(), ConfigError> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    struct Demo {
+        workers: u8,
+        ratio: f64,
+        enabled: bool,
+        format: String,
+        domains: Vec<String>,
+    }
+
+    impl ConfigSpec for Demo {
+        const FILE_NAME: &'static str = "demo.yml";
+        fn fields() -> &'static [FieldMeta] {
+            static FIELDS: [FieldMeta; 5] = [
+                FieldMeta {
+                    name: "workers",
+                    description: "",
+                },
+                FieldMeta {
+                    name: "ratio",
+                    description: "",
+                },
+                FieldMeta {
+                    name: "enabled",
+                    description: "",
+                },
+                FieldMeta {
+                    name: "format",
+                    description: "",
+                },
+                FieldMeta {
+                    name: "domains",
+                    description: "",
+                },
+            ];
+            &FIELDS
+        }
+    }
+
+    #[test]
+    fn reset_field_and_all_restore_defaults() {
+        let demo = Demo {
+            workers: 8,
+            enabled: true,
+            format: "epub".to_string(),
+            ..Demo::default()
+        };
+        let out = reset_field(&demo, "workers").unwrap();
+        assert_eq!(out.workers, 0);
+        assert!(out.enabled);
+        assert!(reset_field(&demo, "missing").is_err());
+
+        let out = reset_all(&demo, &["format"]).unwrap();
+        assert_eq!(out.workers, 0);
+        assert!(!out.enabled);
+        assert_eq!(out.format, "epub");
+    }
+
+    #[test]
+    fn env_overrides_parse_by_type_and_skip_invalid() {
+        let demo = Demo {
+            workers: 4,
+            ratio: 1.5,
+            ..Demo::default()
+        };
+        let out = apply_overrides_with(demo, |key| match key {
+            "TOMATO_WORKERS" => Some("999".to_string()),
+            "TOMATO_RATIO" => Some("2.25".to_string()),
+            "TOMATO_ENABLED" => Some("yes".to_string()),
+            "TOMATO_FORMAT" => Some("epub".to_string()),
+            "TOMATO_DOMAINS" => Some("a.com, b.com".to_string()),
+            _ => None,
+        });
+        // 999 超出 u8 范围，保留原值
+        assert_eq!(out.workers, 4);
+        assert_eq!(out.ratio, 2.25);
+        assert!(out.enabled);
+        assert_eq!(out.format, "epub");
+        assert_eq!(out.domains, vec!["a.com", "b.com"]);
+
+        let out = apply_overrides_with(out, |key| {
+            (key == "TOMATO_ENABLED").then(|| "maybe".to_string())
+        });
+        assert!(out.enabled);
+    }
+
+    #[test]
+    fn env_overrides_are_not_written_back() {
+        let demo = Demo {
+            workers: 4,
+            format: "txt".to_string(),
+            ..Demo::default()
+        };
+        let mut overrides = Vec::new();
+        let mut out = apply_overrides_recording(
+            demo,
+            |key| match key {
+                "TOMATO_WORKERS" => Some("8".to_string()),
+                "TOMATO_FORMAT" => Some("epub".to_string()),
+                _ => None,
+            },
+            &mut overrides,
+        );
+        assert_eq!(out.workers, 8);
+        assert_eq!(overrides.len(), 2);
+
+        // 界面里改过的字段按新值保存，未改动的覆盖值还原为文件原值
+        out.format = "pdf".to_string();
+        let Ok(Value::Mapping(mut mapping)) = serde_yaml::to_value(&out) else {
+            panic!("demo must serialize to a mapping");
+        };
+        restore_env_overridden(&mut mapping, &overrides);
+        let saved: Demo = serde_yaml::from_value(Value::Mapping(mapping)).unwrap();
+        assert_eq!(saved.workers, 4);
+        assert_eq!(saved.format, "pdf");
+    }
+}
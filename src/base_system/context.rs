@@ -10,31 +10,84 @@ use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 
 use super::config::{ConfigSpec, FieldMeta};
+use super::file_cleaner::DumpKind;
 
 pub const OUTPUT_FORMAT_TXT: &str = "txt";
 pub const OUTPUT_FORMAT_EPUB: &str = "epub";
 pub const OUTPUT_FORMAT_PDF: &str = "pdf";
 pub const OUTPUT_FORMAT_BULK_TXT: &str = "bulk_txt";
+/// `bulk_name_template` 支持的占位符。
+pub const BULK_NAME_PLACEHOLDERS: &[&str] = &["index", "index_padded", "title", "book_name"];
+/// 同时输出 TXT 与 EPUB；`novel_format` 也接受任意逗号分隔的组合，如 `epub,pdf`。
+pub const OUTPUT_FORMAT_TXT_EPUB: &str = "txt,epub";
 pub const OUTPUT_FORMAT_ASK_AFTER_DOWNLOAD: &str = "ask_after_download";
 
+/// 官方批量接口单次最多请求的章节数。
+pub const MAX_BATCH_SIZE: usize = 25;
+
+/// "全部恢复默认"时保留当前值的字段（保存路径等关键路径与凭据）。
+pub const RESET_PRESERVED_FIELDS: &[&str] = &[
+    "save_path",
+    "save_path_rules",
+    "replacements_path",
+    "epub_embed_font_path",
+    "pdf_font_path",
+    "webdav_url",
+    "webdav_username",
+    "webdav_password",
+    "audiobook_tts_api_url",
+    "audiobook_tts_api_token",
+];
+
 pub fn output_format_choices() -> &'static [(&'static str, &'static str)] {
-    static CHOICES: [(&str, &str); 5] = [
+    static CHOICES: [(&str, &str); 6] = [
         (OUTPUT_FORMAT_TXT, "txt 格式"),
         (OUTPUT_FORMAT_EPUB, "epub 格式"),
         (OUTPUT_FORMAT_PDF, "pdf 格式"),
+        (OUTPUT_FORMAT_TXT_EPUB, "txt + epub 格式"),
         (OUTPUT_FORMAT_BULK_TXT, "散装文件"),
         (OUTPUT_FORMAT_ASK_AFTER_DOWNLOAD, "下载后选择"),
     ];
     &CHOICES
 }
 
-pub fn output_format_label(choice: &str) -> &'static str {
+pub fn output_format_label(choice: &str) -> String {
     let normalized = choice.trim().to_ascii_lowercase();
-    output_format_choices()
+    if let Some((_, label)) = output_format_choices()
         .iter()
         .find(|(value, _)| *value == normalized)
-        .map(|(_, label)| *label)
-        .unwrap_or("txt 格式")
+    {
+        return label.to_string();
+    }
+    let formats = parse_output_formats(&normalized);
+    if formats.len() > 1 {
+        return format!("{} 格式", formats.join(" + "));
+    }
+    "txt 格式".to_string()
+}
+
+/// 把 `novel_format` 解析为去重后的格式列表（按 txt/epub/pdf 固定顺序），忽略未知项。
+pub fn parse_output_formats(raw: &str) -> Vec<&'static str> {
+    let requested: Vec<String> = raw
+        .split([',', '+', ' ', '，'])
+        .map(|s| s.trim().to_ascii_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect();
+    [OUTPUT_FORMAT_TXT, OUTPUT_FORMAT_EPUB, OUTPUT_FORMAT_PDF]
+        .into_iter()
+        .filter(|fmt| requested.iter().any(|r| r == fmt))
+        .collect()
+}
+
+/// 解析 `分类=路径;分类=路径`（全角分号/等号亦可），忽略无效条目。
+pub fn parse_save_path_rules(raw: &str) -> Vec<(String, String)> {
+    raw.split([';', '；'])
+        .filter_map(|rule| {
+            let (key, path) = rule.split_once(['=', '＝'])?;
+            let (key, path) = (key.trim(), path.trim());
+            (!key.is_empty() && !path.is_empty()).then(|| (key.to_string(), path.to_string()))
+        })
+        .collect()
 }
 
 pub fn output_format_value_from_label(label: &str) -> Option<&'static str> {
@@ -53,6 +106,8 @@ pub struct Config {
     // 网络配置
     #[serde(default = "default_max_workers")]
     pub max_workers: usize,
+    #[serde(default = "default_batch_size", alias = "chapters_per_request")]
+    pub batch_size: usize,
     #[serde(default = "default_request_timeout")]
     pub request_timeout: u64,
     #[serde(default = "default_max_retries")]
@@ -61,8 +116,28 @@ pub struct Config {
     pub max_wait_time: u64,
     #[serde(default = "default_min_wait_time")]
     pub min_wait_time: u64,
+    #[serde(default = "default_wait_jitter_ratio")]
+    pub wait_jitter_ratio: u8,
     #[serde(default = "default_min_connect_timeout")]
     pub min_connect_timeout: f64,
+    #[serde(default = "default_true")]
+    pub fast_switch_on_4xx: bool,
+    #[serde(default = "default_timeout_retry_strategy")]
+    pub timeout_retry_strategy: String,
+    #[serde(default, alias = "max_download_kbps")]
+    pub max_bandwidth_kbps: u64,
+    #[serde(default = "default_false")]
+    pub block_on_incomplete_catalog: bool,
+    #[serde(default)]
+    pub skip_title_patterns: Vec<String>,
+    #[serde(default = "default_false")]
+    pub detect_modified_chapters: bool,
+    #[serde(default)]
+    pub book_download_timeout_secs: u64,
+    #[serde(default = "default_false")]
+    pub wait_for_network: bool,
+    #[serde(default = "default_chapter_length_alert_ratio")]
+    pub chapter_length_alert_ratio: u8,
 
     // 保存配置
     #[serde(default = "default_novel_format")]
@@ -72,8 +147,26 @@ pub struct Config {
     #[serde(default = "default_true")]
     pub auto_clear_dump: bool,
     #[serde(default = "default_false")]
+    pub auto_clear_images: bool,
+    #[serde(default = "default_false")]
+    pub auto_clear_segment_comments: bool,
+    #[serde(default = "default_false")]
+    pub auto_clear_jsonl: bool,
+    #[serde(default = "default_false")]
     pub auto_open_downloaded_files: bool,
     #[serde(default = "default_false")]
+    pub write_output_checksum: bool,
+    #[serde(default = "default_download_history_limit")]
+    pub download_history_limit: usize,
+    #[serde(default = "default_string")]
+    pub webdav_url: String,
+    #[serde(default = "default_string")]
+    pub webdav_username: String,
+    #[serde(default = "default_string")]
+    pub webdav_password: String,
+    #[serde(default = "default_false")]
+    pub enable_notifications: bool,
+    #[serde(default = "default_false")]
     pub enable_audiobook: bool,
     #[serde(default = "default_audiobook_voice")]
     pub audiobook_voice: String,
@@ -99,6 +192,8 @@ pub struct Config {
     // 路径配置
     #[serde(default)]
     pub save_path: String,
+    #[serde(default)]
+    pub save_path_rules: String,
 
     // API 配置
     #[serde(default = "default_true")]
@@ -113,6 +208,18 @@ pub struct Config {
     pub segment_comments_top_n: usize,
     #[serde(default = "default_segment_comments_workers")]
     pub segment_comments_workers: usize,
+    #[serde(default = "default_false")]
+    pub segment_comments_hot_chapters_only: bool,
+    #[serde(default = "default_segment_comments_hot_chapters")]
+    pub segment_comments_hot_chapters: usize,
+    #[serde(default)]
+    pub segment_comments_refresh_hours: u64,
+    #[serde(default = "default_segment_comments_style")]
+    pub segment_comments_style: String,
+    #[serde(default = "default_segment_comments_export_mode")]
+    pub segment_comments_export_mode: String,
+    #[serde(default = "default_false")]
+    pub export_segment_notes_html: bool,
 
     // 媒体配置
     #[serde(default = "default_true")]
@@ -123,6 +230,8 @@ pub struct Config {
     pub media_download_workers: usize,
     #[serde(default = "default_blocked_media_domains")]
     pub blocked_media_domains: Vec<String>,
+    #[serde(default)]
+    pub allowed_media_domains: Vec<String>,
     #[serde(default = "default_false")]
     pub force_convert_images_to_jpeg: bool,
     #[serde(default = "default_true")]
@@ -135,6 +244,54 @@ pub struct Config {
     pub keep_heic_original: bool,
     #[serde(default = "default_first_line_indent_em")]
     pub first_line_indent_em: f32,
+    #[serde(default = "default_false")]
+    pub epub_chapter_nav: bool,
+    #[serde(default = "default_false")]
+    pub epub_chapter_header: bool,
+    #[serde(default = "default_true")]
+    pub epub_include_images: bool,
+    #[serde(default = "default_false")]
+    pub epub_merge_short_chapters: bool,
+    #[serde(default = "default_epub_short_chapter_chars")]
+    pub epub_short_chapter_chars: usize,
+    #[serde(default = "default_epub_merge_max_chapters")]
+    pub epub_merge_max_chapters: usize,
+    #[serde(default)]
+    pub txt_chapter_separator: String,
+    #[serde(default = "default_false")]
+    pub auto_paragraph: bool,
+    #[serde(default = "default_false")]
+    pub strip_duplicate_title_line: bool,
+    #[serde(default = "default_false")]
+    pub trim_blank_paragraphs: bool,
+    #[serde(default)]
+    pub replacements_path: String,
+    #[serde(default = "default_text_conversion")]
+    pub text_conversion: String,
+    #[serde(default = "default_false")]
+    pub text_conversion_description: bool,
+    #[serde(default = "default_false")]
+    pub show_chapter_time: bool,
+    #[serde(default = "default_chapter_title_format")]
+    pub chapter_title_format: String,
+    #[serde(default = "default_bulk_name_template")]
+    pub bulk_name_template: String,
+    #[serde(default = "default_txt_split_mode")]
+    pub txt_split_mode: String,
+    #[serde(default = "default_false")]
+    pub epub_use_title_format: bool,
+    #[serde(default = "default_false")]
+    pub split_epub_by_volume: bool,
+    #[serde(default)]
+    pub epub_embed_font_path: String,
+    #[serde(default = "default_true", alias = "generate_placeholder_cover")]
+    pub epub_placeholder_cover: bool,
+    #[serde(default = "default_false")]
+    pub prefer_hd_cover: bool,
+    #[serde(default = "default_epub_theme")]
+    pub epub_theme: String,
+    #[serde(default = "default_false")]
+    pub epub_highlight_dialogue: bool,
     #[serde(default = "default_media_limit_per_chapter")]
     pub media_limit_per_chapter: usize,
     #[serde(default = "default_media_max_dimension_px")]
@@ -173,15 +330,35 @@ impl Default for Config {
         Self {
             old_cli: default_false(),
             max_workers: default_max_workers(),
+            batch_size: default_batch_size(),
             request_timeout: default_request_timeout(),
             max_retries: default_max_retries(),
             max_wait_time: default_max_wait_time(),
             min_wait_time: default_min_wait_time(),
+            wait_jitter_ratio: default_wait_jitter_ratio(),
             min_connect_timeout: default_min_connect_timeout(),
+            fast_switch_on_4xx: default_true(),
+            timeout_retry_strategy: default_timeout_retry_strategy(),
+            max_bandwidth_kbps: 0,
+            block_on_incomplete_catalog: false,
+            skip_title_patterns: Vec::new(),
+            detect_modified_chapters: false,
+            book_download_timeout_secs: 0,
+            wait_for_network: default_false(),
+            chapter_length_alert_ratio: default_chapter_length_alert_ratio(),
             novel_format: default_novel_format(),
             bulk_files: default_false(),
             auto_clear_dump: default_true(),
+            auto_clear_images: default_false(),
+            auto_clear_segment_comments: default_false(),
+            auto_clear_jsonl: default_false(),
             auto_open_downloaded_files: default_false(),
+            write_output_checksum: default_false(),
+            download_history_limit: default_download_history_limit(),
+            webdav_url: default_string(),
+            webdav_username: default_string(),
+            webdav_password: default_string(),
+            enable_notifications: default_false(),
             enable_audiobook: default_false(),
             audiobook_voice: default_audiobook_voice(),
             audiobook_rate: default_audiobook_rate(),
@@ -194,21 +371,53 @@ impl Default for Config {
             audiobook_tts_api_token: default_string(),
             audiobook_tts_model: default_string(),
             save_path: String::new(),
+            save_path_rules: String::new(),
             use_official_api: default_true(),
             api_endpoints: Vec::new(),
             enable_segment_comments: default_false(),
             segment_comments_top_n: default_segment_comments_top_n(),
             segment_comments_workers: default_segment_comments_workers(),
+            segment_comments_hot_chapters_only: default_false(),
+            segment_comments_hot_chapters: default_segment_comments_hot_chapters(),
+            segment_comments_refresh_hours: 0,
+            segment_comments_style: default_segment_comments_style(),
+            segment_comments_export_mode: default_segment_comments_export_mode(),
+            export_segment_notes_html: default_false(),
             download_comment_images: default_true(),
             download_comment_avatars: default_true(),
             media_download_workers: default_media_download_workers(),
             blocked_media_domains: default_blocked_media_domains(),
+            allowed_media_domains: Vec::new(),
             force_convert_images_to_jpeg: default_false(),
             jpeg_retry_convert: default_true(),
             jpeg_quality: default_jpeg_quality(),
             convert_heic_to_jpeg: default_true(),
             keep_heic_original: default_false(),
             first_line_indent_em: default_first_line_indent_em(),
+            epub_chapter_nav: default_false(),
+            epub_chapter_header: default_false(),
+            epub_include_images: default_true(),
+            epub_merge_short_chapters: default_false(),
+            epub_short_chapter_chars: default_epub_short_chapter_chars(),
+            epub_merge_max_chapters: default_epub_merge_max_chapters(),
+            txt_chapter_separator: String::new(),
+            auto_paragraph: default_false(),
+            strip_duplicate_title_line: default_false(),
+            trim_blank_paragraphs: default_false(),
+            replacements_path: String::new(),
+            text_conversion: default_text_conversion(),
+            text_conversion_description: default_false(),
+            show_chapter_time: default_false(),
+            chapter_title_format: default_chapter_title_format(),
+            bulk_name_template: default_bulk_name_template(),
+            txt_split_mode: default_txt_split_mode(),
+            epub_use_title_format: default_false(),
+            split_epub_by_volume: default_false(),
+            epub_embed_font_path: String::new(),
+            epub_placeholder_cover: default_true(),
+            prefer_hd_cover: default_false(),
+            epub_theme: default_epub_theme(),
+            epub_highlight_dialogue: default_false(),
             media_limit_per_chapter: default_media_limit_per_chapter(),
             media_max_dimension_px: default_media_max_dimension_px(),
             pdf_font_path: None,
@@ -227,7 +436,7 @@ impl ConfigSpec for Config {
     const FILE_NAME: &'static str = "config.yml";
 
     fn fields() -> &'static [FieldMeta] {
-        static FIELDS: [FieldMeta; 44] = [
+        static FIELDS: [FieldMeta; 96] = [
             FieldMeta {
                 name: "old_cli",
                 description: "是否使用老版本命令行界面",
@@ -236,6 +445,10 @@ impl ConfigSpec for Config {
                 name: "max_workers",
                 description: "最大并发线程数",
             },
+            FieldMeta {
+                name: "batch_size",
+                description: "每批请求的章节数上限（1-25，受接口限制；越大请求越少，但单次响应更大、更易触发风控）",
+            },
             FieldMeta {
                 name: "request_timeout",
                 description: "请求超时时间（秒）",
@@ -252,13 +465,53 @@ impl ConfigSpec for Config {
                 name: "min_wait_time",
                 description: "最小冷却时间, 单位ms",
             },
+            FieldMeta {
+                name: "wait_jitter_ratio",
+                description: "重试等待时间的随机抖动比例（百分比，0 关闭）",
+            },
             FieldMeta {
                 name: "min_connect_timeout",
                 description: "最小连接超时时间",
             },
+            FieldMeta {
+                name: "fast_switch_on_4xx",
+                description: "第三方 API 返回 4xx（如 404）时立即切换 endpoint 而不退避重试；5xx/超时仍走退避重试",
+            },
+            FieldMeta {
+                name: "timeout_retry_strategy",
+                description: "连接失败/读取超时的重试策略：conservative(保守，连接失败照常退避) / balanced(均衡，连接失败稍候即换 endpoint，读取超时逐次放宽超时至 3 倍) / aggressive(激进，连接失败立即换 endpoint，读取超时放宽至 4 倍)",
+            },
+            FieldMeta {
+                name: "max_bandwidth_kbps",
+                description: "下载总带宽上限（KB/s），章节与媒体下载共享该额度；0 表示不限速",
+            },
+            FieldMeta {
+                name: "block_on_incomplete_catalog",
+                description: "目录章节数与元数据不符时暂停下载，需再次确认后才继续",
+            },
+            FieldMeta {
+                name: "skip_title_patterns",
+                description: "按章节标题排除下载的正则列表（如 上架感言|请假条），在范围选择之后生效，命中的章节会记入日志",
+            },
+            FieldMeta {
+                name: "detect_modified_chapters",
+                description: "更新时对比已下载章节的正文哈希，列出被作者修订的章节并询问是否重新下载",
+            },
+            FieldMeta {
+                name: "book_download_timeout_secs",
+                description: "单本下载总超时（秒），到期后中止并输出已完成部分，剩余章节记为失败；0 表示不限",
+            },
+            FieldMeta {
+                name: "wait_for_network",
+                description: "连续网络错误（断网/代理错误/超时）时暂停下载并周期性探测连通性，恢复后自动继续未完成章节",
+            },
+            FieldMeta {
+                name: "chapter_length_alert_ratio",
+                description: "章节字数低于全书平均值的百分比时告警（0 关闭）",
+            },
             FieldMeta {
                 name: "novel_format",
-                description: "保存小说格式, 可选: [txt, epub, pdf]",
+                description: "保存小说格式, 可选: [txt, epub, pdf]，可用逗号同时输出多种，如 txt,epub",
             },
             FieldMeta {
                 name: "bulk_files",
@@ -266,12 +519,48 @@ impl ConfigSpec for Config {
             },
             FieldMeta {
                 name: "auto_clear_dump",
-                description: "是否自动清理缓存文件",
+                description: "是否自动清理缓存文件（整本下载成功后删除整个状态目录）",
+            },
+            FieldMeta {
+                name: "auto_clear_images",
+                description: "成书后是否删除图片缓存 (images/)",
+            },
+            FieldMeta {
+                name: "auto_clear_segment_comments",
+                description: "成书后是否删除段评缓存 (segment_comments/)",
+            },
+            FieldMeta {
+                name: "auto_clear_jsonl",
+                description: "成书后是否删除中间 jsonl（chapters.jsonl、downloaded_chapters.jsonl）",
             },
             FieldMeta {
                 name: "auto_open_downloaded_files",
                 description: "下载完成后自动用默认应用打开生成的小说文件/文件夹（txt/epub）",
             },
+            FieldMeta {
+                name: "write_output_checksum",
+                description: "成书后写入 SHA-256 校验文件（<文件名>.sha256），重新生成内容未变时提示无变化（默认关闭）",
+            },
+            FieldMeta {
+                name: "download_history_limit",
+                description: "下载历史保留的最大条数，超出按时间淘汰最早的记录；0 表示不限",
+            },
+            FieldMeta {
+                name: "webdav_url",
+                description: "成书后上传到的 WebDAV 目录地址，如 https://nas.local/dav/novels（留空不上传）",
+            },
+            FieldMeta {
+                name: "webdav_username",
+                description: "WebDAV 用户名（留空则不使用认证）",
+            },
+            FieldMeta {
+                name: "webdav_password",
+                description: "WebDAV 密码",
+            },
+            FieldMeta {
+                name: "enable_notifications",
+                description: "下载完成或失败时发送系统通知（Windows/macOS/Linux/Termux）",
+            },
             FieldMeta {
                 name: "enable_audiobook",
                 description: "是否使用 Edge TTS 生成有声小说",
@@ -318,7 +607,11 @@ impl ConfigSpec for Config {
             },
             FieldMeta {
                 name: "save_path",
-                description: "保存路径",
+                description: "保存路径（支持 ~、$VAR/%VAR%，相对路径基于程序所在目录）",
+            },
+            FieldMeta {
+                name: "save_path_rules",
+                description: "按分类/标签分流保存路径，语法 “分类=路径”，多条用分号分隔（如 “玄幻=D:/玄幻;都市=E:/都市”）；依次匹配书籍分类与标签，未命中时使用 save_path",
             },
             FieldMeta {
                 name: "use_official_api",
@@ -340,6 +633,30 @@ impl ConfigSpec for Config {
                 name: "segment_comments_workers",
                 description: "段评抓取的并发线程数（每章内）",
             },
+            FieldMeta {
+                name: "segment_comments_hot_chapters_only",
+                description: "只抓评论最多的前 N 章段评：先用 stats 请求估算各章评论量，再只对热门章节抓取详情",
+            },
+            FieldMeta {
+                name: "segment_comments_hot_chapters",
+                description: "热门章节模式下抓取段评详情的章节数（N）",
+            },
+            FieldMeta {
+                name: "segment_comments_refresh_hours",
+                description: "段评缓存超过 N 小时后重新拉取各段评论数，只对数量变化的段落刷新详情；0 表示永不刷新",
+            },
+            FieldMeta {
+                name: "segment_comments_style",
+                description: "段评在 EPUB 中的呈现样式：chapter_end(章末汇总式)/inline_footnote(行内脚注式)/after_paragraph(段后块式)",
+            },
+            FieldMeta {
+                name: "segment_comments_export_mode",
+                description: "段评输出去向：embed(嵌入 EPUB 正文)/separate(导出为每章 Markdown 与汇总 JSON，正文不注入)/both(两者都要)",
+            },
+            FieldMeta {
+                name: "export_segment_notes_html",
+                description: "成书后额外导出正文与段评两栏并排的 HTML",
+            },
             FieldMeta {
                 name: "download_comment_images",
                 description: "是否下载评论区图片（不含头像）",
@@ -354,7 +671,11 @@ impl ConfigSpec for Config {
             },
             FieldMeta {
                 name: "blocked_media_domains",
-                description: "拒绝下载的图片域名（包含匹配）",
+                description: "拒绝下载的图片域名（匹配该域名及其子域名；*.example.com 仅匹配子域名；可带路径前缀，如 example.com/img/）",
+            },
+            FieldMeta {
+                name: "allowed_media_domains",
+                description: "只允许下载的图片域名（白名单，与 blocked_media_domains 互斥；支持子域名与路径前缀，如 example.com/img/）",
             },
             FieldMeta {
                 name: "force_convert_images_to_jpeg",
@@ -380,6 +701,102 @@ impl ConfigSpec for Config {
                 name: "first_line_indent_em",
                 description: "EPUB 段落首行缩进 em 数",
             },
+            FieldMeta {
+                name: "epub_chapter_nav",
+                description: "EPUB 每章末尾插入 上一章/目录/下一章 导航链接（默认关闭）",
+            },
+            FieldMeta {
+                name: "epub_chapter_header",
+                description: "EPUB 每章顶部显示页眉（书名 · 章节名），并标注章节序号（第 N / 总数 章）",
+            },
+            FieldMeta {
+                name: "epub_include_images",
+                description: "EPUB 嵌入正文与段评图片；关闭时生成纯文字版（图片替换为占位文字）",
+            },
+            FieldMeta {
+                name: "epub_merge_short_chapters",
+                description: "EPUB 合并连续短章到同一文件（目录仍可通过锚点跳到每章）",
+            },
+            FieldMeta {
+                name: "epub_short_chapter_chars",
+                description: "短章字数阈值，低于该字数的章节参与合并（0 表示不看字数，按固定数量合并）",
+            },
+            FieldMeta {
+                name: "epub_merge_max_chapters",
+                description: "合并后每个文件最多包含的章节数",
+            },
+            FieldMeta {
+                name: "txt_chapter_separator",
+                description: "TXT 章节分隔：空=默认虚线，\\f/formfeed=换页符，blank=仅空行，其余为自定义分隔行（散装模式不适用）",
+            },
+            FieldMeta {
+                name: "auto_paragraph",
+                description: "智能分段：正文缺少换行时按句末标点/对话引号重新切段（默认关闭）",
+            },
+            FieldMeta {
+                name: "strip_duplicate_title_line",
+                description: "去除正文首行与章节标题重复的行，编号差异（如“第12章”前缀）也按重复处理（默认关闭）",
+            },
+            FieldMeta {
+                name: "trim_blank_paragraphs",
+                description: "规整正文空段落：去除首尾空段、仅含空白的段落视为空段，连续空段合并为一个（默认关闭；EPUB 启用段评时为保持段落对齐不删除）",
+            },
+            FieldMeta {
+                name: "replacements_path",
+                description: "正文替换表路径（JSON，纯文本/正则条目），留空则使用工作目录下的 replacements.json；应用于所有输出格式",
+            },
+            FieldMeta {
+                name: "text_conversion",
+                description: "正文简繁转换：none 不转换，s2t 简体转繁体，t2s 繁体转简体；章节标题与正文均转换，在段落规整与替换表之后应用",
+            },
+            FieldMeta {
+                name: "text_conversion_description",
+                description: "启用简繁转换时同时转换简介（默认关闭）",
+            },
+            FieldMeta {
+                name: "show_chapter_time",
+                description: "在 txt 章节头 / epub 章节开头显示章节发布时间",
+            },
+            FieldMeta {
+                name: "chapter_title_format",
+                description: "章节标题模板，占位符 {index}(序号) {title}(原标题) {volume}(卷名)，用于 txt 章节标题",
+            },
+            FieldMeta {
+                name: "bulk_name_template",
+                description: "散装文件名模板，占位符 {index}(序号) {index_padded}(补零序号) {title}(章节名) {book_name}(书名)，须含序号占位符",
+            },
+            FieldMeta {
+                name: "txt_split_mode",
+                description: "TXT 拆分方式：single(整本) / per-chapter(按章散装，同 bulk_files) / per-volume(按卷，每卷一个 txt，无多卷信息时回退整本)",
+            },
+            FieldMeta {
+                name: "epub_use_title_format",
+                description: "EPUB 章节标题是否也使用 chapter_title_format 模板",
+            },
+            FieldMeta {
+                name: "split_epub_by_volume",
+                description: "EPUB 是否按卷拆分导出为多个文件（无分卷信息时回退整本）",
+            },
+            FieldMeta {
+                name: "epub_embed_font_path",
+                description: "EPUB 内嵌字体文件路径（ttf/otf/woff/woff2，留空不嵌入）",
+            },
+            FieldMeta {
+                name: "epub_placeholder_cover",
+                description: "EPUB 无封面时生成占位封面（渐变背景 + 书名/作者，背景取 cover_primary_color，图片另存到书籍文件夹；需可用中文字体）",
+            },
+            FieldMeta {
+                name: "prefer_hd_cover",
+                description: "优先下载高清封面（detail_cover_url），原图保存为 cover_hd.*，另生成压缩版 cover.jpg 供 EPUB 等格式使用",
+            },
+            FieldMeta {
+                name: "epub_theme",
+                description: "EPUB 主题预设：default(默认)/sepia(护眼米色)/night(夜间深色)/paper(纸质衬线)",
+            },
+            FieldMeta {
+                name: "epub_highlight_dialogue",
+                description: "EPUB 对话分色：整段均为引号内对话的段落加 dialogue 样式，与旁白区分颜色（默认关闭）",
+            },
             FieldMeta {
                 name: "media_limit_per_chapter",
                 description: "每章最多下载的媒体数（0 表示不限制）",
@@ -410,25 +827,57 @@ impl ConfigSpec for Config {
 }
 
 impl Config {
-    pub fn configured_output_format_choice(&self) -> &'static str {
-        if self.bulk_files && self.novel_format.eq_ignore_ascii_case(OUTPUT_FORMAT_TXT) {
-            return OUTPUT_FORMAT_BULK_TXT;
-        }
+    /// 成书后需要单独清理的缓存类型（不含 `auto_clear_dump` 的整目录清理）。
+    pub fn dump_kinds_to_clear(&self) -> Vec<DumpKind> {
+        [
+            (self.auto_clear_images, DumpKind::Images),
+            (self.auto_clear_segment_comments, DumpKind::SegmentComments),
+            (self.auto_clear_jsonl, DumpKind::Jsonl),
+        ]
+        .into_iter()
+        .filter_map(|(on, kind)| on.then_some(kind))
+        .collect()
+    }
 
-        match self.novel_format.trim().to_ascii_lowercase().as_str() {
-            OUTPUT_FORMAT_EPUB => OUTPUT_FORMAT_EPUB,
-            OUTPUT_FORMAT_PDF => OUTPUT_FORMAT_PDF,
-            _ => OUTPUT_FORMAT_TXT,
+    pub fn configured_output_format_choice(&self) -> String {
+        let formats = self.output_formats();
+        if self.bulk_files && formats == [OUTPUT_FORMAT_TXT] {
+            return OUTPUT_FORMAT_BULK_TXT.to_string();
         }
+        formats.join(",")
     }
 
-    pub fn current_output_format_choice(&self) -> &'static str {
+    pub fn current_output_format_choice(&self) -> String {
         if self.ask_format_after_download {
-            return OUTPUT_FORMAT_ASK_AFTER_DOWNLOAD;
+            return OUTPUT_FORMAT_ASK_AFTER_DOWNLOAD.to_string();
         }
         self.configured_output_format_choice()
     }
 
+    /// 本次需要生成的成书格式；未配置或无法识别时回退为默认格式。
+    pub fn output_formats(&self) -> Vec<&'static str> {
+        let formats = parse_output_formats(&self.novel_format);
+        if formats.is_empty() {
+            return parse_output_formats(&default_novel_format());
+        }
+        formats
+    }
+
+    /// 是否下载/嵌入段评图片（纯文字版 EPUB 时一律关闭）。
+    pub fn wants_comment_images(&self) -> bool {
+        self.epub_include_images && self.download_comment_images
+    }
+
+    /// 是否下载/嵌入段评头像（纯文字版 EPUB 时一律关闭）。
+    pub fn wants_comment_avatars(&self) -> bool {
+        self.epub_include_images && self.download_comment_avatars
+    }
+
+    /// 输出格式中包含 EPUB；决定章节按 EPUB 模式抓取/缓存以及段评是否可用。
+    pub fn wants_epub(&self) -> bool {
+        self.output_formats().contains(&OUTPUT_FORMAT_EPUB)
+    }
+
     pub fn apply_output_format_choice(&mut self, choice: &str) -> Result<(), String> {
         let normalized = choice.trim().to_ascii_lowercase();
         match normalized.as_str() {
@@ -436,6 +885,14 @@ impl Config {
                 self.novel_format = OUTPUT_FORMAT_TXT.to_string();
                 self.bulk_files = false;
                 self.ask_format_after_download = false;
+                // 选择整本 txt 时，按章拆分也一并取消
+                if self
+                    .txt_split_mode
+                    .trim()
+                    .eq_ignore_ascii_case("per-chapter")
+                {
+                    self.txt_split_mode = default_txt_split_mode();
+                }
             }
             OUTPUT_FORMAT_EPUB => {
                 self.novel_format = OUTPUT_FORMAT_EPUB.to_string();
@@ -459,8 +916,16 @@ impl Config {
                     self.novel_format = default_novel_format();
                 }
             }
+            list if parse_output_formats(list).len() > 1 => {
+                self.novel_format = list.to_string();
+                self.bulk_files = false;
+                self.ask_format_after_download = false;
+            }
             _ => {
-                return Err("保存格式仅支持 txt/epub/pdf/散装文件/下载后选择".to_string());
+                return Err(
+                    "保存格式仅支持 txt/epub/pdf（可用逗号组合，如 txt,epub）/散装文件/下载后选择"
+                        .to_string(),
+                );
             }
         }
 
@@ -474,12 +939,12 @@ impl Config {
             normalized = OUTPUT_FORMAT_TXT.to_string();
             self.bulk_files = true;
         }
-        if normalized != OUTPUT_FORMAT_TXT
-            && normalized != OUTPUT_FORMAT_EPUB
-            && normalized != OUTPUT_FORMAT_PDF
-        {
-            normalized = default_novel_format();
-        }
+        let formats = parse_output_formats(&normalized);
+        normalized = if formats.is_empty() {
+            default_novel_format()
+        } else {
+            formats.join(",")
+        };
 
         self.novel_format = normalized;
         if self.ask_format_after_download || self.novel_format != OUTPUT_FORMAT_TXT {
@@ -521,12 +986,47 @@ impl Config {
         candidates.iter().map(PathBuf::from).find(|p| p.exists())
     }
 
+    /// 保存目录：展开 `~`/环境变量，相对路径基于可执行文件目录解析；留空时为当前工作目录。
     pub fn default_save_dir(&self) -> PathBuf {
         if self.save_path.trim().is_empty() {
             std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
         } else {
-            PathBuf::from(&self.save_path)
+            super::path_expand::resolve_config_path(&self.save_path)
+        }
+    }
+
+    /// 按 `save_path_rules` 为书籍选择保存根目录；首条命中分类或标签的规则生效，未命中返回 None。
+    pub fn save_path_rule_root(&self, category: Option<&str>, tags: &[String]) -> Option<PathBuf> {
+        let category = category.map(str::trim).unwrap_or("");
+        parse_save_path_rules(&self.save_path_rules)
+            .into_iter()
+            .find(|(key, _)| key == category || tags.iter().any(|t| t.trim() == key))
+            .map(|(_, path)| super::path_expand::resolve_config_path(&path))
+    }
+
+    /// 默认保存目录与各 `save_path_rules` 规则目录（去重，默认目录在前）；扫描书库/更新时需遍历全部。
+    pub fn save_roots(&self) -> Vec<PathBuf> {
+        let mut roots = vec![self.default_save_dir()];
+        for (_, path) in parse_save_path_rules(&self.save_path_rules) {
+            let root = super::path_expand::resolve_config_path(&path);
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
         }
+        roots
+    }
+
+    /// 已存有该书缓存的保存根目录；都没有时为默认保存目录。
+    pub fn save_root_for_book(&self, book_id: &str) -> PathBuf {
+        self.save_roots()
+            .into_iter()
+            .find(|root| {
+                matches!(
+                    self.find_existing_status_folder_by_book_id(book_id, Some(root)),
+                    Ok(Some(_))
+                )
+            })
+            .unwrap_or_else(|| self.default_save_dir())
     }
 
     pub fn find_existing_status_folder_by_book_id(
@@ -650,6 +1150,7 @@ impl Config {
         path.join(format!("chapter_status_{}.json", book_id))
             .exists()
             || path.join("downloaded_chapters.jsonl").exists()
+            || path.join("chapters.jsonl").exists()
     }
 
     /// 根据用户配置的首选字段选择书名
@@ -672,6 +1173,92 @@ impl Config {
         }
     }
 
+    /// 实际使用的章节批量大小（限制在 1..=MAX_BATCH_SIZE）。
+    pub fn effective_batch_size(&self) -> usize {
+        self.batch_size.clamp(1, MAX_BATCH_SIZE)
+    }
+
+    /// 图片白名单与黑名单同时配置时返回错误说明；两种模式互斥。
+    pub fn validate_media_domain_filters(&self) -> Result<(), String> {
+        let has_rule = |rules: &[String]| rules.iter().any(|r| !r.trim().is_empty());
+        if has_rule(&self.allowed_media_domains) && has_rule(&self.blocked_media_domains) {
+            return Err(
+                "allowed_media_domains 与 blocked_media_domains 互斥，请清空其中一项".to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// 校验 `bulk_name_template`：只允许已知占位符，且必须含序号以保证文件名唯一、有序。
+    pub fn validate_bulk_name_template(&self) -> Result<(), String> {
+        let template = self.bulk_name_template.trim();
+        if template.is_empty() {
+            return Ok(());
+        }
+        let mut rest = template;
+        let mut has_index = false;
+        while let Some(start) = rest.find(['{', '}']) {
+            if rest[start..].starts_with('}') {
+                return Err(format!("bulk_name_template 含多余的 '}}'：{template}"));
+            }
+            let Some(len) = rest[start..].find('}') else {
+                return Err(format!("bulk_name_template 含未闭合的 '{{'：{template}"));
+            };
+            let name = &rest[start + 1..start + len];
+            if !BULK_NAME_PLACEHOLDERS.contains(&name) {
+                return Err(format!(
+                    "bulk_name_template 含未知占位符 {{{name}}}，可用：{}",
+                    BULK_NAME_PLACEHOLDERS
+                        .iter()
+                        .map(|p| format!("{{{p}}}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ));
+            }
+            has_index |= name.starts_with("index");
+            rest = &rest[start + len + 1..];
+        }
+        if !has_index {
+            return Err(
+                "bulk_name_template 须包含 {index} 或 {index_padded}，否则章节文件会重名"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// 校验 `skip_title_patterns` 中的正则能否编译。
+    pub fn validate_skip_title_patterns(&self) -> Result<(), String> {
+        for pattern in self
+            .skip_title_patterns
+            .iter()
+            .filter(|p| !p.trim().is_empty())
+        {
+            if let Err(e) = regex::Regex::new(pattern.trim()) {
+                return Err(format!("skip_title_patterns 正则无效：{pattern}（{e}）"));
+            }
+        }
+        Ok(())
+    }
+
+    /// 按图片白名单/黑名单判断 URL 是否允许下载；白名单非空时只放行命中的 URL。
+    pub fn media_url_allowed(&self, url: &str) -> bool {
+        let (host, path) = url_host_and_path(url).unwrap_or_default();
+        let matches = |rules: &[String]| {
+            rules
+                .iter()
+                .any(|rule| media_domain_rule_matches(rule, &host, &path))
+        };
+        if self
+            .allowed_media_domains
+            .iter()
+            .any(|r| !r.trim().is_empty())
+        {
+            return matches(&self.allowed_media_domains);
+        }
+        !matches(&self.blocked_media_domains)
+    }
+
     /// 是否设置了“下载完后选择书名”
     pub fn is_ask_after_download(&self) -> bool {
         self.preferred_book_name_field == "ask_after_download"
@@ -725,9 +1312,10 @@ impl Config {
         book_id: &str,
         save_dir: Option<&Path>,
     ) -> io::Result<PathBuf> {
+        // 未指定根目录时沿用该书已有缓存所在的根目录（可能是按规则分流的目录）
         let save_dir = save_dir
             .map(PathBuf::from)
-            .unwrap_or_else(|| self.default_save_dir());
+            .unwrap_or_else(|| self.save_root_for_book(book_id));
         let path = self.migrate_status_folder_to_stable(book_id, Some(&save_dir))?;
         let existed_before = path.exists() && Self::status_folder_has_book_record(&path, book_id);
         if !path.exists() {
@@ -885,8 +1473,60 @@ fn status_downloaded_count(path: &Path) -> usize {
 mod tests {
     use super::{
         Config, OUTPUT_FORMAT_ASK_AFTER_DOWNLOAD, OUTPUT_FORMAT_BULK_TXT, OUTPUT_FORMAT_TXT,
+        OUTPUT_FORMAT_TXT_EPUB, output_format_label, parse_save_path_rules,
     };
 
+    #[test]
+    fn save_path_rules_match_category_then_tags() {
+        assert_eq!(
+            parse_save_path_rules(" 玄幻 = /books/xh ；无效;=/x;都市＝/books/ds;"),
+            vec![
+                ("玄幻".to_string(), "/books/xh".to_string()),
+                ("都市".to_string(), "/books/ds".to_string()),
+            ]
+        );
+
+        let config = Config {
+            save_path_rules: "玄幻=/books/xh;系统=/books/xt".to_string(),
+            ..Config::default()
+        };
+        let resolve = crate::base_system::path_expand::resolve_config_path;
+        let tags = vec!["系统".to_string()];
+        assert_eq!(
+            config.save_path_rule_root(Some("玄幻"), &tags),
+            Some(resolve("/books/xh"))
+        );
+        assert_eq!(
+            config.save_path_rule_root(Some("都市"), &tags),
+            Some(resolve("/books/xt"))
+        );
+        assert_eq!(config.save_path_rule_root(None, &[]), None);
+
+        let roots = config.save_roots();
+        assert_eq!(roots.len(), 3);
+        assert_eq!(roots[0], config.default_save_dir());
+    }
+
+    #[test]
+    fn media_domain_rules_match_host_and_path() {
+        let mut config = Config {
+            blocked_media_domains: vec!["*.bad.com".to_string(), "cdn.com/ads/".to_string()],
+            ..Config::default()
+        };
+        assert!(config.media_url_allowed("https://bad.com/a.png"));
+        assert!(!config.media_url_allowed("https://img.bad.com/a.png"));
+        assert!(!config.media_url_allowed("//cdn.com/ads/1.jpg"));
+        assert!(config.media_url_allowed("https://cdn.com/img/1.jpg"));
+        assert!(config.media_url_allowed("https://notbad.com/a.png"));
+
+        config.allowed_media_domains = vec!["pstatp.com".to_string()];
+        assert!(config.validate_media_domain_filters().is_err());
+        config.blocked_media_domains.clear();
+        assert!(config.validate_media_domain_filters().is_ok());
+        assert!(config.media_url_allowed("https://p3.pstatp.com/x.webp"));
+        assert!(!config.media_url_allowed("https://evilpstatp.com/x.webp"));
+    }
+
     #[test]
     fn status_folder_path_migrates_legacy_folder_when_only_book_name_changes() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -951,6 +1591,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn multi_format_list_is_normalized() {
+        let mut config = Config {
+            bulk_files: true,
+            novel_format: "EPUB, txt,epub".to_string(),
+            ..Config::default()
+        };
+        config.normalize_output_format_fields();
+
+        assert_eq!(config.novel_format, OUTPUT_FORMAT_TXT_EPUB);
+        assert_eq!(config.output_formats(), vec!["txt", "epub"]);
+        assert!(config.wants_epub());
+        assert!(!config.bulk_files);
+        assert_eq!(output_format_label("epub,pdf"), "epub + pdf 格式");
+    }
+
     #[test]
     fn ask_after_download_clears_bulk_flag() {
         let mut config = Config {
@@ -994,6 +1650,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bulk_name_template_rejects_bad_placeholders() {
+        let check = |t: &str| {
+            Config {
+                bulk_name_template: t.to_string(),
+                ..Config::default()
+            }
+            .validate_bulk_name_template()
+        };
+        assert!(check("").is_ok());
+        assert!(check("{index_padded}-{title}").is_ok());
+        assert!(check("{book_name}_{index}").is_ok());
+        assert!(check("{title}").is_err());
+        assert!(check("{index}_{name}").unwrap_err().contains("{name}"));
+        assert!(check("{index}_{title").is_err());
+        assert!(check("index}_{title}").is_err());
+    }
+
     #[test]
     fn safe_fs_name_replaces_windows_double_quote() {
         let sanitized = super::safe_fs_name("第1章 \"你好\"", "_", 120);
@@ -1132,10 +1806,54 @@ fn default_segment_comments_workers() -> usize {
     32
 }
 
+fn default_segment_comments_hot_chapters() -> usize {
+    30
+}
+
 fn default_media_download_workers() -> usize {
     8
 }
 
+/// 解析 URL 的 host（小写）与路径；支持省略协议的 `//host/path`。
+fn url_host_and_path(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    let parsed = match url.strip_prefix("//") {
+        Some(rest) => reqwest::Url::parse(&format!("https://{rest}")),
+        None => reqwest::Url::parse(url),
+    }
+    .ok()?;
+    let host = parsed
+        .host_str()?
+        .trim_end_matches('.')
+        .to_ascii_lowercase();
+    Some((host, parsed.path().to_string()))
+}
+
+/// 单条域名规则：`example.com` 匹配该域名及其子域名，`*.example.com` / `.example.com`
+/// 仅匹配子域名；规则带路径（`example.com/img/`）时还要求 URL 路径以该前缀开头。
+fn media_domain_rule_matches(rule: &str, host: &str, path: &str) -> bool {
+    let rule = rule.trim().to_ascii_lowercase();
+    let rule = rule
+        .strip_prefix("https://")
+        .or_else(|| rule.strip_prefix("http://"))
+        .unwrap_or(&rule);
+    let (host_rule, path_prefix) = match rule.find('/') {
+        Some(idx) => (&rule[..idx], &rule[idx..]),
+        None => (rule, ""),
+    };
+    if host_rule.is_empty() || host.is_empty() {
+        return false;
+    }
+    let host_ok = match host_rule
+        .strip_prefix("*.")
+        .or_else(|| host_rule.strip_prefix('.'))
+    {
+        Some(base) => host.len() > base.len() && host.ends_with(&format!(".{base}")),
+        None => host == host_rule || host.ends_with(&format!(".{host_rule}")),
+    };
+    host_ok && path.to_ascii_lowercase().starts_with(path_prefix)
+}
+
 fn default_blocked_media_domains() -> Vec<String> {
     vec!["p-passport-sign.bytedance.net".to_string()]
 }
@@ -1159,3 +1877,59 @@ fn default_media_max_dimension_px() -> u32 {
 fn default_preferred_book_name_field() -> String {
     "book_name".to_string()
 }
+
+fn default_chapter_title_format() -> String {
+    "{title}".to_string()
+}
+
+fn default_bulk_name_template() -> String {
+    "{index_padded}_{title}".to_string()
+}
+
+fn default_segment_comments_style() -> String {
+    "chapter_end".to_string()
+}
+
+fn default_batch_size() -> usize {
+    MAX_BATCH_SIZE
+}
+
+fn default_epub_short_chapter_chars() -> usize {
+    800
+}
+
+fn default_epub_merge_max_chapters() -> usize {
+    10
+}
+
+fn default_epub_theme() -> String {
+    "default".to_string()
+}
+
+fn default_chapter_length_alert_ratio() -> u8 {
+    20
+}
+
+fn default_wait_jitter_ratio() -> u8 {
+    20
+}
+
+fn default_text_conversion() -> String {
+    "none".to_string()
+}
+
+fn default_segment_comments_export_mode() -> String {
+    "embed".to_string()
+}
+
+fn default_download_history_limit() -> usize {
+    500
+}
+
+fn default_txt_split_mode() -> String {
+    "single".to_string()
+}
+
+fn default_timeout_retry_strategy() -> String {
+    "balanced".to_string()
+}
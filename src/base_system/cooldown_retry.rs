@@ -3,7 +3,15 @@
 use anyhow::{Result, anyhow};
 use serde_json::Value;
 #[cfg(feature = "official-api")]
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use super::request_error::TimeoutRetryStrategy;
+#[cfg(feature = "official-api")]
+use super::request_error::{FailurePhase, RequestErrorKind};
+#[cfg(feature = "official-api")]
+use super::request_trace::{self, OFFICIAL_ENDPOINT, TraceOutcome};
+#[cfg(feature = "official-api")]
+use tracing::warn;
 
 #[cfg(feature = "official-api")]
 use tomato_novel_official_api::FanqieClient;
@@ -14,29 +22,84 @@ pub fn fetch_with_cooldown_retry(
     ids: &str,
     epub_mode: bool,
     book_id: Option<&str>,
+    strategy: TimeoutRetryStrategy,
 ) -> Result<Value> {
     #[cfg(not(feature = "official-api"))]
     {
         let _ = ids;
         let _ = epub_mode;
         let _ = book_id;
+        let _ = strategy;
         Err(anyhow!("no-official-api 构建不支持官方 API cooldown 拉取"))
     }
 
     #[cfg(feature = "official-api")]
     let mut delay = Duration::from_millis(1100);
     #[cfg(feature = "official-api")]
+    let mut last_msg: Option<String> = None;
+    #[cfg(feature = "official-api")]
     for attempt in 0..6 {
+        #[cfg(feature = "official-api")]
+        let started = Instant::now();
         #[cfg(feature = "official-api")]
         match client.get_contents(ids, epub_mode, book_id) {
-            Ok(v) => return Ok(v),
+            Ok(v) => {
+                request_trace::record(
+                    ids,
+                    OFFICIAL_ENDPOINT,
+                    attempt,
+                    started.elapsed(),
+                    TraceOutcome::Ok,
+                    None,
+                    None,
+                );
+                return Ok(v);
+            }
             Err(e) => {
                 let msg = e.to_string();
-                if msg.contains("Cooldown") || msg.contains("CooldownNotReached") {
+                let cooldown = msg.contains("Cooldown") || msg.contains("CooldownNotReached");
+                // 连接失败 / 读取超时按策略原地重试
+                let retry_wait = (!cooldown)
+                    .then(|| {
+                        strategy.official_retry_wait(FailurePhase::classify_message(&msg), delay)
+                    })
+                    .flatten();
+                request_trace::record(
+                    ids,
+                    OFFICIAL_ENDPOINT,
+                    attempt,
+                    started.elapsed(),
+                    if cooldown {
+                        TraceOutcome::Cooldown
+                    } else if retry_wait.is_some() {
+                        TraceOutcome::Retryable
+                    } else {
+                        TraceOutcome::Fatal
+                    },
+                    None,
+                    Some(msg.clone()),
+                );
+                if cooldown {
+                    last_msg = None;
                     std::thread::sleep(delay);
                     delay = std::cmp::min(delay * 2, Duration::from_secs(8));
                     continue;
                 }
+                if let Some(wait) = retry_wait {
+                    let kind = RequestErrorKind::classify_message(&msg);
+                    warn!(
+                        target: "download",
+                        kind = kind.label(),
+                        "{} 官方 API 请求失败，{}ms 后重试：{}",
+                        kind.prefix(),
+                        wait.as_millis(),
+                        msg
+                    );
+                    last_msg = Some(msg);
+                    std::thread::sleep(wait);
+                    delay = std::cmp::min(delay * 2, Duration::from_secs(8));
+                    continue;
+                }
                 if attempt == 0
                     && (msg.contains("tomato_novel_network_core") || msg.contains("Library"))
                 {
@@ -45,10 +108,25 @@ pub fn fetch_with_cooldown_retry(
                         msg
                     ));
                 }
-                return Err(anyhow!(msg));
+                let kind = RequestErrorKind::classify_message(&msg);
+                warn!(
+                    target: "download",
+                    kind = kind.label(),
+                    "{} 官方 API 请求失败：{}",
+                    kind.prefix(),
+                    msg
+                );
+                return Err(anyhow!(kind.annotate(&msg)));
             }
         }
     }
     #[cfg(feature = "official-api")]
-    Err(anyhow!("Cooldown exceeded retries"))
+    match last_msg {
+        Some(msg) => Err(anyhow!(
+            RequestErrorKind::classify_message(&msg).annotate(&msg)
+        )),
+        None => Err(anyhow!(
+            RequestErrorKind::RiskControl.annotate("Cooldown exceeded retries")
+        )),
+    }
 }
@@ -4,7 +4,7 @@
 
 use std::fs::{self, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
@@ -30,6 +30,9 @@ pub struct DownloadHistoryRecord {
     pub failed_chapters: usize,
     pub progress: String,
     pub status: String,
+    /// 下载范围（如 `1-50`）；空为全部章节，勾选下载记为 `勾选 N 章`
+    #[serde(default)]
+    pub range: String,
 }
 
 impl DownloadHistoryRecord {
@@ -60,18 +63,25 @@ impl DownloadHistoryRecord {
             failed_chapters,
             progress,
             status,
+            range: String::new(),
         }
     }
+
+    pub fn with_range(mut self, range: String) -> Self {
+        self.range = range;
+        self
+    }
 }
 
-pub fn append_download_history(record: &DownloadHistoryRecord) {
+/// 追加一条历史；`limit` 大于 0 时只保留最近 `limit` 条，超出的旧记录淘汰。
+pub fn append_download_history(record: &DownloadHistoryRecord, limit: usize) {
     let logs_dir = logging::current_logs_dir().unwrap_or_else(|| PathBuf::from("logs"));
     if fs::create_dir_all(&logs_dir).is_err() {
         return;
     }
 
     let path = logs_dir.join(HISTORY_FILE_NAME);
-    let mut file = match OpenOptions::new().create(true).append(true).open(path) {
+    let mut file = match OpenOptions::new().create(true).append(true).open(&path) {
         Ok(f) => f,
         Err(_) => return,
     };
@@ -83,6 +93,23 @@ pub fn append_download_history(record: &DownloadHistoryRecord) {
 
     let _ = writeln!(file, "{line}");
     let _ = file.flush();
+    drop(file);
+    let _ = prune_history_file(&path, limit);
+}
+
+/// 历史文件超过 `limit` 行时删去最早的记录；`limit` 为 0 表示不限。
+fn prune_history_file(path: &Path, limit: usize) -> std::io::Result<()> {
+    if limit == 0 {
+        return Ok(());
+    }
+    let text = fs::read_to_string(path)?;
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.len() <= limit {
+        return Ok(());
+    }
+    let mut kept = lines[lines.len() - limit..].join("\n");
+    kept.push('\n');
+    fs::write(path, kept)
 }
 
 pub fn read_download_history(limit: usize, keyword: Option<&str>) -> Vec<DownloadHistoryRecord> {
@@ -129,3 +156,21 @@ pub fn read_download_history(limit: usize, keyword: Option<&str>) -> Vec<Downloa
     }
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::prune_history_file;
+
+    #[test]
+    fn prune_keeps_latest_records() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("download_history.jsonl");
+        std::fs::write(&path, "1\n2\n3\n4\n").unwrap();
+
+        prune_history_file(&path, 0).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1\n2\n3\n4\n");
+
+        prune_history_file(&path, 2).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "3\n4\n");
+    }
+}
@@ -0,0 +1,88 @@
+//! 批量下载队列持久化（queue.json）。
+//!
+//! 与 `config.yml` 同目录保存，队列每次变化即落盘；程序崩溃或重启后可询问是否恢复，
+//! 配合断点续传从各本已下载进度继续。队列清空时删除文件。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::logging;
+
+const QUEUE_FILE_NAME: &str = "queue.json";
+
+pub fn queue_file_path() -> PathBuf {
+    logging::current_data_dir()
+        .unwrap_or_default()
+        .join(QUEUE_FILE_NAME)
+}
+
+/// 队列项：足以在重启后重新预览并按原设置下载。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedBook {
+    pub book_id: String,
+    pub book_name: String,
+    #[serde(default)]
+    pub author: String,
+    /// 下载范围输入（同预览页范围语法），空表示全部
+    #[serde(default)]
+    pub range: String,
+    /// 入队时的输出格式（`novel_format`），空表示沿用当前配置
+    #[serde(default)]
+    pub format: String,
+}
+
+pub fn load_queue() -> Vec<QueuedBook> {
+    load_queue_from(&queue_file_path())
+}
+
+pub fn save_queue(list: &[QueuedBook]) -> std::io::Result<()> {
+    save_queue_to(&queue_file_path(), list)
+}
+
+fn load_queue_from(path: &Path) -> Vec<QueuedBook> {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    serde_json::from_str(&raw).unwrap_or_default()
+}
+
+fn save_queue_to(path: &Path, list: &[QueuedBook]) -> std::io::Result<()> {
+    if list.is_empty() {
+        return match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    let raw = serde_json::to_string_pretty(list).map_err(std::io::Error::other)?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, raw)?;
+    fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persist_round_trip_and_clear() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(QUEUE_FILE_NAME);
+
+        let list = vec![QueuedBook {
+            book_id: "1".into(),
+            book_name: "测试书".into(),
+            author: "作者".into(),
+            range: "1-10".into(),
+            format: "epub".into(),
+        }];
+        save_queue_to(&path, &list).unwrap();
+        assert_eq!(load_queue_from(&path), list);
+
+        save_queue_to(&path, &[]).unwrap();
+        assert!(!path.exists());
+        assert!(load_queue_from(&path).is_empty());
+        save_queue_to(&path, &[]).unwrap();
+    }
+}
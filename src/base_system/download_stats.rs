@@ -0,0 +1,203 @@
+//! 全局下载统计（JSON）。
+//!
+//! 与下载历史同目录，累计总书数/章数/字数/耗时、各 endpoint 历史成功率，
+//! 并按日汇总用于展示趋势。每次下载结束更新一次。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+
+use super::logging;
+
+const STATS_FILE_NAME: &str = "download_stats.json";
+/// 按日趋势最多保留的天数。
+const MAX_DAILY_ENTRIES: usize = 365;
+
+/// 串行化读-改-写，避免并发下载互相覆盖。
+static STATS_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn stats_file_path() -> PathBuf {
+    let logs_dir = logging::current_logs_dir().unwrap_or_else(|| PathBuf::from("logs"));
+    logs_dir.join(STATS_FILE_NAME)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EndpointTotals {
+    pub requests: u64,
+    pub success: u64,
+    pub failed: u64,
+}
+
+impl EndpointTotals {
+    /// 历史成功率（百分比），尚无请求时为 None。
+    pub fn success_rate(&self) -> Option<u64> {
+        (self.requests > 0).then(|| self.success * 100 / self.requests)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailyTotals {
+    pub books: u64,
+    pub chapters: u64,
+    pub words: u64,
+    pub elapsed_secs: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DownloadStats {
+    pub books: u64,
+    pub chapters: u64,
+    pub words: u64,
+    pub elapsed_secs: u64,
+    pub first_recorded: String,
+    pub last_updated: String,
+    pub endpoints: BTreeMap<String, EndpointTotals>,
+    /// `YYYY-MM-DD` -> 当日汇总
+    pub daily: BTreeMap<String, DailyTotals>,
+}
+
+/// 单次下载结束时的汇总。
+#[derive(Debug, Clone, Default)]
+pub struct DownloadRunSummary {
+    pub success_chapters: u64,
+    pub words: u64,
+    pub elapsed_secs: u64,
+    pub endpoints: Vec<(String, EndpointTotals)>,
+}
+
+impl DownloadStats {
+    fn apply(&mut self, run: &DownloadRunSummary, now: OffsetDateTime) {
+        let book = u64::from(run.success_chapters > 0);
+        self.books += book;
+        self.chapters += run.success_chapters;
+        self.words += run.words;
+        self.elapsed_secs += run.elapsed_secs;
+
+        for (endpoint, totals) in &run.endpoints {
+            let entry = self.endpoints.entry(endpoint.clone()).or_default();
+            entry.requests += totals.requests;
+            entry.success += totals.success;
+            entry.failed += totals.failed;
+        }
+
+        let day = now
+            .format(format_description!("[year]-[month]-[day]"))
+            .unwrap_or_default();
+        let daily = self.daily.entry(day).or_default();
+        daily.books += book;
+        daily.chapters += run.success_chapters;
+        daily.words += run.words;
+        daily.elapsed_secs += run.elapsed_secs;
+        while self.daily.len() > MAX_DAILY_ENTRIES {
+            self.daily.pop_first();
+        }
+
+        let stamp = now.format(&Rfc3339).unwrap_or_default();
+        if self.first_recorded.is_empty() {
+            self.first_recorded = stamp.clone();
+        }
+        self.last_updated = stamp;
+    }
+
+    /// 最近 `days` 天（有记录的日期）的趋势，按日期升序。
+    pub fn recent_daily(&self, days: usize) -> Vec<(&str, &DailyTotals)> {
+        let skip = self.daily.len().saturating_sub(days);
+        self.daily
+            .iter()
+            .skip(skip)
+            .map(|(d, t)| (d.as_str(), t))
+            .collect()
+    }
+}
+
+pub fn load_download_stats() -> DownloadStats {
+    fs::read_to_string(stats_file_path())
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub fn record_download_stats(run: &DownloadRunSummary) {
+    let _guard = STATS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = stats_file_path();
+    if let Some(parent) = path.parent()
+        && fs::create_dir_all(parent).is_err()
+    {
+        return;
+    }
+
+    let mut stats = load_download_stats();
+    stats.apply(run, OffsetDateTime::now_utc());
+
+    let Ok(text) = serde_json::to_string_pretty(&stats) else {
+        return;
+    };
+    let tmp = path.with_extension("json.tmp");
+    if fs::write(&tmp, text).is_ok() {
+        let _ = fs::rename(&tmp, &path);
+    }
+}
+
+/// 将秒数格式化为「X 小时 Y 分」。
+pub fn format_elapsed(secs: u64) -> String {
+    let hours = secs / 3600;
+    let minutes = secs % 3600 / 60;
+    if hours > 0 {
+        format!("{hours} 小时 {minutes} 分")
+    } else if minutes > 0 {
+        format!("{minutes} 分 {} 秒", secs % 60)
+    } else {
+        format!("{secs} 秒")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DownloadRunSummary, DownloadStats, EndpointTotals};
+    use time::macros::datetime;
+
+    #[test]
+    fn accumulates_runs_and_endpoints() {
+        let mut stats = DownloadStats::default();
+        let run = DownloadRunSummary {
+            success_chapters: 10,
+            words: 30_000,
+            elapsed_secs: 90,
+            endpoints: vec![(
+                "https://a".to_string(),
+                EndpointTotals {
+                    requests: 4,
+                    success: 3,
+                    failed: 1,
+                },
+            )],
+        };
+        stats.apply(&run, datetime!(2026-01-02 03:04:05 UTC));
+        stats.apply(
+            &DownloadRunSummary {
+                elapsed_secs: 5,
+                ..run.clone()
+            },
+            datetime!(2026-01-03 00:00:00 UTC),
+        );
+        stats.apply(
+            &DownloadRunSummary::default(),
+            datetime!(2026-01-03 01:00:00 UTC),
+        );
+
+        assert_eq!(stats.books, 2);
+        assert_eq!(stats.chapters, 20);
+        assert_eq!(stats.elapsed_secs, 95);
+        assert_eq!(stats.endpoints["https://a"].success_rate(), Some(75));
+        let recent = stats.recent_daily(1);
+        assert_eq!(recent, vec![("2026-01-03", &stats.daily["2026-01-03"])]);
+        assert_eq!(stats.first_recorded, "2026-01-02T03:04:05Z");
+    }
+}
@@ -0,0 +1,148 @@
+//! 收藏书籍持久化（favorites.json）。
+//!
+//! 收藏列表与 `config.yml` 同目录保存，供 TUI 主菜单快速入口、搜索结果置顶使用。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use super::logging;
+
+const FAVORITES_FILE_NAME: &str = "favorites.json";
+
+pub fn favorites_file_path() -> PathBuf {
+    logging::current_data_dir()
+        .unwrap_or_default()
+        .join(FAVORITES_FILE_NAME)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FavoriteBook {
+    pub book_id: String,
+    pub book_name: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub added_at: String,
+}
+
+impl FavoriteBook {
+    pub fn new(book_id: String, book_name: String, author: String) -> Self {
+        let added_at = OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string());
+        Self {
+            book_id,
+            book_name,
+            author,
+            added_at,
+        }
+    }
+}
+
+pub fn load_favorites() -> Vec<FavoriteBook> {
+    load_favorites_from(&favorites_file_path())
+}
+
+pub fn save_favorites(list: &[FavoriteBook]) -> std::io::Result<()> {
+    save_favorites_to(&favorites_file_path(), list)
+}
+
+fn load_favorites_from(path: &Path) -> Vec<FavoriteBook> {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    serde_json::from_str(&raw).unwrap_or_default()
+}
+
+fn save_favorites_to(path: &Path, list: &[FavoriteBook]) -> std::io::Result<()> {
+    let raw = serde_json::to_string_pretty(list).map_err(std::io::Error::other)?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, raw)?;
+    fs::rename(&tmp, path)
+}
+
+pub fn is_favorite(list: &[FavoriteBook], book_id: &str) -> bool {
+    list.iter().any(|f| f.book_id == book_id)
+}
+
+/// 切换收藏状态：已收藏则移除，否则追加到末尾。返回切换后的状态（true = 已收藏）。
+pub fn toggle_favorite(list: &mut Vec<FavoriteBook>, book: FavoriteBook) -> bool {
+    if let Some(pos) = list.iter().position(|f| f.book_id == book.book_id) {
+        list.remove(pos);
+        false
+    } else {
+        list.push(book);
+        true
+    }
+}
+
+/// 搜索框补全：收藏优先，其次是下载历史中的书名；只返回以输入为前缀、且比输入更长的候选。
+pub fn completion_candidate<'a, I>(
+    input: &str,
+    favorites: &'a [FavoriteBook],
+    history: I,
+) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    favorites
+        .iter()
+        .map(|f| f.book_name.as_str())
+        .chain(history)
+        .find(|name| name.starts_with(input) && name.len() > input.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_and_persist_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(FAVORITES_FILE_NAME);
+
+        let mut list = Vec::new();
+        let book = FavoriteBook::new("1".into(), "测试书".into(), "作者".into());
+        assert!(toggle_favorite(&mut list, book.clone()));
+        save_favorites_to(&path, &list).unwrap();
+
+        let mut loaded = load_favorites_from(&path);
+        assert!(is_favorite(&loaded, "1"));
+        assert!(!toggle_favorite(&mut loaded, book));
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn completion_prefers_favorites_over_history() {
+        let favorites = vec![FavoriteBook::new(
+            "1".into(),
+            "斗破苍穹".into(),
+            String::new(),
+        )];
+        let history = ["斗罗大陆", "斗破苍穹之后"];
+        assert_eq!(
+            completion_candidate("斗", &favorites, history.iter().copied()),
+            Some("斗破苍穹")
+        );
+        assert_eq!(
+            completion_candidate("斗罗", &favorites, history.iter().copied()),
+            Some("斗罗大陆")
+        );
+        assert_eq!(
+            completion_candidate("斗破苍穹", &favorites, history.iter().copied()),
+            Some("斗破苍穹之后")
+        );
+        assert_eq!(
+            completion_candidate("", &favorites, history.iter().copied()),
+            None
+        );
+    }
+}
@@ -2,10 +2,127 @@
 
 use std::fs;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub fn is_empty_dir(path: impl AsRef<Path>) -> io::Result<bool> {
     let path = path.as_ref();
     let mut entries = fs::read_dir(path)?;
     Ok(entries.next().is_none())
 }
+
+/// 书籍状态目录中可单独清理的缓存类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpKind {
+    /// 正文/段评图片缓存 `images/`
+    Images,
+    /// 段评缓存 `segment_comments/`
+    SegmentComments,
+    /// 中间 jsonl（导出章节与追加日志）
+    Jsonl,
+}
+
+impl DumpKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            DumpKind::Images => "图片缓存",
+            DumpKind::SegmentComments => "段评缓存",
+            DumpKind::Jsonl => "中间 jsonl",
+        }
+    }
+
+    fn paths(self, folder: &Path) -> Vec<PathBuf> {
+        match self {
+            DumpKind::Images => vec![folder.join("images")],
+            DumpKind::SegmentComments => vec![folder.join("segment_comments")],
+            DumpKind::Jsonl => fs::read_dir(folder)
+                .map(|rd| {
+                    rd.flatten()
+                        .map(|e| e.path())
+                        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "jsonl"))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// 文件或目录占用的字节数（目录递归统计，读取失败的部分计为 0）。
+pub fn path_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path)
+        .map(|rd| rd.flatten().map(|e| path_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+/// 删除状态目录中指定类型的缓存，返回实际删除的类型及释放的字节数。
+pub fn clear_dump_kinds(folder: &Path, kinds: &[DumpKind]) -> io::Result<Vec<(DumpKind, u64)>> {
+    let mut cleared = Vec::new();
+    for &kind in kinds {
+        let mut freed = 0;
+        let mut removed_any = false;
+        for path in kind.paths(folder) {
+            if !path.exists() {
+                continue;
+            }
+            let size = path_size(&path);
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+            freed += size;
+            removed_any = true;
+        }
+        if removed_any {
+            cleared.push((kind, freed));
+        }
+    }
+    Ok(cleared)
+}
+
+/// 将字节数格式化为 B/KB/MB/GB。
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DumpKind, clear_dump_kinds, format_bytes};
+    use std::fs;
+
+    #[test]
+    fn clears_only_selected_kinds() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path();
+        fs::create_dir_all(folder.join("images")).unwrap();
+        fs::write(folder.join("images").join("a.jpg"), [0u8; 10]).unwrap();
+        fs::create_dir_all(folder.join("segment_comments")).unwrap();
+        fs::write(folder.join("chapters.jsonl"), "abc").unwrap();
+        fs::write(folder.join("downloaded_chapters.jsonl"), "de").unwrap();
+        fs::write(folder.join("status.json"), "{}").unwrap();
+
+        let cleared = clear_dump_kinds(folder, &[DumpKind::Images, DumpKind::Jsonl]).unwrap();
+        assert_eq!(cleared, vec![(DumpKind::Images, 10), (DumpKind::Jsonl, 5)]);
+        assert!(!folder.join("images").exists());
+        assert!(!folder.join("chapters.jsonl").exists());
+        assert!(folder.join("segment_comments").exists());
+        assert!(folder.join("status.json").exists());
+        assert_eq!(format_bytes(1536), "1.5 KB");
+    }
+}
@@ -0,0 +1,26 @@
+//! 调用系统文件管理器打开目录（或用默认程序打开文件）。
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// 跨平台打开路径：Windows 用 explorer，macOS 用 open，其余用 xdg-open。
+///
+/// 只负责启动外部程序，不等待其退出；路径不存在时返回 `NotFound`。
+pub fn open_in_file_manager(path: &Path) -> io::Result<()> {
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("路径不存在: {}", path.display()),
+        ));
+    }
+    let program = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    Command::new(program).arg(path).spawn()?;
+    Ok(())
+}
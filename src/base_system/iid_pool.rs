@@ -0,0 +1,253 @@
+//! 官方 API 的 IID 池：多个 IID 轮换使用，接近 12 小时或请求失败时后台补充新 IID。
+//!
+//! 注册/切换 IID 需要官方 API crate 实现 [`IidBackend`]；该 crate 目前只暴露单 IID 的
+//! `prewarm_iid()`，在它提供这两个接口之前，主程序仍只做单 IID 的后台续期。
+
+#![allow(dead_code)]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tracing::{info, warn};
+
+/// 官方服务端对单个 IID 的使用时长上限。
+pub const IID_MAX_AGE: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// 距上限不足该时长即视为即将过期，不再分配给新请求。
+const IID_EXPIRY_MARGIN: Duration = Duration::from_secs(30 * 60);
+
+/// 默认池大小。
+pub const DEFAULT_POOL_SIZE: usize = 3;
+
+/// 需要官方 API crate 提供的多 IID 接口。
+pub trait IidBackend: Send + Sync {
+    /// 注册一个新 IID，不影响当前正在使用的 IID。
+    fn register(&self) -> Result<String>;
+    /// 让后续请求改用指定 IID。
+    fn activate(&self, iid: &str) -> Result<()>;
+}
+
+#[derive(Debug, Clone)]
+struct IidSlot {
+    iid: String,
+    registered_at: Instant,
+    healthy: bool,
+    failures: u32,
+}
+
+/// 线程安全的 IID 池。
+pub struct IidPool {
+    slots: Mutex<Vec<IidSlot>>,
+    next: AtomicUsize,
+    capacity: usize,
+    max_age: Duration,
+    refilling: AtomicBool,
+}
+
+impl IidPool {
+    pub fn new(capacity: usize) -> Self {
+        Self::with_max_age(capacity, IID_MAX_AGE)
+    }
+
+    pub fn with_max_age(capacity: usize, max_age: Duration) -> Self {
+        Self {
+            slots: Mutex::new(Vec::new()),
+            next: AtomicUsize::new(0),
+            capacity: capacity.max(1),
+            max_age,
+            refilling: AtomicBool::new(false),
+        }
+    }
+
+    /// 加入一个刚注册的 IID；超出容量时淘汰最旧的。
+    pub fn insert(&self, iid: impl Into<String>) {
+        self.insert_at(iid.into(), Instant::now());
+    }
+
+    fn insert_at(&self, iid: String, registered_at: Instant) {
+        let mut slots = self.lock();
+        slots.retain(|s| s.iid != iid);
+        slots.push(IidSlot {
+            iid,
+            registered_at,
+            healthy: true,
+            failures: 0,
+        });
+        slots.sort_by_key(|s| s.registered_at);
+        while slots.len() > self.capacity {
+            slots.remove(0);
+        }
+    }
+
+    /// 轮换挑选一个健康且未临近过期的 IID。
+    pub fn pick(&self) -> Option<String> {
+        let now = Instant::now();
+        let slots = self.lock();
+        let usable: Vec<&IidSlot> = slots.iter().filter(|s| self.usable(s, now)).collect();
+        if usable.is_empty() {
+            return None;
+        }
+        let idx = self.next.fetch_add(1, Ordering::Relaxed) % usable.len();
+        Some(usable[idx].iid.clone())
+    }
+
+    /// 请求失败：标记为不健康，不再分配。
+    pub fn mark_failed(&self, iid: &str) {
+        if let Some(slot) = self.lock().iter_mut().find(|s| s.iid == iid) {
+            slot.healthy = false;
+            slot.failures = slot.failures.saturating_add(1);
+        }
+    }
+
+    /// 请求成功：恢复健康标记。
+    pub fn mark_ok(&self, iid: &str) {
+        if let Some(slot) = self.lock().iter_mut().find(|s| s.iid == iid) {
+            slot.healthy = true;
+            slot.failures = 0;
+        }
+    }
+
+    /// 可分配的 IID 数量。
+    pub fn healthy_count(&self) -> usize {
+        let now = Instant::now();
+        self.lock().iter().filter(|s| self.usable(s, now)).count()
+    }
+
+    /// 移除不健康或即将过期的 IID，并补充到满容量；返回新加入的数量。
+    pub fn refill(&self, backend: &dyn IidBackend) -> Result<usize> {
+        let now = Instant::now();
+        self.lock().retain(|s| self.usable(s, now));
+        let missing = self.capacity.saturating_sub(self.healthy_count());
+        let mut added = 0;
+        for _ in 0..missing {
+            match backend.register() {
+                Ok(iid) => {
+                    self.insert(iid);
+                    added += 1;
+                }
+                Err(err) if self.healthy_count() > 0 => {
+                    warn!(target: "startup", "IID 池补充失败: {err:#}");
+                    break;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(added)
+    }
+
+    /// 后台补充；已有补充任务进行中时跳过。
+    pub fn refill_in_background(self: &Arc<Self>, backend: Arc<dyn IidBackend>) {
+        if self.refilling.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let pool = Arc::clone(self);
+        std::thread::spawn(move || {
+            match pool.refill(backend.as_ref()) {
+                Ok(0) => {}
+                Ok(added) => info!(target: "startup", "IID 池已补充 {added} 个"),
+                Err(err) => warn!(target: "startup", "IID 池补充失败: {err:#}"),
+            }
+            pool.refilling.store(false, Ordering::SeqCst);
+        });
+    }
+
+    /// 挑选 IID 并交给官方 API 使用；池空时先同步补充一次。
+    pub fn activate_next(&self, backend: &dyn IidBackend) -> Result<String> {
+        let iid = match self.pick() {
+            Some(iid) => iid,
+            None => {
+                self.refill(backend)?;
+                self.pick()
+                    .ok_or_else(|| anyhow::anyhow!("IID 池中没有可用的 IID"))?
+            }
+        };
+        backend.activate(&iid)?;
+        Ok(iid)
+    }
+
+    fn usable(&self, slot: &IidSlot, now: Instant) -> bool {
+        slot.healthy
+            && now.saturating_duration_since(slot.registered_at) + IID_EXPIRY_MARGIN < self.max_age
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<IidSlot>> {
+        self.slots.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    struct FakeBackend {
+        counter: AtomicU32,
+        active: Mutex<Option<String>>,
+    }
+
+    impl FakeBackend {
+        fn new() -> Self {
+            Self {
+                counter: AtomicU32::new(0),
+                active: Mutex::new(None),
+            }
+        }
+    }
+
+    impl IidBackend for FakeBackend {
+        fn register(&self) -> Result<String> {
+            Ok(format!(
+                "iid-{}",
+                self.counter.fetch_add(1, Ordering::SeqCst)
+            ))
+        }
+
+        fn activate(&self, iid: &str) -> Result<()> {
+            *self.active.lock().unwrap() = Some(iid.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn rotates_and_skips_failed() {
+        let pool = IidPool::new(3);
+        pool.insert("a");
+        pool.insert("b");
+        pool.insert("c");
+        let picked: Vec<String> = (0..3).filter_map(|_| pool.pick()).collect();
+        assert_eq!(picked.len(), 3);
+        assert_ne!(picked[0], picked[1]);
+
+        pool.mark_failed("b");
+        assert_eq!(pool.healthy_count(), 2);
+        assert!((0..6).filter_map(|_| pool.pick()).all(|iid| iid != "b"));
+
+        pool.mark_ok("b");
+        assert_eq!(pool.healthy_count(), 3);
+    }
+
+    #[test]
+    fn refill_replaces_expiring_and_failed() {
+        let pool = IidPool::with_max_age(2, Duration::from_secs(3600));
+        let old = Instant::now()
+            .checked_sub(Duration::from_secs(3500))
+            .unwrap();
+        pool.insert_at("old".to_string(), old);
+        pool.insert("bad");
+        pool.mark_failed("bad");
+        assert_eq!(pool.healthy_count(), 0);
+
+        let backend = FakeBackend::new();
+        assert_eq!(pool.refill(&backend).unwrap(), 2);
+        assert_eq!(pool.healthy_count(), 2);
+
+        let iid = pool.activate_next(&backend).unwrap();
+        assert!(iid.starts_with("iid-"));
+        assert_eq!(
+            backend.active.lock().unwrap().as_deref(),
+            Some(iid.as_str())
+        );
+    }
+}
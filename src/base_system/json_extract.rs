@@ -2,10 +2,63 @@
 
 #![cfg_attr(not(feature = "official-api"), allow(dead_code))]
 
+use std::collections::{HashMap, HashSet};
+
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 
 pub type JsonMap = serde_json::Map<String, Value>;
 
+/// 诊断信息中保留的响应体预览长度（字符）。
+const BODY_PREVIEW_CHARS: usize = 80;
+
+/// 响应体无法按 JSON 解析时的诊断结果，替代晦涩的 `Expecting value: line 1 column 1`。
+#[derive(Debug, thiserror::Error)]
+pub enum ResponseJsonError {
+    #[error("服务器返回空响应（可能被风控或 endpoint 失效）")]
+    Empty,
+    #[error("服务器返回非 JSON（{kind}，可能被风控或 endpoint 失效）：{preview}")]
+    NotJson { kind: &'static str, preview: String },
+    /// 形似 JSON 但解析失败，多为传输中断导致的截断
+    #[error("响应 JSON 解析失败：{0}")]
+    Malformed(#[from] serde_json::Error),
+}
+
+impl ResponseJsonError {
+    /// 空响应/非 JSON 通常是当前源失效，应换源而不是原地重试。
+    pub fn should_switch_source(&self) -> bool {
+        !matches!(self, ResponseJsonError::Malformed(_))
+    }
+}
+
+/// 解析 API 响应体：先识别空响应与 HTML/纯文本错误页，再按 JSON 反序列化。
+pub fn parse_json_or_diagnose<T: DeserializeOwned>(body: &[u8]) -> Result<T, ResponseJsonError> {
+    let text = String::from_utf8_lossy(body);
+    let trimmed = text.trim_start_matches('\u{feff}').trim();
+    if trimmed.is_empty() {
+        return Err(ResponseJsonError::Empty);
+    }
+    let looks_like_json = trimmed.starts_with(['{', '[', '"'])
+        || trimmed.starts_with(|c: char| c == '-' || c.is_ascii_digit())
+        || matches!(trimmed, "null" | "true" | "false");
+    if !looks_like_json {
+        let kind = if trimmed.starts_with('<') {
+            "HTML 页面"
+        } else {
+            "纯文本"
+        };
+        let preview = trimmed
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(BODY_PREVIEW_CHARS)
+            .collect();
+        return Err(ResponseJsonError::NotJson { kind, preview });
+    }
+    Ok(serde_json::from_str(trimmed)?)
+}
+
 pub fn collect_maps(raw: &Value) -> Vec<&JsonMap> {
     let mut maps = Vec::new();
     if let Some(map) = raw.as_object() {
@@ -274,6 +327,78 @@ pub fn pick_word_count(map: &JsonMap) -> Option<usize> {
     None
 }
 
+/// 从目录原始 JSON 提取逐章字数（章节 ID -> 字数）；源不提供时返回空表。
+pub fn chapter_word_counts(raw: &Value) -> HashMap<String, usize> {
+    let mut out = HashMap::new();
+    for key in ["catalog_data", "item_data_list", "items"] {
+        let Some(arr) = raw.get(key).and_then(Value::as_array) else {
+            continue;
+        };
+        for obj in arr.iter().filter_map(Value::as_object) {
+            let id = match obj
+                .get("item_id")
+                .or_else(|| obj.get("catalog_id"))
+                .or_else(|| obj.get("id"))
+            {
+                Some(Value::String(s)) if !s.trim().is_empty() => s.trim().to_string(),
+                Some(Value::Number(n)) => n.to_string(),
+                _ => continue,
+            };
+            if let Some(words) = pick_word_count(obj).filter(|w| *w > 0) {
+                out.entry(id).or_insert(words);
+            }
+        }
+    }
+    out
+}
+
+/// 从目录原始 JSON 提取标注为付费/未解锁的章节 ID。
+pub fn paid_chapter_ids(raw: &Value) -> HashSet<String> {
+    let mut out = HashSet::new();
+    for key in ["catalog_data", "item_data_list", "items", "chapters"] {
+        let Some(arr) = raw.get(key).and_then(Value::as_array) else {
+            continue;
+        };
+        for obj in arr.iter().filter_map(Value::as_object) {
+            let id = match obj
+                .get("item_id")
+                .or_else(|| obj.get("itemId"))
+                .or_else(|| obj.get("catalog_id"))
+                .or_else(|| obj.get("id"))
+            {
+                Some(Value::String(s)) if !s.trim().is_empty() => s.trim().to_string(),
+                Some(Value::Number(n)) => n.to_string(),
+                _ => continue,
+            };
+            if is_paid_chapter(obj) {
+                out.insert(id);
+            }
+        }
+    }
+    out
+}
+
+fn is_paid_chapter(map: &JsonMap) -> bool {
+    let candidates = [
+        "need_pay",
+        "needPay",
+        "is_pay",
+        "isPay",
+        "is_vip",
+        "isVip",
+        "is_chapter_lock",
+        "isChapterLock",
+        "chapter_lock",
+        "is_locked",
+    ];
+    candidates.iter().any(|key| match map.get(*key) {
+        Some(Value::Bool(b)) => *b,
+        Some(Value::Number(n)) => n.as_i64().is_some_and(|v| v != 0),
+        Some(Value::String(s)) => matches!(s.trim(), "1" | "true"),
+        _ => false,
+    })
+}
+
 pub fn pick_score(map: &JsonMap) -> Option<f32> {
     let candidates = ["score", "book_score", "rating"];
     for key in candidates {
@@ -437,3 +562,32 @@ pub fn tags_from_value(value: &Value) -> Vec<String> {
         _ => Vec::new(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ResponseJsonError, parse_json_or_diagnose};
+    use serde_json::Value;
+
+    #[test]
+    fn diagnoses_empty_and_non_json_bodies() {
+        let err = parse_json_or_diagnose::<Value>(b"  \r\n").unwrap_err();
+        assert!(matches!(err, ResponseJsonError::Empty));
+        assert!(err.should_switch_source());
+
+        let err = parse_json_or_diagnose::<Value>(b"<html><title>403 Forbidden</title></html>")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ResponseJsonError::NotJson {
+                kind: "HTML 页面",
+                ..
+            }
+        ));
+
+        let err = parse_json_or_diagnose::<Value>(br#"{"code":0,"data":"#).unwrap_err();
+        assert!(!err.should_switch_source());
+
+        let v: Value = parse_json_or_diagnose("\u{feff}{\"code\":0}".as_bytes()).unwrap();
+        assert_eq!(v["code"], 0);
+    }
+}
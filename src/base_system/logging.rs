@@ -48,6 +48,8 @@ pub struct LogOptions {
     pub use_color: bool,
     pub archive_on_exit: bool,
     pub console: bool,
+    /// 控制台日志写到 stderr（默认 stdout），供 JSON 输出模式保持 stdout 干净
+    pub console_stderr: bool,
     pub broadcast_to_ui: bool,
 }
 
@@ -58,6 +60,7 @@ impl Default for LogOptions {
             use_color: true,
             archive_on_exit: true,
             console: true,
+            console_stderr: false,
             broadcast_to_ui: true,
         }
     }
@@ -73,6 +76,14 @@ pub fn current_logs_dir() -> Option<PathBuf> {
     LOGS_DIR.get().cloned()
 }
 
+/// 数据目录（`config.yml` 所在目录，即 logs 的上一级）；日志尚未初始化时为 None。
+pub fn current_data_dir() -> Option<PathBuf> {
+    LOGS_DIR
+        .get()
+        .and_then(|dir| dir.parent())
+        .map(Path::to_path_buf)
+}
+
 #[derive(Clone)]
 struct ChannelWriter {
     tx: crossbeam_channel::Sender<String>,
@@ -146,7 +157,9 @@ impl LogSystem {
             LevelFilter::INFO
         };
 
-        let console_writer: BoxMakeWriter = if options.console {
+        let console_writer: BoxMakeWriter = if options.console && options.console_stderr {
+            BoxMakeWriter::new(io::stderr)
+        } else if options.console {
             BoxMakeWriter::new(io::stdout)
         } else {
             BoxMakeWriter::new(io::sink)
@@ -165,9 +178,10 @@ impl LogSystem {
                 .get_or_init(crossbeam_channel::unbounded)
                 .clone();
             let writer = BoxMakeWriter::new(ChannelWriterMake { tx });
+            // 保留 target：TUI 日志查看器据此着色，Web 端据此过滤
             Some(
                 fmt::layer()
-                    .with_target(false)
+                    .with_target(true)
                     .with_level(true)
                     .with_thread_names(false)
                     .with_ansi(false)
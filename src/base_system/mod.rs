@@ -4,13 +4,25 @@
 
 pub mod app_update;
 pub mod book_id;
+pub mod book_library;
 pub mod book_paths;
 pub mod config;
 pub mod context;
 pub mod cooldown_retry;
 pub mod download_history;
+pub mod download_queue;
+pub mod download_stats;
+pub mod favorites;
 pub mod file_cleaner;
+pub mod file_manager;
+pub mod iid_pool;
 pub mod json_extract;
 pub mod logging;
+pub mod notify;
 pub mod novel_updates;
+pub mod path_expand;
+pub mod request_error;
+pub mod request_trace;
+pub mod search_query;
 pub mod self_update;
+pub mod webdav;
@@ -0,0 +1,157 @@
+//! 桌面系统通知。
+//!
+//! 借助各平台自带命令发送，不引入额外依赖：
+//! - Windows：PowerShell Toast
+//! - macOS：osascript
+//! - Linux：notify-send；Android(Termux)：termux-notification
+//!
+//! 发送在后台线程进行，任何失败都只记录 debug 日志，不影响下载主流程。
+
+use std::process::{Command, Stdio};
+use std::thread;
+
+use tracing::debug;
+
+use super::context::Config;
+
+const APP_NAME: &str = "Tomato-Novel-Downloader";
+
+/// Windows 下通过环境变量把标题/正文交给 PowerShell，避免拼进脚本文本。
+const PS_TITLE_ENV: &str = "TOMATO_NOTIFY_TITLE";
+const PS_BODY_ENV: &str = "TOMATO_NOTIFY_BODY";
+
+/// 下载结束通知（成功/部分失败/失败），未开启 `enable_notifications` 时不做任何事。
+pub fn notify_download_finished(
+    config: &Config,
+    book_name: &str,
+    success: usize,
+    failed: usize,
+    error: Option<&str>,
+) {
+    if !config.enable_notifications {
+        return;
+    }
+    let (title, body) = download_message(book_name, success, failed, error);
+    send_notification(title, body);
+}
+
+fn download_message(
+    book_name: &str,
+    success: usize,
+    failed: usize,
+    error: Option<&str>,
+) -> (String, String) {
+    let book = if book_name.trim().is_empty() {
+        "未知书籍"
+    } else {
+        book_name.trim()
+    };
+    match error {
+        Some(err) => (
+            "下载失败".to_string(),
+            format!("《{}》下载失败：{}", book, err.trim()),
+        ),
+        None if failed == 0 => (
+            "下载完成".to_string(),
+            format!("《{}》已完成，共 {} 章", book, success),
+        ),
+        None => (
+            "下载完成（部分失败）".to_string(),
+            format!("《{}》成功 {} 章，失败 {} 章", book, success, failed),
+        ),
+    }
+}
+
+/// 异步发送一条系统通知，失败静默忽略。
+pub fn send_notification(title: String, body: String) {
+    thread::spawn(move || {
+        let Some(mut cmd) = build_command(&title, &body) else {
+            debug!(target: "notify", "当前平台不支持系统通知");
+            return;
+        };
+        match cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+        {
+            Ok(status) if status.success() => {}
+            Ok(status) => debug!(target: "notify", %status, "系统通知命令返回失败"),
+            Err(e) => debug!(target: "notify", error = %e, "系统通知命令无法执行"),
+        }
+    });
+}
+
+fn build_command(title: &str, body: &str) -> Option<Command> {
+    if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("powershell");
+        cmd.args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-WindowStyle",
+            "Hidden",
+            "-Command",
+            WINDOWS_TOAST_SCRIPT,
+        ]);
+        cmd.env(PS_TITLE_ENV, title).env(PS_BODY_ENV, body);
+        return Some(cmd);
+    }
+    if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("osascript");
+        cmd.args([
+            "-e",
+            &format!(
+                "display notification {} with title {}",
+                applescript_quote(body),
+                applescript_quote(title)
+            ),
+        ]);
+        return Some(cmd);
+    }
+    if cfg!(target_os = "android") {
+        let mut cmd = Command::new("termux-notification");
+        cmd.args(["--title", title, "--content", body]);
+        return Some(cmd);
+    }
+    if cfg!(unix) {
+        let mut cmd = Command::new("notify-send");
+        // `--` 之后都是位置参数，书名以 `-` 开头也不会被当作选项
+        cmd.args(["--app-name", APP_NAME, "--", title, body]);
+        return Some(cmd);
+    }
+    None
+}
+
+/// 借用 PowerShell 的 AppUserModelID，未注册快捷方式的程序也能弹出 Toast。
+/// 标题/正文从环境变量读取，脚本本身是常量，不存在引号转义问题。
+const WINDOWS_TOAST_SCRIPT: &str = "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+     $t = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+     $x = $t.GetElementsByTagName('text'); \
+     $x.Item(0).AppendChild($t.CreateTextNode($env:TOMATO_NOTIFY_TITLE)) > $null; \
+     $x.Item(1).AppendChild($t.CreateTextNode($env:TOMATO_NOTIFY_BODY)) > $null; \
+     [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\WindowsPowerShell\\v1.0\\powershell.exe').Show([Windows.UI.Notifications.ToastNotification]::new($t))";
+
+fn applescript_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_and_quoting() {
+        let (title, body) = download_message("全球尸化", 10, 0, None);
+        assert_eq!(title, "下载完成");
+        assert!(body.contains("共 10 章"));
+        let (title, _) = download_message("全球尸化", 8, 2, None);
+        assert_eq!(title, "下载完成（部分失败）");
+        let (title, body) = download_message("", 0, 0, Some("网络错误"));
+        assert_eq!(title, "下载失败");
+        assert!(body.contains("未知书籍"));
+
+        assert!(WINDOWS_TOAST_SCRIPT.contains(&format!("$env:{PS_TITLE_ENV}")));
+        assert!(WINDOWS_TOAST_SCRIPT.contains(&format!("$env:{PS_BODY_ENV}")));
+        assert_eq!(applescript_quote(r#"a"b\"#), r#""a\"b\\""#);
+    }
+}
@@ -14,6 +14,10 @@ use serde_json::Value;
 #[cfg(feature = "official-api")]
 use tomato_novel_official_api::DirectoryClient;
 
+use crate::base_system::context::Config;
+
+#[cfg(not(feature = "official-api"))]
+use crate::base_system::json_extract;
 #[cfg(not(feature = "official-api"))]
 use crate::network_parser::network::{FanqieWebConfig, FanqieWebNetwork};
 
@@ -28,12 +32,25 @@ pub struct NovelUpdateRow {
     pub new_count: usize,
     pub has_update: bool,
     pub is_ignored: bool,
+    /// 本地 status.json 中记录的标签与分类，用于按标签过滤扫描结果。
+    pub tags: Vec<String>,
+    pub category: String,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct NovelUpdateScanResult {
     pub updates: Vec<NovelUpdateRow>,
     pub no_updates: Vec<NovelUpdateRow>,
+    /// 同一 book_id 对应多个缓存文件夹（书名变更遗留），需要提示用户合并。
+    pub duplicates: Vec<DuplicateBookFolders>,
+}
+
+/// 同一 book_id 的多个本地缓存文件夹；`folders[0]` 为扫描时采用的主文件夹。
+#[derive(Debug, Clone)]
+pub struct DuplicateBookFolders {
+    pub book_id: String,
+    pub book_name: String,
+    pub folders: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -47,10 +64,25 @@ pub struct NovelUpdateProgress {
 struct LocalBookStatus {
     book_id: String,
     book_name: String,
+    author: String,
     folder: PathBuf,
     local_total: usize,
     local_failed: usize,
     is_ignored: bool,
+    tags: Vec<String>,
+    category: String,
+}
+
+/// 书库条目：本地已下载的一本书，仅读取 status.json，不访问网络。
+#[derive(Debug, Clone)]
+pub struct LocalBookEntry {
+    pub book_id: String,
+    pub book_name: String,
+    pub author: String,
+    pub folder: PathBuf,
+    pub local_total: usize,
+    pub local_failed: usize,
+    pub is_ignored: bool,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -63,36 +95,94 @@ struct UpdateCacheFile {
 struct CachedRemoteTotal {
     remote_total: usize,
     checked_ms: u64,
+    /// 目录指纹（章节数 + 末章 ID），用于判断目录自上次检查后是否变化
+    #[serde(default)]
+    fingerprint: String,
+    /// 指纹最近一次变化的时间
+    #[serde(default)]
+    changed_ms: u64,
+}
+
+/// 一次远端目录请求的结果。
+#[derive(Debug, Clone)]
+struct RemoteDirectory {
+    total: usize,
+    fingerprint: String,
+}
+
+impl RemoteDirectory {
+    fn new(total: usize, last_chapter_id: &str) -> Self {
+        Self {
+            total,
+            fingerprint: format!("{total}:{last_chapter_id}"),
+        }
+    }
+}
+
+impl CachedRemoteTotal {
+    fn refreshed(prev: Option<&Self>, remote: &RemoteDirectory, now: u64) -> Self {
+        let changed_ms = match prev {
+            Some(p) if !p.fingerprint.is_empty() && p.fingerprint == remote.fingerprint => {
+                p.changed_ms
+            }
+            _ => now,
+        };
+        Self {
+            remote_total: remote.total,
+            checked_ms: now,
+            fingerprint: remote.fingerprint.clone(),
+            changed_ms,
+        }
+    }
+
+    /// 目录长期未变（多为完结/断更）的书延长缓存有效期，减少多本书扫描时的目录请求。
+    fn ttl_ms(&self, now: u64) -> u64 {
+        let stable = !self.fingerprint.is_empty()
+            && now.saturating_sub(self.changed_ms) >= UPDATE_CACHE_STABLE_AFTER_MS;
+        if stable {
+            UPDATE_CACHE_STABLE_TTL_MS
+        } else {
+            UPDATE_CACHE_TTL_MS
+        }
+    }
 }
 
 const UPDATE_CACHE_FILE: &str = ".tnd_update_cache.json";
 const UPDATE_CACHE_TTL_MS: u64 = 10 * 60 * 1000;
+/// 目录指纹连续这么久未变时，改用 [`UPDATE_CACHE_STABLE_TTL_MS`]。
+const UPDATE_CACHE_STABLE_AFTER_MS: u64 = 3 * 24 * 60 * 60 * 1000;
+const UPDATE_CACHE_STABLE_TTL_MS: u64 = 60 * 60 * 1000;
 const UPDATE_SCAN_WORKERS: usize = 4;
 
-/// 扫描保存目录下的书籍缓存文件夹（新版为 `<book_id>`，兼容旧版 `<book_id>_<book_name>`），并对比远端目录。
+/// 扫描各保存根目录（见 `Config::save_roots`）下的书籍缓存文件夹（新版为 `<book_id>`，兼容旧版 `<book_id>_<book_name>`），并对比远端目录。
 ///
 /// 备注："新章节" 以本地已知章节条目数（包含失败/空内容条目）为基准，避免把失败章误报成新章。
 #[allow(dead_code)]
-pub fn scan_novel_updates(save_dir: &Path) -> Result<NovelUpdateScanResult> {
-    scan_novel_updates_with_progress(save_dir, |_| {})
+pub fn scan_novel_updates(roots: &[PathBuf]) -> Result<NovelUpdateScanResult> {
+    scan_novel_updates_with_progress(roots, |_| {})
 }
 
 /// 带进度回调的更新扫描。回调会在每本书拿到远端章节数后立即触发，适合 TUI/CLI 边扫边显示。
+///
+/// 远端章节数缓存统一存放在第一个根目录（默认保存目录）。
 pub fn scan_novel_updates_with_progress<F>(
-    save_dir: &Path,
+    roots: &[PathBuf],
     mut on_progress: F,
 ) -> Result<NovelUpdateScanResult>
 where
     F: FnMut(NovelUpdateProgress),
 {
-    let local_books = collect_local_book_statuses(save_dir)?;
+    let (local_books, duplicates) = dedupe_local_books(collect_books_in_roots(roots)?);
     if local_books.is_empty() {
         return Ok(NovelUpdateScanResult::default());
     }
+    let Some(cache_dir) = roots.first() else {
+        return Ok(NovelUpdateScanResult::default());
+    };
 
     let total = local_books.len();
     let now = now_ms();
-    let mut cache = load_update_cache(save_dir);
+    let mut cache = load_update_cache(cache_dir);
     let mut needs_refresh = Vec::new();
     let mut updates = Vec::new();
     let mut no_updates = Vec::new();
@@ -107,7 +197,7 @@ where
 
     for book in &local_books {
         if let Some(cached) = cache.entries.get(&book.book_id) {
-            let fresh = now.saturating_sub(cached.checked_ms) <= UPDATE_CACHE_TTL_MS;
+            let fresh = now.saturating_sub(cached.checked_ms) <= cached.ttl_ms(now);
             if fresh && cached.remote_total > 0 {
                 record_update_row(
                     book,
@@ -126,14 +216,10 @@ where
     }
 
     if !needs_refresh.is_empty() {
-        let fetched = fetch_remote_totals_streaming(needs_refresh, |book_id, remote_total| {
-            cache.entries.insert(
-                book_id.clone(),
-                CachedRemoteTotal {
-                    remote_total,
-                    checked_ms: now,
-                },
-            );
+        let fetched = fetch_remote_totals_streaming(needs_refresh, |book_id, remote| {
+            let entry = CachedRemoteTotal::refreshed(cache.entries.get(&book_id), &remote, now);
+            cache.entries.insert(book_id.clone(), entry);
+            let remote_total = remote.total;
 
             if let Some(book) = by_id.get(&book_id) {
                 record_update_row(
@@ -170,7 +256,7 @@ where
             }
         }
 
-        save_update_cache(save_dir, &cache);
+        save_update_cache(cache_dir, &cache);
     }
 
     updates.sort_by(|a, b| b.new_count.cmp(&a.new_count));
@@ -178,9 +264,98 @@ where
     Ok(NovelUpdateScanResult {
         updates,
         no_updates,
+        duplicates,
     })
 }
 
+/// 列出各保存根目录下所有已下载书籍（同一 book_id 多个文件夹时取主文件夹），按书名排序。
+pub fn list_local_books(roots: &[PathBuf]) -> Result<Vec<LocalBookEntry>> {
+    let (books, _duplicates) = dedupe_local_books(collect_books_in_roots(roots)?);
+    let mut entries: Vec<LocalBookEntry> = books
+        .into_iter()
+        .map(|b| LocalBookEntry {
+            book_id: b.book_id,
+            book_name: b.book_name,
+            author: b.author,
+            folder: b.folder,
+            local_total: b.local_total,
+            local_failed: b.local_failed,
+            is_ignored: b.is_ignored,
+        })
+        .collect();
+    entries.sort_by(|a, b| a.book_name.cmp(&b.book_name));
+    Ok(entries)
+}
+
+/// 仅扫描本地目录，找出同一 book_id 存在多个缓存文件夹的书籍（不访问网络）。
+pub fn find_duplicate_book_folders(roots: &[PathBuf]) -> Result<Vec<DuplicateBookFolders>> {
+    let (_, duplicates) = dedupe_local_books(collect_books_in_roots(roots)?);
+    Ok(duplicates)
+}
+
+/// 将同一 book_id 的所有缓存文件夹合并到主文件夹所在根目录下的稳定目录 `<book_id>`。
+///
+/// 复用 `Config::migrate_status_folder_to_stable`：status.json 保留已下载条目更多的一份，
+/// `downloaded_chapters.jsonl` 追加合并，其余文件按“目标已存在则丢弃来源”处理。
+pub fn merge_duplicate_book_folders(
+    config: &Config,
+    dup: &DuplicateBookFolders,
+) -> Result<PathBuf> {
+    let book_id = dup.book_id.as_str();
+    let save_dir = dup
+        .folders
+        .first()
+        .and_then(|f| f.parent())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| config.default_save_dir());
+    config
+        .migrate_status_folder_to_stable(book_id, Some(&save_dir))
+        .with_context(|| format!("merge status folders for {book_id}"))
+}
+
+/// 按 book_id 聚合：每个 id 只保留一个主文件夹参与更新检测，其余作为重复项返回。
+///
+/// 主文件夹优先选已知章节条目最多的（即最新状态），相同时优先稳定目录 `<book_id>`。
+fn dedupe_local_books(
+    books: Vec<LocalBookStatus>,
+) -> (Vec<LocalBookStatus>, Vec<DuplicateBookFolders>) {
+    let mut order: Vec<String> = Vec::new();
+    let mut groups: HashMap<String, Vec<LocalBookStatus>> = HashMap::new();
+    for book in books {
+        if !groups.contains_key(&book.book_id) {
+            order.push(book.book_id.clone());
+        }
+        groups.entry(book.book_id.clone()).or_default().push(book);
+    }
+
+    let mut kept = Vec::with_capacity(order.len());
+    let mut duplicates = Vec::new();
+    for book_id in order {
+        let Some(mut group) = groups.remove(&book_id) else {
+            continue;
+        };
+        group.sort_by(|a, b| {
+            let a_stable =
+                a.folder.file_name().and_then(|n| n.to_str()) == Some(a.book_id.as_str());
+            let b_stable =
+                b.folder.file_name().and_then(|n| n.to_str()) == Some(b.book_id.as_str());
+            b.local_total
+                .cmp(&a.local_total)
+                .then(b_stable.cmp(&a_stable))
+                .then(a.folder.cmp(&b.folder))
+        });
+        if group.len() > 1 {
+            duplicates.push(DuplicateBookFolders {
+                book_id: book_id.clone(),
+                book_name: group[0].book_name.clone(),
+                folders: group.iter().map(|b| b.folder.clone()).collect(),
+            });
+        }
+        kept.push(group.swap_remove(0));
+    }
+    (kept, duplicates)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn record_update_row<F>(
     book: &LocalBookStatus,
@@ -227,7 +402,17 @@ fn row_from_book(book: &LocalBookStatus, remote_total: usize) -> NovelUpdateRow
         new_count,
         has_update,
         is_ignored: book.is_ignored,
+        tags: book.tags.clone(),
+        category: book.category.clone(),
+    }
+}
+
+fn collect_books_in_roots(roots: &[PathBuf]) -> Result<Vec<LocalBookStatus>> {
+    let mut books = Vec::new();
+    for root in roots {
+        books.extend(collect_local_book_statuses(root)?);
     }
+    Ok(books)
 }
 
 fn collect_local_book_statuses(save_dir: &Path) -> Result<Vec<LocalBookStatus>> {
@@ -268,15 +453,30 @@ fn collect_local_book_statuses(save_dir: &Path) -> Result<Vec<LocalBookStatus>>
             .filter(|s| !s.trim().is_empty())
             .map(|s| s.to_string())
             .unwrap_or(legacy_name);
+        let author = status_value
+            .get("author")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        let category = status_value
+            .get("category")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .trim()
+            .to_string();
+        let tags = tags_from_status(&status_value);
         let (local_total, _local_ok, local_failed) = counts.unwrap_or((0, 0, 0));
 
         books.push(LocalBookStatus {
             book_id,
             book_name,
+            author,
             folder: path,
             local_total,
             local_failed,
             is_ignored,
+            tags,
+            category,
         });
     }
 
@@ -311,7 +511,7 @@ fn fetch_remote_totals_streaming<F>(
     mut on_result: F,
 ) -> HashMap<String, usize>
 where
-    F: FnMut(String, usize),
+    F: FnMut(String, RemoteDirectory),
 {
     if book_ids.is_empty() {
         return HashMap::new();
@@ -330,9 +530,9 @@ where
     drop(tx);
 
     let mut results = HashMap::new();
-    for (book_id, remote_total) in rx {
-        results.insert(book_id.clone(), remote_total);
-        on_result(book_id, remote_total);
+    for (book_id, remote) in rx {
+        results.insert(book_id.clone(), remote.total);
+        on_result(book_id, remote);
     }
 
     for handle in handles {
@@ -345,19 +545,18 @@ where
 #[cfg(feature = "official-api")]
 fn fetch_remote_totals_worker(
     queue: Arc<Mutex<VecDeque<String>>>,
-    tx: mpsc::Sender<(String, usize)>,
+    tx: mpsc::Sender<(String, RemoteDirectory)>,
 ) {
     let Ok(client) = DirectoryClient::new() else {
         return;
     };
     while let Some(book_id) = queue.lock().ok().and_then(|mut q| q.pop_front()) {
-        let total = client
-            .fetch_directory(&book_id)
-            .ok()
-            .map(|d| d.chapters.len())
-            .filter(|n| *n > 0);
-        if let Some(total) = total {
-            let _ = tx.send((book_id, total));
+        let remote = client.fetch_directory(&book_id).ok().and_then(|d| {
+            let last = d.chapters.last()?;
+            Some(RemoteDirectory::new(d.chapters.len(), &last.id))
+        });
+        if let Some(remote) = remote {
+            let _ = tx.send((book_id, remote));
         }
     }
 }
@@ -365,18 +564,20 @@ fn fetch_remote_totals_worker(
 #[cfg(not(feature = "official-api"))]
 fn fetch_remote_totals_worker(
     queue: Arc<Mutex<VecDeque<String>>>,
-    tx: mpsc::Sender<(String, usize)>,
+    tx: mpsc::Sender<(String, RemoteDirectory)>,
 ) {
     let Ok(client) = FanqieWebNetwork::new(FanqieWebConfig::default()) else {
         return;
     };
     while let Some(book_id) = queue.lock().ok().and_then(|mut q| q.pop_front()) {
-        let total = client
-            .fetch_chapter_list(&book_id)
-            .map(|list| list.len())
-            .filter(|n| *n > 0);
-        if let Some(total) = total {
-            let _ = tx.send((book_id, total));
+        let remote = client.fetch_chapter_list(&book_id).and_then(|list| {
+            let last = list.last()?.as_object()?;
+            let last_id =
+                json_extract::pick_string(last, &["item_id", "itemId", "id"]).unwrap_or_default();
+            Some(RemoteDirectory::new(list.len(), &last_id))
+        });
+        if let Some(remote) = remote {
+            let _ = tx.send((book_id, remote));
         }
     }
 }
@@ -451,31 +652,169 @@ fn read_status_json(folder: &Path, book_id: &str) -> Option<Value> {
     serde_json::from_str(&data).ok()
 }
 
+/// 从 status JSON 中读取标签：新格式为 `|` 分隔的字符串，也兼容数组与逗号分隔。
+fn tags_from_status(value: &Value) -> Vec<String> {
+    let raw: Vec<String> = match value.get("tags") {
+        Some(Value::String(s)) => s
+            .split(['|', ',', '，', '、'])
+            .map(str::to_string)
+            .collect(),
+        Some(Value::Array(arr)) => arr
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let mut tags: Vec<String> = Vec::new();
+    for tag in raw {
+        let tag = tag.trim();
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// 书籍是否属于给定的分类或标签（精确匹配）。
+pub fn matches_tag_or_category(tags: &[String], category: &str, label: &str) -> bool {
+    category == label || tags.iter().any(|t| t == label)
+}
+
 /// 从已解析的 status JSON 中提取下载计数。
 fn counts_from_status(value: &Value) -> Option<(usize, usize, usize)> {
     let downloaded = value.get("downloaded")?.as_object()?;
     let total = downloaded.len();
-    let mut ok = 0usize;
-    for (_cid, pair) in downloaded {
-        match pair {
-            Value::Array(arr) => {
-                if arr.get(1).and_then(|v| v.as_str()).is_some() {
-                    ok += 1;
-                }
-            }
-            Value::Object(obj) => {
-                if obj
-                    .get("content")
-                    .or_else(|| obj.get("text"))
-                    .and_then(|v| v.as_str())
-                    .is_some()
-                {
-                    ok += 1;
-                }
-            }
-            _ => {}
-        }
-    }
+    let ok = downloaded
+        .values()
+        .filter(|pair| status_entry_ok(pair))
+        .count();
     let failed = total.saturating_sub(ok);
     Some((total, ok, failed))
 }
+
+/// status.json 中单章记录是否为成功下载（content/text 非空）。
+fn status_entry_ok(pair: &Value) -> bool {
+    match pair {
+        Value::Array(arr) => arr.get(1).and_then(|v| v.as_str()).is_some(),
+        Value::Object(obj) => obj
+            .get("content")
+            .or_else(|| obj.get("text"))
+            .and_then(|v| v.as_str())
+            .is_some(),
+        _ => false,
+    }
+}
+
+/// 失败章节列表文件名（位于书籍状态目录内）。
+pub const FAILED_CHAPTERS_FILE: &str = "failed_chapters.txt";
+
+/// 从 status.json 读取已记录的章节 (chapter_id, 标题, 是否下载成功)，按章节 ID 排序。
+pub fn read_status_toc(folder: &Path, book_id: &str) -> Vec<(String, String, bool)> {
+    let Some(status) = read_status_json(folder, book_id) else {
+        return Vec::new();
+    };
+    let Some(downloaded) = status.get("downloaded").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let mut toc: Vec<(String, String, bool)> = downloaded
+        .iter()
+        .map(|(cid, pair)| {
+            let title = match pair {
+                Value::Array(arr) => arr.first().and_then(Value::as_str),
+                Value::Object(obj) => obj.get("title").and_then(Value::as_str),
+                _ => None,
+            };
+            (
+                cid.clone(),
+                title.unwrap_or("").to_string(),
+                status_entry_ok(pair),
+            )
+        })
+        .collect();
+    toc.sort_by(|a, b| match (a.0.parse::<u64>(), b.0.parse::<u64>()) {
+        (Ok(x), Ok(y)) => x.cmp(&y),
+        _ => a.0.cmp(&b.0),
+    });
+    toc
+}
+
+/// 从 status.json 读取失败章节 (chapter_id, 标题)，按章节 ID 排序。
+pub fn read_failed_chapters(folder: &Path, book_id: &str) -> Vec<(String, String)> {
+    read_status_toc(folder, book_id)
+        .into_iter()
+        .filter(|(_, _, ok)| !ok)
+        .map(|(cid, title, _)| (cid, title))
+        .collect()
+}
+
+/// 将失败章节导出到 [`FAILED_CHAPTERS_FILE`]（每行 `章节ID<TAB>标题`）。
+/// 没有失败章节时删除旧列表并返回 `Ok(None)`。
+pub fn export_failed_chapter_list(folder: &Path, book_id: &str) -> Result<Option<PathBuf>> {
+    let path = folder.join(FAILED_CHAPTERS_FILE);
+    let failed = read_failed_chapters(folder, book_id);
+    if failed.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path).with_context(|| format!("删除 {}", path.display()))?;
+        }
+        return Ok(None);
+    }
+    let mut out = format!("# book_id: {}，失败 {} 章\n", book_id, failed.len());
+    for (cid, title) in &failed {
+        out.push_str(&format!("{}\t{}\n", cid, title));
+    }
+    fs::write(&path, out).with_context(|| format!("写入 {}", path.display()))?;
+    Ok(Some(path))
+}
+
+/// 读取每章下载状态：chapter_id -> 是否成功（false 表示有记录但失败）。
+pub fn read_chapter_status_map(folder: &Path, book_id: &str) -> HashMap<String, bool> {
+    read_status_json(folder, book_id)
+        .and_then(|v| {
+            v.get("downloaded")?.as_object().map(|downloaded| {
+                downloaded
+                    .iter()
+                    .map(|(cid, pair)| (cid.clone(), status_entry_ok(pair)))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CachedRemoteTotal, RemoteDirectory, UPDATE_CACHE_STABLE_AFTER_MS,
+        UPDATE_CACHE_STABLE_TTL_MS, UPDATE_CACHE_TTL_MS, matches_tag_or_category, tags_from_status,
+    };
+
+    #[test]
+    fn unchanged_directory_extends_cache_ttl() {
+        let remote = RemoteDirectory::new(120, "7001");
+        let first = CachedRemoteTotal::refreshed(None, &remote, 1_000);
+        assert_eq!(first.changed_ms, 1_000);
+        assert_eq!(first.ttl_ms(1_000), UPDATE_CACHE_TTL_MS);
+
+        let later = 1_000 + UPDATE_CACHE_STABLE_AFTER_MS;
+        let same = CachedRemoteTotal::refreshed(Some(&first), &remote, later);
+        assert_eq!(same.changed_ms, 1_000);
+        assert_eq!(same.ttl_ms(later), UPDATE_CACHE_STABLE_TTL_MS);
+
+        let grown = RemoteDirectory::new(121, "7002");
+        let changed = CachedRemoteTotal::refreshed(Some(&same), &grown, later);
+        assert_eq!(changed.changed_ms, later);
+        assert_eq!(changed.ttl_ms(later), UPDATE_CACHE_TTL_MS);
+    }
+
+    #[test]
+    fn status_tags_parse_and_match_labels() {
+        let tags = tags_from_status(&serde_json::json!({ "tags": "玄幻| 系统 |玄幻|" }));
+        assert_eq!(tags, vec!["玄幻", "系统"]);
+        let tags = tags_from_status(&serde_json::json!({ "tags": ["都市", ""] }));
+        assert_eq!(tags, vec!["都市"]);
+        assert!(tags_from_status(&serde_json::json!({})).is_empty());
+
+        assert!(matches_tag_or_category(&tags, "男频", "男频"));
+        assert!(matches_tag_or_category(&tags, "男频", "都市"));
+        assert!(!matches_tag_or_category(&tags, "男频", "玄幻"));
+    }
+}
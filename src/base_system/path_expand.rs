@@ -0,0 +1,147 @@
+//! 配置路径展开。
+//!
+//! - `~` / `~/...` 展开到用户主目录
+//! - `$VAR`、`${VAR}`、`%VAR%` 展开为环境变量（未定义时保留原文）
+//! - 展开后仍为相对路径的，基于可执行文件所在目录解析
+
+use std::path::{Path, PathBuf};
+
+/// 用户主目录：Windows 优先 `USERPROFILE`，其他平台优先 `HOME`。
+pub fn home_dir() -> Option<PathBuf> {
+    let keys: [&str; 2] = if cfg!(windows) {
+        ["USERPROFILE", "HOME"]
+    } else {
+        ["HOME", "USERPROFILE"]
+    };
+    keys.iter()
+        .filter_map(std::env::var_os)
+        .find(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// 可执行文件所在目录，获取失败时退回当前工作目录。
+pub fn exe_dir() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// 展开 `~` 与环境变量，不做相对路径解析。
+pub fn expand_path_vars(raw: &str) -> String {
+    let home = home_dir().map(|p| p.display().to_string());
+    expand_with(raw, home.as_deref(), |name| std::env::var(name).ok())
+}
+
+/// 展开变量后，将相对路径基于可执行文件目录解析为绝对路径。
+pub fn resolve_config_path(raw: &str) -> PathBuf {
+    resolve_against(&expand_path_vars(raw.trim()), &exe_dir())
+}
+
+fn resolve_against(expanded: &str, base: &Path) -> PathBuf {
+    let path = PathBuf::from(expanded);
+    if path.is_absolute() {
+        path
+    } else {
+        base.join(path)
+    }
+}
+
+fn expand_with(raw: &str, home: Option<&str>, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut rest = raw;
+    let mut out = String::with_capacity(raw.len());
+
+    if let Some(home) = home
+        && let Some(tail) = rest.strip_prefix('~')
+        && (tail.is_empty() || tail.starts_with('/') || tail.starts_with('\\'))
+    {
+        out.push_str(home.trim_end_matches(['/', '\\']));
+        rest = tail;
+    }
+
+    while let Some(pos) = rest.find(['$', '%']) {
+        out.push_str(&rest[..pos]);
+        let marker = &rest[pos..];
+        let (name, consumed) = if let Some(tail) = marker.strip_prefix("${") {
+            match tail.find('}') {
+                Some(end) => (&tail[..end], end + 3),
+                None => ("", 0),
+            }
+        } else if let Some(tail) = marker.strip_prefix('$') {
+            let end = tail
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(tail.len());
+            (&tail[..end], end + 1)
+        } else {
+            let tail = &marker[1..];
+            match tail.find('%') {
+                Some(end) => (&tail[..end], end + 2),
+                None => ("", 0),
+            }
+        };
+
+        let valid = !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        match lookup(name).filter(|_| valid) {
+            Some(value) => {
+                out.push_str(&value);
+                rest = &marker[consumed..];
+            }
+            None => {
+                // 未定义或不是合法变量名：原样保留标记字符
+                out.push_str(&marker[..1]);
+                rest = &marker[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "BOOKS" => Some("/data/books".to_string()),
+            "USER" => Some("alice".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn expands_home_and_env_vars() {
+        let home = Some("/home/alice/");
+        assert_eq!(expand_with("~/novels", home, lookup), "/home/alice/novels");
+        assert_eq!(expand_with("~", home, lookup), "/home/alice");
+        assert_eq!(expand_with("~bob/x", home, lookup), "~bob/x");
+        assert_eq!(
+            expand_with("$BOOKS/tomato", home, lookup),
+            "/data/books/tomato"
+        );
+        assert_eq!(expand_with("${BOOKS}_bak", home, lookup), "/data/books_bak");
+        assert_eq!(expand_with("D:\\%USER%\\书", home, lookup), "D:\\alice\\书");
+        assert_eq!(
+            expand_with("$MISSING/%NOPE%/100%", home, lookup),
+            "$MISSING/%NOPE%/100%"
+        );
+    }
+
+    #[test]
+    fn relative_paths_resolve_against_base() {
+        let base = Path::new("/opt/tomato");
+        assert_eq!(
+            resolve_against("downloads", base),
+            PathBuf::from("/opt/tomato/downloads")
+        );
+        if !cfg!(windows) {
+            assert_eq!(
+                resolve_against("/srv/books", base),
+                PathBuf::from("/srv/books")
+            );
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! 请求失败归类。
+//!
+//! 把网络层/接口层错误归入少数几个类别，日志统一以 `[类别]` 开头，
+//! 最终失败信息附带针对性建议，便于用户从日志直接判断根因。
+
+use std::time::Duration;
+
+use super::json_extract::ResponseJsonError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestErrorKind {
+    Network,
+    Timeout,
+    Proxy,
+    Decrypt,
+    Parse,
+    /// 风控 / 限流 / endpoint 返回错误页
+    RiskControl,
+    Other,
+}
+
+impl RequestErrorKind {
+    /// 优先按错误链中的具体类型判断，再回退到文本关键字。
+    pub fn classify(err: &anyhow::Error) -> Self {
+        let text = format!("{err:#}");
+        if Self::classify_message(&text) == RequestErrorKind::Proxy {
+            return RequestErrorKind::Proxy;
+        }
+        for cause in err.chain() {
+            if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                if e.is_timeout() {
+                    return RequestErrorKind::Timeout;
+                }
+                if let Some(kind) = e.status().and_then(|s| Self::from_status(s.as_u16())) {
+                    return kind;
+                }
+                if e.is_connect() {
+                    return RequestErrorKind::Network;
+                }
+            }
+            if let Some(e) = cause.downcast_ref::<ResponseJsonError>() {
+                return if e.should_switch_source() {
+                    RequestErrorKind::RiskControl
+                } else {
+                    RequestErrorKind::Parse
+                };
+            }
+        }
+        Self::classify_message(&text)
+    }
+
+    /// 按错误文本归类（官方 API 只透出错误字符串）。
+    pub fn classify_message(msg: &str) -> Self {
+        let lower = msg.to_ascii_lowercase();
+        let has = |keys: &[&str]| keys.iter().any(|k| lower.contains(k));
+
+        if has(&["proxy", "socks", "代理"]) {
+            RequestErrorKind::Proxy
+        } else if has(&["timed out", "timeout", "超时"]) {
+            RequestErrorKind::Timeout
+        } else if has(&["decrypt", "cipher", "解密"]) {
+            RequestErrorKind::Decrypt
+        } else if has(&[
+            "cooldown",
+            "403 forbidden",
+            "429 too many",
+            "too many requests",
+            "风控",
+            "非 json",
+            "空响应",
+        ]) {
+            RequestErrorKind::RiskControl
+        } else if has(&["json", "eof while parsing", "expected value", "解析"]) {
+            RequestErrorKind::Parse
+        } else if has(&[
+            "dns",
+            "lookup",
+            "connect",
+            "connection",
+            "unreachable",
+            "no route to host",
+            "error sending request",
+            "网络",
+            "连接",
+        ]) {
+            RequestErrorKind::Network
+        } else {
+            RequestErrorKind::Other
+        }
+    }
+
+    /// 403 / 429 多为风控或限流，其余状态码不单独归类。
+    pub fn from_status(status: u16) -> Option<Self> {
+        matches!(status, 403 | 429).then_some(RequestErrorKind::RiskControl)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RequestErrorKind::Network => "网络",
+            RequestErrorKind::Timeout => "超时",
+            RequestErrorKind::Proxy => "代理",
+            RequestErrorKind::Decrypt => "解密",
+            RequestErrorKind::Parse => "解析",
+            RequestErrorKind::RiskControl => "风控",
+            RequestErrorKind::Other => "其他",
+        }
+    }
+
+    /// 日志前缀，如 `[代理]`。
+    pub fn prefix(self) -> String {
+        format!("[{}]", self.label())
+    }
+
+    /// 面向用户的处理建议。
+    pub fn hint(self) -> &'static str {
+        match self {
+            RequestErrorKind::Network => "疑似网络不可用，请检查网络连接后重试",
+            RequestErrorKind::Timeout => {
+                "请求超时，可调大 request_timeout 或降低 max_workers 后重试"
+            }
+            RequestErrorKind::Proxy => {
+                "疑似代理不可用，请检查 proxy 设置（系统代理或 HTTP(S)_PROXY 环境变量）"
+            }
+            RequestErrorKind::Decrypt => "正文解密失败，请尝试更新程序",
+            RequestErrorKind::Parse => "响应内容不完整或格式异常，可稍后重试",
+            RequestErrorKind::RiskControl => {
+                "疑似触发风控或 endpoint 失效，请降低并发、调大请求间隔或更换 api_endpoints"
+            }
+            RequestErrorKind::Other => "请查看日志了解详情",
+        }
+    }
+
+    /// 最终失败信息：`[类别] 原始信息` + 建议。
+    pub fn annotate(self, msg: &str) -> String {
+        format!("{} {}\n提示：{}", self.prefix(), msg, self.hint())
+    }
+}
+
+/// 失败发生在连接阶段还是读取阶段，决定换端点快速重试还是延长超时再试。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailurePhase {
+    /// DNS / TCP / TLS 握手失败或连接超时
+    Connect,
+    /// 已建立连接，等待响应超时
+    Read,
+    Other,
+}
+
+impl FailurePhase {
+    /// reqwest 的连接超时同时带 `is_connect` 与 `is_timeout`，先判连接。
+    pub fn classify(err: &anyhow::Error) -> Self {
+        for cause in err.chain() {
+            if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                if e.is_connect() {
+                    return FailurePhase::Connect;
+                }
+                if e.is_timeout() {
+                    return FailurePhase::Read;
+                }
+            }
+        }
+        Self::classify_message(&format!("{err:#}"))
+    }
+
+    /// 按错误文本判断（官方 API 只透出错误字符串）。
+    pub fn classify_message(msg: &str) -> Self {
+        match RequestErrorKind::classify_message(msg) {
+            RequestErrorKind::Network => FailurePhase::Connect,
+            RequestErrorKind::Timeout if msg.to_ascii_lowercase().contains("connect") => {
+                FailurePhase::Connect
+            }
+            RequestErrorKind::Timeout => FailurePhase::Read,
+            _ => FailurePhase::Other,
+        }
+    }
+}
+
+/// 连接失败 / 读取超时的重试激进程度（对应 `Config.timeout_retry_strategy`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutRetryStrategy {
+    /// 连接失败照常退避；读取超时小幅延长超时
+    Conservative,
+    /// 连接失败只等最小冷却时间即换端点；读取超时逐次放宽到 3 倍
+    Balanced,
+    /// 连接失败立即换端点；读取超时逐次翻倍到 4 倍
+    Aggressive,
+}
+
+impl TimeoutRetryStrategy {
+    pub const ALL: [TimeoutRetryStrategy; 3] = [
+        TimeoutRetryStrategy::Conservative,
+        TimeoutRetryStrategy::Balanced,
+        TimeoutRetryStrategy::Aggressive,
+    ];
+
+    /// 未知取值回退到均衡。
+    pub fn from_config(raw: &str) -> Self {
+        let raw = raw.trim();
+        Self::ALL
+            .into_iter()
+            .find(|s| raw.eq_ignore_ascii_case(s.as_str()) || raw == s.label())
+            .unwrap_or(TimeoutRetryStrategy::Balanced)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TimeoutRetryStrategy::Conservative => "conservative",
+            TimeoutRetryStrategy::Balanced => "balanced",
+            TimeoutRetryStrategy::Aggressive => "aggressive",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TimeoutRetryStrategy::Conservative => "保守",
+            TimeoutRetryStrategy::Balanced => "均衡",
+            TimeoutRetryStrategy::Aggressive => "激进",
+        }
+    }
+
+    /// 连接失败后换端点前的等待；`None` 表示沿用常规退避。
+    pub fn connect_retry_wait(self, min_wait_ms: u64) -> Option<Duration> {
+        match self {
+            TimeoutRetryStrategy::Conservative => None,
+            TimeoutRetryStrategy::Balanced => Some(Duration::from_millis(min_wait_ms)),
+            TimeoutRetryStrategy::Aggressive => Some(Duration::ZERO),
+        }
+    }
+
+    /// 官方 API 无法换端点或改超时，只决定是否原地重试及等待多久；`None` 表示直接失败。
+    #[cfg_attr(not(feature = "official-api"), allow(dead_code))]
+    pub fn official_retry_wait(self, phase: FailurePhase, backoff: Duration) -> Option<Duration> {
+        match (self, phase) {
+            (_, FailurePhase::Other) | (TimeoutRetryStrategy::Conservative, _) => None,
+            (TimeoutRetryStrategy::Aggressive, FailurePhase::Connect) => Some(Duration::ZERO),
+            (_, FailurePhase::Connect) => Some(backoff / 2),
+            (_, FailurePhase::Read) => Some(backoff),
+        }
+    }
+
+    /// 第 `read_timeouts` 次读取超时后的超时倍率。
+    pub fn read_timeout_scale(self, read_timeouts: u32) -> f64 {
+        let (step, max): (f64, f64) = match self {
+            TimeoutRetryStrategy::Conservative => (1.25, 1.5),
+            TimeoutRetryStrategy::Balanced => (1.5, 3.0),
+            TimeoutRetryStrategy::Aggressive => (2.0, 4.0),
+        };
+        step.powi(read_timeouts.min(8) as i32).min(max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn classifies_common_failures() {
+        let cases = [
+            (
+                "error sending request: ProxyError: tunnel failed",
+                RequestErrorKind::Proxy,
+            ),
+            ("operation timed out", RequestErrorKind::Timeout),
+            ("AES decrypt failed", RequestErrorKind::Decrypt),
+            ("CooldownNotReached", RequestErrorKind::RiskControl),
+            (
+                "HTTP status client error (429 Too Many Requests)",
+                RequestErrorKind::RiskControl,
+            ),
+            (
+                "EOF while parsing a value at line 1",
+                RequestErrorKind::Parse,
+            ),
+            (
+                "dns error: failed to lookup address",
+                RequestErrorKind::Network,
+            ),
+            ("something else", RequestErrorKind::Other),
+        ];
+        for (msg, kind) in cases {
+            assert_eq!(RequestErrorKind::classify_message(msg), kind, "{msg}");
+        }
+
+        let err = anyhow::Error::new(ResponseJsonError::Empty).context("第三方 API 请求重试耗尽");
+        assert_eq!(
+            RequestErrorKind::classify(&err),
+            RequestErrorKind::RiskControl
+        );
+        assert_eq!(
+            RequestErrorKind::classify(&anyhow!("tcp connect error")),
+            RequestErrorKind::Network
+        );
+        assert!(
+            RequestErrorKind::Proxy
+                .annotate("请求失败")
+                .starts_with("[代理] 请求失败\n提示：疑似代理不可用")
+        );
+    }
+
+    #[test]
+    fn separates_connect_and_read_failures() {
+        let cases = [
+            (
+                "tcp connect error: connection refused",
+                FailurePhase::Connect,
+            ),
+            ("connect timed out", FailurePhase::Connect),
+            ("operation timed out", FailurePhase::Read),
+            ("EOF while parsing a value", FailurePhase::Other),
+        ];
+        for (msg, phase) in cases {
+            assert_eq!(FailurePhase::classify_message(msg), phase, "{msg}");
+        }
+
+        let balanced = TimeoutRetryStrategy::from_config("balanced");
+        assert_eq!(balanced.read_timeout_scale(0), 1.0);
+        assert_eq!(balanced.read_timeout_scale(1), 1.5);
+        assert_eq!(balanced.read_timeout_scale(5), 3.0);
+        assert_eq!(
+            TimeoutRetryStrategy::from_config("激进").connect_retry_wait(500),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            TimeoutRetryStrategy::from_config("unknown"),
+            TimeoutRetryStrategy::Balanced
+        );
+    }
+}
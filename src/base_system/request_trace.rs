@@ -0,0 +1,199 @@
+//! 章节组请求的结构化 trace。
+//!
+//! 仅在 `--debug` 下记录：每次尝试选用的 endpoint、序号、耗时、结果与错误类型，
+//! 下载结束后聚合导出到书籍目录的 `request_trace.json`，便于复现偶发失败。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tracing::debug;
+
+pub const REQUEST_TRACE_FILE: &str = "request_trace.json";
+
+/// 官方 API 的 endpoint 标识。
+#[cfg_attr(not(feature = "official-api"), allow(dead_code))]
+pub const OFFICIAL_ENDPOINT: &str = "official";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+fn events() -> &'static Mutex<Vec<TraceEvent>> {
+    static EVENTS: OnceLock<Mutex<Vec<TraceEvent>>> = OnceLock::new();
+    EVENTS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceOutcome {
+    Ok,
+    /// 请求成功但本组章节全部无正文
+    EmptyContent,
+    /// 4xx（408/429 除外），切换 endpoint
+    ClientError,
+    /// 空响应 / 非 JSON（风控页、失效 endpoint），切换 endpoint
+    InvalidBody,
+    /// 5xx / 超时 / 网络错误，退避后重试
+    Retryable,
+    /// 官方 API 冷却未到，等待后重试
+    #[cfg_attr(not(feature = "official-api"), allow(dead_code))]
+    Cooldown,
+    /// 不再重试的错误
+    #[cfg_attr(not(feature = "official-api"), allow(dead_code))]
+    Fatal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceEvent {
+    /// 毫秒时间戳
+    pub at_ms: u64,
+    /// 本组首个章节 ID
+    pub group: String,
+    pub chapters: usize,
+    pub endpoint: String,
+    /// 从 1 开始
+    pub attempt: u32,
+    pub elapsed_ms: u64,
+    pub outcome: TraceOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 记录一次尝试；`ids` 为逗号分隔的章节 ID，`attempt` 从 0 开始。
+pub fn record(
+    ids: &str,
+    endpoint: &str,
+    attempt: u32,
+    elapsed: Duration,
+    outcome: TraceOutcome,
+    status: Option<u16>,
+    error: Option<String>,
+) {
+    if !is_enabled() {
+        return;
+    }
+    let event = TraceEvent {
+        at_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default(),
+        group: ids.split(',').next().unwrap_or_default().to_string(),
+        chapters: ids.split(',').filter(|s| !s.is_empty()).count(),
+        endpoint: endpoint.to_string(),
+        attempt: attempt + 1,
+        elapsed_ms: elapsed.as_millis() as u64,
+        outcome,
+        status,
+        error,
+    };
+    debug!(
+        target: "download",
+        group = %event.group,
+        endpoint = %event.endpoint,
+        attempt = event.attempt,
+        elapsed_ms = event.elapsed_ms,
+        outcome = ?event.outcome,
+        "request trace"
+    );
+    events()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(event);
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct EndpointSummary {
+    pub attempts: usize,
+    pub ok: usize,
+    pub failed: usize,
+    pub avg_elapsed_ms: u64,
+    pub max_elapsed_ms: u64,
+    pub outcomes: BTreeMap<String, usize>,
+}
+
+/// 按 endpoint 聚合。
+pub fn summarize(events: &[TraceEvent]) -> BTreeMap<String, EndpointSummary> {
+    let mut out: BTreeMap<String, EndpointSummary> = BTreeMap::new();
+    let mut total_ms: BTreeMap<String, u64> = BTreeMap::new();
+    for ev in events {
+        let s = out.entry(ev.endpoint.clone()).or_default();
+        s.attempts += 1;
+        if ev.outcome == TraceOutcome::Ok {
+            s.ok += 1;
+        } else {
+            s.failed += 1;
+        }
+        s.max_elapsed_ms = s.max_elapsed_ms.max(ev.elapsed_ms);
+        let outcome = serde_json::to_value(ev.outcome)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        *s.outcomes.entry(outcome).or_default() += 1;
+        *total_ms.entry(ev.endpoint.clone()).or_default() += ev.elapsed_ms;
+    }
+    for (ep, s) in out.iter_mut() {
+        s.avg_elapsed_ms = total_ms.get(ep).copied().unwrap_or_default() / s.attempts as u64;
+    }
+    out
+}
+
+/// 取出已记录的事件并导出到 `folder`；未启用或无记录时返回 `Ok(None)`。
+pub fn export_to(folder: &Path) -> std::io::Result<Option<PathBuf>> {
+    if !is_enabled() {
+        return Ok(None);
+    }
+    let taken = std::mem::take(&mut *events().lock().unwrap_or_else(|e| e.into_inner()));
+    if taken.is_empty() {
+        return Ok(None);
+    }
+    let doc = serde_json::json!({
+        "summary": summarize(&taken),
+        "events": taken,
+    });
+    let path = folder.join(REQUEST_TRACE_FILE);
+    let text = serde_json::to_string_pretty(&doc).map_err(std::io::Error::other)?;
+    std::fs::write(&path, text)?;
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_by_endpoint() {
+        let ev = |endpoint: &str, elapsed_ms: u64, outcome| TraceEvent {
+            at_ms: 0,
+            group: "1".to_string(),
+            chapters: 2,
+            endpoint: endpoint.to_string(),
+            attempt: 1,
+            elapsed_ms,
+            outcome,
+            status: None,
+            error: None,
+        };
+        let summary = summarize(&[
+            ev("a", 100, TraceOutcome::Retryable),
+            ev("a", 300, TraceOutcome::Ok),
+            ev("b", 50, TraceOutcome::ClientError),
+        ]);
+        let a = &summary["a"];
+        assert_eq!((a.attempts, a.ok, a.failed), (2, 1, 1));
+        assert_eq!((a.avg_elapsed_ms, a.max_elapsed_ms), (200, 300));
+        assert_eq!(a.outcomes["retryable"], 1);
+        assert_eq!(summary["b"].outcomes["client_error"], 1);
+    }
+}
@@ -0,0 +1,149 @@
+//! 搜索查询语法解析。
+//!
+//! 支持：
+//! - `author:作者名` / `作者:作者名`：只保留该作者的结果
+//! - `"完整书名"`（含中文引号）：书名完全一致的结果置顶
+//!
+//! 其余文本原样作为关键词交给上游搜索接口。
+
+#![cfg_attr(not(feature = "official-api"), allow(dead_code))]
+
+/// TUI/Web 输入框中展示的语法提示。
+pub const SEARCH_SYNTAX_HINT: &str = "语法: author:作者  \"完整书名\"";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// 实际提交给搜索接口的关键词。
+    pub keyword: String,
+    pub author: Option<String>,
+    pub exact_title: Option<String>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut rest = String::new();
+        let mut author = None;
+        let mut exact_title = None;
+
+        let mut chars = input.trim().chars().peekable();
+        while let Some(c) = chars.next() {
+            if let Some(close) = closing_quote(c) {
+                let quoted: String = chars.by_ref().take_while(|ch| *ch != close).collect();
+                let quoted = quoted.trim();
+                if !quoted.is_empty() {
+                    exact_title = Some(quoted.to_string());
+                }
+                rest.push(' ');
+                continue;
+            }
+            rest.push(c);
+        }
+
+        let mut words = Vec::new();
+        for word in rest.split_whitespace() {
+            if let Some(name) = strip_author_prefix(word) {
+                if !name.is_empty() {
+                    author = Some(name.to_string());
+                }
+                continue;
+            }
+            words.push(word);
+        }
+
+        let keyword = match (&exact_title, words.is_empty(), &author) {
+            (Some(title), _, _) => title.clone(),
+            (None, false, _) => words.join(" "),
+            (None, true, Some(a)) => a.clone(),
+            (None, true, None) => String::new(),
+        };
+
+        Self {
+            keyword,
+            author,
+            exact_title,
+        }
+    }
+
+    /// 是否使用了任何过滤语法（未使用时结果原样返回）。
+    pub fn has_filters(&self) -> bool {
+        self.author.is_some() || self.exact_title.is_some()
+    }
+
+    /// 按作者过滤并将书名精确匹配的结果置顶（其余保持原有顺序）。
+    pub fn apply<T>(
+        &self,
+        items: Vec<T>,
+        title_of: impl Fn(&T) -> &str,
+        author_of: impl Fn(&T) -> &str,
+    ) -> Vec<T> {
+        let mut items: Vec<T> = match self.author.as_deref() {
+            Some(author) => items
+                .into_iter()
+                .filter(|it| author_of(it).trim().contains(author))
+                .collect(),
+            None => items,
+        };
+        if let Some(title) = self.exact_title.as_deref() {
+            items.sort_by_key(|it| title_of(it).trim() != title);
+        }
+        items
+    }
+}
+
+fn closing_quote(c: char) -> Option<char> {
+    match c {
+        '"' => Some('"'),
+        '“' => Some('”'),
+        '「' => Some('」'),
+        _ => None,
+    }
+}
+
+fn strip_author_prefix(word: &str) -> Option<&str> {
+    ["author:", "author：", "作者:", "作者："]
+        .iter()
+        .find_map(|p| {
+            word.get(..p.len())
+                .filter(|head| head.eq_ignore_ascii_case(p))
+                .map(|_| &word[p.len()..])
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_author_and_exact_title() {
+        let q = SearchQuery::parse("author:烂尾楼捏 \"全球尸化\"");
+        assert_eq!(q.author.as_deref(), Some("烂尾楼捏"));
+        assert_eq!(q.exact_title.as_deref(), Some("全球尸化"));
+        assert_eq!(q.keyword, "全球尸化");
+
+        let q = SearchQuery::parse("作者：烂尾楼捏");
+        assert_eq!(q.keyword, "烂尾楼捏");
+        assert!(q.exact_title.is_none());
+
+        let q = SearchQuery::parse("全球 尸化");
+        assert_eq!(q.keyword, "全球 尸化");
+        assert!(!q.has_filters());
+    }
+
+    #[test]
+    fn apply_filters_author_and_pins_exact_title() {
+        let items = vec![
+            ("全球尸化之后", "甲"),
+            ("全球尸化", "烂尾楼捏"),
+            ("全球尸化：末日", "烂尾楼捏"),
+        ];
+        let q = SearchQuery::parse("“全球尸化”");
+        let out = q.apply(items.clone(), |it| it.0, |it| it.1);
+        assert_eq!(out[0].0, "全球尸化");
+        assert_eq!(out.len(), 3);
+
+        let q = SearchQuery::parse("author:烂尾楼捏 全球");
+        let out = q.apply(items, |it| it.0, |it| it.1);
+        assert_eq!(out.len(), 2);
+        assert!(out.iter().all(|it| it.1 == "烂尾楼捏"));
+    }
+}
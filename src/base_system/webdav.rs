@@ -0,0 +1,226 @@
+//! 成书上传到 WebDAV（NAS 等）。
+//!
+//! 作为 finalize 之后的后处理：本地成书已写好，上传失败只记录警告，不影响下载结果。
+//! 目录产物（散装 txt）会先 MKCOL 建目录再逐个 PUT。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use reqwest::Method;
+use reqwest::Url;
+use reqwest::blocking::{Client, RequestBuilder};
+use tracing::{info, warn};
+
+use super::context::Config;
+
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// 界面回显密码时使用的掩码；提交回该值表示保持原密码不变。
+pub const PASSWORD_MASK: &str = "********";
+
+/// 供界面显示的密码：已设置时返回掩码，未设置时为空。
+pub fn masked_password(password: &str) -> String {
+    if password.is_empty() {
+        String::new()
+    } else {
+        PASSWORD_MASK.to_string()
+    }
+}
+
+struct WebDavClient {
+    client: Client,
+    base: Url,
+    username: String,
+    password: String,
+}
+
+impl WebDavClient {
+    fn new(cfg: &Config) -> Result<Self> {
+        let raw = cfg.webdav_url.trim();
+        let base = Url::parse(raw).with_context(|| format!("WebDAV 地址无效: {raw}"))?;
+        if !matches!(base.scheme(), "http" | "https") {
+            return Err(anyhow!("WebDAV 地址必须以 http:// 或 https:// 开头"));
+        }
+        let client = Client::builder()
+            .timeout(UPLOAD_TIMEOUT)
+            .build()
+            .context("创建 WebDAV 客户端失败")?;
+        Ok(Self {
+            client,
+            base,
+            username: cfg.webdav_username.trim().to_string(),
+            password: cfg.webdav_password.clone(),
+        })
+    }
+
+    /// 在基础目录下拼接路径段（自动百分号编码）。
+    fn url_for(&self, segments: &[&str]) -> Url {
+        let mut url = self.base.clone();
+        if let Ok(mut segs) = url.path_segments_mut() {
+            segs.pop_if_empty();
+            segs.extend(segments);
+        }
+        url
+    }
+
+    fn request(&self, method: Method, url: Url) -> RequestBuilder {
+        let req = self.client.request(method, url);
+        if self.username.is_empty() {
+            req
+        } else {
+            req.basic_auth(&self.username, Some(&self.password))
+        }
+    }
+
+    fn put_file(&self, local: &Path, segments: &[&str]) -> Result<u64> {
+        let bytes = fs::read(local).with_context(|| format!("读取 {} 失败", local.display()))?;
+        let size = bytes.len() as u64;
+        let url = self.url_for(segments);
+        let resp = self
+            .request(Method::PUT, url.clone())
+            .body(bytes)
+            .send()
+            .with_context(|| format!("上传 {url} 失败"))?;
+        if !resp.status().is_success() {
+            return Err(anyhow!("上传 {url} 失败: HTTP {}", resp.status()));
+        }
+        Ok(size)
+    }
+
+    /// 创建远端目录；已存在（405）视为成功。
+    fn mkcol(&self, segments: &[&str]) -> Result<()> {
+        let mut url = self.url_for(segments);
+        if let Ok(mut segs) = url.path_segments_mut() {
+            segs.push("");
+        }
+        let method = Method::from_bytes(b"MKCOL").expect("valid method");
+        let resp = self
+            .request(method, url.clone())
+            .send()
+            .with_context(|| format!("创建目录 {url} 失败"))?;
+        let status = resp.status();
+        if status.is_success() || status.as_u16() == 405 {
+            Ok(())
+        } else {
+            Err(anyhow!("创建目录 {url} 失败: HTTP {status}"))
+        }
+    }
+}
+
+fn file_name(path: &Path) -> Option<&str> {
+    path.file_name().and_then(|n| n.to_str())
+}
+
+/// 按卷拆分导出时整本文件不存在，改为收集同目录下 `{书名}_第xx卷*.{ext}` 分卷文件。
+fn volume_siblings(path: &Path) -> Vec<PathBuf> {
+    let (Some(stem), Some(ext), Some(dir)) = (
+        path.file_stem().and_then(|s| s.to_str()),
+        path.extension().and_then(|s| s.to_str()),
+        path.parent(),
+    ) else {
+        return Vec::new();
+    };
+    let prefix = format!("{stem}_第");
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|rd| {
+            rd.flatten()
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.is_file()
+                        && p.extension().and_then(|e| e.to_str()) == Some(ext)
+                        && file_name(p).is_some_and(|n| n.starts_with(&prefix))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn upload_one(client: &WebDavClient, output: &Path) -> Result<(usize, u64)> {
+    if output.is_dir() {
+        let dir_name = file_name(output).ok_or_else(|| anyhow!("目录名无效"))?;
+        client.mkcol(&[dir_name])?;
+        let mut entries: Vec<PathBuf> = fs::read_dir(output)?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_file())
+            .collect();
+        entries.sort();
+        let mut bytes = 0;
+        for entry in &entries {
+            let name = file_name(entry).ok_or_else(|| anyhow!("文件名无效"))?;
+            bytes += client.put_file(entry, &[dir_name, name])?;
+        }
+        return Ok((entries.len(), bytes));
+    }
+
+    let files = if output.is_file() {
+        vec![output.to_path_buf()]
+    } else {
+        volume_siblings(output)
+    };
+    if files.is_empty() {
+        return Err(anyhow!("未找到成书文件"));
+    }
+    let mut bytes = 0;
+    for file in &files {
+        let name = file_name(file).ok_or_else(|| anyhow!("文件名无效"))?;
+        bytes += client.put_file(file, &[name])?;
+    }
+    Ok((files.len(), bytes))
+}
+
+/// 未配置 `webdav_url` 时不做任何事；每个产物独立上传，失败只记录警告。
+pub fn upload_outputs(cfg: &Config, outputs: &[PathBuf]) {
+    if cfg.webdav_url.trim().is_empty() || outputs.is_empty() {
+        return;
+    }
+    let client = match WebDavClient::new(cfg) {
+        Ok(c) => c,
+        Err(e) => {
+            warn!(target: "webdav", error = %e, "WebDAV 配置无效，跳过上传");
+            return;
+        }
+    };
+    for output in outputs {
+        match upload_one(&client, output) {
+            Ok((files, bytes)) => info!(
+                target: "webdav",
+                path = %output.display(),
+                files,
+                bytes,
+                "已上传到 WebDAV"
+            ),
+            Err(e) => warn!(
+                target: "webdav",
+                path = %output.display(),
+                error = ?e,
+                "上传到 WebDAV 失败（本地成书不受影响）"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WebDavClient;
+    use crate::base_system::context::Config;
+
+    #[test]
+    fn joins_and_encodes_remote_path() {
+        let mut cfg = Config::default();
+        cfg.webdav_url = "https://nas.local/dav/novels/".to_string();
+        let client = WebDavClient::new(&cfg).unwrap();
+        assert_eq!(
+            client.url_for(&["我的 书", "第1章.txt"]).as_str(),
+            "https://nas.local/dav/novels/%E6%88%91%E7%9A%84%20%E4%B9%A6/%E7%AC%AC1%E7%AB%A0.txt"
+        );
+
+        let mut bad = Config::default();
+        bad.webdav_url = "ftp://nas.local".to_string();
+        assert!(WebDavClient::new(&bad).is_err());
+    }
+}
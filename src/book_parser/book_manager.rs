@@ -1,16 +1,21 @@
 //! 书籍下载过程的状态管理与落盘。
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::fs::OpenOptions;
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use tracing::{debug, info};
 
 use crate::base_system::{book_paths, context::Config};
 
+use super::parser::ContentParser;
+use super::segment_shared::write_atomic;
+
 pub type DownloadedMap = HashMap<String, (String, Option<String>)>;
 
 pub struct BookManager {
@@ -36,6 +41,10 @@ pub struct BookManager {
     /// 是否已在下载完成后确认过书名
     pub book_name_selected_after_download: bool,
     pub downloaded: DownloadedMap,
+    /// 付费/未解锁而跳过的章节 ID（不视为失败，续传时会再尝试）
+    pub paid_skipped: HashSet<String>,
+    /// 章节正文哈希（开启 `detect_modified_chapters` 时记录），用于更新时识别被修订的章节
+    pub chapter_hashes: HashMap<String, String>,
     pub ignore_updates: bool,
     /// 是否已在下载完成后确认过输出格式
     pub format_selected_after_download: bool,
@@ -46,6 +55,22 @@ pub struct BookManager {
 }
 
 const RESUME_JOURNAL_FILE: &str = "downloaded_chapters.jsonl";
+const DIRECTORY_CACHE_FILE: &str = "directory_cache.json";
+/// 按目录顺序整理后的章节内容（每行 `{id,title,content}`），供二次处理与重建。
+pub const CHAPTERS_JSONL_FILE: &str = "chapters.jsonl";
+
+/// 按状态目录区分的写锁。
+///
+/// 同一本书可能同时被多个 `BookManager` 持有（TUI 切换忽略更新、Web 任务、下载线程），
+/// status.json 与追加日志的写入都需先取得该锁，保证同一目录下的写入串行。
+fn status_write_lock(folder: &Path) -> Arc<Mutex<()>> {
+    static LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = OnceLock::new();
+    let mut locks = LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    locks.entry(folder.to_path_buf()).or_default().clone()
+}
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct ResumeJournalRecord {
@@ -59,7 +84,8 @@ impl BookManager {
         let target = match config.status_folder_path(book_name, book_id, None) {
             Ok(p) => p,
             Err(_) => {
-                let fallback = book_paths::book_folder_path(&config, book_id, Some(book_name));
+                let fallback =
+                    book_paths::book_folder_path(&config, book_id, Some(book_name), None);
                 fs::create_dir_all(&fallback)?;
                 fallback
             }
@@ -88,6 +114,8 @@ impl BookManager {
             previous_book_names: Vec::new(),
             book_name_selected_after_download: false,
             downloaded: HashMap::new(),
+            paid_skipped: HashSet::new(),
+            chapter_hashes: HashMap::new(),
             ignore_updates: false,
             format_selected_after_download: false,
             has_download_activity: false,
@@ -133,6 +161,23 @@ impl BookManager {
             }
         }
 
+        if let Some(paid) = data.get("paid_skipped").and_then(|v| v.as_array()) {
+            self.paid_skipped.extend(
+                paid.iter()
+                    .filter_map(|v| v.as_str())
+                    .filter(|cid| !matches!(self.downloaded.get(*cid), Some((_, Some(_)))))
+                    .map(str::to_string),
+            );
+        }
+
+        if let Some(hashes) = data.get("chapter_hashes").and_then(|v| v.as_object()) {
+            self.chapter_hashes.extend(
+                hashes
+                    .iter()
+                    .filter_map(|(cid, h)| Some((cid.clone(), h.as_str()?.to_string()))),
+            );
+        }
+
         let stored_book_name = data
             .get("book_name")
             .and_then(|v| v.as_str())
@@ -258,9 +303,35 @@ impl BookManager {
             chapter_id.to_string(),
             (title.to_string(), Some(content.to_string())),
         );
+        if self.config.detect_modified_chapters {
+            self.chapter_hashes
+                .insert(chapter_id.to_string(), chapter_content_hash(content));
+        } else {
+            self.chapter_hashes.remove(chapter_id);
+        }
+        self.paid_skipped.remove(chapter_id);
         self.has_download_activity = true;
     }
 
+    /// 已下载章节的正文哈希：优先取记录值，旧状态没有记录时按缓存正文现算。
+    pub fn chapter_hash(&self, chapter_id: &str) -> Option<String> {
+        if let Some(hash) = self.chapter_hashes.get(chapter_id) {
+            return Some(hash.clone());
+        }
+        match self.downloaded.get(chapter_id) {
+            Some((_, Some(content))) => Some(chapter_content_hash(content)),
+            _ => None,
+        }
+    }
+
+    /// 丢弃指定章节的缓存，使其在本轮续传中重新下载。
+    pub fn forget_chapters<'a>(&mut self, ids: impl IntoIterator<Item = &'a str>) {
+        for id in ids {
+            self.downloaded.remove(id);
+            self.chapter_hashes.remove(id);
+        }
+    }
+
     /// 追加式持久化单章内容（JSONL）。用于断点续传：即使进程突然退出，也能恢复已下载章节内容。
     pub fn append_downloaded_chapter(&self, chapter_id: &str, title: &str, content: &str) {
         if chapter_id.trim().is_empty() || content.is_empty() {
@@ -273,6 +344,16 @@ impl BookManager {
             content: content.to_string(),
         };
 
+        let line = match serde_json::to_string(&record) {
+            Ok(s) => s,
+            Err(e) => {
+                debug!(target: "book_manager", error = ?e, "serialize resume journal failed");
+                return;
+            }
+        };
+
+        let lock = status_write_lock(&self.status_folder);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
         if let Err(e) = fs::create_dir_all(&self.status_folder) {
             debug!(target: "book_manager", error = ?e, "create status folder failed (resume journal)");
             return;
@@ -286,13 +367,6 @@ impl BookManager {
                 return;
             }
         };
-        let line = match serde_json::to_string(&record) {
-            Ok(s) => s,
-            Err(e) => {
-                debug!(target: "book_manager", error = ?e, "serialize resume journal failed");
-                return;
-            }
-        };
         if let Err(e) = writeln!(file, "{line}") {
             debug!(target: "book_manager", error = ?e, "write resume journal failed");
             return;
@@ -304,6 +378,16 @@ impl BookManager {
         debug!(target: "book_manager", chapter_id, title, "记录异常章节");
         self.downloaded
             .insert(chapter_id.to_string(), (title.to_string(), None));
+        self.chapter_hashes.remove(chapter_id);
+        self.paid_skipped.remove(chapter_id);
+        self.has_download_activity = true;
+    }
+
+    /// 记录付费/未解锁章节：不写入 downloaded，避免被当作失败反复重试。
+    pub fn save_paid_chapter(&mut self, chapter_id: &str, title: &str) {
+        debug!(target: "book_manager", chapter_id, title, "记录付费跳过章节");
+        self.downloaded.remove(chapter_id);
+        self.paid_skipped.insert(chapter_id.to_string());
         self.has_download_activity = true;
     }
 
@@ -323,21 +407,34 @@ impl BookManager {
             "category": self.category,
             "ignore_updates": self.ignore_updates,
             "downloaded": self.downloaded_as_json(),
+            "paid_skipped": self.paid_skipped_sorted(),
+            "chapter_hashes": self
+                .chapter_hashes
+                .iter()
+                .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+                .collect::<serde_json::Map<String, Value>>(),
         });
 
+        let bytes = serde_json::to_vec_pretty(&data).unwrap_or_default();
+
+        // 序列化在锁外完成；落盘串行且经临时文件替换，避免并发写入交错或读到半截文件
+        let lock = status_write_lock(&self.status_folder);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
         if let Err(e) = fs::create_dir_all(&self.status_folder) {
             debug!(error = ?e, "create status folder failed");
             return;
         }
-        match fs::write(
-            &self.status_file,
-            serde_json::to_string_pretty(&data).unwrap_or_default(),
-        ) {
-            Ok(_) => {}
-            Err(e) => debug!(target: "book_manager", error = ?e, "write status.json failed"),
+        if let Err(e) = write_atomic(&self.status_file, &bytes) {
+            debug!(target: "book_manager", error = ?e, "write status.json failed");
         }
     }
 
+    fn paid_skipped_sorted(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.paid_skipped.iter().map(String::as_str).collect();
+        ids.sort_unstable();
+        ids
+    }
+
     /// 切换忽略更新状态并保存
     pub fn toggle_ignore_updates(&mut self) -> bool {
         self.ignore_updates = !self.ignore_updates;
@@ -360,14 +457,101 @@ impl BookManager {
         self.previous_book_names.push(trimmed.to_string());
     }
 
+    /// 缓存目录顺序与原始目录 JSON，供不联网重新生成成书时使用。
+    pub fn save_directory_cache(&self, chapters: &[(String, String)], raw: &Value) {
+        let data = serde_json::json!({
+            "chapters": chapters
+                .iter()
+                .map(|(id, title)| serde_json::json!({ "id": id, "title": title }))
+                .collect::<Vec<_>>(),
+            "raw": raw,
+        });
+        if let Err(e) = fs::create_dir_all(&self.status_folder) {
+            debug!(target: "book_manager", error = ?e, "create status folder failed (directory cache)");
+            return;
+        }
+        if let Err(e) = fs::write(
+            self.status_folder.join(DIRECTORY_CACHE_FILE),
+            serde_json::to_string(&data).unwrap_or_default(),
+        ) {
+            debug!(target: "book_manager", error = ?e, "write directory cache failed");
+        }
+    }
+
+    /// 读取目录缓存：(章节 ID, 标题) 列表与原始目录 JSON。
+    pub fn load_directory_cache(&self) -> Option<(Vec<(String, String)>, Value)> {
+        let mut data = self.read_json_file(&self.status_folder.join(DIRECTORY_CACHE_FILE))?;
+        let chapters = data
+            .get("chapters")?
+            .as_array()?
+            .iter()
+            .filter_map(|v| {
+                let id = v.get("id")?.as_str()?.to_string();
+                let title = v.get("title").and_then(Value::as_str).unwrap_or(&id);
+                Some((id.clone(), title.to_string()))
+            })
+            .collect();
+        let raw = data.get_mut("raw").map(Value::take).unwrap_or(Value::Null);
+        Some((chapters, raw))
+    }
+
+    /// 按目录顺序导出已下载章节到 `chapters.jsonl`，不在 `order` 中的章节按 ID 排在末尾。
+    ///
+    /// 内容取自与 status.json 相同的内存状态，失败/付费章节不写入；先写临时文件再替换，
+    /// 中途退出不会留下半截文件。
+    pub fn export_chapters_jsonl(&self, order: &[String]) -> std::io::Result<PathBuf> {
+        let mut ids: Vec<&str> = order
+            .iter()
+            .map(String::as_str)
+            .filter(|id| self.downloaded.contains_key(*id))
+            .collect();
+        let listed: HashSet<&str> = ids.iter().copied().collect();
+        let mut rest: Vec<&str> = self
+            .downloaded
+            .keys()
+            .map(String::as_str)
+            .filter(|id| !listed.contains(id))
+            .collect();
+        rest.sort_unstable();
+        ids.extend(rest);
+
+        let mut out = String::new();
+        for id in ids {
+            let Some((title, Some(content))) = self.downloaded.get(id) else {
+                continue;
+            };
+            let record = ResumeJournalRecord {
+                id: id.to_string(),
+                title: title.clone(),
+                content: content.clone(),
+            };
+            out.push_str(&serde_json::to_string(&record).map_err(std::io::Error::other)?);
+            out.push('\n');
+        }
+
+        let lock = status_write_lock(&self.status_folder);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        fs::create_dir_all(&self.status_folder)?;
+        let path = self.status_folder.join(CHAPTERS_JSONL_FILE);
+        write_atomic(&path, out.as_bytes())?;
+        Ok(path)
+    }
+
     fn resume_journal_path(&self) -> PathBuf {
         self.status_folder.join(RESUME_JOURNAL_FILE)
     }
 
-    /// 合并追加日志中的章节内容到 `downloaded`。返回是否成功加载到至少 1 条记录。
+    /// 合并 `chapters.jsonl` 与追加日志中的章节内容到 `downloaded`（只补缺失/失败的章节）。
+    /// 返回是否成功加载到至少 1 条记录。
     fn merge_resume_journal(&mut self) -> bool {
-        let path = self.resume_journal_path();
-        let file = match fs::File::open(&path) {
+        let exported = self.status_folder.join(CHAPTERS_JSONL_FILE);
+        let from_export = self.merge_journal_file(&exported);
+        let from_journal = self.merge_journal_file(&self.resume_journal_path());
+        from_export || from_journal
+    }
+
+    fn merge_journal_file(&mut self, path: &Path) -> bool {
+        let file = match fs::File::open(path) {
             Ok(f) => f,
             Err(_) => return false,
         };
@@ -403,8 +587,12 @@ impl BookManager {
         loaded_any
     }
 
+    /// 成书输出目录：与本书缓存文件夹同一根目录（按 `save_path_rules` 分流的书也能找到）。
     pub fn default_save_dir(&self) -> PathBuf {
-        self.config.default_save_dir()
+        self.status_folder
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| self.config.default_save_dir())
     }
 
     pub fn cleanup_status_folder(&mut self) -> std::io::Result<()> {
@@ -460,3 +648,64 @@ impl BookManager {
         })
     }
 }
+
+/// 章节正文哈希：先按纯文本清洗并去掉所有空白，排版/标签差异不算修改。
+pub fn chapter_content_hash(content: &str) -> String {
+    let plain: String = ContentParser::clean_plain(content, "")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    hex::encode(Sha256::digest(plain.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BookManager, chapter_content_hash};
+    use crate::base_system::context::Config;
+
+    #[test]
+    fn concurrent_status_saves_keep_file_intact() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.save_path = temp_dir.path().display().to_string();
+
+        let handles: Vec<_> = (0..8)
+            .map(|n| {
+                let config = config.clone();
+                std::thread::spawn(move || {
+                    let mut manager = BookManager::new(config, "123", "书").unwrap();
+                    manager.book_id = "123".to_string();
+                    for i in 0..20 {
+                        let cid = format!("{n}-{i}");
+                        manager.save_chapter(&cid, "标题", &"正文".repeat(200));
+                        manager.append_downloaded_chapter(&cid, "标题", "正文");
+                        manager.save_download_status();
+                    }
+                    manager.book_folder().to_path_buf()
+                })
+            })
+            .collect();
+        let folders: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        let folder = &folders[0];
+        let text = std::fs::read_to_string(folder.join("status.json")).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["downloaded"].as_object().unwrap().len(), 20);
+        let journal = std::fs::read_to_string(folder.join("downloaded_chapters.jsonl")).unwrap();
+        assert!(
+            journal
+                .lines()
+                .all(|l| serde_json::from_str::<serde_json::Value>(l).is_ok())
+        );
+    }
+
+    #[test]
+    fn chapter_hash_ignores_markup_but_not_text() {
+        let base = chapter_content_hash("<p>第一段</p><p>第二段</p>");
+        assert_eq!(base, chapter_content_hash("第一段\n\n  第二段  "));
+        assert_ne!(
+            base,
+            chapter_content_hash("<p>第一段</p><p>第二段（修订）</p>")
+        );
+    }
+}
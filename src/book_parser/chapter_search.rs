@@ -0,0 +1,271 @@
+//! 已下载章节的全文搜索。
+//!
+//! 优先读取 `chapters.jsonl`（按目录顺序），缺失时回退到 status.json 与追加日志。
+//! 以字符二元组建立倒排索引筛出候选章节，再逐章精确匹配并截取上下文片段。
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+
+use super::book_manager::CHAPTERS_JSONL_FILE;
+use super::parser::ContentParser;
+
+const STATUS_FILE: &str = "status.json";
+const RESUME_JOURNAL_FILE: &str = "downloaded_chapters.jsonl";
+/// 命中位置前后保留的字符数。
+const SNIPPET_CONTEXT_CHARS: usize = 24;
+/// 每章最多返回的片段数。
+const MAX_SNIPPETS_PER_CHAPTER: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct SearchChapter {
+    pub id: String,
+    pub title: String,
+    /// 去标签后的纯文本
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChapterHit {
+    /// 章节在目录中的序号（从 0 开始）
+    pub index: usize,
+    pub chapter_id: String,
+    pub title: String,
+    /// 本章命中次数
+    pub count: usize,
+    pub snippets: Vec<String>,
+}
+
+/// 章节文本与二元组倒排索引。
+pub struct ChapterIndex {
+    chapters: Vec<SearchChapter>,
+    /// 二元组 -> 包含它的章节序号（升序、去重）
+    postings: HashMap<(char, char), Vec<usize>>,
+}
+
+impl ChapterIndex {
+    pub fn build(chapters: Vec<SearchChapter>) -> Self {
+        let mut postings: HashMap<(char, char), Vec<usize>> = HashMap::new();
+        for (idx, ch) in chapters.iter().enumerate() {
+            let mut seen = HashSet::new();
+            let chars: Vec<char> = ch.text.chars().map(fold_case).collect();
+            for pair in chars.windows(2) {
+                let key = (pair[0], pair[1]);
+                if seen.insert(key) {
+                    postings.entry(key).or_default().push(idx);
+                }
+            }
+        }
+        Self { chapters, postings }
+    }
+
+    /// 从书籍状态目录加载并建立索引。
+    pub fn load(status_folder: &Path) -> Result<Self> {
+        let chapters = load_downloaded_chapters(status_folder);
+        if chapters.is_empty() {
+            return Err(anyhow!("未找到已下载章节内容: {}", status_folder.display()));
+        }
+        Ok(Self::build(chapters))
+    }
+
+    pub fn chapter_count(&self) -> usize {
+        self.chapters.len()
+    }
+
+    /// 搜索关键词（不区分大小写），按目录顺序返回命中章节。
+    pub fn search(&self, keyword: &str) -> Vec<ChapterHit> {
+        let needle: Vec<char> = keyword.trim().chars().map(fold_case).collect();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+
+        self.candidates(&needle)
+            .into_iter()
+            .filter_map(|idx| {
+                let ch = &self.chapters[idx];
+                let positions = find_all(&ch.text, &needle);
+                if positions.is_empty() {
+                    return None;
+                }
+                let snippets = positions
+                    .iter()
+                    .take(MAX_SNIPPETS_PER_CHAPTER)
+                    .map(|&(start, end)| snippet(&ch.text, start, end))
+                    .collect();
+                Some(ChapterHit {
+                    index: idx,
+                    chapter_id: ch.id.clone(),
+                    title: ch.title.clone(),
+                    count: positions.len(),
+                    snippets,
+                })
+            })
+            .collect()
+    }
+
+    /// 取所有二元组倒排表的交集；单字关键词无法用二元组过滤，退化为全量扫描。
+    fn candidates(&self, needle: &[char]) -> Vec<usize> {
+        if needle.len() < 2 {
+            return (0..self.chapters.len()).collect();
+        }
+        let mut lists: Vec<&Vec<usize>> = Vec::new();
+        for pair in needle.windows(2) {
+            match self.postings.get(&(pair[0], pair[1])) {
+                Some(list) => lists.push(list),
+                None => return Vec::new(),
+            }
+        }
+        lists.sort_by_key(|l| l.len());
+        let mut result = lists[0].clone();
+        for list in &lists[1..] {
+            let set: HashSet<usize> = list.iter().copied().collect();
+            result.retain(|idx| set.contains(idx));
+            if result.is_empty() {
+                break;
+            }
+        }
+        result
+    }
+}
+
+/// 逐字符的大小写折叠，保证索引、关键词与正文位置一一对应。
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// 返回命中的字符区间 `[start, end)`（按字符计，不重叠）。
+fn find_all(text: &str, needle: &[char]) -> Vec<(usize, usize)> {
+    let lower: Vec<char> = text.chars().map(fold_case).collect();
+    let needle_len = needle.len();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i + needle_len <= lower.len() {
+        if lower[i..i + needle_len] == *needle {
+            out.push((i, i + needle_len));
+            i += needle_len;
+        } else {
+            i += 1;
+        }
+    }
+    out
+}
+
+/// 截取命中位置前后的上下文，换行折叠为空格，首尾截断处补省略号。
+fn snippet(text: &str, start: usize, end: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let from = start.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let to = (end + SNIPPET_CONTEXT_CHARS).min(chars.len());
+    let mut out = String::new();
+    if from > 0 {
+        out.push('…');
+    }
+    let body: String = chars[from..to].iter().collect();
+    out.push_str(&body.split_whitespace().collect::<Vec<_>>().join(" "));
+    if to < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+fn to_search_chapter(id: String, title: String, content: &str) -> SearchChapter {
+    let text = ContentParser::clean_plain(content, &title);
+    SearchChapter { id, title, text }
+}
+
+/// 读取已下载章节：`chapters.jsonl` 优先，其次 status.json + 追加日志（按章节 ID 排序）。
+pub fn load_downloaded_chapters(status_folder: &Path) -> Vec<SearchChapter> {
+    let exported = read_jsonl(&status_folder.join(CHAPTERS_JSONL_FILE));
+    if !exported.is_empty() {
+        return exported
+            .into_iter()
+            .map(|(id, title, content)| to_search_chapter(id, title, &content))
+            .collect();
+    }
+
+    let mut merged: HashMap<String, (String, String)> = HashMap::new();
+    if let Ok(text) = fs::read_to_string(status_folder.join(STATUS_FILE))
+        && let Ok(data) = serde_json::from_str::<Value>(&text)
+        && let Some(dl) = data.get("downloaded").and_then(Value::as_object)
+    {
+        for (cid, pair) in dl {
+            let Some(arr) = pair.as_array() else {
+                continue;
+            };
+            let title = arr.first().and_then(Value::as_str).unwrap_or("");
+            if let Some(content) = arr.get(1).and_then(Value::as_str) {
+                merged.insert(cid.clone(), (title.to_string(), content.to_string()));
+            }
+        }
+    }
+    for (id, title, content) in read_jsonl(&status_folder.join(RESUME_JOURNAL_FILE)) {
+        merged.entry(id).or_insert((title, content));
+    }
+
+    let mut ids: Vec<String> = merged.keys().cloned().collect();
+    ids.sort_by(|a, b| match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(x), Ok(y)) => x.cmp(&y),
+        _ => a.cmp(b),
+    });
+    ids.into_iter()
+        .filter_map(|id| {
+            let (title, content) = merged.remove(&id)?;
+            Some(to_search_chapter(id, title, &content))
+        })
+        .collect()
+}
+
+fn read_jsonl(path: &Path) -> Vec<(String, String, String)> {
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| {
+            let v: Value = serde_json::from_str(line.trim()).ok()?;
+            let id = v.get("id")?.as_str()?.to_string();
+            let content = v.get("content")?.as_str()?.to_string();
+            let title = v
+                .get("title")
+                .and_then(Value::as_str)
+                .unwrap_or(&id)
+                .to_string();
+            (!id.is_empty() && !content.is_empty()).then_some((id, title, content))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChapterIndex, SearchChapter};
+
+    fn chapter(id: &str, text: &str) -> SearchChapter {
+        SearchChapter {
+            id: id.to_string(),
+            title: format!("第{id}章"),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn finds_keyword_with_context_in_order() {
+        let index = ChapterIndex::build(vec![
+            chapter("1", "少年站在山门前，抬头望向云海。"),
+            chapter("2", "无关内容"),
+            chapter("3", "云海翻涌，云海之下是万丈深渊。Rust 与 rust。"),
+        ]);
+        let hits = index.search("云海");
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].chapter_id, "1");
+        assert_eq!(hits[1].count, 2);
+        assert!(hits[0].snippets[0].contains("望向云海"));
+
+        assert_eq!(index.search("RUST")[0].count, 2);
+        assert!(index.search("不存在的词").is_empty());
+        assert_eq!(index.search("海").len(), 2);
+    }
+}
@@ -0,0 +1,479 @@
+//! EPUB 生成后的轻量结构自检。
+//!
+//! 不追求 epubcheck 的完整覆盖，只检查最容易因生成 bug 出错的部分：
+//! OPF/NCX/nav 引用文件是否存在、NCX 与 nav 目录是否一致、spine 章节顺序、封面声明、空章节文件。
+
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Read as _};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+fn re_tag(name: &str) -> Regex {
+    Regex::new(&format!(r"(?is)<{name}\b[^>]*>")).unwrap()
+}
+
+fn re_item() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| re_tag("item"))
+}
+
+fn re_itemref() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| re_tag("itemref"))
+}
+
+fn re_meta() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| re_tag("meta"))
+}
+
+fn re_attr() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| Regex::new(r#"(?s)([\w:-]+)\s*=\s*["']([^"']*)["']"#).unwrap())
+}
+
+fn re_ref_attr() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| Regex::new(r#"(?i)\b(?:src|href)\s*=\s*["']([^"']+)["']"#).unwrap())
+}
+
+fn re_chapter_file() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| Regex::new(r"chapter_(\d+)\.xhtml$").unwrap())
+}
+
+fn re_ncx_content() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| Regex::new(r#"(?is)<content\b[^>]*\bsrc\s*=\s*["']([^"']+)["']"#).unwrap())
+}
+
+fn re_toc_nav() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| {
+        Regex::new(
+            r#"(?is)<nav\b[^>]*epub:type\s*=\s*["'][^"']*\btoc\b[^"']*["'][^>]*>(.*?)</nav>"#,
+        )
+        .unwrap()
+    })
+}
+
+fn re_anchor_href() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| Regex::new(r#"(?is)<a\b[^>]*\bhref\s*=\s*["']([^"']+)["']"#).unwrap())
+}
+
+fn re_any_tag() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| Regex::new(r"(?s)<[^>]*>").unwrap())
+}
+
+fn attrs(tag: &str) -> HashMap<String, String> {
+    re_attr()
+        .captures_iter(tag)
+        .map(|c| (c[1].to_ascii_lowercase(), c[2].to_string()))
+        .collect()
+}
+
+/// 读取并检查已写出的 EPUB 文件，返回发现的问题列表（为空表示通过）。
+pub(crate) fn check_epub_file(path: &Path) -> anyhow::Result<Vec<String>> {
+    let bytes = std::fs::read(path)?;
+    Ok(check_epub_bytes(&bytes))
+}
+
+pub(crate) fn check_epub_bytes(bytes: &[u8]) -> Vec<String> {
+    let mut issues = Vec::new();
+    let mut archive = match zip::ZipArchive::new(Cursor::new(bytes)) {
+        Ok(a) => a,
+        Err(e) => return vec![format!("无法作为 zip 打开: {e}")],
+    };
+
+    let mut files: HashMap<String, Vec<u8>> = HashMap::new();
+    let mut first_entry = None;
+    for i in 0..archive.len() {
+        let Ok(mut entry) = archive.by_index(i) else {
+            issues.push(format!("无法读取第 {i} 个 zip 条目"));
+            continue;
+        };
+        let name = entry.name().to_string();
+        if i == 0 {
+            first_entry = Some(name.clone());
+        }
+        let mut data = Vec::new();
+        if entry.read_to_end(&mut data).is_err() {
+            issues.push(format!("读取文件失败: {name}"));
+            continue;
+        }
+        files.insert(name, data);
+    }
+
+    match files.get("mimetype") {
+        Some(m) if m.as_slice() == b"application/epub+zip" => {
+            if first_entry.as_deref() != Some("mimetype") {
+                issues.push("mimetype 不是 zip 中的第一个文件".to_string());
+            }
+        }
+        Some(_) => issues.push("mimetype 内容不是 application/epub+zip".to_string()),
+        None => issues.push("缺少 mimetype 文件".to_string()),
+    }
+
+    let text = |name: &str| {
+        files
+            .get(name)
+            .map(|d| String::from_utf8_lossy(d).into_owned())
+    };
+
+    let Some(container) = text("META-INF/container.xml") else {
+        issues.push("缺少 META-INF/container.xml".to_string());
+        return issues;
+    };
+    let Some(opf_path) = re_tag("rootfile")
+        .find(&container)
+        .and_then(|m| attrs(m.as_str()).remove("full-path"))
+    else {
+        issues.push("container.xml 未声明 rootfile".to_string());
+        return issues;
+    };
+    let Some(opf) = text(&opf_path) else {
+        issues.push(format!("container.xml 引用的 OPF 不存在: {opf_path}"));
+        return issues;
+    };
+    let opf_dir = parent_dir(&opf_path);
+
+    // manifest：id -> (zip 内路径, media-type, properties)
+    let mut manifest: HashMap<String, (String, String, String)> = HashMap::new();
+    for m in re_item().find_iter(&opf) {
+        let a = attrs(m.as_str());
+        let (Some(id), Some(href)) = (a.get("id"), a.get("href")) else {
+            continue;
+        };
+        let full = resolve_href(&opf_dir, href);
+        if !files.contains_key(&full) {
+            issues.push(format!("OPF manifest 引用的文件不存在: {full}"));
+        }
+        manifest.insert(
+            id.clone(),
+            (
+                full,
+                a.get("media-type").cloned().unwrap_or_default(),
+                a.get("properties").cloned().unwrap_or_default(),
+            ),
+        );
+    }
+
+    // spine
+    let mut last_chapter: Option<u64> = None;
+    let mut spine_count = 0usize;
+    for m in re_itemref().find_iter(&opf) {
+        let a = attrs(m.as_str());
+        let Some(idref) = a.get("idref") else {
+            continue;
+        };
+        spine_count += 1;
+        let Some((full, media_type, _)) = manifest.get(idref) else {
+            issues.push(format!("spine 引用了未声明的 manifest id: {idref}"));
+            continue;
+        };
+        if let Some(c) = re_chapter_file().captures(full)
+            && let Ok(n) = c[1].parse::<u64>()
+        {
+            if last_chapter.is_some_and(|prev| n <= prev) {
+                issues.push(format!("spine 章节顺序异常: {full}"));
+            }
+            last_chapter = Some(n);
+        }
+        if media_type.contains("xhtml")
+            && let Some(data) = files.get(full)
+            && is_empty_page(&String::from_utf8_lossy(data))
+        {
+            issues.push(format!("空章节文件: {full}"));
+        }
+    }
+    if spine_count == 0 {
+        issues.push("spine 为空".to_string());
+    }
+
+    // 封面：EPUB2 的 <meta name="cover"> 与 EPUB3 的 properties="cover-image"
+    for m in re_meta().find_iter(&opf) {
+        let a = attrs(m.as_str());
+        if a.get("name").map(String::as_str) != Some("cover") {
+            continue;
+        }
+        let Some(id) = a.get("content") else {
+            continue;
+        };
+        match manifest.get(id) {
+            Some((full, media_type, _)) => {
+                if !media_type.starts_with("image/") {
+                    issues.push(format!("封面声明指向非图片资源: {full}"));
+                } else if files.get(full).is_some_and(|d| d.is_empty()) {
+                    issues.push(format!("封面图片为空文件: {full}"));
+                }
+            }
+            None => issues.push(format!("封面声明引用了未知 manifest id: {id}")),
+        }
+    }
+    for (full, _, props) in manifest.values() {
+        if props.split_whitespace().any(|p| p == "cover-image")
+            && files.get(full).is_none_or(|d| d.is_empty())
+        {
+            issues.push(format!("cover-image 资源缺失或为空: {full}"));
+        }
+    }
+
+    // NCX / nav 中的链接
+    let mut nav_files: HashSet<&String> = HashSet::new();
+    let mut ncx_file = None;
+    let mut nav_doc = None;
+    for (full, media_type, props) in manifest.values() {
+        if media_type == "application/x-dtbncx+xml" {
+            nav_files.insert(full);
+            ncx_file = Some(full);
+        } else if props.split_whitespace().any(|p| p == "nav") {
+            nav_files.insert(full);
+            nav_doc = Some(full);
+        }
+    }
+    let is_epub3 = re_tag("package")
+        .find(&opf)
+        .and_then(|m| attrs(m.as_str()).remove("version"))
+        .is_some_and(|v| v.starts_with('3'));
+    if ncx_file.is_none() {
+        issues.push("缺少 toc.ncx，仅支持 EPUB2 的阅读器将无法显示目录".to_string());
+    }
+    if is_epub3 && nav_doc.is_none() {
+        issues.push("EPUB3 缺少 nav 目录文档".to_string());
+    }
+    if let (Some(ncx), Some(nav)) = (ncx_file, nav_doc)
+        && let (Some(ncx_text), Some(nav_text)) = (text(ncx), text(nav))
+    {
+        let ncx_dir = parent_dir(ncx);
+        let ncx_targets: Vec<String> = re_ncx_content()
+            .captures_iter(&ncx_text)
+            .map(|c| toc_target(&ncx_dir, &c[1]))
+            .collect();
+        let nav_dir = parent_dir(nav);
+        let nav_targets: Vec<String> = re_toc_nav()
+            .captures(&nav_text)
+            .map(|toc| {
+                re_anchor_href()
+                    .captures_iter(&toc[1])
+                    .map(|c| toc_target(&nav_dir, &c[1]))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if ncx_targets != nav_targets {
+            issues.push(format!(
+                "toc.ncx 与 nav 目录不一致（{} 项 / {} 项）",
+                ncx_targets.len(),
+                nav_targets.len()
+            ));
+        }
+    }
+    for nav in nav_files {
+        let Some(data) = text(nav) else {
+            continue;
+        };
+        let dir = parent_dir(nav);
+        for c in re_ref_attr().captures_iter(&data) {
+            let href = &c[1];
+            if href.contains("://") || href.starts_with('#') || href.starts_with("mailto:") {
+                continue;
+            }
+            let full = resolve_href(&dir, href);
+            if !files.contains_key(&full) {
+                issues.push(format!("{nav} 引用的文件不存在: {full}"));
+            }
+        }
+    }
+
+    issues
+}
+
+/// 目录项指向的 zip 内路径（保留 #fragment），用于比较 NCX 与 nav。
+fn toc_target(base_dir: &str, href: &str) -> String {
+    let full = resolve_href(base_dir, href);
+    match href.split_once('#') {
+        Some((_, frag)) => format!("{full}#{frag}"),
+        None => full,
+    }
+}
+
+fn parent_dir(path: &str) -> String {
+    match path.rfind('/') {
+        Some(i) => path[..=i].to_string(),
+        None => String::new(),
+    }
+}
+
+/// 将相对 href（去掉 #fragment，解码 %xx，处理 ../）解析为 zip 内路径。
+fn resolve_href(base_dir: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or("");
+    let href = percent_decode(href);
+    let mut parts: Vec<&str> = base_dir.split('/').filter(|s| !s.is_empty()).collect();
+    for seg in href.split('/') {
+        match seg {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            s => parts.push(s),
+        }
+    }
+    parts.join("/")
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let hex = |b: u8| (b as char).to_digit(16);
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && let (Some(h), Some(l)) = (hex(bytes[i + 1]), hex(bytes[i + 2]))
+        {
+            out.push((h * 16 + l) as u8);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// body 内既无文字也无图片即视为空页面。
+fn is_empty_page(html: &str) -> bool {
+    let lower = html.to_ascii_lowercase();
+    let body = match (lower.find("<body"), lower.rfind("</body>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    if body.to_ascii_lowercase().contains("<img") || body.contains("<image") {
+        return false;
+    }
+    let stripped = re_any_tag().replace_all(body, "");
+    stripped
+        .replace("&nbsp;", "")
+        .replace("&#160;", "")
+        .trim()
+        .is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write as _;
+
+    fn build_epub(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut out = Cursor::new(Vec::new());
+        {
+            let mut w = zip::ZipWriter::new(&mut out);
+            for (name, data) in files {
+                w.start_file(*name, zip::write::FileOptions::default())
+                    .unwrap();
+                w.write_all(data.as_bytes()).unwrap();
+            }
+            w.finish().unwrap();
+        }
+        out.into_inner()
+    }
+
+    const CONTAINER: &str = r#"<container><rootfiles><rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/></rootfiles></container>"#;
+
+    #[test]
+    fn reports_missing_and_empty_files() {
+        let opf = r#"<package><manifest>
+            <item id="c1" href="chapter_00001.xhtml" media-type="application/xhtml+xml"/>
+            <item id="c2" href="chapter_00002.xhtml" media-type="application/xhtml+xml"/>
+            <item id="c3" href="chapter_00003.xhtml" media-type="application/xhtml+xml"/>
+            </manifest><spine><itemref idref="c2"/><itemref idref="c1"/><itemref idref="c3"/></spine></package>"#;
+        let bytes = build_epub(&[
+            ("mimetype", "application/epub+zip"),
+            ("META-INF/container.xml", CONTAINER),
+            ("OEBPS/content.opf", opf),
+            (
+                "OEBPS/chapter_00001.xhtml",
+                "<html><body><p>正文</p></body></html>",
+            ),
+            (
+                "OEBPS/chapter_00002.xhtml",
+                "<html><body><p> </p></body></html>",
+            ),
+        ]);
+        let issues = check_epub_bytes(&bytes);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.contains("不存在: OEBPS/chapter_00003.xhtml"))
+        );
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.contains("空章节文件: OEBPS/chapter_00002.xhtml"))
+        );
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.contains("顺序异常: OEBPS/chapter_00001.xhtml"))
+        );
+    }
+
+    #[test]
+    fn valid_minimal_epub_passes() {
+        let opf = r#"<package><metadata><meta name="cover" content="cover"/></metadata><manifest>
+            <item id="cover" href="images/cover.jpg" media-type="image/jpeg"/>
+            <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+            <item id="c1" href="chapter_00001.xhtml" media-type="application/xhtml+xml"/>
+            </manifest><spine><itemref idref="c1"/></spine></package>"#;
+        let ncx = r#"<ncx><navMap><navPoint><content src="chapter_00001.xhtml#top"/></navPoint></navMap></ncx>"#;
+        let bytes = build_epub(&[
+            ("mimetype", "application/epub+zip"),
+            ("META-INF/container.xml", CONTAINER),
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/toc.ncx", ncx),
+            ("OEBPS/images/cover.jpg", "jpg"),
+            (
+                "OEBPS/chapter_00001.xhtml",
+                "<html><body><p>正文</p></body></html>",
+            ),
+        ]);
+        assert!(check_epub_bytes(&bytes).is_empty());
+    }
+
+    #[test]
+    fn reports_ncx_nav_mismatch() {
+        let opf = r#"<package version="3.0"><manifest>
+            <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+            <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+            <item id="v1" href="aux_00001.xhtml" media-type="application/xhtml+xml"/>
+            <item id="c1" href="chapter_00001.xhtml" media-type="application/xhtml+xml"/>
+            </manifest><spine><itemref idref="v1"/><itemref idref="c1"/></spine></package>"#;
+        let ncx = r#"<ncx><navMap><navPoint><content src="aux_00001.xhtml"/>
+            <navPoint><content src="chapter_00001.xhtml"/></navPoint></navPoint></navMap></ncx>"#;
+        let nav_same = r#"<html><body><nav epub:type="toc"><ol><li><a href="aux_00001.xhtml">卷一</a>
+            <ol><li><a href="chapter_00001.xhtml">第一章</a></li></ol></li></ol></nav>
+            <nav epub:type="landmarks"><ol><li><a href="aux_00001.xhtml">正文</a></li></ol></nav></body></html>"#;
+        let nav_diff = r#"<html><body><nav epub:type="toc"><ol><li><a href="aux_00001.xhtml">卷一</a></li></ol></nav></body></html>"#;
+        let page = "<html><body><p>正文</p></body></html>";
+        let build = |nav: &str| {
+            build_epub(&[
+                ("mimetype", "application/epub+zip"),
+                ("META-INF/container.xml", CONTAINER),
+                ("OEBPS/content.opf", opf),
+                ("OEBPS/toc.ncx", ncx),
+                ("OEBPS/nav.xhtml", nav),
+                ("OEBPS/aux_00001.xhtml", page),
+                ("OEBPS/chapter_00001.xhtml", page),
+            ])
+        };
+        assert!(check_epub_bytes(&build(nav_same)).is_empty());
+        assert!(
+            check_epub_bytes(&build(nav_diff))
+                .iter()
+                .any(|i| i.contains("不一致"))
+        );
+    }
+}
@@ -1,30 +1,186 @@
 //! EPUB 生成器。
 
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{Cursor, Read as _, Write as _};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-use anyhow::Result;
-use epub_builder::{EpubBuilder, EpubContent, EpubVersion, ReferenceType, ZipLibrary};
+use anyhow::{Context as _, Result};
+use epub_builder::{EpubBuilder, EpubContent, EpubVersion, ReferenceType, TocElement, ZipLibrary};
+use regex::Regex;
 use tracing::warn;
 
+use super::html_utils::DIALOGUE_CLASS;
+use super::placeholder_cover::{PLACEHOLDER_COVER_FILE, render_placeholder_cover};
 use crate::base_system::{book_paths, context::Config};
 
+/// 内嵌字体在 CSS 中使用的 font-family 名称。
+const EMBED_FONT_FAMILY: &str = "TomatoEmbedFont";
+/// 超过该大小的内嵌字体会给出体积警告。
+const EMBED_FONT_WARN_BYTES: usize = 8 * 1024 * 1024;
+
+/// 章节正文为空时的占位内容。
+pub(crate) const EMPTY_CHAPTER_HTML: &str =
+    "<p class='no-indent'>本章内容未下载完成或为空（可能是用户中断或网络错误）。</p>";
+
 /// 用于从 book_id 确定性生成 UUID v5 的命名空间。
 /// 这保证同一本书（同 book_id）的 dc:identifier 永远不变。
 const EPUB_UUID_NAMESPACE: uuid::Uuid = uuid::Uuid::from_bytes([
     0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8,
 ]);
 
+/// 生成失败时保留中间产物的目录名（位于输出文件所在目录下）。
+const EPUB_BUILD_DIR: &str = "epub_build";
+
+/// 目录中取不到章节时间时 `dcterms:modified` 的兜底值（与 zip 条目 mtime 一致）。
+const FALLBACK_MODIFIED: &str = "1980-01-01T00:00:00Z";
+
+fn opf_modified_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(<meta property="dcterms:modified">)[^<]*(</)"#)
+            .expect("compile opf modified regex")
+    })
+}
+
+/// zip 条目顺序：`mimetype` 必须第一个，其次 `META-INF/container.xml`，其余按路径排序。
+fn zip_entry_rank(name: &str) -> (u8, &str) {
+    match name {
+        "mimetype" => (0, name),
+        "META-INF/container.xml" => (1, name),
+        _ => (2, name),
+    }
+}
+
+/// EPUB 内置主题预设（`Config.epub_theme`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpubTheme {
+    Default,
+    /// 护眼米色
+    Sepia,
+    /// 夜间深色
+    Night,
+    /// 纸质衬线
+    Paper,
+}
+
+/// 主题注入到样式表中的颜色、字体与行距。
+struct ThemePalette {
+    /// body 背景色；为空时不设置，交由阅读器决定。
+    background: &'static str,
+    text: &'static str,
+    heading_bg: &'static str,
+    heading_text: &'static str,
+    block_bg: &'static str,
+    /// 对话分色（`epub_highlight_dialogue`）使用的文字颜色。
+    dialogue_text: &'static str,
+    font_family: &'static str,
+    line_height: &'static str,
+}
+
+impl EpubTheme {
+    pub const ALL: [EpubTheme; 4] = [
+        EpubTheme::Default,
+        EpubTheme::Sepia,
+        EpubTheme::Night,
+        EpubTheme::Paper,
+    ];
+
+    /// 未知取值回退到默认主题。
+    pub fn from_config(raw: &str) -> Self {
+        let raw = raw.trim();
+        Self::ALL
+            .into_iter()
+            .find(|t| raw.eq_ignore_ascii_case(t.as_str()) || raw == t.label())
+            .unwrap_or(EpubTheme::Default)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EpubTheme::Default => "default",
+            EpubTheme::Sepia => "sepia",
+            EpubTheme::Night => "night",
+            EpubTheme::Paper => "paper",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EpubTheme::Default => "默认",
+            EpubTheme::Sepia => "护眼米色",
+            EpubTheme::Night => "夜间深色",
+            EpubTheme::Paper => "纸质衬线",
+        }
+    }
+
+    fn palette(self) -> ThemePalette {
+        match self {
+            EpubTheme::Default => ThemePalette {
+                background: "",
+                text: "#000",
+                heading_bg: "#f5f5f5",
+                heading_text: "#333",
+                block_bg: "#fafafa",
+                dialogue_text: "#1f4e79",
+                font_family: "serif",
+                line_height: "1.5",
+            },
+            EpubTheme::Sepia => ThemePalette {
+                background: "#f6efd9",
+                text: "#3b3024",
+                heading_bg: "#ece2c6",
+                heading_text: "#3b3024",
+                block_bg: "#efe6cc",
+                dialogue_text: "#7a4a1e",
+                font_family: "serif",
+                line_height: "1.7",
+            },
+            EpubTheme::Night => ThemePalette {
+                background: "#1b1d22",
+                text: "#c9c9c9",
+                heading_bg: "#2a2d33",
+                heading_text: "#dddddd",
+                block_bg: "#24272d",
+                dialogue_text: "#8fb8de",
+                font_family: "sans-serif",
+                line_height: "1.7",
+            },
+            EpubTheme::Paper => ThemePalette {
+                background: "#fbfaf6",
+                text: "#222",
+                heading_bg: "#f0ede4",
+                heading_text: "#222",
+                block_bg: "#f5f2ea",
+                dialogue_text: "#2d4f73",
+                font_family: "\"Songti SC\", \"Noto Serif CJK SC\", \"Source Han Serif SC\", serif",
+                line_height: "1.8",
+            },
+        }
+    }
+}
+
 pub struct EpubGenerator {
     book: EpubBuilder<ZipLibrary>,
     chapters: Vec<(String, String)>,
+    /// 合并短章的文件 -> 各原始章节的 (href, 标题)，作为目录子项
+    toc_children: HashMap<String, Vec<(String, String)>>,
+    /// 分卷内章节的文件名，目录中作为分卷的下一级（NCX 与 nav 共用同一层级）
+    volume_members: HashSet<String>,
+    /// 当前是否处于某个分卷之下
+    in_volume: bool,
     style: String,
     #[allow(dead_code)]
     file_counter: usize,
     title: String,
+    author: String,
+    /// 占位封面背景色（`cover_primary_color`）。
+    cover_color: Option<String>,
+    modified: Option<i64>,
     /// 原始 book_id，用于生成确定性 UUID 并在后处理中替换为 dc:identifier 的值。
     book_id: String,
+    /// 已写入的资源 (路径, MIME, 字节数)，生成失败时写入诊断清单。
+    resources: Vec<(String, String, usize)>,
 }
 
 impl EpubGenerator {
@@ -80,15 +236,24 @@ impl EpubGenerator {
         } else {
             "text-indent:0;".to_string()
         };
-        let css = format!(
-            "body {{ font-family: serif; color:#000 !important; line-height:1.5; }}
-             p {{ color:#000 !important; {} margin:0 0 .8em 0; line-height:1.5; }}
+        let theme = EpubTheme::from_config(&cfg.epub_theme);
+        let palette = theme.palette();
+        let background_rule = if palette.background.is_empty() {
+            String::new()
+        } else {
+            format!(" background-color:{};", palette.background)
+        };
+        let mut css = format!(
+            "body {{ font-family: {font}; color:{text} !important;{background} line-height:{lh}; }}
+             p {{ color:{text} !important; {indent} margin:0 0 .8em 0; line-height:{lh}; }}
              p.no-indent {{ text-indent:0; }}
+             p.intro-heading {{ margin:.6em 0 .4em 0; }}
+             ul.intro-list {{ margin:0 0 .8em 1.2em; padding:0; }}
              p.img-desc {{ color:#999 !important; font-size:0.75em; text-indent:0; text-align:center; margin:-.4em 0 .9em 0; }}
              a.seg-link {{ color: inherit; text-decoration: none; }}
              a.seg-link:hover {{ text-decoration: underline; }}
              .seg-count {{ color:#999; font-size:0.75em; margin-left:.3em; text-decoration: none; }}
-             h3 {{ background:#f5f5f5; padding:.4em .6em; color:#333; font-weight:500; }}
+             h3 {{ background:{heading_bg}; padding:.4em .6em; color:{heading_text}; font-weight:500; }}
              .para-title .para-index {{ font-weight:600; margin-right:.25em; color:#333; }}
              .para-title .para-src {{ color:#333; }}
              .para-title small {{ color:#666; }}
@@ -100,20 +265,81 @@ impl EpubGenerator {
              .avatar {{ width:36px; height:36px; border-radius:50%; object-fit:cover; vertical-align:middle; margin-right:.5em; }}
              .seg-meta {{ color:#666; display:block; text-align:right; }}
              li.seg-item {{ border-bottom:1px solid #ddd; padding:.5em 0 .6em 0; }}
-             li.seg-item:last-child {{ border-bottom:none; }}",
-            indent_rule
+             li.seg-item:last-child {{ border-bottom:none; }}
+             .seg-block {{ margin:-.4em 0 1em 1em; padding:.3em .6em; border-left:3px solid #ddd; background:{block_bg}; font-size:.85em; }}
+             .seg-block p, aside.seg-footnote p {{ text-indent:0; }}
+             p.seg-block-title {{ color:#666 !important; font-size:.9em; margin:0 0 .3em 0; }}
+             aside.seg-footnote {{ font-size:.85em; border-top:1px solid #ddd; margin-top:1em; padding-top:.4em; }}
+             p.chapter-nav {{ text-align:center; font-size:.85em; margin-top:1.5em; color:#666; }}
+             p.chapter-nav a {{ color:#666; text-decoration:none; }}
+             p.chapter-nav .nav-disabled {{ color:#bbb; }}
+             p.chapter-header {{ color:#999 !important; font-size:.75em; text-align:center; margin:0 0 .6em 0; padding-bottom:.3em; border-bottom:1px solid #ddd; }}
+             p.chapter-index {{ color:#999 !important; font-size:.8em; text-align:center; }}
+             p.chapter-time {{ color:#999 !important; font-size:.8em; text-align:right; }}",
+            font = palette.font_family,
+            text = palette.text,
+            background = background_rule,
+            lh = palette.line_height,
+            indent = indent_rule,
+            heading_bg = palette.heading_bg,
+            heading_text = palette.heading_text,
+            block_bg = palette.block_bg,
         );
+        if cfg.epub_highlight_dialogue {
+            css.push_str(&format!(
+                "\np.{} {{ color:{} !important; }}",
+                DIALOGUE_CLASS, palette.dialogue_text
+            ));
+        }
+        if let Some((resource_path, bytes, mime)) = load_embed_font(cfg) {
+            match book.add_resource(&resource_path, Cursor::new(bytes), mime) {
+                Ok(_) => css.push_str(&format!(
+                    "\n@font-face {{ font-family: \"{0}\"; src: url(\"{1}\"); }}\n\
+                     body, p {{ font-family: \"{0}\", {2}; }}",
+                    EMBED_FONT_FAMILY, resource_path, palette.font_family
+                )),
+                Err(e) => warn!(target: "epub", error = %e, "嵌入字体写入 EPUB 失败，已跳过"),
+            }
+        }
 
         Ok(Self {
             book,
             chapters: Vec::new(),
+            toc_children: HashMap::new(),
+            volume_members: HashSet::new(),
+            in_volume: false,
             style: css,
             file_counter: 0,
             title: title.to_string(),
+            author: author.to_string(),
+            cover_color: None,
+            modified: None,
             book_id: identifier.to_string(),
+            resources: Vec::new(),
         })
     }
 
+    pub fn set_cover_color(&mut self, color: Option<String>) {
+        self.cover_color = color;
+    }
+
+    /// 设置 `dcterms:modified`（Unix 秒），应取自内容本身（如最新章节首发时间）。
+    pub fn set_modified_time(&mut self, secs: Option<i64>) {
+        self.modified = secs;
+    }
+
+    fn modified_str(&self) -> String {
+        self.modified
+            .and_then(|secs| time::OffsetDateTime::from_unix_timestamp(secs).ok())
+            .and_then(|dt| {
+                dt.format(time::macros::format_description!(
+                    "[year]-[month]-[day]T[hour]:[minute]:[second]Z"
+                ))
+                .ok()
+            })
+            .unwrap_or_else(|| FALLBACK_MODIFIED.to_string())
+    }
+
     #[allow(dead_code)]
     pub fn add_chapter(&mut self, title: &str, content: &str) {
         let file_name = format!("chapter_{:05}.xhtml", self.file_counter);
@@ -122,14 +348,52 @@ impl EpubGenerator {
     }
 
     pub fn add_chapter_named(&mut self, file_name: String, title: &str, content: &str) {
+        self.add_chapter_with_header_named(file_name, title, "", content);
+    }
+
+    /// 同 `add_chapter_named`，`header` 放在章节 `<h1>` 之前作为页眉。
+    pub fn add_chapter_with_header_named(
+        &mut self,
+        file_name: String,
+        title: &str,
+        header: &str,
+        content: &str,
+    ) {
         let cleaned = if content.trim().is_empty() {
-            "<p class='no-indent'>本章内容未下载完成或为空（可能是用户中断或网络错误）。</p>"
-                .to_string()
+            EMPTY_CHAPTER_HTML.to_string()
         } else {
             content.to_string()
         };
+        self.mark_volume_member(&file_name);
+        let body = format!("{}<h1>{}</h1>\n{}", header, html_escape(title), cleaned);
+        self.chapters
+            .push((file_name, wrap_xhtml_page(title, &body)));
+    }
+
+    /// 分卷标题页：之后加入的正文章节在目录中归入该卷，直到下一个分卷或附加页。
+    pub fn add_volume_page_named(&mut self, file_name: String, title: &str, content: &str) {
+        self.add_aux_page_named(file_name, title, content, true);
+        self.in_volume = true;
+    }
+
+    fn mark_volume_member(&mut self, file_name: &str) {
+        if self.in_volume {
+            self.volume_members.insert(file_name.to_string());
+        }
+    }
+
+    /// 多个短章合并为一个文件：正文自带各章标题，`children` 为各章锚点，保证目录可跳到每一章。
+    pub fn add_merged_chapters_named(
+        &mut self,
+        file_name: String,
+        title: &str,
+        content: &str,
+        children: Vec<(String, String)>,
+    ) {
+        self.toc_children.insert(file_name.clone(), children);
+        self.mark_volume_member(&file_name);
         self.chapters
-            .push((file_name, wrap_chapter_html(title, &cleaned)));
+            .push((file_name, wrap_xhtml_page(title, content)));
     }
 
     #[allow(dead_code)]
@@ -154,6 +418,7 @@ impl EpubGenerator {
         } else {
             content.to_string()
         };
+        self.in_volume = false;
         if include_in_spine {
             self.chapters
                 .push((file_name.clone(), wrap_chapter_html(title, &cleaned)));
@@ -162,13 +427,78 @@ impl EpubGenerator {
     }
 
     pub fn add_resource_bytes(&mut self, path: &str, bytes: Vec<u8>, mime: &str) -> Result<()> {
+        let len = bytes.len();
         self.book
             .add_resource(path, Cursor::new(bytes), mime)
-            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+            .map_err(|e| anyhow::anyhow!("添加资源 {path} 失败: {e}"))?;
+        self.resources
+            .push((path.to_string(), mime.to_string(), len));
         Ok(())
     }
 
+    /// 生成 EPUB；失败时把已生成的 xhtml、样式与资源清单保留到 `epub_build/<文件名>/`，
+    /// 成功则清理该目录。
     pub fn generate(&mut self, output_path: &Path, cfg: &Config) -> Result<()> {
+        let build_dir = epub_build_dir(output_path);
+        match self.generate_inner(output_path, cfg) {
+            Ok(()) => {
+                if build_dir.exists() {
+                    let _ = fs::remove_dir_all(&build_dir);
+                    if let Some(parent) = build_dir.parent() {
+                        // 仅在为空时删除
+                        let _ = fs::remove_dir(parent);
+                    }
+                }
+                Ok(())
+            }
+            Err(err) => match self.dump_build_artifacts(&build_dir, &err) {
+                Ok(()) => {
+                    warn!(
+                        target: "epub",
+                        error = %format!("{err:#}"),
+                        dir = %build_dir.display(),
+                        "EPUB 生成失败，已保留中间产物"
+                    );
+                    Err(err.context(format!(
+                        "EPUB 生成失败，中间产物已保留于 {}",
+                        build_dir.display()
+                    )))
+                }
+                Err(e) => {
+                    warn!(target: "epub", error = %e, "保存 EPUB 中间产物失败");
+                    Err(err)
+                }
+            },
+        }
+    }
+
+    /// 写出诊断用中间产物：error.txt（错误链）、style.css、text/*.xhtml 与 resources.txt。
+    fn dump_build_artifacts(&self, dir: &Path, err: &anyhow::Error) -> Result<()> {
+        if dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+        let text_dir = dir.join("text");
+        fs::create_dir_all(&text_dir)?;
+        fs::write(dir.join("error.txt"), format!("{err:?}\n"))?;
+        fs::write(dir.join("style.css"), &self.style)?;
+        for (file_name, html) in &self.chapters {
+            let name = Path::new(file_name)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| file_name.replace(['/', '\\'], "_"));
+            fs::write(text_dir.join(name), html)?;
+        }
+        let manifest: String = self
+            .resources
+            .iter()
+            .map(|(path, mime, len)| format!("{path}\t{mime}\t{len}\n"))
+            .collect();
+        fs::write(dir.join("resources.txt"), manifest)?;
+        Ok(())
+    }
+
+    fn generate_inner(&mut self, output_path: &Path, cfg: &Config) -> Result<()> {
+        let mut has_cover = false;
         if let Some(base) = cfg.get_status_folder_path() {
             let candidates = book_paths::cover_file_candidates(&base, Some(&self.title));
             let candidate_refs: Vec<&Path> = candidates.iter().map(|p| p.as_path()).collect();
@@ -182,7 +512,8 @@ impl EpubGenerator {
                 let cursor = Cursor::new(bytes);
                 self.book
                     .add_cover_image(cover_name, cursor, mime)
-                    .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+                    .map_err(|e| anyhow::anyhow!("写入封面 {} 失败: {e}", found_path.display()))?;
+                has_cover = true;
             } else {
                 warn!(
                     target: "epub",
@@ -193,40 +524,91 @@ impl EpubGenerator {
                 );
             }
         }
+        if !has_cover && cfg.epub_placeholder_cover {
+            self.add_placeholder_cover(cfg)
+                .context("写入占位封面失败")?;
+        }
 
         // 使用 stylesheet() 而非 add_resource()，防止 epub-builder 自动创建空的 stylesheet.css。
         self.book
             .stylesheet(Cursor::new(self.style.clone()))
-            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+            .map_err(|e| anyhow::anyhow!("写入样式表失败: {e}"))?;
 
+        // EPUB3 输出时 epub-builder 会同时写 toc.ncx 与 nav.xhtml，两者来自同一份目录树，
+        // 分卷/章节层级通过 level 表达，保证新旧阅读器看到的目录一致。
         for (file_name, html) in &self.chapters {
+            let level = if self.volume_members.contains(file_name) {
+                2
+            } else {
+                1
+            };
+            let chapter_title = title_from_file_or_html(file_name, html);
+            let mut content = EpubContent::new(file_name.clone(), Cursor::new(html.clone()))
+                .title(chapter_title.clone())
+                .reftype(ReferenceType::Text)
+                .level(level);
+            for (href, title) in self.toc_children.get(file_name).into_iter().flatten() {
+                content =
+                    content.child(TocElement::new(href.clone(), title.clone()).level(level + 1));
+            }
             self.book
-                .add_content(
-                    EpubContent::new(file_name.clone(), Cursor::new(html.clone()))
-                        .title(title_from_file_or_html(file_name, html))
-                        .reftype(ReferenceType::Text),
-                )
-                .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+                .add_content(content)
+                .map_err(|e| anyhow::anyhow!("添加章节 {file_name}（{chapter_title}）失败: {e}"))?;
         }
 
         let parent = output_path.parent().unwrap_or_else(|| Path::new("."));
-        fs::create_dir_all(parent)?;
+        fs::create_dir_all(parent)
+            .with_context(|| format!("创建输出目录 {} 失败", parent.display()))?;
 
         // 先生成到内存缓冲区，然后后处理替换 dc:identifier 为原始 book_id。
         // epub-builder 强制输出 urn:uuid:xxx 格式，无法通过 API 设置纯文本 identifier。
         let mut buffer = Vec::new();
         self.book
             .generate(&mut buffer)
-            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+            .map_err(|e| anyhow::anyhow!("打包 EPUB 失败: {e}"))?;
 
-        let buffer = self.fixup_epub_identifier(buffer)?;
-        fs::write(output_path, buffer)?;
+        let buffer = self
+            .fixup_epub_identifier(buffer)
+            .context("EPUB 后处理失败")?;
+        fs::write(output_path, buffer)
+            .with_context(|| format!("写出 {} 失败", output_path.display()))?;
         Ok(())
     }
 
+    /// 无封面时生成占位封面并另存到书籍文件夹；找不到字体或渲染失败只记录警告。
+    fn add_placeholder_cover(&mut self, cfg: &Config) -> Result<()> {
+        let Some(font_path) = cfg.resolve_pdf_font_path() else {
+            warn!(target: "epub", "未找到可用的中文字体，跳过占位封面（可设置 pdf_font_path）");
+            return Ok(());
+        };
+        match render_placeholder_cover(
+            &self.title,
+            &self.author,
+            self.cover_color.as_deref(),
+            &font_path,
+        ) {
+            Ok(png) => {
+                if let Some(folder) = cfg.get_status_folder_path()
+                    && let Err(e) = fs::write(folder.join(PLACEHOLDER_COVER_FILE), &png)
+                {
+                    warn!(target: "epub", error = ?e, "保存占位封面到书籍文件夹失败");
+                }
+                self.book
+                    .add_cover_image("images/cover.png", Cursor::new(png), "image/png")
+                    .map(|_| ())
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))
+            }
+            Err(e) => {
+                warn!(target: "epub", error = ?e, "生成占位封面失败");
+                Ok(())
+            }
+        }
+    }
+
     /// 后处理 EPUB zip：
     /// 1. 将 content.opf 和 toc.ncx 中的 `urn:uuid:xxx` 替换为原始 book_id
     /// 2. 在 toc.ncx 的 `<head>` 中补充 `<meta name="dtb:uid" content="{book_id}" />`
+    /// 3. 固定 zip 条目 mtime 与条目顺序，`dcterms:modified` 取自内容，使输出可复现
     fn fixup_epub_identifier(&self, epub_bytes: Vec<u8>) -> Result<Vec<u8>> {
         let stable_uuid = uuid::Uuid::new_v5(&EPUB_UUID_NAMESPACE, self.book_id.as_bytes());
         let urn_str = format!("urn:uuid:{}", stable_uuid.hyphenated());
@@ -248,11 +630,13 @@ impl EpubGenerator {
             entries.push((name, compression, data));
         }
         drop(archive);
+        entries.sort_by(|a, b| zip_entry_rank(&a.0).cmp(&zip_entry_rank(&b.0)));
 
         // 重写 zip，在 content.opf 和 toc.ncx 中将 urn:uuid:xxx 替换为原始 book_id，
         // 并在 toc.ncx 中补充 dtb:uid meta。
         // 同时移除 com.apple.ibooks.display-options.xml，该文件声明 specified-fonts=true
         // 导致 Calibre/Kindle 转换时误认为 EPUB 内嵌了字体，回退到英文默认字体。
+        let modified = self.modified_str();
         let dtb_uid_meta = format!("<meta name=\"dtb:uid\" content=\"{}\" />", self.book_id);
         let mut out = Cursor::new(Vec::new());
         {
@@ -263,7 +647,10 @@ impl EpubGenerator {
                     continue;
                 }
                 let needs_fixup = name.ends_with("content.opf") || name.ends_with("toc.ncx");
-                let options = zip::write::FileOptions::default().compression_method(compression);
+                let options = zip::write::FileOptions::default()
+                    .compression_method(compression)
+                    .last_modified_time(zip::DateTime::default())
+                    .unix_permissions(0o644);
                 writer
                     .start_file(&name, options)
                     .map_err(|e| anyhow::anyhow!("zip write error: {e}"))?;
@@ -272,6 +659,11 @@ impl EpubGenerator {
                     match String::from_utf8(data) {
                         Ok(text) => {
                             let mut fixed = text.replace(&urn_str, &self.book_id);
+                            if name.ends_with("content.opf") {
+                                fixed = opf_modified_regex()
+                                    .replace_all(&fixed, format!("${{1}}{modified}${{2}}"))
+                                    .into_owned();
+                            }
                             // 在 toc.ncx 中补充 dtb:uid（epub-builder 模板未包含此项）
                             if name.ends_with("toc.ncx") && !fixed.contains("dtb:uid") {
                                 fixed = fixed.replace(
@@ -298,6 +690,16 @@ impl EpubGenerator {
     }
 }
 
+/// `<输出目录>/epub_build/<输出文件名>`，按卷导出时各卷互不覆盖。
+fn epub_build_dir(output_path: &Path) -> PathBuf {
+    let parent = output_path.parent().unwrap_or_else(|| Path::new("."));
+    let stem = output_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "book".to_string());
+    parent.join(EPUB_BUILD_DIR).join(stem)
+}
+
 fn read_first_existing<'a>(paths: &[&'a Path]) -> Option<(&'a Path, Vec<u8>)> {
     for p in paths {
         if let Ok(bytes) = fs::read(p) {
@@ -331,10 +733,14 @@ fn html_escape(input: &str) -> String {
 }
 
 fn wrap_chapter_html(title: &str, body: &str) -> String {
-    let escaped_title = html_escape(title);
+    wrap_xhtml_page(title, &format!("<h1>{}</h1>\n{}", html_escape(title), body))
+}
+
+fn wrap_xhtml_page(title: &str, body: &str) -> String {
     format!(
-        "<?xml version='1.0' encoding='utf-8'?>\n<!DOCTYPE html>\n<html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\" epub:prefix=\"z3998: http://www.daisy.org/z3998/2012/vocab/structure/#\" lang=\"zh\" xml:lang=\"zh\">\n  <head>\n    <title>{}</title>\n    <link href=\"stylesheet.css\" rel=\"stylesheet\" type=\"text/css\"/>\n  </head>\n  <body><h1>{}</h1>\n{}\n  </body>\n</html>",
-        escaped_title, escaped_title, body
+        "<?xml version='1.0' encoding='utf-8'?>\n<!DOCTYPE html>\n<html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\" epub:prefix=\"z3998: http://www.daisy.org/z3998/2012/vocab/structure/#\" lang=\"zh\" xml:lang=\"zh\">\n  <head>\n    <title>{}</title>\n    <link href=\"stylesheet.css\" rel=\"stylesheet\" type=\"text/css\"/>\n  </head>\n  <body>{}\n  </body>\n</html>",
+        html_escape(title),
+        body
     )
 }
 
@@ -350,3 +756,52 @@ fn title_from_file_or_html(file_name: &str, html: &str) -> String {
     }
     file_name.to_string()
 }
+
+/// 读取 `epub_embed_font_path` 指定的字体，返回 (EPUB 内资源路径, 字节, mimetype)。
+/// 未配置、文件不存在或格式不支持时返回 None（记录 warning 后按无字体继续生成）。
+fn load_embed_font(cfg: &Config) -> Option<(String, Vec<u8>, &'static str)> {
+    let raw = cfg.epub_embed_font_path.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    let path = crate::base_system::path_expand::resolve_config_path(raw);
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    let Some(mime) = font_mime_from_ext(&ext) else {
+        warn!(target: "epub", path = %path.display(), "不支持的字体格式（仅 ttf/otf/woff/woff2），跳过嵌入");
+        return None;
+    };
+    let bytes = match fs::read(&path) {
+        Ok(b) if !b.is_empty() => b,
+        Ok(_) => {
+            warn!(target: "epub", path = %path.display(), "字体文件为空，跳过嵌入");
+            return None;
+        }
+        Err(e) => {
+            warn!(target: "epub", path = %path.display(), error = %e, "字体文件读取失败，跳过嵌入");
+            return None;
+        }
+    };
+    if bytes.len() > EMBED_FONT_WARN_BYTES {
+        warn!(
+            target: "epub",
+            path = %path.display(),
+            size_mb = bytes.len() / 1024 / 1024,
+            "内嵌字体较大，生成的 EPUB 体积会明显增加，建议使用子集化字体"
+        );
+    }
+    Some((format!("fonts/embedded.{}", ext), bytes, mime))
+}
+
+fn font_mime_from_ext(ext: &str) -> Option<&'static str> {
+    match ext {
+        "ttf" => Some("font/ttf"),
+        "otf" => Some("font/otf"),
+        "woff" => Some("font/woff"),
+        "woff2" => Some("font/woff2"),
+        _ => None,
+    }
+}
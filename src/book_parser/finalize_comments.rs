@@ -0,0 +1,161 @@
+//! 段评单独导出：每章一个 Markdown，另附全书汇总 JSON。
+//!
+//! 与旁注 HTML 共用 `segment_comments/<章节ID>.json` 缓存读取；没有评论的章节不生成文件。
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
+
+use super::book_manager::BookManager;
+use super::finalize_html::{ParaNote, load_para_notes};
+use super::html_utils::decode_text_entities;
+use super::segment_utils::content_paragraph_texts;
+use crate::base_system::context::safe_fs_name;
+
+const COMMENTS_DIR_SUFFIX: &str = "_comments";
+
+/// 导出位置：与主成书同目录，`<书名>_comments/` 存放各章 Markdown，`<书名>_comments.json` 为汇总。
+fn comments_export_paths(main_output: &Path) -> (PathBuf, PathBuf) {
+    let stem = main_output
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("book");
+    (
+        main_output.with_file_name(format!("{stem}{COMMENTS_DIR_SUFFIX}")),
+        main_output.with_file_name(format!("{stem}{COMMENTS_DIR_SUFFIX}.json")),
+    )
+}
+
+fn render_chapter_markdown(title: &str, paras: &[(usize, String, Vec<ParaNote>)]) -> String {
+    let mut md = format!("# {title}\n");
+    for (_, text, notes) in paras {
+        let _ = write!(md, "\n> {text}\n\n");
+        for n in notes {
+            let _ = writeln!(md, "- {} —— {} · 赞 {}", n.text, n.user, n.digg);
+        }
+    }
+    md
+}
+
+/// 写出各章 Markdown 与汇总 JSON，返回汇总 JSON 路径。
+///
+/// 目录中上次导出的 `.md` 会先清掉，避免章节改名或评论被删后残留旧文件。
+pub(super) fn finalize_comments_export(
+    manager: &BookManager,
+    chapters: &[Value],
+    main_output: &Path,
+) -> anyhow::Result<PathBuf> {
+    let (dir, json_path) = comments_export_paths(main_output);
+    if let Ok(entries) = std::fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "md") {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    let folder = manager.book_folder();
+    let width = chapters.len().to_string().len().max(4);
+    let mut summary = Vec::new();
+    for (idx, ch) in chapters.iter().enumerate() {
+        let chapter_id = ch.get("id").and_then(Value::as_str).unwrap_or("");
+        let title = ch.get("title").and_then(Value::as_str).unwrap_or("");
+        let content = ch.get("content").and_then(Value::as_str).unwrap_or("");
+
+        let mut notes = load_para_notes(folder, chapter_id);
+        if notes.is_empty() {
+            continue;
+        }
+        let paras: Vec<(usize, String, Vec<ParaNote>)> = content_paragraph_texts(content)
+            .iter()
+            .enumerate()
+            .filter_map(|(para_idx, text)| {
+                let notes = notes.remove(&para_idx)?;
+                Some((para_idx, decode_text_entities(text), notes))
+            })
+            .collect();
+        if paras.is_empty() {
+            continue;
+        }
+
+        std::fs::create_dir_all(&dir)?;
+        let file_name = format!(
+            "{:0width$}_{}.md",
+            idx + 1,
+            safe_fs_name(title, "_", 120),
+            width = width
+        );
+        std::fs::write(dir.join(file_name), render_chapter_markdown(title, &paras))?;
+
+        summary.push(json!({
+            "id": chapter_id,
+            "title": title,
+            "paras": paras
+                .iter()
+                .map(|(para_idx, text, notes)| json!({
+                    "index": para_idx,
+                    "text": text,
+                    "comments": notes
+                        .iter()
+                        .map(|n| json!({ "user": n.user, "text": n.text, "digg": n.digg }))
+                        .collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+        }));
+    }
+
+    let data = json!({
+        "book_id": manager.book_id,
+        "book_name": manager.book_name,
+        "chapters": summary,
+    });
+    std::fs::write(&json_path, serde_json::to_vec_pretty(&data)?)?;
+    Ok(json_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::finalize_comments_export;
+    use crate::base_system::context::Config;
+    use crate::book_parser::book_manager::BookManager;
+
+    #[test]
+    fn exports_markdown_and_summary_json() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.save_path = temp_dir.path().display().to_string();
+        let mut manager = BookManager::new(config, "123", "书").unwrap();
+        manager.book_name = "书".to_string();
+
+        let seg_dir = manager.book_folder().join("segment_comments");
+        std::fs::create_dir_all(&seg_dir).unwrap();
+        let cache = serde_json::json!({
+            "paras": {"1": {"count": 1, "detail": {"reviews": [
+                {"text": "写得好", "user": {"name": "读者甲"}, "digg_count": 3}
+            ]}}}
+        });
+        std::fs::write(seg_dir.join("1.json"), cache.to_string()).unwrap();
+
+        let chapters = vec![
+            serde_json::json!({"id": "1", "title": "第1章", "content": "<p>第一段</p><p>第二段</p>"}),
+            serde_json::json!({"id": "2", "title": "第2章", "content": "<p>无评论</p>"}),
+        ];
+        let json_path =
+            finalize_comments_export(&manager, &chapters, &temp_dir.path().join("书.epub"))
+                .unwrap();
+
+        let md =
+            std::fs::read_to_string(temp_dir.path().join("书_comments/0001_第1章.md")).unwrap();
+        assert!(md.starts_with("# 第1章\n"));
+        assert!(md.contains("> 第二段\n\n- 写得好 —— 读者甲 · 赞 3"));
+        assert!(!md.contains("第一段"));
+        assert!(!temp_dir.path().join("书_comments/0002_第2章.md").exists());
+
+        let summary: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(json_path).unwrap()).unwrap();
+        assert_eq!(summary["chapters"].as_array().unwrap().len(), 1);
+        assert_eq!(summary["chapters"][0]["paras"][0]["index"], 1);
+    }
+}
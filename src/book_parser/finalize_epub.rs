@@ -23,20 +23,39 @@ fn re_inline_img() -> &'static Regex {
     R.get_or_init(|| Regex::new(r#"(?is)<img[^>]*?\bsrc\s*=\s*['\"]([^'\"]+)['\"][^>]*>"#).unwrap())
 }
 
+fn re_any_img() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| Regex::new(r"(?is)<img\b[^>]*>").unwrap())
+}
+
+fn re_img_alt() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| Regex::new(r#"(?is)\balt\s*=\s*['\"]([^'\"]*)['\"]"#).unwrap())
+}
+
+use crate::base_system::context::safe_fs_name;
+use crate::base_system::json_extract;
+
 use super::book_manager::BookManager;
-use super::epub_generator::EpubGenerator;
+use super::epub_check::check_epub_file;
+use super::epub_generator::{EMPTY_CHAPTER_HTML, EpubGenerator};
+use super::finalize_utils::{plan_short_chapter_groups, render_chapter_title};
 use super::html_utils::{
     clean_epub_body, decode_xhtml_attr_url, description_to_plain_text, escape_html,
-    render_description_xhtml_fragment,
+    mark_dialogue_paragraphs, render_description_xhtml_fragment, visible_char_count,
 };
 use super::image_utils::{ensure_cached_image, sha1_hex};
+use super::segment_shared::{extract_chapter_time_map, latest_chapter_time};
 #[cfg(feature = "official-api")]
 use super::segment_shared::{extract_item_version_map, extract_para_counts_from_stats};
-use super::segment_utils;
+#[cfg(feature = "official-api")]
+use super::segment_utils::SegmentCommentExportMode;
+use super::segment_utils::{self, SegmentCommentStyle};
 
 #[cfg(feature = "official-api")]
 use super::segment_comments::{
-    load_segment_comments_cache, prefetch_comment_media, render_segment_comment_page,
+    load_segment_comments_cache, prefetch_comment_media, render_segment_comment_blocks,
+    render_segment_comment_page,
 };
 
 #[cfg(feature = "official-api")]
@@ -44,13 +63,57 @@ use tomato_novel_official_api::{CommentDownloadOptions, DirectoryClient, ReviewC
 
 // ── EPUB 入口 ───────────────────────────────────────────────────
 
-#[cfg_attr(not(feature = "official-api"), allow(unused_variables, unused_mut))]
 pub(super) fn finalize_epub(
     manager: &BookManager,
     chapters: &[Value],
     path: &Path,
     directory_raw: Option<&Value>,
     mut reporter: Option<&mut crate::download::downloader::ProgressReporter>,
+) -> anyhow::Result<()> {
+    if manager.config.split_epub_by_volume
+        && let Some(raw) = directory_raw
+    {
+        let parts = split_chapters_by_volume(chapters, raw);
+        if parts.len() > 1 {
+            for (i, (vol_title, part)) in parts.iter().enumerate() {
+                let vol_path = volume_output_path(path, i + 1, vol_title);
+                info!(
+                    target: "volume",
+                    idx = i + 1,
+                    title = %vol_title,
+                    chapters = part.len(),
+                    path = %vol_path.display(),
+                    "按卷导出 EPUB"
+                );
+                let reporter_ref = {
+                    #[allow(clippy::needless_option_as_deref)]
+                    reporter.as_deref_mut()
+                };
+                build_epub(
+                    manager,
+                    part,
+                    &vol_path,
+                    directory_raw,
+                    reporter_ref,
+                    Some((i + 1, vol_title.as_str())),
+                )?;
+            }
+            return Ok(());
+        }
+        info!(target: "volume", "未提取到多卷信息，按整本导出 EPUB");
+    }
+    build_epub(manager, chapters, path, directory_raw, reporter, None)
+}
+
+/// 生成单个 EPUB；`volume` 为 `Some((序号, 卷名))` 时表示按卷拆分中的一卷。
+#[cfg_attr(not(feature = "official-api"), allow(unused_variables, unused_mut))]
+fn build_epub(
+    manager: &BookManager,
+    chapters: &[Value],
+    path: &Path,
+    directory_raw: Option<&Value>,
+    mut reporter: Option<&mut crate::download::downloader::ProgressReporter>,
+    volume: Option<(usize, &str)>,
 ) -> anyhow::Result<()> {
     let description_meta = description_to_plain_text(&manager.description);
 
@@ -65,24 +128,47 @@ pub(super) fn finalize_epub(
         manager.tags.clone()
     };
 
+    // 分卷导出时每卷使用独立的书名与 identifier，避免阅读器把各卷视为同一本书
+    let (identifier, epub_title) = match volume {
+        Some((idx, vol)) => (
+            format!("{}-vol{}", manager.book_id, idx),
+            format!("{} {}", manager.book_name, vol),
+        ),
+        None => (manager.book_id.clone(), manager.book_name.clone()),
+    };
+
     let mut epub_gen = EpubGenerator::new(
-        &manager.book_id,
-        &manager.book_name,
+        &identifier,
+        &epub_title,
         &manager.author,
         &tags,
         &description_meta,
         &manager.config,
     )?;
+    epub_gen.set_cover_color(directory_raw.and_then(|raw| {
+        json_extract::collect_maps(raw)
+            .into_iter()
+            .find_map(json_extract::pick_cover_primary_color)
+    }));
+
+    let known_chapter_ids: HashSet<String> = chapters
+        .iter()
+        .filter_map(|ch| ch.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()))
+        .collect();
+    epub_gen.set_modified_time(
+        directory_raw.and_then(|raw| latest_chapter_time(raw, &known_chapter_ids)),
+    );
 
     info!(
         target: "segment",
         enable_segment_comments = manager.config.enable_segment_comments,
+        segment_comments_export_mode = %manager.config.segment_comments_export_mode,
         novel_format = %manager.config.novel_format,
         use_official_api = manager.config.use_official_api,
         top_n = manager.config.segment_comments_top_n,
         workers = manager.config.segment_comments_workers,
-        download_comment_images = manager.config.download_comment_images,
-        download_comment_avatars = manager.config.download_comment_avatars,
+        download_comment_images = manager.config.wants_comment_images(),
+        download_comment_avatars = manager.config.wants_comment_avatars(),
         directory_raw_present = directory_raw.is_some(),
         "segment comment pipeline start"
     );
@@ -106,10 +192,6 @@ pub(super) fn finalize_epub(
     let _ = epub_gen.add_aux_page_named("aux_00000.xhtml".to_string(), "简介", &intro_html, true);
 
     // #201: 分卷标题
-    let known_chapter_ids: HashSet<String> = chapters
-        .iter()
-        .filter_map(|ch| ch.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()))
-        .collect();
 
     let volumes: Vec<(String, Vec<String>)> = directory_raw
         .map(|raw| extract_volume_to_chapter_ids(raw, &known_chapter_ids))
@@ -185,9 +267,12 @@ pub(super) fn finalize_epub(
         }
     }
 
+    // separate 模式下段评只单独导出，EPUB 正文不注入
     #[cfg(feature = "official-api")]
     let enable_segment_comments = manager.config.enable_segment_comments
-        && manager.config.novel_format.eq_ignore_ascii_case("epub");
+        && manager.config.wants_epub()
+        && SegmentCommentExportMode::from_config(&manager.config.segment_comments_export_mode)
+            .embeds();
 
     #[cfg(feature = "official-api")]
     let mut item_versions = directory_raw
@@ -263,16 +348,20 @@ pub(super) fn finalize_epub(
             "segment comment scan chapter"
         );
 
-        let rewritten = embed_inline_images_chapter_named(
-            &mut epub_gen,
-            &manager.config,
-            chapter_id,
-            content_html,
-            &mut image_cache,
-            &mut resources_added,
-            &images_dir,
-        )
-        .unwrap_or_else(|_| content_html.to_string());
+        let rewritten = if manager.config.epub_include_images {
+            embed_inline_images_chapter_named(
+                &mut epub_gen,
+                &manager.config,
+                chapter_id,
+                content_html,
+                &mut image_cache,
+                &mut resources_added,
+                &images_dir,
+            )
+            .unwrap_or_else(|_| content_html.to_string())
+        } else {
+            strip_inline_images(content_html)
+        };
 
         let mut seg_counts = serde_json::Map::new();
         #[cfg(feature = "official-api")]
@@ -736,16 +825,76 @@ pub(super) fn finalize_epub(
     #[cfg(not(feature = "official-api"))]
     let comment_page_for_chapter: HashMap<String, String> = HashMap::new();
     #[cfg(feature = "official-api")]
+    let mut comment_blocks_for_chapter: HashMap<String, HashMap<usize, String>> = HashMap::new();
+    #[cfg(not(feature = "official-api"))]
+    let comment_blocks_for_chapter: HashMap<String, HashMap<usize, String>> = HashMap::new();
+    let comment_style = SegmentCommentStyle::from_config(&manager.config.segment_comments_style);
+    #[cfg(feature = "official-api")]
     let mut comment_pages: Vec<(String, String)> = Vec::new();
     #[cfg(feature = "official-api")]
     let mut comment_page_index = 0usize;
 
+    // 合并短章：带段评的章节（含段落锚点）不参与，分卷边界处断开
+    let merge_groups: Vec<Vec<usize>> = if manager.config.epub_merge_short_chapters {
+        let sizes: Vec<Option<usize>> = builds
+            .iter()
+            .map(|b| {
+                b.seg_counts
+                    .is_empty()
+                    .then(|| visible_char_count(&b.raw_xhtml))
+            })
+            .collect();
+        let volume_of = |b: &ChapterBuild| volume_title_by_chapter_id.get(&b.chapter_id);
+        let breaks: Vec<bool> = builds
+            .iter()
+            .enumerate()
+            .map(|(idx, b)| idx > 0 && volume_of(b) != volume_of(&builds[idx - 1]))
+            .collect();
+        plan_short_chapter_groups(
+            &sizes,
+            &breaks,
+            manager.config.epub_short_chapter_chars,
+            manager.config.epub_merge_max_chapters,
+        )
+    } else {
+        (0..builds.len()).map(|idx| vec![idx]).collect()
+    };
+    // 每章所在文件与跳转地址：合并文件内非首章用 `#c{章节ID}` 锚点
+    let mut chapter_files: Vec<String> = vec![String::new(); builds.len()];
+    let mut chapter_hrefs: Vec<String> = vec![String::new(); builds.len()];
+    for group in &merge_groups {
+        let file = format!("chapter_{:05}.xhtml", 1 + group[0]);
+        for &idx in group {
+            chapter_hrefs[idx] = if idx == group[0] {
+                file.clone()
+            } else {
+                format!(
+                    "{}#c{}",
+                    file,
+                    segment_utils::stable_id_fragment(&builds[idx].chapter_id)
+                )
+            };
+            chapter_files[idx] = file.clone();
+        }
+    }
+
     // #263: 在正文中增加可见目录页（table-of-contents.html），
     // 并放入 spine 前部，便于在不依赖阅读器侧边栏时快速跳转章节。
     let toc_entries: Vec<(String, String)> = builds
         .iter()
         .enumerate()
-        .map(|(idx, b)| (format!("chapter_{:05}.xhtml", 1 + idx), b.title.clone()))
+        .map(|(idx, b)| {
+            let title = if manager.config.epub_use_title_format {
+                let vol = volume_title_by_chapter_id
+                    .get(&b.chapter_id)
+                    .map(String::as_str)
+                    .unwrap_or("");
+                render_chapter_title(&manager.config.chapter_title_format, idx + 1, &b.title, vol)
+            } else {
+                b.title.clone()
+            };
+            (chapter_hrefs[idx].clone(), title)
+        })
         .collect();
     let toc_volumes: Vec<String> = builds
         .iter()
@@ -766,9 +915,24 @@ pub(super) fn finalize_epub(
 
     #[cfg(feature = "official-api")]
     for (idx, b) in builds.iter().enumerate() {
-        let chapter_file = format!("chapter_{:05}.xhtml", 1 + idx);
+        let chapter_file = chapter_files[idx].clone();
 
-        if !b.per_para.is_empty() {
+        if !b.per_para.is_empty() && !comment_style.uses_comment_page() {
+            prefetch_comment_media(&manager.config, &b.per_para, &images_dir);
+            let blocks = render_segment_comment_blocks(
+                &b.title,
+                &b.per_para,
+                comment_style,
+                &manager.config,
+                &mut resources_added,
+                &images_dir,
+                &mut epub_gen,
+            );
+            comment_blocks_for_chapter.insert(b.chapter_id.clone(), blocks);
+            if let Some(r) = reporter.as_deref_mut() {
+                r.inc_comment_saved();
+            }
+        } else if !b.per_para.is_empty() {
             prefetch_comment_media(&manager.config, &b.per_para, &images_dir);
 
             let comment_file = format!(
@@ -797,43 +961,139 @@ pub(super) fn finalize_epub(
         }
     }
 
+    let chapter_times = match directory_raw {
+        Some(raw) if manager.config.show_chapter_time => extract_chapter_time_map(raw),
+        _ => HashMap::new(),
+    };
+
     // 按序插入分卷标题页和正文章节
     let mut inserted_volumes: HashSet<String> = HashSet::new();
-    for (idx, b) in builds.iter().enumerate() {
-        if let Some(vol) = volume_title_by_chapter_id.get(&b.chapter_id) {
-            let vol_trim = vol.trim();
-            if !vol_trim.is_empty()
-                && inserted_volumes.insert(vol_trim.to_string())
-                && let Some(file) = volume_file_by_title.get(vol_trim)
-            {
-                info!(
-                    target: "volume",
-                    title = %vol_trim,
-                    file = %file,
-                    before_chapter_id = %b.chapter_id,
-                    before_chapter_index = idx,
-                    "inserting volume title page"
-                );
-                let body = format!("<p class=\"no-indent\">{}</p>", escape_html(vol_trim));
-                let _ = epub_gen.add_aux_page_named(file.clone(), vol_trim, &body, true);
+    for (group_idx, group) in merge_groups.iter().enumerate() {
+        let merged = group.len() > 1;
+        let mut group_out = String::new();
+        for &idx in group {
+            let b = &builds[idx];
+            if let Some(vol) = volume_title_by_chapter_id.get(&b.chapter_id) {
+                let vol_trim = vol.trim();
+                if !vol_trim.is_empty()
+                    && inserted_volumes.insert(vol_trim.to_string())
+                    && let Some(file) = volume_file_by_title.get(vol_trim)
+                {
+                    info!(
+                        target: "volume",
+                        title = %vol_trim,
+                        file = %file,
+                        before_chapter_id = %b.chapter_id,
+                        before_chapter_index = idx,
+                        "inserting volume title page"
+                    );
+                    let body = format!("<p class=\"no-indent\">{}</p>", escape_html(vol_trim));
+                    epub_gen.add_volume_page_named(file.clone(), vol_trim, &body);
+                }
             }
+
+            let comment_file = comment_page_for_chapter
+                .get(&b.chapter_id)
+                .map(|s| s.as_str())
+                .unwrap_or("");
+
+            // 对话分色在注入段评前标记，避免误标段评块内的段落
+            let highlight = manager.config.epub_highlight_dialogue;
+            let comment_blocks = comment_blocks_for_chapter.get(&b.chapter_id);
+            let content = if highlight && (comment_blocks.is_some() || !comment_file.is_empty()) {
+                std::borrow::Cow::Owned(mark_dialogue_paragraphs(&b.raw_xhtml))
+            } else {
+                std::borrow::Cow::Borrowed(b.raw_xhtml.as_str())
+            };
+            let body = if let Some(blocks) = comment_blocks {
+                segment_utils::inject_segment_blocks(&content, &b.seg_counts, blocks, comment_style)
+            } else if !comment_file.is_empty() {
+                segment_utils::inject_segment_links(&content, comment_file, &b.seg_counts)
+            } else if highlight {
+                mark_dialogue_paragraphs(&clean_epub_body(&b.raw_xhtml))
+            } else {
+                clean_epub_body(&b.raw_xhtml)
+            };
+            let body = if body.trim().is_empty() {
+                EMPTY_CHAPTER_HTML.to_string()
+            } else {
+                body
+            };
+            // 合并文件没有统一标题，每章自带 <h1>
+            let heading = if merged {
+                format!("<h1>{}</h1>\n", escape_html(&toc_entries[idx].1))
+            } else {
+                String::new()
+            };
+            let time = chapter_times
+                .get(&b.chapter_id)
+                .map(|t| {
+                    format!(
+                        "<p class=\"no-indent chapter-time\">发布时间：{}</p>\n",
+                        escape_html(t)
+                    )
+                })
+                .unwrap_or_default();
+            let index_note = if manager.config.epub_chapter_header {
+                format!(
+                    "<p class=\"no-indent chapter-index\">第 {} / {} 章</p>\n",
+                    idx + 1,
+                    builds.len()
+                )
+            } else {
+                String::new()
+            };
+            // 段落 id 只依赖章节 ID 与正文顺序，重新生成时保持一致，便于阅读器定位进度
+            group_out.push_str(&format!(
+                "<section id=\"c{}\">\n{}{}{}{}\n</section>\n",
+                segment_utils::stable_id_fragment(&b.chapter_id),
+                heading,
+                index_note,
+                time,
+                segment_utils::assign_stable_para_ids(&body, &b.chapter_id)
+            ));
         }
 
-        let comment_file = comment_page_for_chapter
-            .get(&b.chapter_id)
-            .map(|s| s.as_str())
-            .unwrap_or("");
+        if manager.config.epub_chapter_nav {
+            // 文件名已在目录阶段确定，这里直接按分组取前后文件
+            let prev = group_idx
+                .checked_sub(1)
+                .and_then(|i| merge_groups.get(i))
+                .map(|g| chapter_files[g[0]].as_str());
+            let next = merge_groups
+                .get(group_idx + 1)
+                .map(|g| chapter_files[g[0]].as_str());
+            group_out.push_str(&build_chapter_nav_html(prev, next));
+        }
 
-        let chapter_out = if !comment_file.is_empty() {
-            segment_utils::inject_segment_links(&b.raw_xhtml, comment_file, &b.seg_counts)
+        let first = group[0];
+        if merged {
+            let last = group[group.len() - 1];
+            let title = format!("{} ~ {}", toc_entries[first].1, toc_entries[last].1);
+            if manager.config.epub_chapter_header {
+                group_out.insert_str(0, &build_chapter_header_html(&manager.book_name, &title));
+            }
+            let children = group.iter().map(|&idx| toc_entries[idx].clone()).collect();
+            epub_gen.add_merged_chapters_named(
+                chapter_files[first].clone(),
+                &title,
+                &group_out,
+                children,
+            );
         } else {
-            clean_epub_body(&b.raw_xhtml)
-        };
-        epub_gen.add_chapter_named(
-            format!("chapter_{:05}.xhtml", 1 + idx),
-            &b.title,
-            &chapter_out,
-        );
+            let title = &toc_entries[first].1;
+            let header = if manager.config.epub_chapter_header {
+                build_chapter_header_html(&manager.book_name, title)
+            } else {
+                String::new()
+            };
+            epub_gen.add_chapter_with_header_named(
+                chapter_files[first].clone(),
+                title,
+                &header,
+                &group_out,
+            );
+        }
     }
 
     // 追加段评页
@@ -844,11 +1104,84 @@ pub(super) fn finalize_epub(
     }
 
     epub_gen.generate(path, &manager.config)?;
+    report_epub_check(path);
     Ok(())
 }
 
+/// 生成后做一次结构自检，问题只记录 warning，不影响导出结果。
+fn report_epub_check(path: &Path) {
+    match check_epub_file(path) {
+        Ok(issues) if issues.is_empty() => {
+            info!(target: "epub", path = %path.display(), "EPUB 自检通过");
+        }
+        Ok(issues) => {
+            for issue in &issues {
+                warn!(target: "epub", path = %path.display(), "EPUB 自检: {}", issue);
+            }
+            warn!(
+                target: "epub",
+                path = %path.display(),
+                count = issues.len(),
+                "EPUB 自检发现 {} 个问题，文件可能在部分阅读器中无法正常打开",
+                issues.len()
+            );
+        }
+        Err(e) => {
+            warn!(target: "epub", path = %path.display(), error = %e, "EPUB 自检读取文件失败");
+        }
+    }
+}
+
 // ── 分卷提取 ────────────────────────────────────────────────────
 
+/// 按 directory_raw 的分卷信息将章节切分为若干卷；缺少卷归属的章节并入前一卷。
+pub(super) fn split_chapters_by_volume(
+    chapters: &[Value],
+    directory_raw: &Value,
+) -> Vec<(String, Vec<Value>)> {
+    let known_chapter_ids: HashSet<String> = chapters
+        .iter()
+        .filter_map(|ch| ch.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()))
+        .collect();
+    let mut volume_by_id: HashMap<String, String> = HashMap::new();
+    for (title, ids) in extract_volume_to_chapter_ids(directory_raw, &known_chapter_ids) {
+        let t = title.trim();
+        if t.is_empty() {
+            continue;
+        }
+        for id in ids {
+            volume_by_id.entry(id).or_insert_with(|| t.to_string());
+        }
+    }
+
+    let mut parts: Vec<(String, Vec<Value>)> = Vec::new();
+    for ch in chapters {
+        let vol = ch
+            .get("id")
+            .and_then(|v| v.as_str())
+            .and_then(|id| volume_by_id.get(id));
+        match (vol, parts.last_mut()) {
+            (Some(v), Some((cur, list))) if cur == v => list.push(ch.clone()),
+            (None, Some((_, list))) => list.push(ch.clone()),
+            (v, _) => parts.push((v.cloned().unwrap_or_default(), vec![ch.clone()])),
+        }
+    }
+    parts
+}
+
+/// `书名.epub` -> `书名_第01卷_卷名.epub`（扩展名沿用原路径）。
+pub(super) fn volume_output_path(path: &Path, idx: usize, volume_title: &str) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("book");
+    let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("epub");
+    let vol = volume_title.trim();
+    let name = if vol.is_empty() {
+        format!("{stem}_第{idx:02}卷.{ext}")
+    } else {
+        format!("{stem}_第{idx:02}卷_{}.{ext}", safe_fs_name(vol, "_", 60))
+    };
+    path.with_file_name(name)
+}
+
 /// #204: 判断卷名是否为番茄系统自动生成的默认名称。
 /// 匹配：「默认」「第一卷」「第1卷」「第一卷 默认」「卷一」「Volume 1」等。
 fn is_default_volume_name(name: &str) -> bool {
@@ -1187,6 +1520,52 @@ fn embed_inline_images_chapter_named(
     Ok(rewritten)
 }
 
+/// 纯文字版：移除正文内联图片，以 `[图片]`（有 alt 时附上说明）占位。
+fn strip_inline_images(html: &str) -> String {
+    re_any_img()
+        .replace_all(html, |caps: &regex::Captures| {
+            let alt = re_img_alt()
+                .captures(&caps[0])
+                .and_then(|c| c.get(1))
+                .map(|m| m.as_str().trim())
+                .filter(|s| !s.is_empty());
+            match alt {
+                Some(alt) => format!("<span class=\"img-omitted\">[图片：{}]</span>", alt),
+                None => "<span class=\"img-omitted\">[图片]</span>".to_string(),
+            }
+        })
+        .to_string()
+}
+
+/// 章节底部导航：上一章 / 目录 / 下一章；首章、末章缺失的一侧以纯文本占位。
+fn build_chapter_nav_html(prev: Option<&str>, next: Option<&str>) -> String {
+    let link = |file: Option<&str>, label: &str| match file {
+        Some(f) => format!("<a href=\"{}\">{}</a>", escape_html(f), label),
+        None => format!("<span class=\"nav-disabled\">{}</span>", label),
+    };
+    format!(
+        "<p class=\"no-indent chapter-nav\">{} | <a href=\"table-of-contents.html\">目录</a> | {}</p>",
+        link(prev, "上一章"),
+        link(next, "下一章")
+    )
+}
+
+/// 章节页眉：书名 · 章节名，供不显示书名的阅读器定位。
+fn build_chapter_header_html(book_name: &str, title: &str) -> String {
+    let book_name = book_name.trim();
+    if book_name.is_empty() {
+        return format!(
+            "<p class=\"no-indent chapter-header\">{}</p>\n",
+            escape_html(title)
+        );
+    }
+    format!(
+        "<p class=\"no-indent chapter-header\">{} · {}</p>\n",
+        escape_html(book_name),
+        escape_html(title)
+    )
+}
+
 fn build_inline_toc_html(toc_entries: &[(String, String)], volumes: &[String]) -> String {
     let mut out = String::new();
     out.push_str("<nav epub:type=\"toc\" id=\"inline-toc\">\n");
@@ -1228,3 +1607,29 @@ fn build_inline_toc_html(toc_entries: &[(String, String)], volumes: &[String]) -
     out.push_str("</nav>");
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{build_chapter_header_html, strip_inline_images};
+
+    #[test]
+    fn strips_images_with_placeholder() {
+        let html = r#"<p>前文<img src="https://a/b.jpg" alt="地图"/></p><p><img src='x.png'></p>"#;
+        assert_eq!(
+            strip_inline_images(html),
+            "<p>前文<span class=\"img-omitted\">[图片：地图]</span></p><p><span class=\"img-omitted\">[图片]</span></p>"
+        );
+    }
+
+    #[test]
+    fn chapter_header_escapes_and_skips_empty_book_name() {
+        assert_eq!(
+            build_chapter_header_html("书<名>", "第1章 开局"),
+            "<p class=\"no-indent chapter-header\">书&lt;名&gt; · 第1章 开局</p>\n"
+        );
+        assert_eq!(
+            build_chapter_header_html(" ", "序章"),
+            "<p class=\"no-indent chapter-header\">序章</p>\n"
+        );
+    }
+}
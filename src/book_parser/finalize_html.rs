@@ -0,0 +1,216 @@
+//! 段评旁注 HTML：正文与对应段评两栏并排，便于分析读者反应。
+//!
+//! 段落按段评计数方式对齐 `para_idx`，评论取自 `segment_comments/<章节ID>.json` 缓存；
+//! 缓存按通用 JSON 读取，未开启段评或没有评论的段落旁注留空。
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::book_manager::BookManager;
+use super::finalize_utils::volume_title_map_for_chapters;
+use super::html_utils::{decode_text_entities, description_to_text, escape_html};
+use super::segment_utils::{content_paragraph_texts, convert_bracket_emojis};
+
+const NOTES_HTML_SUFFIX: &str = "_段评旁注.html";
+
+const NOTES_HTML_STYLE: &str = "body{max-width:72em;margin:0 auto;padding:1em 2em;font-family:sans-serif;line-height:1.7;color:#222}\
+h1{text-align:center}.meta{text-align:center;color:#666}\
+h2.volume{text-align:center;margin-top:2.5em}\
+.row{display:grid;grid-template-columns:minmax(0,3fr) minmax(0,2fr);gap:1.5em;border-bottom:1px dashed #eee}\
+.row p{margin:.4em 0;text-indent:2em}\
+.notes{font-size:.85em;color:#555;border-left:3px solid #e0e0e0;padding-left:.8em}\
+.notes:empty{border-left-color:transparent}\
+.note{margin:.3em 0}.note .who{color:#999;margin-left:.5em}";
+
+pub(super) struct ParaNote {
+    pub(super) user: String,
+    pub(super) text: String,
+    pub(super) digg: u64,
+}
+
+/// 旁注 HTML 与主成书同目录、同名加后缀。
+pub(super) fn notes_html_path(main_output: &Path) -> PathBuf {
+    let stem = main_output
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("book");
+    main_output.with_file_name(format!("{stem}{NOTES_HTML_SUFFIX}"))
+}
+
+/// 读取单章段评缓存：段落序号 -> 评论列表。
+pub(super) fn load_para_notes(folder: &Path, chapter_id: &str) -> HashMap<usize, Vec<ParaNote>> {
+    let path = folder
+        .join("segment_comments")
+        .join(format!("{chapter_id}.json"));
+    let Some(cache) = std::fs::read(&path)
+        .ok()
+        .and_then(|b| serde_json::from_slice::<Value>(&b).ok())
+    else {
+        return HashMap::new();
+    };
+    let Some(paras) = cache.get("paras").and_then(Value::as_object) else {
+        return HashMap::new();
+    };
+
+    let mut out = HashMap::new();
+    for (idx, para) in paras {
+        let Ok(idx) = idx.parse::<usize>() else {
+            continue;
+        };
+        let Some(reviews) = para.pointer("/detail/reviews").and_then(Value::as_array) else {
+            continue;
+        };
+        let notes: Vec<ParaNote> = reviews
+            .iter()
+            .filter_map(|r| {
+                let text = convert_bracket_emojis(r.get("text")?.as_str()?);
+                let text = text.trim();
+                if text.is_empty() {
+                    return None;
+                }
+                let user = r
+                    .pointer("/user/name")
+                    .and_then(Value::as_str)
+                    .filter(|s| !s.trim().is_empty())
+                    .unwrap_or("匿名");
+                Some(ParaNote {
+                    user: user.to_string(),
+                    text: text.to_string(),
+                    digg: r.get("digg_count").and_then(Value::as_u64).unwrap_or(0),
+                })
+            })
+            .collect();
+        if !notes.is_empty() {
+            out.insert(idx, notes);
+        }
+    }
+    out
+}
+
+fn render_notes(notes: Option<&Vec<ParaNote>>) -> String {
+    let mut html = String::new();
+    for n in notes.into_iter().flatten() {
+        let _ = write!(
+            html,
+            "<p class=\"note\">{}<span class=\"who\">— {} · 赞 {}</span></p>",
+            escape_html(&n.text),
+            escape_html(&n.user),
+            n.digg
+        );
+    }
+    html
+}
+
+/// 生成段评旁注 HTML 并写入 `path`。
+pub(super) fn finalize_notes_html(
+    manager: &BookManager,
+    chapters: &[Value],
+    path: &Path,
+    directory_raw: Option<&Value>,
+) -> anyhow::Result<()> {
+    let volume_title_by_chapter_id = volume_title_map_for_chapters(chapters, directory_raw);
+    let mut inserted_volumes: HashSet<&str> = HashSet::new();
+    let title = escape_html(&manager.book_name);
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\"/>\n\
+         <title>{title} · 段评旁注</title>\n<style>{NOTES_HTML_STYLE}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n"
+    );
+    if !manager.author.trim().is_empty() {
+        let _ = writeln!(
+            html,
+            "<p class=\"meta\">作者：{}</p>",
+            escape_html(&manager.author)
+        );
+    }
+    if !manager.description.trim().is_empty() {
+        let _ = writeln!(
+            html,
+            "<p class=\"meta\">{}</p>",
+            escape_html(&description_to_text(&manager.description))
+        );
+    }
+
+    let folder = manager.book_folder();
+    for ch in chapters {
+        let chapter_id = ch.get("id").and_then(Value::as_str).unwrap_or("");
+        let chapter_title = ch.get("title").and_then(Value::as_str).unwrap_or("");
+        let content = ch.get("content").and_then(Value::as_str).unwrap_or("");
+
+        if let Some(volume) = volume_title_by_chapter_id.get(chapter_id)
+            && inserted_volumes.insert(volume.as_str())
+        {
+            let _ = writeln!(html, "<h2 class=\"volume\">{}</h2>", escape_html(volume));
+        }
+
+        let notes = load_para_notes(folder, chapter_id);
+        let _ = writeln!(
+            html,
+            "<section class=\"chapter\" id=\"c{}\">\n<h2>{}</h2>",
+            escape_html(chapter_id),
+            escape_html(chapter_title)
+        );
+        for (idx, para) in content_paragraph_texts(content).iter().enumerate() {
+            let _ = writeln!(
+                html,
+                "<div class=\"row\"><p>{}</p><aside class=\"notes\">{}</aside></div>",
+                escape_html(&decode_text_entities(para)),
+                render_notes(notes.get(&idx))
+            );
+        }
+        html.push_str("</section>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+
+    std::fs::write(path, html)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{finalize_notes_html, notes_html_path};
+    use crate::base_system::context::Config;
+    use crate::book_parser::book_manager::BookManager;
+
+    #[test]
+    fn aligns_comments_to_paragraphs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.save_path = temp_dir.path().display().to_string();
+        let mut manager = BookManager::new(config, "123", "书").unwrap();
+        manager.book_name = "书".to_string();
+
+        let seg_dir = manager.book_folder().join("segment_comments");
+        std::fs::create_dir_all(&seg_dir).unwrap();
+        let cache = serde_json::json!({
+            "chapter_id": "1",
+            "book_id": "123",
+            "item_version": "v",
+            "top_n": 10,
+            "paras": {"1": {"count": 1, "detail": {"reviews": [
+                {"text": "写得好", "user": {"name": "读者甲"}, "digg_count": 3}
+            ]}}}
+        });
+        std::fs::write(seg_dir.join("1.json"), cache.to_string()).unwrap();
+
+        let chapters = vec![serde_json::json!({
+            "id": "1",
+            "title": "第1章",
+            "content": "<p>第一段</p><p>第二段</p>"
+        })];
+        let out = notes_html_path(&temp_dir.path().join("书.epub"));
+        finalize_notes_html(&manager, &chapters, &out, None).unwrap();
+
+        let html = std::fs::read_to_string(&out).unwrap();
+        assert!(out.ends_with("书_段评旁注.html"));
+        assert!(html.contains("<p>第一段</p><aside class=\"notes\"></aside>"));
+        assert!(html.contains("<p>第二段</p><aside class=\"notes\"><p class=\"note\">写得好"));
+        assert!(html.contains("读者甲 · 赞 3"));
+    }
+}
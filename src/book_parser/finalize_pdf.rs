@@ -11,6 +11,7 @@ use tracing::{debug, info, warn};
 
 use super::book_manager::BookManager;
 use super::finalize_utils::volume_title_map_for_chapters;
+use super::html_utils::description_to_text;
 use super::parser::ContentParser;
 use crate::base_system::book_paths;
 
@@ -242,7 +243,7 @@ pub(super) fn finalize_pdf(
         doc.push(Break::new(1));
         doc.push(Paragraph::new("简介：").styled(meta_style));
         // 先按换行符拆分，再对每小段做视觉宽度预分行
-        for sub_line in manager.description.trim().lines() {
+        for sub_line in description_to_text(&manager.description).lines() {
             let sub_line = sub_line.trim();
             if sub_line.is_empty() {
                 doc.push(Break::new(0.3));
@@ -1,30 +1,87 @@
 //! 导出收尾（finalize）与后处理。
 //!
-//! 包括写入最终文件、自动打开产物等"完成后"逻辑。
-//! 具体子模块：`finalize_epub`、`html_utils`、`image_utils`、`segment_comments`、`segment_shared`。
+//! 包括写入最终文件、上传 WebDAV、自动打开产物等"完成后"逻辑。
+//! 具体子模块：`finalize_epub`、`finalize_html`、`html_utils`、`image_utils`、`segment_comments`、`segment_shared`。
 
 use std::fs::{self, File};
 use std::io::{self, BufRead, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use std::{collections::HashMap, collections::HashSet};
 
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 
 use tracing::{error, info, warn};
 
+use super::html_utils::{decode_text_entities, description_to_text};
 use super::parser::ContentParser;
+use super::segment_shared::extract_chapter_time_map;
+use super::segment_utils::SegmentCommentExportMode;
 
 use crossterm::event::EnableMouseCapture;
 use crossterm::terminal::enable_raw_mode;
 
 use super::audio_generator::generate_audiobook;
 use super::book_manager::BookManager;
-use super::finalize_epub::finalize_epub;
+use super::finalize_comments::finalize_comments_export;
+use super::finalize_epub::{finalize_epub, split_chapters_by_volume, volume_output_path};
+use super::finalize_html::{finalize_notes_html, notes_html_path};
 use super::finalize_pdf::finalize_pdf;
-use crate::base_system::context::safe_fs_name;
+use crate::base_system::context::{Config, safe_fs_name};
+use crate::base_system::file_manager::open_in_file_manager;
+use crate::base_system::webdav;
 use crate::download::downloader;
 
+/// TXT 拆分方式（对应 `Config.txt_split_mode`；`bulk_files` 视同按章）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TxtSplitMode {
+    /// 整本一个文件（默认）。
+    Single,
+    /// 每章一个文件（散装）。
+    PerChapter,
+    /// 每卷一个文件；无多卷信息时退化为整本。
+    PerVolume,
+}
+
+impl TxtSplitMode {
+    pub(crate) const ALL: [TxtSplitMode; 3] = [
+        TxtSplitMode::Single,
+        TxtSplitMode::PerChapter,
+        TxtSplitMode::PerVolume,
+    ];
+
+    /// 未知取值回退到整本。
+    pub(crate) fn from_config(cfg: &Config) -> Self {
+        if cfg.bulk_files {
+            return TxtSplitMode::PerChapter;
+        }
+        Self::parse(&cfg.txt_split_mode).unwrap_or(TxtSplitMode::Single)
+    }
+
+    pub(crate) fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        Self::ALL
+            .into_iter()
+            .find(|m| raw.eq_ignore_ascii_case(m.as_str()) || raw == m.label())
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            TxtSplitMode::Single => "single",
+            TxtSplitMode::PerChapter => "per-chapter",
+            TxtSplitMode::PerVolume => "per-volume",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            TxtSplitMode::Single => "整本",
+            TxtSplitMode::PerChapter => "按章",
+            TxtSplitMode::PerVolume => "按卷",
+        }
+    }
+}
+
 /// 生成最终输出；返回是否需要延迟清理缓存。
 pub fn run_finalize(
     manager: &mut BookManager,
@@ -64,38 +121,127 @@ pub fn run_finalize(
         manager.format_selected_after_download = true;
     }
 
-    let fmt = manager.config.novel_format.to_lowercase();
-    let output_path = match prepare_output_path(manager, &fmt) {
-        Ok(p) => p,
-        Err(e) => {
-            error!(target: "book_manager", error = ?e, "prepare output path failed");
-            return false;
-        }
-    };
+    // 正文首段与章节标题重复时去除，避免成书中标题出现两次。
+    let deduped_chapters = manager
+        .config
+        .strip_duplicate_title_line
+        .then(|| ContentParser::strip_duplicate_titles(chapters));
+    let chapters = deduped_chapters.as_deref().unwrap_or(chapters);
+
+    // 规整空段落；EPUB 段评按段落序号挂载，删段会错位，此时保持原样。
+    let keep_para_slots = manager.config.enable_segment_comments && manager.config.wants_epub();
+    let trimmed_chapters = (manager.config.trim_blank_paragraphs && !keep_para_slots)
+        .then(|| ContentParser::trim_blank_paragraphs(chapters));
+    let chapters = trimmed_chapters.as_deref().unwrap_or(chapters);
+
+    // 自定义替换表对所有输出格式（含有声书）生效。
+    let replaced_chapters = super::text_replace::load_for_config(&manager.config)
+        .map(|replacer| replacer.apply_chapters(chapters));
+    let chapters = replaced_chapters.as_deref().unwrap_or(chapters);
+
+    // 简繁转换放在写盘前的最后一步；none 时不构建词表。
+    let converter =
+        super::text_convert::TextConversion::from_config(&manager.config.text_conversion)
+            .converter();
+    let converted_chapters =
+        converter.map(|conv| super::text_convert::convert_chapters(conv, chapters));
+    let chapters = converted_chapters.as_deref().unwrap_or(chapters);
+    // 简介只在成书期间替换，结束后还原，避免写回 status.json。
+    let mut original_description = converter
+        .filter(|_| manager.config.text_conversion_description)
+        .map(|conv| {
+            let converted = conv.apply_text(&manager.description);
+            std::mem::replace(&mut manager.description, converted)
+        });
+
+    // 多格式输出（如 txt,epub）复用同一批章节，依次生成每种成书。
+    let formats = manager.config.output_formats();
+    let mut outputs: Vec<PathBuf> = Vec::with_capacity(formats.len());
+    for fmt in formats {
+        let output_path = match prepare_output_path(manager, fmt) {
+            Ok(p) => p,
+            Err(e) => {
+                error!(target: "book_manager", format = fmt, error = ?e, "prepare output path failed");
+                restore_description(manager, original_description.take());
+                return false;
+            }
+        };
 
-    let result: anyhow::Result<()> = if fmt == "txt" {
-        finalize_txt(manager, chapters, &output_path, directory_raw)
-    } else if fmt == "pdf" {
-        finalize_pdf(manager, chapters, &output_path, directory_raw)
-    } else {
-        let reporter_ref = {
-            #[allow(clippy::needless_option_as_deref)]
-            reporter.as_deref_mut()
+        let result: anyhow::Result<()> = match fmt {
+            "txt" => finalize_txt(manager, chapters, &output_path, directory_raw),
+            "pdf" => finalize_pdf(manager, chapters, &output_path, directory_raw),
+            _ => {
+                let reporter_ref = {
+                    #[allow(clippy::needless_option_as_deref)]
+                    reporter.as_deref_mut()
+                };
+                finalize_epub(manager, chapters, &output_path, directory_raw, reporter_ref)
+            }
         };
-        finalize_epub(manager, chapters, &output_path, directory_raw, reporter_ref)
-    };
 
-    if let Err(e) = result {
-        error!(target: "book_manager", error = ?e, "finalize failed");
-        return false;
-    }
+        if let Err(e) = result {
+            error!(target: "book_manager", format = fmt, error = ?e, "finalize failed");
+            restore_description(manager, original_description.take());
+            return false;
+        }
 
-    archive_previous_main_outputs(manager, &output_path);
+        info!(target: "book_manager", "written: {}", output_path.display());
+        outputs.push(output_path);
+    }
+    if manager.config.write_output_checksum {
+        for path in outputs.iter().filter(|p| p.is_file()) {
+            match write_checksum_sidecar(path) {
+                Ok(ChecksumStatus::Unchanged) => {
+                    info!(target: "book_manager", "内容无变化: {}", path.display())
+                }
+                Ok(ChecksumStatus::Written) => {}
+                Err(e) => {
+                    warn!(target: "book_manager", path = %path.display(), error = ?e, "写入 SHA-256 校验文件失败")
+                }
+            }
+        }
+    }
+    if manager.config.export_segment_notes_html
+        && let Some(main) = outputs.first()
+    {
+        let notes_path = notes_html_path(main);
+        match finalize_notes_html(manager, chapters, &notes_path, directory_raw) {
+            Ok(()) => {
+                info!(target: "book_manager", "written: {}", notes_path.display());
+                outputs.push(notes_path);
+            }
+            Err(e) => warn!(target: "book_manager", error = ?e, "段评旁注 HTML 导出失败"),
+        }
+    }
+    if manager.config.enable_segment_comments
+        && SegmentCommentExportMode::from_config(&manager.config.segment_comments_export_mode)
+            .exports()
+        && let Some(main) = outputs.first()
+    {
+        match finalize_comments_export(manager, chapters, main) {
+            Ok(summary_path) => {
+                info!(target: "book_manager", "written: {}", summary_path.display());
+                outputs.push(summary_path);
+            }
+            Err(e) => warn!(target: "book_manager", error = ?e, "段评单独导出失败"),
+        }
+    }
+    restore_description(manager, original_description.take());
+    let Some(output_path) = outputs.first() else {
+        return false;
+    };
 
-    info!(target: "book_manager", "written: {}", output_path.display());
+    archive_previous_main_outputs(manager, output_path);
+    webdav::upload_outputs(&manager.config, &outputs);
 
     if manager.config.auto_open_downloaded_files {
-        if let Err(e) = open_in_default_app(&output_path) {
+        // 按卷拆分导出时不存在整本文件、或同时输出多种格式时，改为打开所在目录
+        let open_target = if outputs.len() == 1 && output_path.exists() {
+            output_path.as_path()
+        } else {
+            output_path.parent().unwrap_or(output_path.as_path())
+        };
+        if let Err(e) = open_in_file_manager(open_target) {
             warn!(target: "book_manager", error = ?e, "auto open downloaded file failed");
         }
 
@@ -130,17 +276,10 @@ pub fn run_finalize(
     true
 }
 
-fn open_in_default_app(path: &Path) -> std::io::Result<()> {
-    if cfg!(target_os = "windows") {
-        Command::new("explorer").arg(path).spawn()?;
-        return Ok(());
-    }
-    if cfg!(target_os = "macos") {
-        Command::new("open").arg(path).spawn()?;
-        return Ok(());
+fn restore_description(manager: &mut BookManager, original: Option<String>) {
+    if let Some(description) = original {
+        manager.description = description;
     }
-    Command::new("xdg-open").arg(path).spawn()?;
-    Ok(())
 }
 
 fn prepare_output_path(manager: &BookManager, fmt: &str) -> std::io::Result<PathBuf> {
@@ -153,8 +292,8 @@ fn prepare_output_path(manager: &BookManager, fmt: &str) -> std::io::Result<Path
     let dir = manager.default_save_dir();
     std::fs::create_dir_all(&dir)?;
 
-    // bulk_files: TXT 每章一个文件，输出到"小说名"文件夹
-    if fmt == "txt" && manager.config.bulk_files {
+    // 按章拆分（bulk_files）：TXT 每章一个文件，输出到"小说名"文件夹
+    if fmt == "txt" && TxtSplitMode::from_config(&manager.config) == TxtSplitMode::PerChapter {
         return Ok(dir.join(&safe_book));
     }
 
@@ -176,6 +315,48 @@ fn prepare_output_path(manager: &BookManager, fmt: &str) -> std::io::Result<Path
     Ok(output_path)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChecksumStatus {
+    /// 校验文件已写入（首次生成或内容有变化）
+    Written,
+    /// 与上次生成的哈希一致
+    Unchanged,
+}
+
+fn checksum_sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".sha256");
+    PathBuf::from(name)
+}
+
+fn sha256_file_hex(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// 计算成书 SHA-256 并写入 sha256sum 格式的 `<文件名>.sha256`。
+fn write_checksum_sidecar(path: &Path) -> io::Result<ChecksumStatus> {
+    let hex = sha256_file_hex(path)?;
+    let sidecar = checksum_sidecar_path(path);
+    let previous = fs::read_to_string(&sidecar).ok();
+    let unchanged = previous
+        .as_deref()
+        .and_then(|s| s.split_whitespace().next())
+        .is_some_and(|old| old.eq_ignore_ascii_case(&hex));
+    if unchanged {
+        return Ok(ChecksumStatus::Unchanged);
+    }
+    let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+    fs::write(&sidecar, format!("{hex}  {file_name}\n"))?;
+    Ok(ChecksumStatus::Written)
+}
+
 fn archive_previous_main_outputs(manager: &BookManager, output_path: &Path) {
     if manager.previous_book_names.is_empty() {
         return;
@@ -205,6 +386,9 @@ fn archive_previous_main_outputs(manager: &BookManager, output_path: &Path) {
             (dir.join(format!("{safe_old}.epub")), "旧 EPUB"),
             (dir.join(format!("{safe_old}.txt")), "旧 TXT"),
             (dir.join(format!("{safe_old}.pdf")), "旧 PDF"),
+            (dir.join(format!("{safe_old}.epub.sha256")), "旧 EPUB 校验"),
+            (dir.join(format!("{safe_old}.txt.sha256")), "旧 TXT 校验"),
+            (dir.join(format!("{safe_old}.pdf.sha256")), "旧 PDF 校验"),
             (dir.join(&safe_old), "旧 bulk TXT 目录"),
         ];
 
@@ -308,54 +492,23 @@ fn finalize_txt(
     directory_raw: Option<&Value>,
 ) -> anyhow::Result<()> {
     let volume_title_by_chapter_id = volume_title_map_for_chapters(chapters, directory_raw);
+    let chapter_times = match directory_raw {
+        Some(raw) if manager.config.show_chapter_time => extract_chapter_time_map(raw),
+        _ => HashMap::new(),
+    };
 
-    if manager.config.bulk_files {
+    let split_mode = TxtSplitMode::from_config(&manager.config);
+    if split_mode == TxtSplitMode::PerChapter {
+        manager
+            .config
+            .validate_bulk_name_template()
+            .map_err(anyhow::Error::msg)?;
         std::fs::create_dir_all(path)?;
         let mut inserted_volumes: HashSet<String> = HashSet::new();
 
         // 书籍信息
         let mut meta = File::create(path.join("0000_书籍信息.txt"))?;
-        writeln!(meta, "书名：{}", manager.book_name)?;
-        if !manager.author.trim().is_empty() {
-            writeln!(meta, "作者：{}", manager.author)?;
-        }
-        writeln!(meta, "book_id={}", manager.book_id)?;
-
-        let status_text = match manager.finished {
-            Some(true) => "完结",
-            Some(false) => "连载",
-            None => "未知",
-        };
-        writeln!(meta, "状态：{}", status_text)?;
-
-        if let Some(score) = manager.score {
-            writeln!(meta, "评分：{:.1}", score)?;
-        }
-        if let Some(word_count) = manager.word_count {
-            writeln!(meta, "字数：{}", word_count)?;
-        }
-        if let Some(chapter_count) = manager.chapter_count {
-            writeln!(meta, "章节：{}", chapter_count)?;
-        }
-        if let Some(category) = manager.category.as_deref()
-            && !category.trim().is_empty()
-        {
-            writeln!(meta, "分类：{}", category.trim())?;
-        }
-        if !manager.tags.trim().is_empty() {
-            writeln!(meta, "标签：{}", manager.tags)?;
-        }
-        if let Some(read_count_text) = manager.read_count_text.as_deref()
-            && !read_count_text.trim().is_empty()
-        {
-            writeln!(meta, "在读：{}", read_count_text.trim())?;
-        }
-
-        if !manager.description.trim().is_empty() {
-            writeln!(meta)?;
-            writeln!(meta, "简介：")?;
-            writeln!(meta, "{}", manager.description.trim())?;
-        }
+        write_book_info(&mut meta, manager)?;
 
         // 章节拆分
         let width = chapters.len().to_string().len().max(4);
@@ -364,14 +517,15 @@ fn finalize_txt(
             let title = ch.get("title").and_then(|v| v.as_str()).unwrap_or("章节");
             let content = ch.get("content").and_then(|v| v.as_str()).unwrap_or("");
             // 缓存为 XHTML，写入 txt 时实时清洗为纯文本
-            let content = ContentParser::clean_plain(content, title);
-
-            let safe_title = safe_fs_name(title, "_", 120);
-            let filename = format!(
-                "{num:0width$}_{title}.txt",
-                num = idx + 1,
-                width = width,
-                title = safe_title
+            let content = decode_text_entities(&ContentParser::clean_plain(content, title));
+            let title = &decode_text_entities(title);
+
+            let filename = render_bulk_file_name(
+                &manager.config.bulk_name_template,
+                idx + 1,
+                width,
+                title,
+                &manager.book_name,
             );
             let mut f = File::create(path.join(filename))?;
             if let Some(vol) = volume_title_by_chapter_id.get(chapter_id)
@@ -381,7 +535,18 @@ fn finalize_txt(
                 writeln!(f, "分卷：{}", vol.trim())?;
                 writeln!(f)?;
             }
-            writeln!(f, "{}", title)?;
+            let vol = volume_title_by_chapter_id
+                .get(chapter_id)
+                .map(String::as_str)
+                .unwrap_or("");
+            writeln!(
+                f,
+                "{}",
+                render_chapter_title(&manager.config.chapter_title_format, idx + 1, title, vol)
+            )?;
+            if let Some(t) = chapter_times.get(chapter_id) {
+                writeln!(f, "发布时间：{}", t)?;
+            }
             writeln!(f)?;
             writeln!(f, "{}", content.trim_end())?;
         }
@@ -389,62 +554,119 @@ fn finalize_txt(
         return Ok(());
     }
 
-    let mut f = File::create(path)?;
+    if split_mode == TxtSplitMode::PerVolume
+        && let Some(raw) = directory_raw
+    {
+        let parts = split_chapters_by_volume(chapters, raw);
+        if parts.len() > 1 {
+            let mut first_index = 1;
+            for (i, (vol_title, part)) in parts.iter().enumerate() {
+                let vol_path = volume_output_path(path, i + 1, vol_title);
+                info!(
+                    target: "volume",
+                    idx = i + 1,
+                    title = %vol_title,
+                    chapters = part.len(),
+                    path = %vol_path.display(),
+                    "按卷导出 TXT"
+                );
+                write_txt_book(
+                    manager,
+                    part,
+                    &vol_path,
+                    &volume_title_by_chapter_id,
+                    &chapter_times,
+                    first_index,
+                )?;
+                first_index += part.len();
+            }
+            return Ok(());
+        }
+        info!(target: "volume", "未提取到多卷信息，按整本导出 TXT");
+    }
 
-    writeln!(f, "书名：{}", manager.book_name)?;
+    write_txt_book(
+        manager,
+        chapters,
+        path,
+        &volume_title_by_chapter_id,
+        &chapter_times,
+        1,
+    )
+}
+
+/// 书名、作者、状态、简介等书籍信息块（整本开头与散装 `0000_书籍信息.txt` 共用）。
+fn write_book_info(w: &mut impl Write, manager: &BookManager) -> io::Result<()> {
+    writeln!(w, "书名：{}", manager.book_name)?;
     if !manager.author.trim().is_empty() {
-        writeln!(f, "作者：{}", manager.author)?;
+        writeln!(w, "作者：{}", manager.author)?;
     }
-    writeln!(f, "book_id={}", manager.book_id)?;
+    writeln!(w, "book_id={}", manager.book_id)?;
 
     let status_text = match manager.finished {
         Some(true) => "完结",
         Some(false) => "连载",
         None => "未知",
     };
-    writeln!(f, "状态：{}", status_text)?;
+    writeln!(w, "状态：{}", status_text)?;
 
     if let Some(score) = manager.score {
-        writeln!(f, "评分：{:.1}", score)?;
+        writeln!(w, "评分：{:.1}", score)?;
     }
     if let Some(word_count) = manager.word_count {
-        writeln!(f, "字数：{}", word_count)?;
+        writeln!(w, "字数：{}", word_count)?;
     }
     if let Some(chapter_count) = manager.chapter_count {
-        writeln!(f, "章节：{}", chapter_count)?;
+        writeln!(w, "章节：{}", chapter_count)?;
     }
     if let Some(category) = manager.category.as_deref()
         && !category.trim().is_empty()
     {
-        writeln!(f, "分类：{}", category.trim())?;
+        writeln!(w, "分类：{}", category.trim())?;
     }
     if !manager.tags.trim().is_empty() {
-        writeln!(f, "标签：{}", manager.tags)?;
+        writeln!(w, "标签：{}", manager.tags)?;
     }
     if let Some(read_count_text) = manager.read_count_text.as_deref()
         && !read_count_text.trim().is_empty()
     {
-        writeln!(f, "在读：{}", read_count_text.trim())?;
+        writeln!(w, "在读：{}", read_count_text.trim())?;
     }
 
     if !manager.description.trim().is_empty() {
-        writeln!(f)?;
-        writeln!(f, "简介：")?;
-        writeln!(f, "{}", manager.description.trim())?;
+        writeln!(w)?;
+        writeln!(w, "简介：")?;
+        writeln!(w, "{}", description_to_text(&manager.description))?;
     }
+    Ok(())
+}
+
+/// 写出一个整本格式的 TXT；`first_index` 为首章在全书中的序号，按卷拆分时保持序号连续。
+fn write_txt_book(
+    manager: &BookManager,
+    chapters: &[Value],
+    path: &Path,
+    volume_title_by_chapter_id: &HashMap<String, String>,
+    chapter_times: &HashMap<String, String>,
+    first_index: usize,
+) -> anyhow::Result<()> {
+    let mut f = File::create(path)?;
+    write_book_info(&mut f, manager)?;
 
     writeln!(f)?;
     writeln!(f, "{}", "=".repeat(40))?;
     writeln!(f)?;
 
     let mut last_volume: Option<String> = None;
+    let separator = txt_chapter_separator_block(&manager.config.txt_chapter_separator);
 
-    for ch in chapters {
+    for (idx, ch) in chapters.iter().enumerate() {
         let chapter_id = ch.get("id").and_then(|v| v.as_str()).unwrap_or("");
         let title = ch.get("title").and_then(|v| v.as_str()).unwrap_or("章节");
         let content = ch.get("content").and_then(|v| v.as_str()).unwrap_or("");
         // 缓存为 XHTML，写入 txt 时实时清洗为纯文本
-        let content = ContentParser::clean_plain(content, title);
+        let content = decode_text_entities(&ContentParser::clean_plain(content, title));
+        let title = &decode_text_entities(title);
 
         if let Some(vol) = volume_title_by_chapter_id.get(chapter_id)
             && !vol.trim().is_empty()
@@ -453,13 +675,133 @@ fn finalize_txt(
             writeln!(f, "【{}】\n", vol.trim())?;
             last_volume = Some(vol.trim().to_string());
         }
-        writeln!(f, "{}\n", title)?;
+        let vol = volume_title_by_chapter_id
+            .get(chapter_id)
+            .map(String::as_str)
+            .unwrap_or("");
+        writeln!(
+            f,
+            "{}\n",
+            render_chapter_title(
+                &manager.config.chapter_title_format,
+                first_index + idx,
+                title,
+                vol
+            )
+        )?;
+        if let Some(t) = chapter_times.get(chapter_id) {
+            writeln!(f, "发布时间：{}\n", t)?;
+        }
         writeln!(f, "{}\n", content.trim_end())?;
-        writeln!(f, "\n----------------------------------------\n")?;
+        writeln!(f, "{}", separator)?;
     }
     Ok(())
 }
 
+/// 整本 TXT 章节之间的分隔块（散装模式不使用）。
+///
+/// 空值保持原有虚线；`\f`/`formfeed` 插入换页符；`blank` 仅留空行；
+/// 其余作为自定义分隔行，支持 `\n`、`\t`、`\f` 转义。
+pub(super) fn txt_chapter_separator_block(setting: &str) -> String {
+    // 配置里可能直接写入换页符本身，trim 时保留
+    let setting = setting.trim_matches(|c: char| c.is_whitespace() && c != '\u{000C}');
+    match setting.to_ascii_lowercase().as_str() {
+        "" => format!("\n{}\n", "-".repeat(40)),
+        "\u{000C}" | "\\f" | "formfeed" | "pagebreak" => "\u{000C}".to_string(),
+        "blank" | "none" => String::new(),
+        _ => {
+            let custom = setting
+                .replace("\\n", "\n")
+                .replace("\\t", "\t")
+                .replace("\\f", "\u{000C}");
+            format!("\n{}\n", custom)
+        }
+    }
+}
+
+/// 按 `chapter_title_format` 模板渲染章节标题（占位符 `{index}` `{title}` `{volume}`）。
+///
+/// 模板为空或渲染结果为空时退回原标题；卷名缺失时顺带去掉两端残留的分隔符。
+pub(super) fn render_chapter_title(
+    template: &str,
+    index: usize,
+    title: &str,
+    volume: &str,
+) -> String {
+    let template = template.trim();
+    if template.is_empty() || template == "{title}" {
+        return title.to_string();
+    }
+    let volume = volume.trim();
+    let rendered = template
+        .replace("{index}", &index.to_string())
+        .replace("{title}", title.trim())
+        .replace("{volume}", volume);
+    let mut rendered = rendered.split_whitespace().collect::<Vec<_>>().join(" ");
+    if volume.is_empty() {
+        rendered = rendered
+            .trim_matches(|c: char| {
+                c.is_whitespace() || matches!(c, '-' | '|' | '·' | ':' | '：' | '—' | '/')
+            })
+            .to_string();
+    }
+    if rendered.is_empty() {
+        title.to_string()
+    } else {
+        rendered
+    }
+}
+
+/// 按 `bulk_name_template` 渲染散装章节文件名（含 `.txt` 后缀），结果经 `safe_fs_name` 清理。
+///
+/// 模板为空时按默认 `{index_padded}_{title}`；调用前应已通过 `validate_bulk_name_template`。
+pub(super) fn render_bulk_file_name(
+    template: &str,
+    index: usize,
+    width: usize,
+    title: &str,
+    book_name: &str,
+) -> String {
+    let template = match template.trim() {
+        "" => "{index_padded}_{title}",
+        t => t,
+    };
+    let rendered = template
+        .replace("{index_padded}", &format!("{index:0width$}"))
+        .replace("{index}", &index.to_string())
+        .replace("{title}", title.trim())
+        .replace("{book_name}", book_name.trim());
+    format!("{}.txt", safe_fs_name(&rendered, "_", 120))
+}
+
+/// 合并短章分组：按顺序把连续短章归为一组，返回每组的章节下标。
+///
+/// `sizes[i]` 为 `None` 表示该章不参与合并（如带段评锚点），`breaks[i]` 表示该章开启新分卷；
+/// `threshold` 为 0 时不看字数，按 `limit` 固定数量合并。
+pub(super) fn plan_short_chapter_groups(
+    sizes: &[Option<usize>],
+    breaks: &[bool],
+    threshold: usize,
+    limit: usize,
+) -> Vec<Vec<usize>> {
+    let limit = limit.max(1);
+    let is_short = |i: usize| sizes[i].is_some_and(|n| threshold == 0 || n < threshold);
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for i in 0..sizes.len() {
+        if let Some(last) = groups.last_mut()
+            && is_short(i)
+            && !breaks.get(i).copied().unwrap_or(false)
+            && last.len() < limit
+            && last.iter().all(|&j| is_short(j))
+        {
+            last.push(i);
+            continue;
+        }
+        groups.push(vec![i]);
+    }
+    groups
+}
+
 pub(super) fn volume_title_map_for_chapters(
     chapters: &[Value],
     directory_raw: Option<&Value>,
@@ -505,6 +847,47 @@ pub(super) fn volume_title_map_for_chapters(
     volume_title_by_chapter_id
 }
 
+/// 目录分卷在 `chapter_ids`（目录顺序）中的 1 起始闭区间 `(卷名, 起始, 结束)`。
+///
+/// 仅有一个默认卷时视为无分卷，返回空。
+pub(crate) fn volume_chapter_spans(
+    chapter_ids: &[&str],
+    directory_raw: &Value,
+) -> Vec<(String, usize, usize)> {
+    let position: HashMap<&str, usize> = chapter_ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| (*id, i + 1))
+        .collect();
+    let known_chapter_ids: HashSet<String> = chapter_ids.iter().map(|s| s.to_string()).collect();
+    if known_chapter_ids.is_empty() {
+        return Vec::new();
+    }
+
+    let mut spans: Vec<(String, usize, usize)> =
+        extract_volume_to_chapter_ids(directory_raw, &known_chapter_ids)
+            .into_iter()
+            .filter_map(|(title, ids)| {
+                let title = title.trim();
+                if title.is_empty() {
+                    return None;
+                }
+                let positions = ids
+                    .iter()
+                    .filter_map(|id| position.get(id.as_str()).copied());
+                let start = positions.clone().min()?;
+                let end = positions.max()?;
+                Some((title.to_string(), start, end))
+            })
+            .collect();
+    spans.sort_by_key(|(_, start, _)| *start);
+
+    if spans.len() == 1 && is_default_volume_name(&spans[0].0) {
+        return Vec::new();
+    }
+    spans
+}
+
 fn is_default_volume_name(name: &str) -> bool {
     let s = name.trim();
     if s.is_empty() {
@@ -876,7 +1259,10 @@ fn prompt_format_selection(manager: &BookManager) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::{
-        archive_previous_audiobook_outputs, archive_previous_main_outputs, prepare_output_path,
+        ChecksumStatus, archive_previous_audiobook_outputs, archive_previous_main_outputs,
+        checksum_sidecar_path, finalize_txt, plan_short_chapter_groups, prepare_output_path,
+        render_bulk_file_name, render_chapter_title, txt_chapter_separator_block,
+        volume_chapter_spans, write_checksum_sidecar,
     };
     use crate::base_system::context::Config;
     use crate::book_parser::book_manager::BookManager;
@@ -920,6 +1306,193 @@ mod tests {
         assert!(temp_dir.path().join("olds").join("旧书名").exists());
     }
 
+    #[test]
+    fn checksum_sidecar_reports_unchanged_content() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let book = temp_dir.path().join("书.txt");
+        std::fs::write(&book, "abc").unwrap();
+
+        assert_eq!(
+            write_checksum_sidecar(&book).unwrap(),
+            ChecksumStatus::Written
+        );
+        let sidecar = std::fs::read_to_string(checksum_sidecar_path(&book)).unwrap();
+        assert_eq!(
+            sidecar,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  书.txt\n"
+        );
+        assert_eq!(
+            write_checksum_sidecar(&book).unwrap(),
+            ChecksumStatus::Unchanged
+        );
+
+        std::fs::write(&book, "abcd").unwrap();
+        assert_eq!(
+            write_checksum_sidecar(&book).unwrap(),
+            ChecksumStatus::Written
+        );
+    }
+
+    #[test]
+    fn volume_chapter_spans_follow_directory_order() {
+        let raw = serde_json::json!({
+            "item_data_list": [
+                {"item_id": "a", "volume_name": "第一卷 起"},
+                {"item_id": "b", "volume_name": "第一卷 起"},
+                {"item_id": "c", "volume_name": "第二卷 承"},
+                {"item_id": "d", "volume_name": "第二卷 承"},
+            ]
+        });
+        let spans = volume_chapter_spans(&["a", "b", "c", "d"], &raw);
+        assert_eq!(
+            spans,
+            vec![
+                ("第一卷 起".to_string(), 1, 2),
+                ("第二卷 承".to_string(), 3, 4)
+            ]
+        );
+
+        let single = serde_json::json!({
+            "item_data_list": [{"item_id": "a", "volume_name": "默认"}]
+        });
+        assert!(volume_chapter_spans(&["a"], &single).is_empty());
+    }
+
+    #[test]
+    fn txt_chapter_separator_styles() {
+        let dashes = format!("\n{}\n", "-".repeat(40));
+        assert_eq!(txt_chapter_separator_block(""), dashes);
+        assert_eq!(txt_chapter_separator_block("  "), dashes);
+        assert_eq!(txt_chapter_separator_block("\\f"), "\u{000C}");
+        assert_eq!(txt_chapter_separator_block("FormFeed"), "\u{000C}");
+        assert_eq!(txt_chapter_separator_block("\u{000C}"), "\u{000C}");
+        assert_eq!(txt_chapter_separator_block("blank"), "");
+        assert_eq!(txt_chapter_separator_block("* * *"), "\n* * *\n");
+        assert_eq!(
+            txt_chapter_separator_block("===\\n\\f"),
+            "\n===\n\u{000C}\n"
+        );
+    }
+
+    #[test]
+    fn plan_short_chapter_groups_respects_limits() {
+        let sizes = [
+            Some(100),
+            Some(200),
+            Some(5000),
+            Some(50),
+            None,
+            Some(80),
+            Some(90),
+            Some(10),
+        ];
+        let breaks = [false, false, false, false, false, false, true, false];
+        assert_eq!(
+            plan_short_chapter_groups(&sizes, &breaks, 800, 10),
+            vec![vec![0, 1], vec![2], vec![3], vec![4], vec![5], vec![6, 7]]
+        );
+        // 阈值为 0：按固定数量合并，不可合并的章节仍单独成组
+        assert_eq!(
+            plan_short_chapter_groups(&sizes, &[], 0, 2),
+            vec![vec![0, 1], vec![2, 3], vec![4], vec![5, 6], vec![7]]
+        );
+    }
+
+    #[test]
+    fn render_chapter_title_fills_placeholders() {
+        assert_eq!(render_chapter_title("", 3, "开局", "第一卷"), "开局");
+        assert_eq!(
+            render_chapter_title("Chapter {index}: {title}", 3, "开局", ""),
+            "Chapter 3: 开局"
+        );
+        assert_eq!(
+            render_chapter_title("{volume} - 第{index}章 {title}", 1, "开局", "第一卷"),
+            "第一卷 - 第1章 开局"
+        );
+        assert_eq!(
+            render_chapter_title("{volume} - 第{index}章 {title}", 1, "开局", ""),
+            "第1章 开局"
+        );
+        assert_eq!(render_chapter_title("{volume}", 1, "开局", ""), "开局");
+    }
+
+    #[test]
+    fn render_bulk_file_name_fills_placeholders() {
+        assert_eq!(
+            render_bulk_file_name("", 7, 4, "开局", "书"),
+            "0007_开局.txt"
+        );
+        assert_eq!(
+            render_bulk_file_name("{index_padded}-{title}", 7, 4, "a/b", "书"),
+            "0007-a、b.txt"
+        );
+        assert_eq!(
+            render_bulk_file_name("{book_name} 第{index}章 {title}", 12, 4, "开局", "书"),
+            "书 第12章 开局.txt"
+        );
+    }
+
+    #[test]
+    fn finalize_txt_writes_chapter_publish_time_when_enabled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.save_path = temp_dir.path().display().to_string();
+        config.show_chapter_time = true;
+
+        let manager = BookManager::new(config, "123", "书").unwrap();
+        let chapters =
+            vec![serde_json::json!({"id": "1", "title": "第1章", "content": "<p>正文</p>"})];
+        let directory = serde_json::json!({
+            "item_data_list": [{"item_id": "1", "first_pass_time": 1_700_000_000}]
+        });
+        let out = temp_dir.path().join("书.txt");
+        finalize_txt(&manager, &chapters, &out, Some(&directory)).unwrap();
+
+        let text = std::fs::read_to_string(out).unwrap();
+        assert!(text.contains("发布时间：2023-11-15 06:13"));
+    }
+
+    #[test]
+    fn finalize_txt_splits_per_volume() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.save_path = temp_dir.path().display().to_string();
+        config.txt_split_mode = "per-volume".to_string();
+
+        let mut manager = BookManager::new(config, "123", "书").unwrap();
+        manager.book_id = "123".to_string();
+        manager.book_name = "书".to_string();
+        let chapters: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .map(|id| serde_json::json!({"id": id, "title": format!("章{id}"), "content": format!("<p>正文{id}</p>")}))
+            .collect();
+        let directory = serde_json::json!({
+            "item_data_list": [
+                {"item_id": "a", "volume_name": "第一卷 起"},
+                {"item_id": "b", "volume_name": "第一卷 起"},
+                {"item_id": "c", "volume_name": "第二卷 承"},
+            ]
+        });
+        let out = temp_dir.path().join("书.txt");
+        finalize_txt(&manager, &chapters, &out, Some(&directory)).unwrap();
+
+        assert!(!out.exists());
+        let first =
+            std::fs::read_to_string(temp_dir.path().join("书_第01卷_第一卷 起.txt")).unwrap();
+        let second =
+            std::fs::read_to_string(temp_dir.path().join("书_第02卷_第二卷 承.txt")).unwrap();
+        assert!(first.starts_with("书名：书"));
+        assert!(first.contains("正文a") && first.contains("正文b") && !first.contains("正文c"));
+        assert!(second.contains("【第二卷 承】") && second.contains("正文c"));
+
+        // 只有默认卷时退化为整本
+        let single = serde_json::json!({
+            "item_data_list": [{"item_id": "a", "volume_name": "默认"}]
+        });
+        finalize_txt(&manager, &chapters[..1], &out, Some(&single)).unwrap();
+        assert!(out.exists());
+    }
+
     #[test]
     fn keep_same_name_outputs_on_normal_overwrite_path() {
         let temp_dir = tempfile::tempdir().unwrap();
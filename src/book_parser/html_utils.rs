@@ -24,11 +24,21 @@ fn re_img_tag() -> &'static Regex {
     R.get_or_init(|| Regex::new(r#"(?is)<img\b[^>]*?>"#).unwrap())
 }
 
+fn re_block_break() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| Regex::new(r"(?is)<br\s*/?>|</(?:p|div|li|h[1-6])\s*>").unwrap())
+}
+
 fn re_all_tags() -> &'static Regex {
     static R: OnceLock<Regex> = OnceLock::new();
     R.get_or_init(|| Regex::new(r"(?is)<[^>]+>").unwrap())
 }
 
+fn re_para_parts() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| Regex::new(r"(?is)(<p\b[^>]*>)(.*?)(</p>)").unwrap())
+}
+
 fn re_decimal_entity() -> &'static Regex {
     static R: OnceLock<Regex> = OnceLock::new();
     R.get_or_init(|| Regex::new(r"&#(\d+);").unwrap())
@@ -133,6 +143,35 @@ fn unescape_basic_entities_once(s: &str) -> String {
         .replace("&amp;", "&")
 }
 
+/// 写入 txt 前的实体解码：在基础实体之外补充常见命名实体，
+/// 并把不换行空格统一为普通空格、`&emsp;` 转为全角空格。
+pub(crate) fn decode_text_entities(s: &str) -> String {
+    let decoded = unescape_basic_entities(s);
+    if !decoded.contains('&') && !decoded.contains('\u{00A0}') {
+        return decoded.into_owned();
+    }
+    decoded
+        .replace("&emsp;", "\u{3000}")
+        .replace("&ensp;", " ")
+        .replace("&thinsp;", " ")
+        .replace("&middot;", "\u{00B7}")
+        .replace("&bull;", "\u{2022}")
+        .replace("&times;", "\u{00D7}")
+        .replace("&divide;", "\u{00F7}")
+        .replace("&laquo;", "\u{00AB}")
+        .replace("&raquo;", "\u{00BB}")
+        .replace("&sbquo;", "\u{201A}")
+        .replace("&bdquo;", "\u{201E}")
+        .replace("&copy;", "\u{00A9}")
+        .replace("&reg;", "\u{00AE}")
+        .replace("&trade;", "\u{2122}")
+        .replace("&deg;", "\u{00B0}")
+        .replace("&plusmn;", "\u{00B1}")
+        .replace("&yen;", "\u{00A5}")
+        .replace("&shy;", "")
+        .replace('\u{00A0}', " ")
+}
+
 // ── HTML 转义 ───────────────────────────────────────────────────
 
 pub(crate) fn escape_html(s: &str) -> String {
@@ -241,22 +280,137 @@ pub(crate) fn render_description_xhtml_fragment(description: &str) -> String {
         return normalized;
     }
 
-    // Plain-text: preserve line breaks as empty <p></p> and normal paragraphs.
+    render_plain_description(raw)
+}
+
+/// 列表项前缀：`- `、`· `、`1.`、`2、` 等；返回去掉前缀后的文本。
+fn strip_list_marker(line: &str) -> Option<&str> {
+    // `-`/`*` 需后跟空白，避免误判 `-_-`、`*注` 之类
+    for marker in [
+        "- ", "* ", "•", "·", "●", "○", "◆", "◇", "■", "□", "★", "☆", "▶", "►",
+    ] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            let rest = rest.trim_start();
+            if !rest.is_empty() {
+                return Some(rest);
+            }
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if (1..=3).contains(&digits) {
+        let rest = &line[digits..];
+        for sep in ['.', '、', '）', ')', '．'] {
+            if let Some(item) = rest.strip_prefix(sep) {
+                let item = item.trim_start();
+                if !item.is_empty() {
+                    return Some(item);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// 行首的 `【…】` 标注加粗；整行都是标注时作为小标题。
+fn render_description_line(line: &str) -> String {
+    if let Some(rest) = line.strip_prefix('【')
+        && let Some(end) = rest.find('】')
+    {
+        let label = &rest[..end];
+        let tail = rest[end + '】'.len_utf8()..].trim_start();
+        if tail.is_empty() {
+            return format!(
+                "<p class=\"no-indent intro-heading\"><strong>【{}】</strong></p>",
+                escape_html(label)
+            );
+        }
+        return format!(
+            "<p><strong>【{}】</strong>{}</p>",
+            escape_html(label),
+            escape_html(tail)
+        );
+    }
+    format!("<p>{}</p>", escape_html(line))
+}
+
+/// 纯文本简介的轻量结构识别：按行分段，连续的项目符号/编号行合并为列表，
+/// 整行 `【…】` 视为小标题，空行只作分隔。
+fn render_plain_description(raw: &str) -> String {
     let normalized = raw.replace("\r\n", "\n").replace('\r', "\n");
     let mut out = String::new();
+    let mut in_list = false;
     for line in normalized.split('\n') {
-        let trimmed_end = line.trim_end();
-        if trimmed_end.trim().is_empty() {
-            out.push_str("<p></p>");
+        let line = line.trim();
+        let item = if line.is_empty() {
+            None
         } else {
-            out.push_str("<p>");
-            out.push_str(&escape_html(trimmed_end));
-            out.push_str("</p>");
+            strip_list_marker(line)
+        };
+        if in_list && item.is_none() {
+            out.push_str("</ul>");
+            in_list = false;
+        }
+        if line.is_empty() {
+            continue;
         }
+        match item {
+            Some(item) => {
+                if !in_list {
+                    out.push_str("<ul class=\"intro-list\">");
+                    in_list = true;
+                }
+                out.push_str("<li>");
+                out.push_str(&escape_html(item));
+                out.push_str("</li>");
+            }
+            None => out.push_str(&render_description_line(line)),
+        }
+    }
+    if in_list {
+        out.push_str("</ul>");
+    }
+    if out.is_empty() {
+        out.push_str("<p></p>");
     }
     out
 }
 
+/// 简介转为保留换行的纯文本（TXT/PDF 用）：HTML 的 `<br>`/段落结束视为换行，
+/// 去掉标签并解码实体，连续空行合并为一行。
+pub(crate) fn description_to_text(description: &str) -> String {
+    let raw = description.trim();
+    if raw.is_empty() {
+        return String::new();
+    }
+    let text = if looks_like_html(raw) {
+        let with_breaks = re_block_break().replace_all(raw, "\n");
+        decode_text_entities(&re_all_tags().replace_all(&with_breaks, ""))
+    } else {
+        decode_text_entities(raw)
+    };
+    let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
+    let mut lines: Vec<&str> = Vec::new();
+    for line in normalized.split('\n').map(str::trim) {
+        if line.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// 正文可见字数（去掉标签与空白），用于判断短章。
+pub(crate) fn visible_char_count(html: &str) -> usize {
+    re_all_tags()
+        .split(html)
+        .flat_map(str::chars)
+        .filter(|c| !c.is_whitespace())
+        .count()
+}
+
 pub(crate) fn description_to_plain_text(description: &str) -> String {
     let raw = description.trim();
     if raw.is_empty() {
@@ -282,6 +436,63 @@ pub(crate) fn description_to_plain_text(description: &str) -> String {
     out.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
+// ── 对话分色 ────────────────────────────────────────────────────
+
+/// 对话段落使用的 class，样式见 EpubGenerator 的样式表。
+pub(crate) const DIALOGUE_CLASS: &str = "dialogue";
+
+/// 保守识别整段对话：以引号开头，引号外只有空白/标点，不含旁白文字；
+/// 引号内过短且无句读的（如单独加引号的称谓、书名）不算对话。
+pub(crate) fn is_dialogue_text(text: &str) -> bool {
+    const OPENERS: [(char, char); 4] = [('“', '”'), ('「', '」'), ('『', '』'), ('"', '"')];
+    let text = text.trim();
+    if !text.starts_with(|c| OPENERS.iter().any(|(o, _)| *o == c)) {
+        return false;
+    }
+    let mut closers: Vec<char> = Vec::new();
+    let mut inner_chars = 0usize;
+    let mut has_stop = false;
+    for ch in text.chars() {
+        if closers.last() == Some(&ch) {
+            closers.pop();
+            continue;
+        }
+        if let Some((_, close)) = OPENERS.iter().find(|(o, _)| *o == ch) {
+            closers.push(*close);
+            continue;
+        }
+        if closers.is_empty() {
+            if ch.is_whitespace() || "。！？!?…，,、—~～".contains(ch) {
+                continue;
+            }
+            return false;
+        }
+        if "。！？!?…，,~～".contains(ch) {
+            has_stop = true;
+        }
+        if !ch.is_whitespace() {
+            inner_chars += 1;
+        }
+    }
+    closers.is_empty() && inner_chars >= 2 && (has_stop || inner_chars >= 6)
+}
+
+/// 为整段对话的正文段落加上 `class="dialogue"`；已有 class 的段落（图注、段评等）不处理。
+pub(crate) fn mark_dialogue_paragraphs(html: &str) -> String {
+    let re_tags = re_all_tags();
+    re_para_parts()
+        .replace_all(html, |caps: &regex::Captures| {
+            let (open, inner, close) = (&caps[1], &caps[2], &caps[3]);
+            let text = re_tags.replace_all(inner, "");
+            if open.contains("class=") || !is_dialogue_text(&unescape_basic_entities(&text)) {
+                return caps[0].to_string();
+            }
+            let head = open.trim_end_matches('>').trim_end();
+            format!("{head} class=\"{DIALOGUE_CLASS}\">{inner}{close}")
+        })
+        .into_owned()
+}
+
 // ── EPUB 正文清理 ──────────────────────────────────────────────
 
 pub(crate) fn clean_epub_body(html: &str) -> String {
@@ -372,7 +583,43 @@ pub(crate) fn clean_epub_body(html: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{clean_epub_body, unescape_basic_entities};
+    use super::{
+        clean_epub_body, decode_text_entities, description_to_text, is_dialogue_text,
+        mark_dialogue_paragraphs, render_description_xhtml_fragment, unescape_basic_entities,
+    };
+
+    #[test]
+    fn description_keeps_structure() {
+        let desc = "【末世+病娇+无敌】\n\n林默醒来时，世界已经变了。\n- 第一卷：尸潮\n- 第二卷：<围城>\n【提示】本书慢热";
+        assert_eq!(
+            render_description_xhtml_fragment(desc),
+            concat!(
+                "<p class=\"no-indent intro-heading\"><strong>【末世+病娇+无敌】</strong></p>",
+                "<p>林默醒来时，世界已经变了。</p>",
+                "<ul class=\"intro-list\"><li>第一卷：尸潮</li><li>第二卷：&lt;围城&gt;</li></ul>",
+                "<p><strong>【提示】</strong>本书慢热</p>"
+            )
+        );
+        assert_eq!(
+            description_to_text("<p>第一段&amp;</p><p>第二段<br/>续行</p>"),
+            "第一段&\n第二段\n续行"
+        );
+        assert_eq!(description_to_text("a\r\n\r\n\r\nb\n"), "a\n\nb");
+    }
+
+    #[test]
+    fn decode_text_entities_covers_common_entities() {
+        assert_eq!(
+            decode_text_entities("A&amp;B&nbsp;C&#x4E2D;&#25991;&hellip;"),
+            "A&B C中文…"
+        );
+        assert_eq!(
+            decode_text_entities("&emsp;&emsp;正文&middot;续&amp;emsp;"),
+            "\u{3000}\u{3000}正文·续\u{3000}"
+        );
+        assert_eq!(decode_text_entities("无\u{00A0}实体"), "无 实体");
+        assert_eq!(decode_text_entities("&lt;tag&gt; &copy;"), "<tag> ©");
+    }
 
     #[test]
     fn unescape_basic_entities_decodes_nested_entities() {
@@ -398,4 +645,25 @@ mod tests {
         assert!(!out.contains("＂hi＂"));
         assert!(!out.contains("：-）"));
     }
+
+    #[test]
+    fn marks_only_whole_dialogue_paragraphs() {
+        assert!(is_dialogue_text("“你来了？”"));
+        assert!(is_dialogue_text("“走吧。”“好。”"));
+        assert!(is_dialogue_text("「别过来！」"));
+        assert!(is_dialogue_text("\"Hi, there.\""));
+        // 含旁白、单独加引号的称谓、未闭合引号都不算
+        assert!(!is_dialogue_text("“你来了？”他问。"));
+        assert!(!is_dialogue_text("他说：“走吧。”"));
+        assert!(!is_dialogue_text("“天机阁”"));
+        assert!(!is_dialogue_text("“话没说完"));
+
+        let out = mark_dialogue_paragraphs(
+            "<p>他推门进来。</p><p idx=\"2\">“你来了？”</p><p class=\"img-desc\">“图”</p>",
+        );
+        assert_eq!(
+            out,
+            "<p>他推门进来。</p><p idx=\"2\" class=\"dialogue\">“你来了？”</p><p class=\"img-desc\">“图”</p>"
+        );
+    }
 }
@@ -103,6 +103,18 @@ fn try_convert_to_jpeg(bytes: &[u8], quality: u8, max_dim: u32) -> Option<Vec<u8
     Some(out)
 }
 
+/// `media_max_dimension_px` 未设置时，压缩版封面的最长边上限。
+const COVER_MAX_DIMENSION_PX: u32 = 1600;
+
+/// 由高清封面生成压缩版 JPEG（质量取 `jpeg_quality`）。
+pub(crate) fn compress_cover_jpeg(cfg: &Config, bytes: &[u8]) -> Option<Vec<u8>> {
+    let max_dim = match cfg.media_max_dimension_px {
+        0 => COVER_MAX_DIMENSION_PX,
+        px => px,
+    };
+    try_convert_to_jpeg(bytes, cfg.jpeg_quality, max_dim)
+}
+
 // ── 缓存查找 ────────────────────────────────────────────────────
 
 fn find_cached_image(
@@ -127,15 +139,8 @@ fn fetch_and_normalize_image(
     cfg: &Config,
     url: &str,
 ) -> anyhow::Result<Option<(Vec<u8>, &'static str, &'static str)>> {
-    if !cfg.blocked_media_domains.is_empty() {
-        let lowered = url.to_ascii_lowercase();
-        if cfg
-            .blocked_media_domains
-            .iter()
-            .any(|d| !d.trim().is_empty() && lowered.contains(&d.to_ascii_lowercase()))
-        {
-            return Ok(None);
-        }
+    if !cfg.media_url_allowed(url) {
+        return Ok(None);
     }
 
     let bytes = match crate::third_party::media_fetch::fetch_bytes(
@@ -185,15 +190,8 @@ pub(crate) fn ensure_cached_image(
     url: &str,
     images_dir: &Path,
 ) -> anyhow::Result<Option<(PathBuf, &'static str, &'static str)>> {
-    if !cfg.blocked_media_domains.is_empty() {
-        let lowered = url.to_ascii_lowercase();
-        if cfg
-            .blocked_media_domains
-            .iter()
-            .any(|d| !d.trim().is_empty() && lowered.contains(&d.to_ascii_lowercase()))
-        {
-            return Ok(None);
-        }
+    if !cfg.media_url_allowed(url) {
+        return Ok(None);
     }
 
     let hash = sha1_hex(url);
@@ -30,14 +30,21 @@ pub mod audio_generator {
     }
 }
 pub mod book_manager;
+pub mod chapter_search;
+pub(crate) mod epub_check;
 pub mod epub_generator;
+pub(crate) mod finalize_comments;
 pub(crate) mod finalize_epub;
+pub(crate) mod finalize_html;
 pub(crate) mod finalize_pdf;
 pub(crate) mod finalize_utils;
 pub(crate) mod html_utils;
 pub(crate) mod image_utils;
 pub mod parser;
+pub(crate) mod placeholder_cover;
 #[cfg(feature = "official-api")]
 pub(crate) mod segment_comments;
 pub(crate) mod segment_shared;
 pub mod segment_utils;
+pub(crate) mod text_convert;
+pub(crate) mod text_replace;
@@ -30,6 +30,16 @@ fn re_strip_tags() -> &'static Regex {
     R.get_or_init(|| Regex::new(r"<[^>]+>").unwrap())
 }
 
+fn re_title_number_prefix() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| {
+        Regex::new(
+            r"^(?:第[0-9零〇一二两三四五六七八九十百千万]+[章节回话集卷]|chapter[0-9]+|[0-9]+[.．、\-]?)",
+        )
+        .unwrap()
+    })
+}
+
 fn re_strip_header() -> &'static Regex {
     static R: OnceLock<Regex> = OnceLock::new();
     R.get_or_init(|| Regex::new(r"<header[^>]*>.*?</header>").unwrap())
@@ -60,6 +70,16 @@ fn re_br_normalize() -> &'static Regex {
     R.get_or_init(|| Regex::new(r"(?i)<br\s*/?>").unwrap())
 }
 
+fn re_img_tag() -> &'static Regex {
+    static R: OnceLock<Regex> = OnceLock::new();
+    R.get_or_init(|| Regex::new(r"(?i)<img\b").unwrap())
+}
+
+/// 智能分段：仅当正文纯文本长度超过该值且没有任何段落分隔时才触发。
+const AUTO_PARAGRAPH_TRIGGER_CHARS: usize = 300;
+/// 智能分段：非对话段落至少累积到该长度才在句末断开。
+const AUTO_PARAGRAPH_MIN_CHARS: usize = 80;
+
 pub struct ContentParser;
 
 impl ContentParser {
@@ -91,11 +111,16 @@ impl ContentParser {
                 .unwrap_or(cid.as_str());
 
             // 缓存统一保存为 XHTML 格式，txt 的清洗在 finalize 阶段完成。
-            let processed = if cfg.novel_format.eq_ignore_ascii_case("epub") {
+            let mut processed = if cfg.wants_epub() {
                 Self::prepare_epub_xhtml(raw_content)
             } else {
                 Self::clean_xhtml(raw_content, title)
             };
+            if cfg.auto_paragraph
+                && let Some(resplit) = Self::auto_paragraph_xhtml(&processed)
+            {
+                processed = resplit;
+            }
 
             out.insert(cid.clone(), (processed, title.to_string()));
         }
@@ -179,6 +204,163 @@ impl ContentParser {
         }
     }
 
+    /// 判断正文某行是否为重复的章节标题：规整后相同，或去掉「第N章」/数字编号前缀后
+    /// 相同或高度相似（标题带编号而正文首行不带等情况）。
+    pub fn is_duplicate_title_line(line: &str, title: &str) -> bool {
+        let line = normalize_title_for_compare(line);
+        let title = normalize_title_for_compare(title);
+        if line.is_empty() || title.is_empty() {
+            return false;
+        }
+        if line == title {
+            return true;
+        }
+        let line_core = re_title_number_prefix().replace(&line, "");
+        let title_core = re_title_number_prefix().replace(&title, "");
+        if line_core.is_empty() || title_core.is_empty() {
+            return false;
+        }
+        if line_core == title_core {
+            return true;
+        }
+        // 仅对足够长的标题做相似度判断，避免短句误删
+        let a: Vec<char> = line_core.chars().collect();
+        let b: Vec<char> = title_core.chars().collect();
+        let longest = a.len().max(b.len());
+        longest >= 6 && edit_distance(&a, &b) * 100 <= longest * 15
+    }
+
+    /// 清空正文开头与章节标题重复的段落；未发现重复时返回 `None`。
+    ///
+    /// 段落标签保留为空段，使段评的段落序号仍与 API 计数对齐。
+    pub fn strip_duplicate_title_xhtml(xhtml: &str, title: &str) -> Option<String> {
+        let mut dup_inner = Vec::new();
+        let mut last_end = 0;
+        for cap in re_para().captures_iter(xhtml) {
+            let (whole, inner) = (cap.get(0)?, cap.get(1)?);
+            // 段落前若有其他可见文本（已注入的标题元素除外），则不再视为首行
+            let between =
+                Self::unescape_html_entities(&Self::strip_tags(&xhtml[last_end..whole.start()]));
+            let between = between.trim();
+            if !between.is_empty() && !Self::is_duplicate_title_line(between, title) {
+                break;
+            }
+            last_end = whole.end();
+            let text = Self::unescape_html_entities(&Self::strip_tags(inner.as_str()));
+            if text.trim().is_empty() {
+                continue;
+            }
+            if !Self::is_duplicate_title_line(&text, title) {
+                break;
+            }
+            dup_inner.push(inner.range());
+        }
+        if dup_inner.is_empty() {
+            return None;
+        }
+        let mut out = String::with_capacity(xhtml.len());
+        let mut pos = 0;
+        for range in dup_inner {
+            out.push_str(&xhtml[pos..range.start]);
+            pos = range.end;
+        }
+        out.push_str(&xhtml[pos..]);
+        Some(out)
+    }
+
+    /// 返回去除重复标题行后的章节副本。
+    pub fn strip_duplicate_titles(chapters: &[Value]) -> Vec<Value> {
+        chapters
+            .iter()
+            .map(|ch| {
+                let mut ch = ch.clone();
+                let title = ch
+                    .get("title")
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+                    .to_string();
+                if let Some(obj) = ch.as_object_mut()
+                    && let Some(content) = obj.get("content").and_then(Value::as_str)
+                    && let Some(stripped) = Self::strip_duplicate_title_xhtml(content, &title)
+                {
+                    obj.insert("content".to_string(), Value::String(stripped));
+                }
+                ch
+            })
+            .collect()
+    }
+
+    /// 段落是否为空段：去掉标签与实体后只剩空白（含全角空格），且不含图片。
+    fn is_blank_paragraph(inner: &str) -> bool {
+        !re_img_tag().is_match(inner)
+            && Self::unescape_html_entities(&Self::strip_tags(inner))
+                .trim()
+                .is_empty()
+    }
+
+    /// 规整空段落：去除首尾空段，连续空段只保留第一个作为分隔；无变化时返回 `None`。
+    pub fn trim_blank_paragraphs_xhtml(xhtml: &str) -> Option<String> {
+        let paras: Vec<(std::ops::Range<usize>, bool)> = re_para()
+            .captures_iter(xhtml)
+            .filter_map(|cap| {
+                let whole = cap.get(0)?;
+                let inner = cap.get(1).map_or("", |m| m.as_str());
+                Some((whole.range(), Self::is_blank_paragraph(inner)))
+            })
+            .collect();
+        let first = paras.iter().position(|(_, blank)| !blank);
+        let last = paras.iter().rposition(|(_, blank)| !blank);
+
+        let mut dropped = Vec::new();
+        for (i, (range, blank)) in paras.iter().enumerate() {
+            if !blank {
+                continue;
+            }
+            let outside = match (first, last) {
+                (Some(f), Some(l)) => i < f || i > l,
+                _ => true,
+            };
+            // 与前一空段之间只有空白才算连续
+            let follows_blank =
+                i > 0 && paras[i - 1].1 && xhtml[paras[i - 1].0.end..range.start].trim().is_empty();
+            if outside || follows_blank {
+                dropped.push(range.clone());
+            }
+        }
+        if dropped.is_empty() {
+            return None;
+        }
+        let mut out = String::with_capacity(xhtml.len());
+        let mut pos = 0;
+        for range in dropped {
+            out.push_str(&xhtml[pos..range.start]);
+            // 顺带去掉被删段落后的换行，避免留下空行
+            pos = range.end;
+            while xhtml[pos..].starts_with(['\n', '\r']) {
+                pos += 1;
+            }
+        }
+        out.push_str(&xhtml[pos..]);
+        Some(out)
+    }
+
+    /// 返回规整空段落后的章节副本。
+    pub fn trim_blank_paragraphs(chapters: &[Value]) -> Vec<Value> {
+        chapters
+            .iter()
+            .map(|ch| {
+                let mut ch = ch.clone();
+                if let Some(obj) = ch.as_object_mut()
+                    && let Some(content) = obj.get("content").and_then(Value::as_str)
+                    && let Some(trimmed) = Self::trim_blank_paragraphs_xhtml(content)
+                {
+                    obj.insert("content".to_string(), Value::String(trimmed));
+                }
+                ch
+            })
+            .collect()
+    }
+
     /// 简化的 XHTML 清洗：去掉 <header> 与脚本，保留主体文本。
     pub fn clean_xhtml(raw: &str, _title: &str) -> String {
         let stripped = Self::strip_header(raw);
@@ -210,6 +392,37 @@ impl ContentParser {
         paragraphs.join("\n")
     }
 
+    /// 智能分段：正文只有一整块文本（无 `<p>`/`<br>`/换行分隔）时，重新切成多个 `<p>`。
+    ///
+    /// 已有正确分段、含图片或篇幅过短的正文返回 `None`，保持原样。
+    pub fn auto_paragraph_xhtml(xhtml: &str) -> Option<String> {
+        if re_img_tag().is_match(xhtml) || re_br_normalize().is_match(xhtml) {
+            return None;
+        }
+        if re_para().captures_iter(xhtml).count() > 1 {
+            return None;
+        }
+        let text = Self::unescape_html_entities(&Self::strip_tags(xhtml));
+        let text = text.trim();
+        if text.lines().filter(|l| !l.trim().is_empty()).count() > 1
+            || text.chars().count() < AUTO_PARAGRAPH_TRIGGER_CHARS
+        {
+            return None;
+        }
+
+        let paragraphs = split_paragraphs(text);
+        if paragraphs.len() <= 1 {
+            return None;
+        }
+        Some(
+            paragraphs
+                .iter()
+                .map(|p| format!("<p>{}</p>", Self::escape_html(p)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
     fn strip_tags(raw: &str) -> String {
         // 粗暴去标签，避免引入额外 HTML 解析库
         let s = re_strip_tags().replace_all(raw, "");
@@ -357,6 +570,85 @@ impl ContentParser {
     }
 }
 
+fn is_sentence_end(ch: char) -> bool {
+    matches!(ch, '。' | '！' | '？' | '!' | '?' | '…')
+}
+
+fn is_open_quote(ch: char) -> bool {
+    matches!(ch, '“' | '「' | '『')
+}
+
+fn is_close_quote(ch: char) -> bool {
+    matches!(ch, '”' | '」' | '』')
+}
+
+/// 按中文句末标点 + 对话引号 + 长度启发式切段。
+///
+/// - 引号内不断开；
+/// - 完整的对话（以句末标点收尾的引号）单独成段；
+/// - 叙述文字累积到 `AUTO_PARAGRAPH_MIN_CHARS` 后在下一个句末断开。
+fn split_paragraphs(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut cur_len = 0usize;
+    let mut depth = 0usize;
+    let mut prev: Option<char> = None;
+
+    let mut flush = |cur: &mut String, cur_len: &mut usize| {
+        let para = cur.trim();
+        if !para.is_empty() {
+            out.push(para.to_string());
+        }
+        cur.clear();
+        *cur_len = 0;
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i];
+        // 新的一句对话：前一句已结束时另起一段
+        if is_open_quote(ch)
+            && depth == 0
+            && prev.is_some_and(|p| is_sentence_end(p) || is_close_quote(p))
+        {
+            flush(&mut cur, &mut cur_len);
+        }
+        if is_open_quote(ch) {
+            depth += 1;
+        } else if is_close_quote(ch) {
+            depth = depth.saturating_sub(1);
+        }
+        cur.push(ch);
+        cur_len += 1;
+
+        let dialogue_end = is_close_quote(ch) && depth == 0 && prev.is_some_and(is_sentence_end);
+        let sentence_end = is_sentence_end(ch) && depth == 0;
+        if !ch.is_whitespace() {
+            prev = Some(ch);
+        }
+
+        if dialogue_end || sentence_end {
+            // 吸收连续的句末标点（如“……”“？！”）
+            while let Some(&next) = chars.get(i + 1) {
+                if !is_sentence_end(next) {
+                    break;
+                }
+                cur.push(next);
+                cur_len += 1;
+                prev = Some(next);
+                i += 1;
+            }
+            if dialogue_end || cur_len >= AUTO_PARAGRAPH_MIN_CHARS {
+                flush(&mut cur, &mut cur_len);
+            }
+        }
+        i += 1;
+    }
+    flush(&mut cur, &mut cur_len);
+    out
+}
+
 fn normalize_title_for_compare(s: &str) -> String {
     s.chars()
         .filter(|ch| {
@@ -370,6 +662,21 @@ fn normalize_title_for_compare(s: &str) -> String {
         .to_lowercase()
 }
 
+/// 按字符计算的编辑距离。
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::ContentParser;
@@ -425,6 +732,25 @@ mod tests {
         assert!(!out.contains("：-）"));
     }
 
+    #[test]
+    fn auto_paragraph_splits_single_block_by_sentence_and_dialogue() {
+        let narration =
+            "他沿着山路走了很久，天色渐渐暗了下来，远处的村庄亮起了零星的灯火。".repeat(6);
+        let raw = format!("<p>{narration}“你终于来了。”老人说道。{narration}</p>");
+        let out = ContentParser::auto_paragraph_xhtml(&raw).expect("should split");
+
+        assert!(out.matches("<p>").count() >= 3);
+        assert!(out.contains("<p>“你终于来了。”</p>"));
+    }
+
+    #[test]
+    fn auto_paragraph_keeps_existing_paragraphs() {
+        let long = "这是一段很长的正文内容。".repeat(40);
+        let raw = format!("<p>{long}</p>\n<p>第二段。</p>");
+        assert!(ContentParser::auto_paragraph_xhtml(&raw).is_none());
+        assert!(ContentParser::auto_paragraph_xhtml("<p>短文本。</p>").is_none());
+    }
+
     #[test]
     fn clean_xhtml_then_clean_plain_restores_text_entities() {
         let raw = "<p>他说&#34;A&amp;B&#34;</p>";
@@ -435,4 +761,38 @@ mod tests {
         assert!(!out.contains("&#34;"));
         assert!(!out.contains("&amp;"));
     }
+
+    #[test]
+    fn strips_fuzzy_duplicated_title_paragraph() {
+        let raw = "<p>开局一把刀</p><p>正文第一段</p>";
+        let out = ContentParser::strip_duplicate_title_xhtml(raw, "第12章 开局一把刀").unwrap();
+        assert_eq!(out, "<p></p><p>正文第一段</p>");
+
+        let raw = "<p>第十二章：开局一把刀</p><p>正文</p>";
+        assert!(ContentParser::strip_duplicate_title_xhtml(raw, "第12章 开局一把刀").is_some());
+
+        let raw = "<p>正文第一段</p><p>开局一把刀</p>";
+        assert!(ContentParser::strip_duplicate_title_xhtml(raw, "第12章 开局一把刀").is_none());
+        assert!(!ContentParser::is_duplicate_title_line(
+            "他说",
+            "第1章 开局"
+        ));
+    }
+
+    #[test]
+    fn trims_and_collapses_blank_paragraphs() {
+        let raw = "<p></p>\n<p>　</p>\n<p>第一段</p>\n<p> </p>\n<p>&nbsp;</p>\n<p><br/></p>\n<p>第二段</p>\n<p></p>\n<p>第三段</p>\n<p>  </p>";
+        let out = ContentParser::trim_blank_paragraphs_xhtml(raw).unwrap();
+        assert_eq!(
+            out,
+            "<p>第一段</p>\n<p> </p>\n<p>第二段</p>\n<p></p>\n<p>第三段</p>\n"
+        );
+
+        // 含图片的段落不算空段；已规整的正文不改动
+        assert!(
+            ContentParser::trim_blank_paragraphs_xhtml("<p><img src=\"a.png\"/></p><p>正文</p>")
+                .is_none()
+        );
+        assert!(ContentParser::trim_blank_paragraphs_xhtml(&out).is_none());
+    }
 }
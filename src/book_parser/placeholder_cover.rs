@@ -0,0 +1,198 @@
+//! 无封面时的占位封面：纵向渐变背景 + 居中书名/作者。
+//!
+//! 字体沿用 PDF 的查找逻辑（`pdf_font_path` 或系统 CJK 字体），找不到字体时不生成。
+
+use std::io::Cursor;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use image::{ImageFormat, Rgb, RgbImage};
+use rusttype::{Font, FontCollection, Scale, point};
+
+const COVER_WIDTH: u32 = 600;
+const COVER_HEIGHT: u32 = 800;
+const SIDE_MARGIN: f32 = 60.0;
+const TITLE_PX: f32 = 56.0;
+const AUTHOR_PX: f32 = 30.0;
+const TITLE_MAX_LINES: usize = 4;
+/// 未提供或无法解析 `cover_primary_color` 时的背景色。
+const DEFAULT_BG: [u8; 3] = [0x3a, 0x4a, 0x5c];
+/// 渐变底部相对主色的亮度系数。
+const GRADIENT_BOTTOM_SHADE: f32 = 0.6;
+/// 生成的占位封面另存到书籍文件夹时使用的文件名（不在封面查找候选内）。
+pub(crate) const PLACEHOLDER_COVER_FILE: &str = "placeholder_cover.png";
+
+/// 解析 `#RGB` / `#RRGGBB` / `0xRRGGBB`（可省略前缀，忽略 alpha）。
+pub(crate) fn parse_hex_color(raw: &str) -> Option<[u8; 3]> {
+    let s = raw.trim();
+    let s = s
+        .strip_prefix('#')
+        .or_else(|| s.strip_prefix("0x"))
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    if !s.is_ascii() {
+        return None;
+    }
+    let hex = match s.len() {
+        3 => s.chars().flat_map(|c| [c, c]).collect::<String>(),
+        6 => s.to_string(),
+        8 => s[s.len() - 6..].to_string(),
+        _ => return None,
+    };
+    let v = u32::from_str_radix(&hex, 16).ok()?;
+    Some([(v >> 16) as u8, (v >> 8) as u8, v as u8])
+}
+
+/// 自上而下由主色渐变到较暗的同色。
+fn gradient_background(bg: [u8; 3]) -> RgbImage {
+    RgbImage::from_fn(COVER_WIDTH, COVER_HEIGHT, |_, y| {
+        let t = y as f32 / (COVER_HEIGHT - 1) as f32;
+        let factor = 1.0 - (1.0 - GRADIENT_BOTTOM_SHADE) * t;
+        Rgb(bg.map(|c| (c as f32 * factor).round() as u8))
+    })
+}
+
+fn load_font(path: &Path) -> Result<Font<'static>> {
+    let bytes = std::fs::read(path).with_context(|| format!("读取字体失败: {}", path.display()))?;
+    FontCollection::from_bytes(bytes)
+        .and_then(|c| c.font_at(0))
+        .map_err(|e| anyhow!("解析字体失败: {e}"))
+}
+
+fn text_width(font: &Font<'_>, scale: Scale, text: &str) -> f32 {
+    font.layout(text, scale, point(0.0, 0.0))
+        .last()
+        .map(|g| g.position().x + g.unpositioned().h_metrics().advance_width)
+        .unwrap_or(0.0)
+}
+
+/// 按宽度折行；超出行数时末行以省略号结尾。
+fn wrap_text(
+    font: &Font<'_>,
+    scale: Scale,
+    text: &str,
+    max_width: f32,
+    max_lines: usize,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for ch in text.chars() {
+        let mut candidate = current.clone();
+        candidate.push(ch);
+        if !current.is_empty() && text_width(font, scale, &candidate) > max_width {
+            lines.push(std::mem::take(&mut current));
+            if lines.len() == max_lines {
+                if let Some(last) = lines.last_mut() {
+                    last.pop();
+                    last.push('…');
+                }
+                return lines;
+            }
+            current.push(ch);
+        } else {
+            current = candidate;
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn draw_line(img: &mut RgbImage, font: &Font<'_>, scale: Scale, text: &str, top: f32, fg: [u8; 3]) {
+    let ascent = font.v_metrics(scale).ascent;
+    let left = (COVER_WIDTH as f32 - text_width(font, scale, text)) / 2.0;
+    for glyph in font.layout(text, scale, point(left, top + ascent)) {
+        let Some(bb) = glyph.pixel_bounding_box() else {
+            continue;
+        };
+        glyph.draw(|gx, gy, v| {
+            let x = gx as i32 + bb.min.x;
+            let y = gy as i32 + bb.min.y;
+            if x < 0 || y < 0 || x >= COVER_WIDTH as i32 || y >= COVER_HEIGHT as i32 {
+                return;
+            }
+            let px = img.get_pixel_mut(x as u32, y as u32);
+            for (channel, fg) in px.0.iter_mut().zip(fg) {
+                let blended = *channel as f32 * (1.0 - v) + fg as f32 * v;
+                *channel = blended.round() as u8;
+            }
+        });
+    }
+}
+
+/// 渲染占位封面，返回 PNG 字节。
+pub(crate) fn render_placeholder_cover(
+    title: &str,
+    author: &str,
+    primary_color: Option<&str>,
+    font_path: &Path,
+) -> Result<Vec<u8>> {
+    let font = load_font(font_path)?;
+    let bg = primary_color
+        .and_then(parse_hex_color)
+        .unwrap_or(DEFAULT_BG);
+    // 按渐变中部的亮度选择黑/白文字，保证可读。
+    let mid_factor = (1.0 + GRADIENT_BOTTOM_SHADE) / 2.0;
+    let luma = (0.299 * bg[0] as f32 + 0.587 * bg[1] as f32 + 0.114 * bg[2] as f32) * mid_factor;
+    let fg = if luma > 150.0 { [0x20; 3] } else { [0xff; 3] };
+
+    let mut img = gradient_background(bg);
+    let max_width = COVER_WIDTH as f32 - SIDE_MARGIN * 2.0;
+
+    let title_scale = Scale::uniform(TITLE_PX);
+    let title_lines = wrap_text(&font, title_scale, title.trim(), max_width, TITLE_MAX_LINES);
+    let title_line_h = TITLE_PX * 1.3;
+    let author = author.trim();
+    let author_scale = Scale::uniform(AUTHOR_PX);
+    let author_block = if author.is_empty() {
+        0.0
+    } else {
+        AUTHOR_PX * 2.5
+    };
+
+    let block_h = title_lines.len() as f32 * title_line_h + author_block;
+    let mut top = (COVER_HEIGHT as f32 - block_h) / 2.0;
+    for line in &title_lines {
+        draw_line(&mut img, &font, title_scale, line, top, fg);
+        top += title_line_h;
+    }
+    if !author.is_empty() {
+        let author_lines = wrap_text(&font, author_scale, author, max_width, 1);
+        if let Some(line) = author_lines.first() {
+            draw_line(
+                &mut img,
+                &font,
+                author_scale,
+                line,
+                top + AUTHOR_PX * 1.2,
+                fg,
+            );
+        }
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    img.write_to(&mut out, ImageFormat::Png)
+        .context("编码占位封面失败")?;
+    Ok(out.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{COVER_HEIGHT, gradient_background, parse_hex_color};
+
+    #[test]
+    fn parses_common_color_forms() {
+        assert_eq!(parse_hex_color("#1a2B3c"), Some([0x1a, 0x2b, 0x3c]));
+        assert_eq!(parse_hex_color("fff"), Some([0xff, 0xff, 0xff]));
+        assert_eq!(parse_hex_color("0xFF102030"), Some([0x10, 0x20, 0x30]));
+        assert_eq!(parse_hex_color("rgb(1,2,3)"), None);
+    }
+
+    #[test]
+    fn gradient_darkens_towards_bottom() {
+        let img = gradient_background([200, 100, 50]);
+        assert_eq!(img.get_pixel(0, 0).0, [200, 100, 50]);
+        assert_eq!(img.get_pixel(0, COVER_HEIGHT - 1).0, [120, 60, 30]);
+    }
+}
@@ -3,7 +3,7 @@
 //! 包含段落评论的缓存加载、评论媒体（头像/图片）预取、评论页 XHTML 渲染。
 
 #[cfg(feature = "official-api")]
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 #[cfg(feature = "official-api")]
 use std::fs;
 #[cfg(feature = "official-api")]
@@ -27,6 +27,8 @@ use super::image_utils::{ensure_cached_image, sha1_hex};
 use super::segment_shared::SegmentCommentsChapterCache;
 #[cfg(feature = "official-api")]
 use super::segment_utils;
+#[cfg(feature = "official-api")]
+use super::segment_utils::SegmentCommentStyle;
 
 // ── 缓存加载 ────────────────────────────────────────────────────
 
@@ -51,7 +53,7 @@ pub(crate) fn prefetch_comment_media(
     per_para: &[(i32, tomato_novel_official_api::ReviewResponse)],
     images_dir: &Path,
 ) {
-    if !(cfg.download_comment_images || cfg.download_comment_avatars) {
+    if !(cfg.wants_comment_images() || cfg.wants_comment_avatars()) {
         return;
     }
 
@@ -60,7 +62,7 @@ pub(crate) fn prefetch_comment_media(
 
     for (_para_idx, resp) in per_para {
         for item in &resp.reviews {
-            if cfg.download_comment_avatars
+            if cfg.wants_comment_avatars()
                 && let Some(url) = item.user.avatar.as_deref()
             {
                 let u = url.trim();
@@ -68,7 +70,7 @@ pub(crate) fn prefetch_comment_media(
                     urls.push(u.to_string());
                 }
             }
-            if cfg.download_comment_images {
+            if cfg.wants_comment_images() {
                 for img in &item.images {
                     let u = img.url.trim();
                     if !u.is_empty() && seen.insert(u.to_string()) {
@@ -174,24 +176,98 @@ pub(crate) fn render_segment_comment_page(
             idx_usize
         ));
 
-        html.push_str("<ol>");
-        for item in &resp.reviews {
-            let user = item
-                .user
-                .name
-                .as_deref()
-                .filter(|s| !s.trim().is_empty())
-                .unwrap_or("匿名");
-            let text = segment_utils::convert_bracket_emojis(&item.text);
-            if text.trim().is_empty() {
-                continue;
+        html.push_str(&render_para_reviews(
+            resp,
+            cfg,
+            resources_added,
+            images_dir,
+            epub,
+            &mut avatar_used,
+            &mut image_used,
+        ));
+    }
+
+    let top_n_cfg = cfg.segment_comments_top_n.max(1);
+    html.push_str(&format!(
+        "<p><small>仅展示每段前 {} 条评论（若有），实际总数以接口为准。</small></p>",
+        top_n_cfg
+    ));
+
+    info!(
+        target: "segment",
+        chapter = %chapter_title,
+        para_groups = per_para.len(),
+        avatar_used,
+        image_used,
+        download_comment_avatars = cfg.wants_comment_avatars(),
+        download_comment_images = cfg.wants_comment_images(),
+        "segment comment page rendered"
+    );
+
+    Ok(html)
+}
+
+/// 渲染单个段落的评论列表（`<ol>`），头像/图片按配置写入 EPUB 资源。
+#[cfg(feature = "official-api")]
+#[allow(clippy::too_many_arguments)]
+fn render_para_reviews(
+    resp: &tomato_novel_official_api::ReviewResponse,
+    cfg: &crate::base_system::context::Config,
+    resources_added: &mut HashSet<String>,
+    images_dir: &Path,
+    epub: &mut EpubGenerator,
+    avatar_used: &mut usize,
+    image_used: &mut usize,
+) -> String {
+    let mut html = String::from("<ol>");
+    for item in &resp.reviews {
+        let user = item
+            .user
+            .name
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or("匿名");
+        let text = segment_utils::convert_bracket_emojis(&item.text);
+        if text.trim().is_empty() {
+            continue;
+        }
+        let text = escape_html(text.trim());
+
+        let mut avatar_html = String::new();
+        if cfg.wants_comment_avatars()
+            && let Some(url) = item.user.avatar.as_deref()
+        {
+            if let Ok(Some((path, mime, ext))) = ensure_cached_image(cfg, url, images_dir) {
+                let hash = sha1_hex(url);
+                let resource_path = format!("images/{}{}", hash, ext);
+                if !resources_added.contains(&resource_path)
+                    && let Ok(bytes) = fs::read(&path)
+                    && epub.add_resource_bytes(&resource_path, bytes, mime).is_ok()
+                {
+                    resources_added.insert(resource_path.clone());
+                }
+                if resources_added.contains(&resource_path) {
+                    avatar_html = format!(
+                        "<img class=\"avatar\" alt=\"\" src=\"{}\"/>",
+                        escape_html(&resource_path)
+                    );
+                    *avatar_used += 1;
+                } else {
+                    debug!(target: "segment", url = %url, "avatar not added to epub resources (read/add_resource failed)");
+                }
+            } else {
+                debug!(target: "segment", url = %url, "avatar ensure_cached_image failed/empty");
             }
-            let text = escape_html(text.trim());
+        }
 
-            let mut avatar_html = String::new();
-            if cfg.download_comment_avatars
-                && let Some(url) = item.user.avatar.as_deref()
-            {
+        let mut images_html = String::new();
+        if cfg.wants_comment_images() {
+            let mut imgs = Vec::new();
+            for img in &item.images {
+                let url = img.url.trim();
+                if url.is_empty() {
+                    continue;
+                }
                 if let Ok(Some((path, mime, ext))) = ensure_cached_image(cfg, url, images_dir) {
                     let hash = sha1_hex(url);
                     let resource_path = format!("images/{}{}", hash, ext);
@@ -202,98 +278,106 @@ pub(crate) fn render_segment_comment_page(
                         resources_added.insert(resource_path.clone());
                     }
                     if resources_added.contains(&resource_path) {
-                        avatar_html = format!(
-                            "<img class=\"avatar\" alt=\"\" src=\"{}\"/>",
+                        imgs.push(format!(
+                            "<img alt=\"\" src=\"{}\"/>",
                             escape_html(&resource_path)
-                        );
-                        avatar_used += 1;
+                        ));
+                        *image_used += 1;
                     } else {
-                        debug!(target: "segment", url = %url, "avatar not added to epub resources (read/add_resource failed)");
+                        debug!(target: "segment", url = %url, "comment image not added to epub resources (read/add_resource failed)");
                     }
                 } else {
-                    debug!(target: "segment", url = %url, "avatar ensure_cached_image failed/empty");
+                    debug!(target: "segment", url = %url, "comment image ensure_cached_image failed/empty");
                 }
             }
-
-            let mut images_html = String::new();
-            if cfg.download_comment_images {
-                let mut imgs = Vec::new();
-                for img in &item.images {
-                    let url = img.url.trim();
-                    if url.is_empty() {
-                        continue;
-                    }
-                    if let Ok(Some((path, mime, ext))) = ensure_cached_image(cfg, url, images_dir) {
-                        let hash = sha1_hex(url);
-                        let resource_path = format!("images/{}{}", hash, ext);
-                        if !resources_added.contains(&resource_path)
-                            && let Ok(bytes) = fs::read(&path)
-                            && epub.add_resource_bytes(&resource_path, bytes, mime).is_ok()
-                        {
-                            resources_added.insert(resource_path.clone());
-                        }
-                        if resources_added.contains(&resource_path) {
-                            imgs.push(format!(
-                                "<img alt=\"\" src=\"{}\"/>",
-                                escape_html(&resource_path)
-                            ));
-                            image_used += 1;
-                        } else {
-                            debug!(target: "segment", url = %url, "comment image not added to epub resources (read/add_resource failed)");
-                        }
-                    } else {
-                        debug!(target: "segment", url = %url, "comment image ensure_cached_image failed/empty");
-                    }
-                }
-                if !imgs.is_empty() {
-                    images_html = format!("<div class=\"seg-images\">{}</div>", imgs.join(""));
-                }
+            if !imgs.is_empty() {
+                images_html = format!("<div class=\"seg-images\">{}</div>", imgs.join(""));
             }
+        }
 
-            let mut meta_line = String::new();
-            meta_line.push_str("<small class=\"seg-meta\">");
-            meta_line.push_str(&avatar_html);
-            meta_line.push_str(&format!("作者：{}", escape_html(user)));
+        let mut meta_line = String::new();
+        meta_line.push_str("<small class=\"seg-meta\">");
+        meta_line.push_str(&avatar_html);
+        meta_line.push_str(&format!("作者：{}", escape_html(user)));
 
-            if let Some(ts) = item.created_ts {
-                let mut t = ts;
-                if t > 1_000_000_000_000 {
-                    t /= 1000;
-                }
-                if t > 0 {
-                    meta_line.push_str(&format!(" | 时间：{}", escape_html(&t.to_string())));
-                }
+        if let Some(ts) = item.created_ts {
+            let mut t = ts;
+            if t > 1_000_000_000_000 {
+                t /= 1000;
             }
-            meta_line.push_str(&format!(" | 赞：{}", item.digg_count));
-            meta_line.push_str("</small>");
-
-            html.push_str("<li class=\"seg-item\">");
-            html.push_str(&format!("<p>{}</p>", text));
-            if !images_html.is_empty() {
-                html.push_str(&images_html);
+            if t > 0 {
+                meta_line.push_str(&format!(" | 时间：{}", escape_html(&t.to_string())));
             }
-            html.push_str(&format!("<p>{}</p>", meta_line));
-            html.push_str("</li>");
         }
-        html.push_str("</ol>");
+        meta_line.push_str(&format!(" | 赞：{}", item.digg_count));
+        meta_line.push_str("</small>");
+
+        html.push_str("<li class=\"seg-item\">");
+        html.push_str(&format!("<p>{}</p>", text));
+        if !images_html.is_empty() {
+            html.push_str(&images_html);
+        }
+        html.push_str(&format!("<p>{}</p>", meta_line));
+        html.push_str("</li>");
     }
+    html.push_str("</ol>");
+    html
+}
 
-    let top_n_cfg = cfg.segment_comments_top_n.max(1);
-    html.push_str(&format!(
-        "<p><small>仅展示每段前 {} 条评论（若有），实际总数以接口为准。</small></p>",
-        top_n_cfg
-    ));
+/// 行内脚注式/段后块式：按段落序号渲染可直接注入正文的评论块。
+#[cfg(feature = "official-api")]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_segment_comment_blocks(
+    chapter_title: &str,
+    per_para: &[(i32, tomato_novel_official_api::ReviewResponse)],
+    style: SegmentCommentStyle,
+    cfg: &crate::base_system::context::Config,
+    resources_added: &mut HashSet<String>,
+    images_dir: &Path,
+    epub: &mut EpubGenerator,
+) -> HashMap<usize, String> {
+    let mut avatar_used = 0usize;
+    let mut image_used = 0usize;
+    let mut blocks = HashMap::new();
+
+    for (para_idx, resp) in per_para {
+        let idx_usize = (*para_idx).max(0) as usize;
+        let list = render_para_reviews(
+            resp,
+            cfg,
+            resources_added,
+            images_dir,
+            epub,
+            &mut avatar_used,
+            &mut image_used,
+        );
+        if list == "<ol></ol>" {
+            continue;
+        }
+        let mut block = format!(
+            "<p class=\"seg-block-title\">段评（{}）</p>{}",
+            resp.reviews.len(),
+            list
+        );
+        if style == SegmentCommentStyle::InlineFootnote {
+            // 不支持弹窗脚注的阅读器会把脚注显示在章末，提供返回链接
+            block.push_str(&format!(
+                "<div class=\"back-to-chapter\"><a href=\"#p-{}\">↩ 回到正文</a></div>",
+                idx_usize
+            ));
+        }
+        blocks.insert(idx_usize, block);
+    }
 
     info!(
         target: "segment",
         chapter = %chapter_title,
-        para_groups = per_para.len(),
+        style = style.as_str(),
+        para_groups = blocks.len(),
         avatar_used,
         image_used,
-        download_comment_avatars = cfg.download_comment_avatars,
-        download_comment_images = cfg.download_comment_images,
-        "segment comment page rendered"
+        "segment comment blocks rendered"
     );
 
-    Ok(html)
+    blocks
 }
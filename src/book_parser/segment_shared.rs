@@ -4,7 +4,7 @@
 
 #[cfg(feature = "official-api")]
 use std::collections::BTreeMap;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use serde_json::Value;
@@ -30,6 +30,45 @@ pub(crate) struct SegmentCommentsChapterCache {
     pub(crate) top_n: usize,
     #[serde(default)]
     pub(crate) paras: BTreeMap<String, SegmentCommentsParaCache>,
+    /// 抓取时间（Unix 秒）；旧缓存缺省为 0，视为需要刷新
+    #[serde(default)]
+    pub(crate) fetched_at: u64,
+    /// 是否已抓取段评详情；热门章节模式下的冷门章节只存评论数，为 false。
+    /// 旧缓存没有该字段，均为完整抓取，缺省视为 true。
+    #[serde(default = "default_details_complete")]
+    pub(crate) details_complete: bool,
+}
+
+#[cfg(feature = "official-api")]
+fn default_details_complete() -> bool {
+    true
+}
+
+#[cfg(feature = "official-api")]
+impl SegmentCommentsChapterCache {
+    /// 沿用旧缓存中评论数未变的段落详情，返回沿用的段落数；章节版本或 `top_n` 变化时不沿用。
+    pub(crate) fn reuse_unchanged_details(&mut self, old: SegmentCommentsChapterCache) -> usize {
+        if old.top_n != self.top_n || old.item_version != self.item_version {
+            return 0;
+        }
+        let mut reused = 0;
+        for (idx, old_para) in old.paras {
+            if let Some(para) = self.paras.get_mut(&idx)
+                && para.count == old_para.count
+                && old_para.detail.is_some()
+            {
+                para.detail = old_para.detail;
+                reused += 1;
+            }
+        }
+        reused
+    }
+}
+
+/// 段评缓存是否已过刷新间隔；`refresh_hours` 为 0 表示永不刷新。
+#[cfg_attr(not(feature = "official-api"), allow(dead_code))]
+pub(crate) fn segment_cache_is_stale(fetched_at: u64, now: u64, refresh_hours: u64) -> bool {
+    refresh_hours > 0 && now.saturating_sub(fetched_at) >= refresh_hours.saturating_mul(3600)
 }
 
 // ── 共享工具函数 ─────────────────────────────────────────────────
@@ -40,9 +79,11 @@ pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
         path.extension().and_then(|s| s.to_str()).unwrap_or("")
     ));
     std::fs::write(&tmp, bytes)?;
-    // best-effort replace
-    let _ = std::fs::remove_file(path);
-    std::fs::rename(tmp, path)?;
+    // 多数平台 rename 可直接覆盖，读者始终看到完整文件；失败（如 Windows 目标被占用）再先删后换
+    if std::fs::rename(&tmp, path).is_err() {
+        let _ = std::fs::remove_file(path);
+        std::fs::rename(tmp, path)?;
+    }
     Ok(())
 }
 
@@ -101,6 +142,126 @@ pub(crate) fn extract_item_version_map(directory_raw: &Value) -> HashMap<String,
     out
 }
 
+/// 章节时间字段：首发时间优先，缺失时退回更新时间。
+const CHAPTER_TIME_KEYS: [&str; 5] = [
+    "first_pass_time",
+    "publish_time",
+    "create_time",
+    "update_time",
+    "last_update_time",
+];
+
+/// 遍历目录原始数据中的章节条目：(chapter_id, 条目对象)。
+fn for_each_catalog_item(
+    directory_raw: &Value,
+    mut f: impl FnMut(String, &serde_json::Map<String, Value>),
+) {
+    fn pick_id(v: Option<&Value>) -> Option<String> {
+        match v {
+            Some(Value::String(s)) if !s.trim().is_empty() => Some(s.trim().to_string()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+
+    let candidates = [
+        directory_raw.get("catalog_data"),
+        directory_raw.get("item_data_list"),
+        directory_raw.get("items"),
+    ];
+    for arr in candidates {
+        let Some(arr) = arr.and_then(Value::as_array) else {
+            continue;
+        };
+        for item in arr {
+            let Some(obj) = item.as_object() else {
+                continue;
+            };
+            let Some(id) = pick_id(
+                obj.get("item_id")
+                    .or_else(|| obj.get("catalog_id"))
+                    .or_else(|| obj.get("id")),
+            ) else {
+                continue;
+            };
+            f(id, obj);
+        }
+    }
+}
+
+/// 解析数字时间戳（秒或毫秒）为 Unix 秒；非数字返回 `Err(原字符串)`。
+fn parse_unix_secs(v: &Value) -> Option<Result<i64, String>> {
+    let ts = match v {
+        Value::Number(n) => n.as_i64()?,
+        Value::String(s) => {
+            let t = s.trim();
+            if t.is_empty() || t == "0" {
+                return None;
+            }
+            match t.parse::<i64>() {
+                Ok(n) => n,
+                Err(_) => return Some(Err(t.to_string())),
+            }
+        }
+        _ => return None,
+    };
+    if ts <= 0 {
+        return None;
+    }
+    Some(Ok(if ts > 100_000_000_000 { ts / 1000 } else { ts }))
+}
+
+/// 从目录原始数据提取章节发布时间：chapter_id -> `YYYY-MM-DD HH:MM`（北京时间）。
+///
+/// 优先取首发时间（`first_pass_time`/`publish_time`/`create_time`），缺失时退回更新时间。
+/// 数字时间戳自动识别秒/毫秒；无法识别的字符串原样保留。
+pub(crate) fn extract_chapter_time_map(directory_raw: &Value) -> HashMap<String, String> {
+    fn format_time(v: &Value) -> Option<String> {
+        let secs = match parse_unix_secs(v)? {
+            Ok(secs) => secs,
+            Err(raw) => return Some(raw),
+        };
+        let dt = time::OffsetDateTime::from_unix_timestamp(secs)
+            .ok()?
+            .to_offset(time::macros::offset!(+8));
+        dt.format(time::macros::format_description!(
+            "[year]-[month]-[day] [hour]:[minute]"
+        ))
+        .ok()
+    }
+
+    let mut out = HashMap::new();
+    for_each_catalog_item(directory_raw, |id, obj| {
+        if let Some(t) = CHAPTER_TIME_KEYS
+            .iter()
+            .find_map(|k| obj.get(*k).and_then(format_time))
+        {
+            out.entry(id).or_insert(t);
+        }
+    });
+    out
+}
+
+/// 取给定章节中最晚的发布时间（Unix 秒），用作 EPUB 的 `dcterms:modified`。
+///
+/// 只看数字时间戳；`chapter_ids` 为空时统计目录中全部章节。
+pub(crate) fn latest_chapter_time(
+    directory_raw: &Value,
+    chapter_ids: &HashSet<String>,
+) -> Option<i64> {
+    let mut latest = None;
+    for_each_catalog_item(directory_raw, |id, obj| {
+        if !chapter_ids.is_empty() && !chapter_ids.contains(&id) {
+            return;
+        }
+        let ts = CHAPTER_TIME_KEYS
+            .iter()
+            .find_map(|k| obj.get(*k).and_then(parse_unix_secs)?.ok());
+        latest = latest.max(ts);
+    });
+    latest
+}
+
 #[cfg(feature = "official-api")]
 pub(crate) fn extract_para_counts_from_stats(stats: &Value) -> serde_json::Map<String, Value> {
     let mut out = serde_json::Map::new();
@@ -248,3 +409,44 @@ pub(crate) fn extract_para_counts_from_stats(stats: &Value) -> serde_json::Map<S
 
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{latest_chapter_time, segment_cache_is_stale};
+    use std::collections::HashSet;
+
+    #[test]
+    fn segment_cache_refresh_interval() {
+        assert!(!segment_cache_is_stale(0, 1_000_000, 0));
+        assert!(segment_cache_is_stale(0, 1_000_000, 1));
+        assert!(!segment_cache_is_stale(1_000_000 - 3599, 1_000_000, 1));
+        assert!(segment_cache_is_stale(1_000_000 - 3600, 1_000_000, 1));
+    }
+
+    #[test]
+    fn latest_chapter_time_prefers_first_pass_time() {
+        let raw = serde_json::json!({
+            "item_data_list": [
+                {"item_id": "1", "first_pass_time": "1700000000", "update_time": 1800000000},
+                {"item_id": "2", "first_pass_time": 1700000500000_i64},
+                {"item_id": "3", "publish_time": "not a time"},
+            ]
+        });
+        assert_eq!(
+            latest_chapter_time(&raw, &HashSet::new()),
+            Some(1_700_000_500)
+        );
+        let only_first: HashSet<String> = ["1".to_string()].into_iter().collect();
+        assert_eq!(latest_chapter_time(&raw, &only_first), Some(1_700_000_000));
+        let none: HashSet<String> = ["3".to_string()].into_iter().collect();
+        assert_eq!(latest_chapter_time(&raw, &none), None);
+    }
+
+    #[cfg(feature = "official-api")]
+    #[test]
+    fn legacy_chapter_cache_counts_as_complete() {
+        let legacy = r#"{"chapter_id":"1","book_id":"2","item_version":"0","top_n":10}"#;
+        let cache: super::SegmentCommentsChapterCache = serde_json::from_str(legacy).unwrap();
+        assert!(cache.details_complete);
+    }
+}
@@ -3,6 +3,7 @@
 #![cfg_attr(not(feature = "official-api"), allow(dead_code))]
 
 use regex::Regex;
+use std::collections::HashMap;
 use std::sync::OnceLock;
 
 use super::html_utils::{escape_html, unescape_basic_entities};
@@ -29,6 +30,11 @@ fn id_attr_regex() -> &'static Regex {
     REGEX.get_or_init(|| Regex::new(r"(?is)\bid\s*=").unwrap())
 }
 
+fn para_or_block_tag_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"(?is)<(/?)(div|aside)\b[^>]*>|<p\b[^>]*>").unwrap())
+}
+
 fn html_tags_regex() -> &'static Regex {
     static REGEX: OnceLock<Regex> = OnceLock::new();
     REGEX.get_or_init(|| Regex::new(r"<[^>]+>").unwrap())
@@ -147,18 +153,117 @@ pub fn extract_para_snippet(chapter_html: &str, target_idx: usize) -> String {
     String::new()
 }
 
-pub fn inject_segment_links(
+/// 段评在 EPUB 中的呈现方式（对应 `Config.segment_comments_style`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentCommentStyle {
+    /// 每章段评汇总为独立页面，正文段尾以计数角标跳转（兼容性最好）。
+    ChapterEnd,
+    /// 段尾为 EPUB3 脚注引用，评论以脚注附在章末，支持的阅读器会弹窗显示。
+    InlineFootnote,
+    /// 评论以引用块形式紧跟在对应段落之后。
+    AfterParagraph,
+}
+
+impl SegmentCommentStyle {
+    pub const ALL: [SegmentCommentStyle; 3] = [
+        SegmentCommentStyle::ChapterEnd,
+        SegmentCommentStyle::InlineFootnote,
+        SegmentCommentStyle::AfterParagraph,
+    ];
+
+    /// 未知取值回退到章末汇总式。
+    pub fn from_config(raw: &str) -> Self {
+        let raw = raw.trim();
+        Self::ALL
+            .into_iter()
+            .find(|s| raw.eq_ignore_ascii_case(s.as_str()) || raw == s.label())
+            .unwrap_or(SegmentCommentStyle::ChapterEnd)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SegmentCommentStyle::ChapterEnd => "chapter_end",
+            SegmentCommentStyle::InlineFootnote => "inline_footnote",
+            SegmentCommentStyle::AfterParagraph => "after_paragraph",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SegmentCommentStyle::ChapterEnd => "章末汇总式",
+            SegmentCommentStyle::InlineFootnote => "行内脚注式",
+            SegmentCommentStyle::AfterParagraph => "段后块式",
+        }
+    }
+
+    /// 是否需要为每章生成独立的段评页。
+    pub fn uses_comment_page(self) -> bool {
+        self == SegmentCommentStyle::ChapterEnd
+    }
+}
+
+/// 段评输出去向（对应 `Config.segment_comments_export_mode`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentCommentExportMode {
+    /// 嵌入 EPUB 正文（默认）。
+    Embed,
+    /// 导出为独立的 Markdown/JSON 文件，EPUB 正文保持纯净。
+    Separate,
+    /// 同时嵌入与导出。
+    Both,
+}
+
+impl SegmentCommentExportMode {
+    pub const ALL: [SegmentCommentExportMode; 3] = [
+        SegmentCommentExportMode::Embed,
+        SegmentCommentExportMode::Separate,
+        SegmentCommentExportMode::Both,
+    ];
+
+    /// 未知取值回退到嵌入。
+    pub fn from_config(raw: &str) -> Self {
+        let raw = raw.trim();
+        Self::ALL
+            .into_iter()
+            .find(|m| raw.eq_ignore_ascii_case(m.as_str()) || raw == m.label())
+            .unwrap_or(SegmentCommentExportMode::Embed)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SegmentCommentExportMode::Embed => "embed",
+            SegmentCommentExportMode::Separate => "separate",
+            SegmentCommentExportMode::Both => "both",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SegmentCommentExportMode::Embed => "嵌入正文",
+            SegmentCommentExportMode::Separate => "单独导出",
+            SegmentCommentExportMode::Both => "嵌入并导出",
+        }
+    }
+
+    /// 是否把段评注入 EPUB 正文。
+    pub fn embeds(self) -> bool {
+        self != SegmentCommentExportMode::Separate
+    }
+
+    /// 是否导出独立的段评文件。
+    pub fn exports(self) -> bool {
+        self != SegmentCommentExportMode::Embed
+    }
+}
+
+/// 按 API 的段落计数方式遍历正文段落并允许改写。
+///
+/// 回调参数依次为：内容段落序号、开标签、段内 HTML、紧跟在 `</p>` 之后追加的内容。
+/// 标题会被移除（EpubGenerator 会自行注入 `<h1>`），非内容段落原样保留且不计数。
+fn rewrite_content_paras(
     content_html: &str,
-    comments_file: &str,
-    seg_counts: &serde_json::Map<String, serde_json::Value>,
+    mut on_para: impl FnMut(usize, &mut String, &mut String, &mut String),
 ) -> String {
-    // Mirror Python logic in `segment_utils.py`:
-    // - iterate <p> in-order with a monotonically increasing idx
-    // - SKIP non-content paragraphs (picture wrappers, volume titles, etc.) to match API counting
-    // - SKIP headings (EpubGenerator already injects <h1> for chapter title)
-    // - if cnt>0 and <p> has no id=, add id="p-{idx}" while preserving other attrs
-    // - append a badge link to the segment comment page
-
     let mut out = String::new();
     let mut last_end = 0usize;
     let mut content_idx = 0usize; // Index for content paragraphs only
@@ -190,33 +295,182 @@ pub fn inject_segment_links(
             continue;
         }
 
-        let cnt = seg_counts
-            .get(&content_idx.to_string())
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0);
+        let mut trailing = String::new();
+        on_para(content_idx, &mut open_tag, &mut inner, &mut trailing);
 
+        out.push_str(&open_tag);
+        out.push_str(&inner);
+        out.push_str(close_tag);
+        out.push_str(&trailing);
+
+        last_end = m.end();
+        content_idx += 1; // Only increment for content paragraphs
+    }
+
+    out.push_str(&content_html[last_end..]);
+    out
+}
+
+fn seg_count_of(seg_counts: &serde_json::Map<String, serde_json::Value>, idx: usize) -> u64 {
+    seg_counts
+        .get(&idx.to_string())
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0)
+}
+
+/// 段落未带 id 时补上 `id="p-{idx}"`，保留其他属性。
+fn ensure_para_id(open_tag: &mut String, idx: usize) {
+    if !id_attr_regex().is_match(open_tag) && open_tag.ends_with('>') {
+        open_tag.pop();
+        open_tag.push_str(&format!(" id=\"p-{}\">", idx));
+    }
+}
+
+pub fn inject_segment_links(
+    content_html: &str,
+    comments_file: &str,
+    seg_counts: &serde_json::Map<String, serde_json::Value>,
+) -> String {
+    // Mirror Python logic in `segment_utils.py`:
+    // - iterate <p> in-order with a monotonically increasing idx
+    // - SKIP non-content paragraphs (picture wrappers, volume titles, etc.) to match API counting
+    // - SKIP headings (EpubGenerator already injects <h1> for chapter title)
+    // - if cnt>0 and <p> has no id=, add id="p-{idx}" while preserving other attrs
+    // - append a badge link to the segment comment page
+    rewrite_content_paras(content_html, |idx, open_tag, inner, _| {
+        let cnt = seg_count_of(seg_counts, idx);
         if cnt > 0 {
-            if !id_attr_regex().is_match(&open_tag) && open_tag.ends_with('>') {
-                open_tag.pop();
-                open_tag.push_str(&format!(" id=\"p-{}\">", content_idx));
-            }
+            ensure_para_id(open_tag, idx);
             inner.push_str(&format!(
                 " <a class=\"seg-count\" href=\"{}#para-{}\" title=\"查看本段评论\">({})</a>",
                 html_escape_attr(comments_file),
-                content_idx,
+                idx,
                 cnt
             ));
         }
+    })
+}
 
-        out.push_str(&open_tag);
-        out.push_str(&inner);
-        out.push_str(close_tag);
+/// 将已渲染好的段评块直接注入正文（行内脚注式 / 段后块式）。
+///
+/// `blocks` 以内容段落序号为键；没有对应块的段落不做改动。
+pub fn inject_segment_blocks(
+    content_html: &str,
+    seg_counts: &serde_json::Map<String, serde_json::Value>,
+    blocks: &HashMap<usize, String>,
+    style: SegmentCommentStyle,
+) -> String {
+    let mut footnotes = String::new();
+    let mut out = rewrite_content_paras(content_html, |idx, open_tag, inner, trailing| {
+        let Some(block) = blocks.get(&idx) else {
+            return;
+        };
+        ensure_para_id(open_tag, idx);
+        let cnt = seg_count_of(seg_counts, idx);
+        match style {
+            SegmentCommentStyle::InlineFootnote => {
+                inner.push_str(&format!(
+                    " <a class=\"seg-count\" epub:type=\"noteref\" href=\"#para-{}\">({})</a>",
+                    idx, cnt
+                ));
+                footnotes.push_str(&format!(
+                    "<aside class=\"seg-footnote\" epub:type=\"footnote\" id=\"para-{}\">{}</aside>\n",
+                    idx, block
+                ));
+            }
+            SegmentCommentStyle::AfterParagraph => {
+                trailing.push_str(&format!(
+                    "<div class=\"seg-block\" id=\"para-{}\">{}</div>",
+                    idx, block
+                ));
+            }
+            SegmentCommentStyle::ChapterEnd => {}
+        }
+    });
+    if !footnotes.is_empty() {
+        out.push('\n');
+        out.push_str(&footnotes);
+    }
+    out
+}
+
+/// 按段评计数方式提取正文段落的纯文本，下标即段评缓存中的段落序号。
+pub fn content_paragraph_texts(content_html: &str) -> Vec<String> {
+    let mut paras = Vec::new();
+    rewrite_content_paras(content_html, |_, _, inner, _| {
+        paras.push(strip_tags(inner).trim().to_string());
+    });
+    paras
+}
+
+/// 将章节 ID 规整为合法的 XML id 片段（仅保留字母数字、`-`、`_`）。
+pub fn stable_id_fragment(chapter_id: &str) -> String {
+    chapter_id
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
 
+/// 为正文段落补上稳定唯一的 id：`p{chapter_id}_{idx}`，用于阅读器同步阅读进度。
+///
+/// idx 为顶层 `<p>` 的序号，段评块（`seg-block` / `seg-footnote`）内部的段落不计数；
+/// 已带 id 的段落（如段评锚点 `p-N`）保持原样但仍占用序号，保证同一内容重新生成时 id 不变。
+pub fn assign_stable_para_ids(html: &str, chapter_id: &str) -> String {
+    let fragment = stable_id_fragment(chapter_id);
+    let mut out = String::with_capacity(html.len() + html.len() / 8);
+    let mut last_end = 0usize;
+    let mut para_idx = 0usize;
+    let mut block_depth = 0usize;
+
+    for caps in para_or_block_tag_regex().captures_iter(html) {
+        let m = caps.get(0).unwrap();
+        let tag = m.as_str();
+        if caps.get(2).is_some() {
+            let closing = caps.get(1).is_some_and(|c| !c.as_str().is_empty());
+            if block_depth > 0 {
+                if closing {
+                    block_depth -= 1;
+                } else if !tag.ends_with("/>") {
+                    block_depth += 1;
+                }
+            } else if !closing
+                && class_attr_regex().captures(tag).is_some_and(|c| {
+                    c[1].split_whitespace()
+                        .any(|cls| cls == "seg-block" || cls == "seg-footnote")
+                })
+            {
+                block_depth = 1;
+            }
+            continue;
+        }
+        if block_depth > 0 {
+            continue;
+        }
+
+        out.push_str(&html[last_end..m.start()]);
+        if id_attr_regex().is_match(tag) || !tag.ends_with('>') {
+            out.push_str(tag);
+        } else {
+            let (head, close) = match tag.strip_suffix("/>") {
+                Some(head) => (head.trim_end(), "/>"),
+                None => (&tag[..tag.len() - 1], ">"),
+            };
+            out.push_str(&format!(
+                "{} id=\"p{}_{}\"{}",
+                head, fragment, para_idx, close
+            ));
+        }
         last_end = m.end();
-        content_idx += 1; // Only increment for content paragraphs
+        para_idx += 1;
     }
-
-    out.push_str(&content_html[last_end..]);
+    out.push_str(&html[last_end..]);
     out
 }
 
@@ -305,8 +559,12 @@ fn emoji_map() -> std::collections::HashMap<&'static str, String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{extract_para_snippet, inject_segment_links};
+    use super::{
+        SegmentCommentStyle, assign_stable_para_ids, extract_para_snippet, inject_segment_blocks,
+        inject_segment_links,
+    };
     use serde_json::json;
+    use std::collections::HashMap;
 
     #[test]
     fn extract_para_snippet_decodes_html_entities() {
@@ -333,4 +591,57 @@ mod tests {
         assert!(!out.contains("&amp;#34;"));
         assert!(!out.contains("&amp;#39;"));
     }
+
+    #[test]
+    fn inject_segment_blocks_follows_style() {
+        let mut seg_counts = serde_json::Map::new();
+        seg_counts.insert("1".to_string(), json!(2));
+        let blocks = HashMap::from([(1usize, "<ol><li>评论</li></ol>".to_string())]);
+        let html = "<h1>标题</h1><p>第一段</p><p>第二段</p>";
+
+        let out = inject_segment_blocks(
+            html,
+            &seg_counts,
+            &blocks,
+            SegmentCommentStyle::AfterParagraph,
+        );
+        assert!(!out.contains("<h1>"));
+        assert!(out.contains(
+            r#"<p id="p-1">第二段</p><div class="seg-block" id="para-1"><ol><li>评论</li></ol></div>"#
+        ));
+
+        let out = inject_segment_blocks(
+            html,
+            &seg_counts,
+            &blocks,
+            SegmentCommentStyle::InlineFootnote,
+        );
+        assert!(out.contains(r##"epub:type="noteref" href="#para-1">(2)</a></p>"##));
+        assert!(out.trim_end().ends_with("</aside>"));
+
+        assert_eq!(
+            SegmentCommentStyle::from_config("段后块式"),
+            SegmentCommentStyle::AfterParagraph
+        );
+        assert_eq!(
+            SegmentCommentStyle::from_config("unknown"),
+            SegmentCommentStyle::ChapterEnd
+        );
+    }
+
+    #[test]
+    fn assign_stable_para_ids_skips_comment_blocks() {
+        let html = concat!(
+            r#"<p class="no-indent">一</p><p id="p-1">二</p>"#,
+            r#"<div class="seg-block" id="para-1"><div><p>评论</p></div></div>"#,
+            r#"<p>三</p><aside class="seg-footnote" epub:type="footnote"><p>注</p></aside>"#
+        );
+        let out = assign_stable_para_ids(html, "7143:01");
+        assert!(out.contains(r#"<p class="no-indent" id="p7143_01_0">一</p>"#));
+        assert!(out.contains(r#"<p id="p-1">二</p>"#));
+        assert!(out.contains("<div><p>评论</p></div>"));
+        assert!(out.contains(r#"<p id="p7143_01_2">三</p>"#));
+        assert!(out.contains("<p>注</p>"));
+        assert_eq!(assign_stable_para_ids(html, "7143:01"), out);
+    }
 }
@@ -0,0 +1,157 @@
+//! 正文简繁转换（`text_conversion`：none / s2t / t2s）。
+//!
+//! 词表为 OpenCC 格式（`简体<TAB>繁体候选…`）：单字表给出默认映射，词组表纠正
+//! 一简对多繁的情形。s2t 取第一个候选，t2s 把所有候选反查回简体。两表合并后
+//! 按最长匹配一次扫描，只作用于标签之外的文本；词表在首次使用时构建。
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use serde_json::Value;
+use tracing::warn;
+
+use super::text_replace::TextReplacer;
+
+const ST_CHARACTERS: &str = include_str!("opencc/STCharacters.txt");
+const ST_PHRASES: &str = include_str!("opencc/STPhrases.txt");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextConversion {
+    None,
+    /// 简体转繁体
+    S2T,
+    /// 繁体转简体
+    T2S,
+}
+
+impl TextConversion {
+    pub const ALL: [TextConversion; 3] = [
+        TextConversion::None,
+        TextConversion::S2T,
+        TextConversion::T2S,
+    ];
+
+    /// 未知取值视为不转换。
+    pub fn from_config(raw: &str) -> Self {
+        let raw = raw.trim();
+        Self::ALL
+            .into_iter()
+            .find(|c| raw.eq_ignore_ascii_case(c.as_str()) || raw == c.label())
+            .unwrap_or(TextConversion::None)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TextConversion::None => "none",
+            TextConversion::S2T => "s2t",
+            TextConversion::T2S => "t2s",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TextConversion::None => "不转换",
+            TextConversion::S2T => "简体转繁体",
+            TextConversion::T2S => "繁体转简体",
+        }
+    }
+
+    /// 对应方向的转换器；`None` 不构建词表。
+    pub fn converter(self) -> Option<&'static TextReplacer> {
+        static S2T: OnceLock<Option<TextReplacer>> = OnceLock::new();
+        static T2S: OnceLock<Option<TextReplacer>> = OnceLock::new();
+        let cell = match self {
+            TextConversion::None => return None,
+            TextConversion::S2T => &S2T,
+            TextConversion::T2S => &T2S,
+        };
+        cell.get_or_init(|| build_converter(self)).as_ref()
+    }
+}
+
+/// 解析 OpenCC 词表行：`key<TAB>候选1 候选2…`，`#` 开头为注释。
+fn dict_entries(text: &str) -> impl Iterator<Item = (&str, Vec<&str>)> {
+    text.lines()
+        .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+        .filter_map(|l| {
+            let (key, values) = l.split_once('\t')?;
+            let values: Vec<&str> = values.split_whitespace().collect();
+            (!values.is_empty()).then_some((key.trim(), values))
+        })
+}
+
+fn build_converter(conversion: TextConversion) -> Option<TextReplacer> {
+    let mut pairs = Vec::new();
+    let mut seen = HashSet::new();
+    for (simp, trads) in dict_entries(ST_PHRASES).chain(dict_entries(ST_CHARACTERS)) {
+        match conversion {
+            TextConversion::S2T => pairs.push((simp.to_string(), trads[0].to_string())),
+            TextConversion::T2S => {
+                // 原样条目（如“乾坤”）也要保留，才能挡住单字回退
+                for trad in trads {
+                    if seen.insert(trad) {
+                        pairs.push((trad.to_string(), simp.to_string()));
+                    }
+                }
+            }
+            TextConversion::None => return None,
+        }
+    }
+    match TextReplacer::from_pairs(pairs) {
+        Ok(replacer) => Some(replacer),
+        Err(e) => {
+            warn!(target: "book_manager", error = ?e, "简繁转换词表构建失败，已跳过");
+            None
+        }
+    }
+}
+
+/// 返回标题与正文均已转换的章节副本。
+pub fn convert_chapters(converter: &TextReplacer, chapters: &[Value]) -> Vec<Value> {
+    chapters
+        .iter()
+        .map(|ch| {
+            let mut ch = ch.clone();
+            if let Some(obj) = ch.as_object_mut() {
+                if let Some(title) = obj.get("title").and_then(Value::as_str) {
+                    let converted = converter.apply_text(title);
+                    obj.insert("title".to_string(), Value::String(converted));
+                }
+                if let Some(content) = obj.get("content").and_then(Value::as_str) {
+                    let converted = converter.apply_xhtml(content);
+                    obj.insert("content".to_string(), Value::String(converted));
+                }
+            }
+            ch
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_both_directions_with_phrase_overrides() {
+        assert!(TextConversion::from_config("none").converter().is_none());
+        assert_eq!(TextConversion::from_config("S2T"), TextConversion::S2T);
+        assert_eq!(TextConversion::from_config("乱写"), TextConversion::None);
+
+        let s2t = TextConversion::S2T.converter().unwrap();
+        assert_eq!(
+            s2t.apply_xhtml(r#"<p class="x">后来她的头发很干净，皇后说话了</p>"#),
+            r#"<p class="x">後來她的頭髮很乾淨，皇后說話了</p>"#
+        );
+
+        let t2s = TextConversion::T2S.converter().unwrap();
+        assert_eq!(
+            t2s.apply_text("後來她的頭髮很乾淨，乾坤"),
+            "后来她的头发很干净，乾坤"
+        );
+
+        let chapters = vec![serde_json::json!({"title": "第一章 发现", "content": "<p>这里</p>"})];
+        let out = convert_chapters(s2t, &chapters);
+        assert_eq!(out[0]["title"], "第一章 發現");
+        assert_eq!(out[0]["content"], "<p>這裏</p>");
+    }
+}
@@ -0,0 +1,246 @@
+//! 正文自定义替换表：修正 OCR 错字、还原被替换的敏感字。
+//!
+//! 表文件为 JSON 数组，每项 `{"from": "...", "to": "...", "regex": false}`；
+//! 也接受 `{"错字": "正字"}` 形式的纯文本对照。
+//!
+//! 纯文本条目合并为一个 Aho-Corasick 自动机一次扫描（最长匹配优先），
+//! 之后按文件顺序依次应用正则条目。替换只作用于标签之外的文本，不会破坏 XHTML 结构。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use aho_corasick::{AhoCorasick, MatchKind};
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::base_system::context::Config;
+
+/// 未配置 `replacements_path` 时，在工作目录（与 config.yml 同目录）查找该文件。
+pub const DEFAULT_REPLACEMENTS_FILE: &str = "replacements.json";
+
+#[derive(Debug, Deserialize)]
+struct RawEntry {
+    from: String,
+    #[serde(default)]
+    to: String,
+    #[serde(default)]
+    regex: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawTable {
+    List(Vec<RawEntry>),
+    Map(BTreeMap<String, String>),
+}
+
+pub struct TextReplacer {
+    plain: Option<(AhoCorasick, Vec<String>)>,
+    regexes: Vec<(Regex, String)>,
+}
+
+fn tag_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"<[^>]*>").expect("compile tag regex"))
+}
+
+/// 文本节点转义（不转义引号，与正文缓存保持一致）。
+fn escape_text(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn build_plain(
+    patterns: Vec<String>,
+    replacements: Vec<String>,
+) -> Result<Option<(AhoCorasick, Vec<String>)>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let ac = AhoCorasick::builder()
+        .match_kind(MatchKind::LeftmostLongest)
+        .build(&patterns)
+        .context("构建替换表自动机失败")?;
+    Ok(Some((ac, replacements)))
+}
+
+impl TextReplacer {
+    pub fn from_json(text: &str) -> Result<Self> {
+        let table: RawTable = serde_json::from_str(text).context("解析替换表 JSON 失败")?;
+        let entries = match table {
+            RawTable::List(list) => list,
+            RawTable::Map(map) => map
+                .into_iter()
+                .map(|(from, to)| RawEntry {
+                    from,
+                    to,
+                    regex: false,
+                })
+                .collect(),
+        };
+
+        let mut patterns = Vec::new();
+        let mut replacements = Vec::new();
+        let mut regexes = Vec::new();
+        for entry in entries {
+            if entry.from.is_empty() {
+                continue;
+            }
+            if entry.regex {
+                let re = Regex::new(&entry.from)
+                    .with_context(|| format!("替换表正则无效: {}", entry.from))?;
+                regexes.push((re, entry.to));
+            } else {
+                // 正文缓存为 XHTML，纯文本条目按转义后的形式匹配与写回。
+                patterns.push(escape_text(&entry.from));
+                replacements.push(escape_text(&entry.to));
+            }
+        }
+
+        let plain = build_plain(patterns, replacements)?;
+        Ok(Self { plain, regexes })
+    }
+
+    /// 由纯文本对照表构建（最长匹配优先），供内置词表复用。
+    pub(crate) fn from_pairs<I>(pairs: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let (patterns, replacements): (Vec<String>, Vec<String>) = pairs
+            .into_iter()
+            .filter(|(from, _)| !from.is_empty())
+            .map(|(from, to)| (escape_text(&from), escape_text(&to)))
+            .unzip();
+        let plain = build_plain(patterns, replacements)?;
+        Ok(Self {
+            plain,
+            regexes: Vec::new(),
+        })
+    }
+
+    /// 读取替换表；文件不存在时返回 `Ok(None)`。
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(t) => t,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| format!("读取替换表失败: {}", path.display()));
+            }
+        };
+        let replacer = Self::from_json(&text)?;
+        Ok((!replacer.is_empty()).then_some(replacer))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plain.is_none() && self.regexes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.plain.as_ref().map(|(_, r)| r.len()).unwrap_or(0) + self.regexes.len()
+    }
+
+    /// 替换一段不含标签的文本。
+    pub fn apply_text(&self, text: &str) -> String {
+        let mut out = match &self.plain {
+            Some((ac, replacements)) => ac.replace_all(text, replacements),
+            None => text.to_string(),
+        };
+        for (re, to) in &self.regexes {
+            if let std::borrow::Cow::Owned(replaced) = re.replace_all(&out, to.as_str()) {
+                out = replaced;
+            }
+        }
+        out
+    }
+
+    /// 仅替换 XHTML 中标签之间的文本。
+    pub fn apply_xhtml(&self, html: &str) -> String {
+        let mut out = String::with_capacity(html.len());
+        let mut last = 0;
+        for tag in tag_regex().find_iter(html) {
+            out.push_str(&self.apply_text(&html[last..tag.start()]));
+            out.push_str(tag.as_str());
+            last = tag.end();
+        }
+        out.push_str(&self.apply_text(&html[last..]));
+        out
+    }
+
+    /// 返回替换 `content` 后的章节副本。
+    pub fn apply_chapters(&self, chapters: &[Value]) -> Vec<Value> {
+        chapters
+            .iter()
+            .map(|ch| {
+                let mut ch = ch.clone();
+                if let Some(obj) = ch.as_object_mut()
+                    && let Some(content) = obj.get("content").and_then(Value::as_str)
+                {
+                    let replaced = self.apply_xhtml(content);
+                    obj.insert("content".to_string(), Value::String(replaced));
+                }
+                ch
+            })
+            .collect()
+    }
+}
+
+pub fn replacements_path(cfg: &Config) -> PathBuf {
+    let configured = cfg.replacements_path.trim();
+    if configured.is_empty() {
+        PathBuf::from(DEFAULT_REPLACEMENTS_FILE)
+    } else {
+        PathBuf::from(configured)
+    }
+}
+
+/// 按配置加载替换表；未配置或加载失败时返回 `None`（失败会记录警告，不影响生成）。
+pub fn load_for_config(cfg: &Config) -> Option<TextReplacer> {
+    let path = replacements_path(cfg);
+    match TextReplacer::load(&path) {
+        Ok(Some(replacer)) => {
+            info!(
+                target: "book_manager",
+                path = %path.display(),
+                entries = replacer.len(),
+                "已加载正文替换表"
+            );
+            Some(replacer)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            warn!(target: "book_manager", path = %path.display(), error = ?e, "正文替换表加载失败，已跳过");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextReplacer;
+
+    #[test]
+    fn replaces_plain_then_regex_outside_tags() {
+        let replacer = TextReplacer::from_json(
+            r#"[
+                {"from": "口口", "to": "成人"},
+                {"from": "口", "to": "X"},
+                {"from": "第(\\d+)张", "to": "第${1}章", "regex": true},
+                {"from": "p", "to": "P"}
+            ]"#,
+        )
+        .unwrap();
+        let html = r#"<p class="a">口口和口，见第3张</p>"#;
+        assert_eq!(
+            replacer.apply_xhtml(html),
+            r#"<p class="a">成人和X，见第3章</p>"#
+        );
+
+        let map = TextReplacer::from_json(r#"{"A&B": "C"}"#).unwrap();
+        assert_eq!(map.apply_xhtml("<p>A&amp;B</p>"), "<p>C</p>");
+    }
+}
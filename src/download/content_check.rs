@@ -0,0 +1,144 @@
+//! 章节正文长度异常检测：找出疑似缺失或被截断的章节。
+//!
+//! 以本次选中且下载成功章节的平均字数为基准，字数为 0 或低于
+//! `chapter_length_alert_ratio`% 的章节会被告警，并导出到 [`SUSPICIOUS_CHAPTERS_FILE`]。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tracing::{info, warn};
+
+use crate::book_parser::book_manager::BookManager;
+
+use super::models::ChapterRef;
+
+pub const SUSPICIOUS_CHAPTERS_FILE: &str = "suspicious_chapters.txt";
+/// 告警日志中最多逐条列出的章节数，其余见导出文件。
+const MAX_LOGGED: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuspiciousChapter {
+    /// 章节在本次选中范围内的序号（从 0 开始）
+    pub index: usize,
+    pub id: String,
+    pub title: String,
+    pub chars: usize,
+}
+
+/// 按字数筛出异常章节；`ratio` 为平均字数的百分比，0 表示关闭检测。
+pub fn find_suspicious(chapters: &[(String, String, usize)], ratio: u8) -> Vec<SuspiciousChapter> {
+    if ratio == 0 || chapters.is_empty() {
+        return Vec::new();
+    }
+    let total: usize = chapters.iter().map(|(_, _, n)| *n).sum();
+    let threshold = total * ratio as usize / (chapters.len() * 100);
+    chapters
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, _, n))| *n == 0 || *n < threshold)
+        .map(|(index, (id, title, n))| SuspiciousChapter {
+            index,
+            id: id.clone(),
+            title: title.clone(),
+            chars: *n,
+        })
+        .collect()
+}
+
+/// 检测本次选中章节并输出告警；无异常时删除旧的导出文件。
+pub(crate) fn report_suspicious_chapters(manager: &BookManager, chosen: &[ChapterRef]) {
+    let ratio = manager.config.chapter_length_alert_ratio;
+    if ratio == 0 {
+        return;
+    }
+    let chapters: Vec<(String, String, usize)> = chosen
+        .iter()
+        .filter_map(|ch| {
+            let (_, content) = manager.downloaded.get(&ch.id)?;
+            let content = content.as_deref()?;
+            Some((
+                ch.id.clone(),
+                ch.title.clone(),
+                super::downloader::count_text_chars(content),
+            ))
+        })
+        .collect();
+    let suspicious = find_suspicious(&chapters, ratio);
+
+    let folder = manager.book_folder();
+    if !folder.exists() {
+        return;
+    }
+    match export_suspicious_list(folder, &manager.book_id, &suspicious) {
+        Ok(Some(path)) => {
+            warn!(
+                target: "download",
+                count = suspicious.len(),
+                path = %path.display(),
+                "检测到字数异常章节，可能缺失或被截断"
+            );
+            for ch in suspicious.iter().take(MAX_LOGGED) {
+                warn!(
+                    target: "download",
+                    "  第 {} 章 {}：{} 字",
+                    ch.index + 1,
+                    ch.title,
+                    ch.chars
+                );
+            }
+            if suspicious.len() > MAX_LOGGED {
+                info!(
+                    target: "download",
+                    "  …… 另有 {} 章，详见导出文件",
+                    suspicious.len() - MAX_LOGGED
+                );
+            }
+        }
+        Ok(None) => {}
+        Err(e) => warn!(target: "download", error = %e, "导出字数异常章节列表失败"),
+    }
+}
+
+/// 写入 [`SUSPICIOUS_CHAPTERS_FILE`]（每行 `章节ID<TAB>标题<TAB>字数`）。
+fn export_suspicious_list(
+    folder: &Path,
+    book_id: &str,
+    suspicious: &[SuspiciousChapter],
+) -> Result<Option<PathBuf>> {
+    let path = folder.join(SUSPICIOUS_CHAPTERS_FILE);
+    if suspicious.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path).with_context(|| format!("删除 {}", path.display()))?;
+        }
+        return Ok(None);
+    }
+    let mut out = format!("# book_id: {}，字数异常 {} 章\n", book_id, suspicious.len());
+    for ch in suspicious {
+        out.push_str(&format!("{}\t{}\t{}\n", ch.id, ch.title, ch.chars));
+    }
+    fs::write(&path, out).with_context(|| format!("写入 {}", path.display()))?;
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_suspicious;
+
+    fn ch(id: &str, chars: usize) -> (String, String, usize) {
+        (id.to_string(), format!("第{id}章"), chars)
+    }
+
+    #[test]
+    fn flags_empty_and_short_chapters() {
+        let chapters = vec![ch("1", 3000), ch("2", 0), ch("3", 3000), ch("4", 200)];
+        // 平均 1550，20% 阈值为 310
+        let hits = find_suspicious(&chapters, 20);
+        let ids: Vec<&str> = hits.iter().map(|h| h.id.as_str()).collect();
+        assert_eq!(ids, ["2", "4"]);
+        assert_eq!(hits[1].index, 3);
+
+        assert!(find_suspicious(&chapters, 0).is_empty());
+        assert!(find_suspicious(&[ch("1", 10), ch("2", 12)], 20).is_empty());
+    }
+}
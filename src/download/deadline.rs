@@ -0,0 +1,99 @@
+//! 单本下载总超时。
+//!
+//! 沿用取消标记的传递方式：下载期间向下游传一个内部标记，由看门狗线程
+//! 同步用户取消信号，并在到期时置位。暂停期间不计时。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use super::pause;
+
+const DEADLINE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+pub(crate) struct DownloadDeadline {
+    cancel: Option<Arc<AtomicBool>>,
+    timed_out: Arc<AtomicBool>,
+    done: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl DownloadDeadline {
+    /// `timeout_secs` 为 0 时不启动看门狗，直接透传用户取消标记。
+    pub(crate) fn start(timeout_secs: u64, user_cancel: Option<Arc<AtomicBool>>) -> Self {
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let done = Arc::new(AtomicBool::new(false));
+        if timeout_secs == 0 {
+            return Self {
+                cancel: user_cancel,
+                timed_out,
+                done,
+                handle: None,
+            };
+        }
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let limit = Duration::from_secs(timeout_secs);
+        let handle = {
+            let cancel = Arc::clone(&cancel);
+            let timed_out = Arc::clone(&timed_out);
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                let mut elapsed = Duration::ZERO;
+                let mut last = Instant::now();
+                while !done.load(Ordering::Relaxed) {
+                    std::thread::sleep(DEADLINE_POLL_INTERVAL);
+                    let now = Instant::now();
+                    if !pause::is_paused() {
+                        elapsed += now - last;
+                    }
+                    last = now;
+
+                    if user_cancel
+                        .as_ref()
+                        .is_some_and(|c| c.load(Ordering::Relaxed))
+                    {
+                        cancel.store(true, Ordering::Relaxed);
+                        return;
+                    }
+                    if elapsed >= limit {
+                        timed_out.store(true, Ordering::Relaxed);
+                        cancel.store(true, Ordering::Relaxed);
+                        return;
+                    }
+                }
+            })
+        };
+
+        Self {
+            cancel: Some(cancel),
+            timed_out,
+            done,
+            handle: Some(handle),
+        }
+    }
+
+    /// 传给下载流程的取消标记（用户取消或超时都会置位）。
+    pub(crate) fn cancel_flag(&self) -> Option<&Arc<AtomicBool>> {
+        self.cancel.as_ref()
+    }
+
+    pub(crate) fn timed_out(&self) -> bool {
+        self.timed_out.load(Ordering::Relaxed)
+    }
+
+    /// 停止看门狗；之后不会再因超时置位。
+    pub(crate) fn stop(&mut self) {
+        self.done.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for DownloadDeadline {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
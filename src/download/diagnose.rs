@@ -0,0 +1,148 @@
+//! 试运行诊断（`--dry-run`）：依次检查 book_id 解析、目录、endpoint 与首章正文，只读不写。
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+
+use super::models::BookMeta;
+use super::plan::prepare_download_plan_dry;
+use super::third_party::validate_endpoints;
+use super::trial::trial_read;
+use crate::base_system::book_id::resolve_book_id;
+use crate::base_system::context::Config;
+
+#[derive(Debug, Clone)]
+pub struct DiagnoseStep {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+    pub elapsed: Duration,
+}
+
+/// 执行一项检查并计时；成功时用 `describe` 生成说明，失败时记录完整错误链。
+fn timed<T>(
+    name: &'static str,
+    f: impl FnOnce() -> Result<T>,
+    describe: impl FnOnce(&T) -> String,
+) -> (Option<T>, DiagnoseStep) {
+    let started = Instant::now();
+    let result = f();
+    let elapsed = started.elapsed();
+    let (value, ok, detail) = match result {
+        Ok(v) => {
+            let detail = describe(&v);
+            (Some(v), true, detail)
+        }
+        Err(e) => (None, false, format!("{e:#}")),
+    };
+    (
+        value,
+        DiagnoseStep {
+            name,
+            ok,
+            detail,
+            elapsed,
+        },
+    )
+}
+
+/// 逐项执行诊断，每完成一项回调一次；前置步骤失败时后续依赖它的步骤不再执行。
+pub fn run_diagnose(
+    config: &Config,
+    input: &str,
+    mut on_step: impl FnMut(&DiagnoseStep),
+) -> Vec<DiagnoseStep> {
+    let mut steps = Vec::new();
+    let mut finish = |step: DiagnoseStep| {
+        on_step(&step);
+        steps.push(step);
+    };
+
+    let (book_id, step) = timed(
+        "解析 book_id",
+        || {
+            resolve_book_id(input)
+                .ok_or_else(|| anyhow!("无法从输入中解析 book_id：{}", input.trim()))
+        },
+        |id| id.clone(),
+    );
+    finish(step);
+    let Some(book_id) = book_id else {
+        return steps;
+    };
+
+    let (plan, step) = timed(
+        "拉取目录",
+        || {
+            let plan = prepare_download_plan_dry(config, &book_id, BookMeta::default())?;
+            if plan.chapters.is_empty() {
+                return Err(anyhow!("目录为空"));
+            }
+            Ok(plan)
+        },
+        |plan| {
+            format!(
+                "《{}》共 {} 章",
+                plan.meta.book_name.as_deref().unwrap_or(&book_id),
+                plan.chapters.len()
+            )
+        },
+    );
+    finish(step);
+    let Some(first) = plan.as_ref().and_then(|p| p.chapters.first()) else {
+        return steps;
+    };
+
+    if cfg!(feature = "official-api") && config.use_official_api {
+        finish(DiagnoseStep {
+            name: "探测 endpoint",
+            ok: true,
+            detail: "官方 API 模式，无需探测".to_string(),
+            elapsed: Duration::ZERO,
+        });
+    } else {
+        let total = config
+            .api_endpoints
+            .iter()
+            .filter(|s| !s.trim().is_empty())
+            .count();
+        let (_, step) = timed(
+            "探测 endpoint",
+            || {
+                let ok = validate_endpoints(config, &first.id);
+                if total == 0 {
+                    Err(anyhow!("api_endpoints 为空"))
+                } else if ok.is_empty() {
+                    Err(anyhow!("全部 {total} 个 endpoint 均未返回正文"))
+                } else {
+                    Ok(ok)
+                }
+            },
+            |ok| format!("可用 {}/{}：{}", ok.len(), total, ok.join(", ")),
+        );
+        finish(step);
+    }
+
+    let (_, step) = timed(
+        "首章正文",
+        || trial_read(config, &book_id, first),
+        |t| format!("《{}》{} 字（来源 {}）", t.title, t.chars, t.source),
+    );
+    finish(step);
+
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stops_when_book_id_unresolvable() {
+        let mut seen = Vec::new();
+        let steps = run_diagnose(&Config::default(), "不是链接", |s| seen.push(s.name));
+        assert_eq!(steps.len(), 1);
+        assert!(!steps[0].ok);
+        assert_eq!(seen, ["解析 book_id"]);
+    }
+}
@@ -7,7 +7,6 @@ use std::collections::HashSet;
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
-#[cfg(feature = "official-api")]
 use std::time::Instant;
 
 #[cfg(feature = "official-api")]
@@ -18,19 +17,37 @@ use serde_json::{Map, Value, json};
 use tracing::{debug, error, info, warn};
 
 use crate::base_system::book_paths;
-use crate::base_system::context::Config;
+use crate::base_system::context::{Config, MAX_BATCH_SIZE};
 #[cfg(feature = "official-api")]
 use crate::base_system::cooldown_retry::fetch_with_cooldown_retry;
 use crate::base_system::download_history::{DownloadHistoryRecord, append_download_history};
+use crate::base_system::download_stats::{
+    DownloadRunSummary, EndpointTotals, record_download_stats,
+};
+use crate::base_system::file_cleaner;
+use crate::base_system::json_extract;
+use crate::base_system::notify;
+use crate::base_system::novel_updates;
+#[cfg(feature = "official-api")]
+use crate::base_system::request_error::{FailurePhase, RequestErrorKind, TimeoutRetryStrategy};
+use crate::base_system::request_trace;
 use crate::book_parser::book_manager::BookManager;
 use crate::book_parser::finalize_utils;
 use crate::book_parser::parser::ContentParser;
+use crate::third_party::bandwidth;
 
+use super::content_check;
+use super::deadline::DownloadDeadline;
+use super::modified::detect_modified_chapters;
+use super::network_wait::retry_after_network_recovery;
+use super::pause::wait_while_paused;
 use super::progress::{make_reporter, segment_enabled};
 use super::segment_pool::{
     SegmentCommentPool, count_segment_comment_cache_files, extract_item_version_map,
 };
-use super::third_party::{fetch_group_third_party, validate_endpoints};
+use super::third_party::{
+    endpoint_stats_snapshot, fetch_group_third_party, reset_endpoint_stats, validate_endpoints,
+};
 
 #[cfg(feature = "official-api")]
 use tomato_novel_official_api::{ContentFetchReport, FanqieClient};
@@ -40,11 +57,11 @@ use std::sync::atomic::AtomicBool;
 // ── 向后兼容重导出（外部代码通过 download::downloader::Xxx 引用）──
 pub use super::models::{
     BookMeta, BookNameAsker, BookNameOption, ChapterRange, ChapterRef, DownloadFlowOptions,
-    DownloadMode, DownloadPlan, DownloadResult, FormatAsker, ProgressSnapshot, RetryFailed,
-    SavePhase,
+    DownloadMode, DownloadPlan, DownloadResult, FormatAsker, ModifiedAsker, ProgressSnapshot,
+    RetryFailed, SavePhase, VolumeInfo, strip_volume_prefix, volume_range,
 };
-pub(crate) use super::plan::apply_range;
 pub use super::plan::prepare_download_plan;
+pub(crate) use super::plan::{apply_range, apply_selection, apply_title_filter};
 pub(crate) use super::progress::ProgressReporter;
 
 // ── ChapterDownloader（官方 API 批量下载）──────────────────────
@@ -54,6 +71,7 @@ pub struct ChapterDownloader {
     book_id: String,
     client: FanqieClient,
     config: Config,
+    paid_chapters: HashSet<String>,
 }
 
 #[cfg(feature = "official-api")]
@@ -110,10 +128,17 @@ impl ChapterDownloader {
             book_id: book_id.to_string(),
             client,
             config,
+            paid_chapters: HashSet::new(),
         }
     }
 
-    /// 下载一批章节，使用官方批量接口，每批动态分组 15~25 章。
+    /// 设置目录中标注为付费的章节；这些章节返回空内容时直接跳过，不再重试。
+    pub fn with_paid_chapters(mut self, paid_chapters: HashSet<String>) -> Self {
+        self.paid_chapters = paid_chapters;
+        self
+    }
+
+    /// 下载一批章节，使用官方批量接口，按 `batch_size` 动态均衡分组。
     pub fn download_book(
         &self,
         manager: &mut BookManager,
@@ -130,7 +155,7 @@ impl ChapterDownloader {
         let start = Instant::now();
         info!("开始下载：{} ({} 章)", book_name, chapters.len());
 
-        let groups = build_dynamic_chapter_groups(chapters);
+        let groups = build_dynamic_chapter_groups(chapters, self.config.effective_batch_size());
         let total_groups = groups.len() as u64;
         let total_chapters = chapters.len() as u64;
         let mut saved_in_job: u64 = 0;
@@ -152,21 +177,26 @@ impl ChapterDownloader {
 
         let mut result = DownloadResult::default();
         let mut deferred_retry: Vec<DeferredChapter> = Vec::new();
-        let epub_mode = self.config.novel_format == "epub";
+        let epub_mode = self.config.wants_epub();
+        let retry_strategy = TimeoutRetryStrategy::from_config(&self.config.timeout_retry_strategy);
 
         if worker_count <= 1 {
             for (group_idx, group) in groups.iter().enumerate() {
+                wait_while_paused(cancel);
                 if cancel.map(|c| c.load(Ordering::Relaxed)).unwrap_or(false) {
                     info!(target: "download", "收到停止信号，结束任务");
                     return Err(anyhow!("用户停止下载"));
                 }
 
-                let outcome = match fetch_group_best_effort(
-                    &self.client,
-                    group,
-                    epub_mode,
-                    Some(&self.book_id),
-                ) {
+                let outcome = match retry_after_network_recovery(&self.config, cancel, || {
+                    fetch_group_best_effort(
+                        &self.client,
+                        group,
+                        epub_mode,
+                        Some(&self.book_id),
+                        retry_strategy,
+                    )
+                }) {
                     Ok(v) => v,
                     Err(err) => {
                         let reason = err.to_string();
@@ -236,6 +266,14 @@ impl ChapterDownloader {
                                 );
                             }
                         }
+                        _ if self.paid_chapters.contains(&ch.id) => {
+                            skip_paid_chapter(manager, ch, &mut result);
+                            if let Some(bar) = save_bar.as_ref() {
+                                bar.inc(1);
+                            }
+                            progress.inc_saved();
+                            saved_in_job += 1;
+                        }
                         _ => {
                             deferred_retry
                                 .push(DeferredChapter::new(ch.clone(), "章节内容缺失或为空"));
@@ -282,6 +320,7 @@ impl ChapterDownloader {
                         }
                     };
                     for group in rx.iter() {
+                        wait_while_paused(cancel.as_ref());
                         if cancel
                             .as_ref()
                             .map(|c| c.load(Ordering::Relaxed))
@@ -290,13 +329,16 @@ impl ChapterDownloader {
                             let _ = tx.send(Err(anyhow!("用户停止下载")));
                             return;
                         }
-                        let epub_mode = cfg.novel_format == "epub";
-                        let value = fetch_group_best_effort(
-                            &client,
-                            &group,
-                            epub_mode,
-                            Some(&book_id_clone),
-                        )
+                        let epub_mode = cfg.wants_epub();
+                        let value = retry_after_network_recovery(&cfg, cancel.as_ref(), || {
+                            fetch_group_best_effort(
+                                &client,
+                                &group,
+                                epub_mode,
+                                Some(&book_id_clone),
+                                TimeoutRetryStrategy::from_config(&cfg.timeout_retry_strategy),
+                            )
+                        })
                         .or_else(|err| {
                             let reason = err.to_string();
                             Ok(GroupFetchOutcome {
@@ -347,6 +389,11 @@ impl ChapterDownloader {
                             progress.inc_saved();
                             saved_in_job += 1;
                         }
+                        _ if self.paid_chapters.contains(&ch.id) => {
+                            skip_paid_chapter(manager, ch, &mut result);
+                            progress.inc_saved();
+                            saved_in_job += 1;
+                        }
                         _ => {
                             deferred_retry
                                 .push(DeferredChapter::new(ch.clone(), "章节内容缺失或为空"));
@@ -432,7 +479,8 @@ impl ChapterDownloader {
                     DeferredRetryOutcome::Failed(deferred) => {
                         log_failed_chapter(&deferred.chapter, &deferred.reason);
                         manager.save_error_chapter(&deferred.chapter.id, &deferred.chapter.title);
-                        result.failed += 1;
+                        result.record_failure(&deferred.reason);
+                        progress.set_top_failure(result.failure_stats.top());
                     }
                 }
 
@@ -474,6 +522,7 @@ impl ChapterDownloader {
             "下载完成：{} 成功 {} 章，失败 {} 章，用时 {:.1}s",
             book_name, result.success, result.failed, elapsed
         );
+        log_failure_summary(&result);
 
         Ok(result)
     }
@@ -497,10 +546,12 @@ pub fn download_with_plan(
         DownloadFlowOptions {
             mode: DownloadMode::Resume,
             range,
+            selection: None,
             retry_failed: RetryFailed::Never,
             stage_callback: None,
             book_name_asker: None,
             format_asker: None,
+            modified_asker: None,
         },
         progress,
         cancel_flag,
@@ -516,17 +567,30 @@ pub fn download_with_plan_flow(
     cancel_flag: Option<Arc<AtomicBool>>,
 ) -> Result<()> {
     info!(target: "download", book_id = %plan.book_id, "启动下载");
+    bandwidth::set_limit_kbps(config.max_bandwidth_kbps);
+    #[cfg(feature = "official-api")]
+    crate::prewarm_state::probe_iid_if_due();
 
     let DownloadFlowOptions {
         mode,
         range,
+        selection,
         mut retry_failed,
         mut stage_callback,
         mut book_name_asker,
         mut format_asker,
+        mut modified_asker,
     } = options;
 
-    let chosen_chapters = apply_range(&plan.chapters, range);
+    let history_range = match (selection.as_ref(), range) {
+        (Some(ids), _) => format!("勾选 {} 章", ids.len()),
+        (None, Some(r)) => format!("{}-{}", r.start, r.end),
+        (None, None) => String::new(),
+    };
+    let chosen_chapters = apply_title_filter(
+        config,
+        apply_selection(&plan.chapters, range, selection.as_ref()),
+    );
     if chosen_chapters.is_empty() {
         return Err(anyhow!("范围无效或章节为空"));
     }
@@ -541,18 +605,44 @@ pub fn download_with_plan_flow(
 
     if matches!(mode, DownloadMode::Full | DownloadMode::RangeIgnoreHistory) {
         manager.downloaded.clear();
+    } else if config.detect_modified_chapters && matches!(mode, DownloadMode::Resume) {
+        // 须在覆盖目录缓存前执行：对比依赖上一次保存的 item_version
+        let modified = detect_modified_chapters(config, &manager, &plan._raw, &chosen_chapters);
+        if !modified.is_empty() {
+            let titles: Vec<&str> = modified.iter().map(|ch| ch.title.as_str()).collect();
+            warn!(
+                target: "download",
+                count = modified.len(),
+                "检测到正文被修改的章节：{}",
+                titles.join("、")
+            );
+            if modified_asker.as_mut().is_some_and(|ask| ask(&modified)) {
+                manager.forget_chapters(modified.iter().map(|ch| ch.id.as_str()));
+                info!(target: "download", count = modified.len(), "将重新下载被修改的章节");
+            }
+        }
     }
 
+    let directory: Vec<(String, String)> = plan
+        .chapters
+        .iter()
+        .map(|ch| (ch.id.clone(), ch.title.clone()))
+        .collect();
+    manager.save_directory_cache(&directory, &plan._raw);
+
     let mut pending = match mode {
         DownloadMode::FailedOnly => pending_failed(&manager, &chosen_chapters),
         _ => pending_resume(&manager, &chosen_chapters),
     };
 
     let mut reporter = make_reporter(config, &chosen_chapters, &pending, progress);
+    let mut run_stats = RunStatsTracker::start(&manager, &chosen_chapters);
+    let mut deadline =
+        DownloadDeadline::start(config.book_download_timeout_secs, cancel_flag.clone());
 
     loop {
         let book_name = manager.book_name.clone();
-        let result = match download_chapters_into_manager(
+        let round = download_chapters_into_manager(
             config,
             &plan.book_id,
             &book_name,
@@ -561,21 +651,51 @@ pub fn download_with_plan_flow(
             &pending,
             Some(&plan._raw),
             &mut reporter,
-            cancel_flag.as_ref(),
-        ) {
+            deadline.cancel_flag(),
+        );
+        run_stats.collect_endpoint_round();
+        let result = match round {
             Ok(v) => v,
+            Err(_) if deadline.timed_out() => {
+                mark_unfinished_failed(&mut manager, &chosen_chapters);
+                warn!(
+                    target: "download",
+                    book_id = %plan.book_id,
+                    timeout_secs = config.book_download_timeout_secs,
+                    success = count_success_for_chosen(&manager, &chosen_chapters),
+                    "单本下载超时，已中止并输出已完成部分"
+                );
+                break;
+            }
             Err(e) => {
                 let success = count_success_for_chosen(&manager, &chosen_chapters);
-                let failed = chosen_chapters.len().saturating_sub(success);
-                append_download_history(&DownloadHistoryRecord::new(
-                    manager.book_id.clone(),
-                    manager.book_name.clone(),
-                    manager.author.clone(),
-                    chosen_chapters.len(),
-                    success,
-                    failed,
-                    "failed".to_string(),
-                ));
+                let failed = count_failed_for_chosen(&manager, &chosen_chapters, success);
+                append_download_history(
+                    &DownloadHistoryRecord::new(
+                        manager.book_id.clone(),
+                        manager.book_name.clone(),
+                        manager.author.clone(),
+                        chosen_chapters.len(),
+                        success,
+                        failed,
+                        "failed".to_string(),
+                    )
+                    .with_range(history_range),
+                    config.download_history_limit,
+                );
+                run_stats.finish(&manager, &chosen_chapters, &plan._raw);
+                if !cancel_flag
+                    .as_ref()
+                    .is_some_and(|c| c.load(Ordering::Relaxed))
+                {
+                    notify::notify_download_finished(
+                        config,
+                        &manager.book_name,
+                        success,
+                        failed,
+                        Some(&e.to_string()),
+                    );
+                }
                 return Err(e);
             }
         };
@@ -600,6 +720,12 @@ pub fn download_with_plan_flow(
 
         reporter.reset_for_retry(chosen_chapters.len(), pending.len());
     }
+    deadline.stop();
+
+    let order: Vec<String> = plan.chapters.iter().map(|ch| ch.id.clone()).collect();
+    if let Err(e) = manager.export_chapters_jsonl(&order) {
+        warn!(target: "download", error = %e, "导出 chapters.jsonl 失败");
+    }
 
     let finalize_result = finalize_from_manager(
         &mut manager,
@@ -612,21 +738,42 @@ pub fn download_with_plan_flow(
     );
 
     let success = count_success_for_chosen(&manager, &chosen_chapters);
-    let failed = chosen_chapters.len().saturating_sub(success);
+    let failed = count_failed_for_chosen(&manager, &chosen_chapters, success);
     let status = if finalize_result.is_ok() && failed == 0 {
         "success"
     } else {
         "failed"
     };
-    append_download_history(&DownloadHistoryRecord::new(
-        manager.book_id.clone(),
-        manager.book_name.clone(),
-        manager.author.clone(),
-        chosen_chapters.len(),
-        success,
-        failed,
-        status.to_string(),
-    ));
+    append_download_history(
+        &DownloadHistoryRecord::new(
+            manager.book_id.clone(),
+            manager.book_name.clone(),
+            manager.author.clone(),
+            chosen_chapters.len(),
+            success,
+            failed,
+            status.to_string(),
+        )
+        .with_range(history_range),
+        config.download_history_limit,
+    );
+    run_stats.finish(&manager, &chosen_chapters, &plan._raw);
+    export_failed_list(&manager);
+    content_check::report_suspicious_chapters(&manager, &chosen_chapters);
+    export_request_trace(&manager);
+    if !cancel_flag
+        .as_ref()
+        .is_some_and(|c| c.load(Ordering::Relaxed))
+    {
+        let err_text = finalize_result.as_ref().err().map(|e| e.to_string());
+        notify::notify_download_finished(
+            config,
+            &manager.book_name,
+            success,
+            failed,
+            err_text.as_deref(),
+        );
+    }
 
     finalize_result
 }
@@ -670,6 +817,14 @@ pub(crate) fn init_manager_from_plan(config: &Config, plan: &DownloadPlan) -> Re
         .clone()
         .unwrap_or_else(|| plan.book_id.clone());
 
+    // 命中 save_path_rules 时，本书的缓存与成书都落到规则指定的目录
+    let mut config = config.clone();
+    if let Some(root) = config.save_path_rule_root(meta.category.as_deref(), &meta.tags) {
+        info!(target: "download", book_id = %plan.book_id, root = %root.display(), "按保存路径规则分流");
+        config.save_path = root.to_string_lossy().into_owned();
+    }
+    let config = &config;
+
     if let Err(e) = rename_old_folder_if_needed(config, &plan.book_id, &book_name) {
         debug!(
             target: "download",
@@ -712,6 +867,106 @@ pub(crate) fn pending_failed(manager: &BookManager, chapters: &[ChapterRef]) ->
         .collect()
 }
 
+/// 单次下载的全局统计采集：只计本次新完成的章节，endpoint 统计按轮次累加。
+struct RunStatsTracker {
+    started: Instant,
+    done_before: HashSet<String>,
+    endpoints: Vec<(String, EndpointTotals)>,
+}
+
+impl RunStatsTracker {
+    fn start(manager: &BookManager, chapters: &[ChapterRef]) -> Self {
+        let done_before = chapters
+            .iter()
+            .filter(|ch| matches!(manager.downloaded.get(&ch.id), Some((_, Some(_)))))
+            .map(|ch| ch.id.clone())
+            .collect();
+        Self {
+            started: Instant::now(),
+            done_before,
+            endpoints: Vec::new(),
+        }
+    }
+
+    /// 每轮下载开始时会重置实时 endpoint 统计，因此每轮结束后累加一次。
+    fn collect_endpoint_round(&mut self) {
+        for stat in endpoint_stats_snapshot() {
+            if stat.requests == 0 {
+                continue;
+            }
+            let idx = match self
+                .endpoints
+                .iter()
+                .position(|(ep, _)| *ep == stat.endpoint)
+            {
+                Some(idx) => idx,
+                None => {
+                    self.endpoints
+                        .push((stat.endpoint.clone(), EndpointTotals::default()));
+                    self.endpoints.len() - 1
+                }
+            };
+            let totals = &mut self.endpoints[idx].1;
+            totals.requests += stat.requests;
+            totals.success += stat.success;
+            totals.failed += stat.failed;
+        }
+    }
+
+    fn finish(&mut self, manager: &BookManager, chapters: &[ChapterRef], raw: &Value) {
+        let word_counts = json_extract::chapter_word_counts(raw);
+        let mut success_chapters = 0u64;
+        let mut words = 0u64;
+        for ch in chapters {
+            if self.done_before.contains(&ch.id) {
+                continue;
+            }
+            let Some((_, Some(content))) = manager.downloaded.get(&ch.id) else {
+                continue;
+            };
+            success_chapters += 1;
+            words += word_counts
+                .get(&ch.id)
+                .copied()
+                .unwrap_or_else(|| count_text_chars(content)) as u64;
+        }
+        record_download_stats(&DownloadRunSummary {
+            success_chapters,
+            words,
+            elapsed_secs: self.started.elapsed().as_secs(),
+            endpoints: std::mem::take(&mut self.endpoints),
+        });
+    }
+}
+
+/// 目录未提供字数时的估算：标签外的非空白字符数。
+pub(crate) fn count_text_chars(content: &str) -> usize {
+    let mut in_tag = false;
+    content
+        .chars()
+        .filter(|&c| {
+            match c {
+                '<' => in_tag = true,
+                '>' if in_tag => {
+                    in_tag = false;
+                    return false;
+                }
+                _ => {}
+            }
+            !in_tag && !c.is_whitespace()
+        })
+        .count()
+}
+
+/// 超时中止时，将尚未成功且非付费跳过的章节记为失败，便于后续「仅重试失败」。
+fn mark_unfinished_failed(manager: &mut BookManager, chapters: &[ChapterRef]) {
+    for ch in pending_resume(manager, chapters) {
+        if !manager.paid_skipped.contains(&ch.id) {
+            manager.save_error_chapter(&ch.id, &ch.title);
+        }
+    }
+}
+
 fn count_success_for_chosen(manager: &BookManager, chapters: &[ChapterRef]) -> usize {
     chapters
         .iter()
@@ -719,6 +974,19 @@ fn count_success_for_chosen(manager: &BookManager, chapters: &[ChapterRef]) -> u
         .count()
 }
 
+/// 未成功且不属于付费跳过的章节数。
+fn count_failed_for_chosen(
+    manager: &BookManager,
+    chapters: &[ChapterRef],
+    success: usize,
+) -> usize {
+    let paid = chapters
+        .iter()
+        .filter(|ch| manager.paid_skipped.contains(&ch.id))
+        .count();
+    chapters.len().saturating_sub(success + paid)
+}
+
 // ── 核心下载编排 ──────────────────────────────────────────────
 
 #[allow(clippy::too_many_arguments)]
@@ -734,10 +1002,16 @@ pub(crate) fn download_chapters_into_manager(
     cancel: Option<&Arc<AtomicBool>>,
 ) -> Result<DownloadResult> {
     // 初始化段评进度：以磁盘缓存为准，避免断点续传时"假满"。
+    // 开启段评刷新时所有章节都会重新提交检查，进度从 0 计起。
+    let refresh_segments = config.segment_comments_refresh_hours > 0;
     if segment_enabled(config) && reporter.snapshot.comment_total > 0 {
         let seg_dir = manager.book_folder().join("segment_comments");
         let _ = std::fs::create_dir_all(&seg_dir);
-        let cached = count_segment_comment_cache_files(&seg_dir);
+        let cached = if refresh_segments {
+            0
+        } else {
+            count_segment_comment_cache_files(&seg_dir)
+        };
         reporter.snapshot.comment_fetch = cached.min(reporter.snapshot.comment_total);
         reporter.snapshot.comment_saved = reporter.snapshot.comment_fetch;
         reporter.emit();
@@ -752,6 +1026,12 @@ pub(crate) fn download_chapters_into_manager(
     let item_versions = directory_raw
         .map(extract_item_version_map)
         .unwrap_or_default();
+    let paid_chapters = directory_raw
+        .map(json_extract::paid_chapter_ids)
+        .unwrap_or_default();
+    if !paid_chapters.is_empty() {
+        debug!(target: "download", paid = paid_chapters.len(), "目录中标注付费章节");
+    }
     let status_dir = manager.book_folder().to_path_buf();
     let mut seg_pool = SegmentCommentPool::new(
         config.clone(),
@@ -761,12 +1041,12 @@ pub(crate) fn download_chapters_into_manager(
         cancel.cloned(),
     );
 
-    // 段评与正文同时开始：先为缺失缓存的章节提交段评抓取任务。
+    // 段评与正文同时开始：先为缺失（或开启刷新时可能过期）缓存的章节提交段评抓取任务。
     if let Some(pool) = seg_pool.as_ref() {
         let seg_dir = manager.book_folder().join("segment_comments");
         for ch in chosen_chapters {
             let out_path = seg_dir.join(format!("{}.json", ch.id));
-            if !out_path.exists() {
+            if refresh_segments || !out_path.exists() {
                 pool.submit(&ch.id);
             }
         }
@@ -782,10 +1062,14 @@ pub(crate) fn download_chapters_into_manager(
         return Ok(DownloadResult::default());
     }
 
+    // 官方 API 不走 endpoint 池，先清掉上一轮第三方下载的看板数据
+    reset_endpoint_stats(&[]);
+
     #[cfg(feature = "official-api")]
     let result = if config.use_official_api {
         let client = FanqieClient::new().context("init FanqieClient")?;
-        let downloader = ChapterDownloader::new(book_id, config.clone(), client);
+        let downloader = ChapterDownloader::new(book_id, config.clone(), client)
+            .with_paid_chapters(paid_chapters);
         downloader.download_book(
             manager,
             book_name,
@@ -800,6 +1084,7 @@ pub(crate) fn download_chapters_into_manager(
             book_name,
             manager,
             pending_chapters,
+            &paid_chapters,
             reporter,
             cancel,
             seg_pool.as_ref(),
@@ -812,6 +1097,7 @@ pub(crate) fn download_chapters_into_manager(
         book_name,
         manager,
         pending_chapters,
+        &paid_chapters,
         reporter,
         cancel,
         seg_pool.as_ref(),
@@ -825,11 +1111,13 @@ pub(crate) fn download_chapters_into_manager(
 }
 
 /// 第三方 API 模式下载流程（提取以避免 `#[cfg]` 块之间代码重复）。
+#[allow(clippy::too_many_arguments)]
 fn download_third_party_flow(
     config: &Config,
     book_name: &str,
     manager: &mut BookManager,
     pending_chapters: &[ChapterRef],
+    paid_chapters: &HashSet<String>,
     reporter: &mut ProgressReporter,
     cancel: Option<&Arc<AtomicBool>>,
     seg_pool: Option<&SegmentCommentPool>,
@@ -860,16 +1148,17 @@ fn download_third_party_flow(
     }
 
     info!(target: "download", endpoints = valid.len(), "第三方 API 地址池预热完成");
+    reset_endpoint_stats(&valid);
 
     let endpoints = Arc::new(std::sync::Mutex::new(valid));
     let picker = Arc::new(AtomicUsize::new(0));
     let worker_count = config.max_workers.max(1);
-    let epub_mode = config.novel_format.eq_ignore_ascii_case("epub");
+    let epub_mode = config.wants_epub();
 
     let (tx_jobs, rx_jobs) = channel::unbounded::<Vec<ChapterRef>>();
     let (tx_res, rx_res) = channel::unbounded::<Result<(Vec<ChapterRef>, Value)>>();
 
-    for group in build_dynamic_chapter_groups(pending_chapters) {
+    for group in build_dynamic_chapter_groups(pending_chapters, config.effective_batch_size()) {
         tx_jobs.send(group.to_vec()).ok();
     }
     drop(tx_jobs);
@@ -883,6 +1172,7 @@ fn download_third_party_flow(
         let cancel = cancel.cloned();
         std::thread::spawn(move || {
             for group in rx.iter() {
+                wait_while_paused(cancel.as_ref());
                 if cancel
                     .as_ref()
                     .map(|c| c.load(Ordering::Relaxed))
@@ -891,7 +1181,9 @@ fn download_third_party_flow(
                     let _ = tx.send(Err(anyhow!("用户停止下载")));
                     return;
                 }
-                let value = fetch_group_third_party(&cfg, &endpoints, &picker, &group, epub_mode);
+                let value = retry_after_network_recovery(&cfg, cancel.as_ref(), || {
+                    fetch_group_third_party(&cfg, &endpoints, &picker, &group, epub_mode)
+                });
                 let _ = tx.send(value.map(|v| (group, v)));
             }
         });
@@ -919,10 +1211,15 @@ fn download_third_party_flow(
                         pool.submit(&ch.id);
                     }
                 }
+                _ if paid_chapters.contains(&ch.id) => {
+                    skip_paid_chapter(manager, ch, &mut result);
+                }
                 _ => {
-                    log_failed_chapter(ch, "章节内容缺失或为空");
+                    let reason = "章节内容缺失或为空";
+                    log_failed_chapter(ch, reason);
                     manager.save_error_chapter(&ch.id, &ch.title);
-                    result.failed += 1;
+                    result.record_failure(reason);
+                    reporter.set_top_failure(result.failure_stats.top());
                 }
             }
             reporter.inc_saved();
@@ -941,6 +1238,7 @@ fn download_third_party_flow(
         book_name,
         pending_chapters.len()
     );
+    log_failure_summary(&result);
     Ok(result)
 }
 
@@ -1023,6 +1321,9 @@ pub(crate) fn finalize_from_manager(
         }
     }
 
+    if let Some(r) = reporter.as_deref_mut() {
+        r.flush();
+    }
     let result_code = 0;
     let reporter_ref = reporter.as_deref_mut();
     let finalize_ok = finalize_utils::run_finalize(
@@ -1042,14 +1343,10 @@ pub(crate) fn finalize_from_manager(
         .unwrap_or(false);
 
     let all_success = count_success_for_chosen(manager, chosen) == chosen.len();
-    if finalize_ok
-        && manager.config.auto_clear_dump
-        && finished
-        && full_book_range
-        && all_success
-        && let Err(e) = manager.delete_status_folder()
-    {
-        error!(target: "book_manager", error = ?e, "删除状态目录失败");
+    if finalize_ok {
+        let clear_all =
+            manager.config.auto_clear_dump && finished && full_book_range && all_success;
+        clear_dump_after_finalize(manager, clear_all);
     }
 
     if let Some(r) = reporter {
@@ -1059,6 +1356,116 @@ pub(crate) fn finalize_from_manager(
     Ok(())
 }
 
+/// 成书成功后清理缓存：满足整体清理条件时删除整个状态目录，否则按开关只删选中的类型。
+fn clear_dump_after_finalize(manager: &mut BookManager, clear_all: bool) {
+    let folder = manager.book_folder().to_path_buf();
+    if clear_all {
+        let freed = file_cleaner::path_size(&folder);
+        match manager.delete_status_folder() {
+            Ok(()) => info!(
+                target: "book_manager",
+                "已清理全部缓存（状态目录），释放 {}",
+                file_cleaner::format_bytes(freed)
+            ),
+            Err(e) => error!(target: "book_manager", error = ?e, "删除状态目录失败"),
+        }
+        return;
+    }
+
+    let kinds = manager.config.dump_kinds_to_clear();
+    if kinds.is_empty() {
+        return;
+    }
+    match file_cleaner::clear_dump_kinds(&folder, &kinds) {
+        Ok(cleared) if cleared.is_empty() => {}
+        Ok(cleared) => {
+            let total: u64 = cleared.iter().map(|(_, bytes)| bytes).sum();
+            let detail = cleared
+                .iter()
+                .map(|(kind, bytes)| {
+                    format!("{} {}", kind.label(), file_cleaner::format_bytes(*bytes))
+                })
+                .collect::<Vec<_>>()
+                .join("，");
+            info!(
+                target: "book_manager",
+                "已清理缓存：{}；共释放 {}",
+                detail,
+                file_cleaner::format_bytes(total)
+            );
+        }
+        Err(e) => warn!(target: "book_manager", error = ?e, "清理缓存失败"),
+    }
+}
+
+/// 不联网重新生成成书：读取书籍目录中的下载状态、章节缓存与目录缓存，跳过下载直接 finalize。
+///
+/// 只输出本地有记录的章节；没有目录缓存时按章节 ID 数值排序。
+pub fn regenerate_from_cache(
+    config: &Config,
+    book_id: &str,
+    book_name: &str,
+    progress: Option<Box<dyn FnMut(ProgressSnapshot) + Send>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> Result<()> {
+    info!(target: "download", book_id, "从本地缓存重新生成");
+    bandwidth::set_limit_kbps(config.max_bandwidth_kbps);
+    let mut manager = BookManager::new(config.clone(), book_id, book_name)?;
+    if !manager.load_existing_status(book_id, book_name) {
+        return Err(anyhow!("未找到《{}》的本地下载记录", book_name));
+    }
+    if manager.book_id.is_empty() {
+        manager.book_id = book_id.to_string();
+    }
+    // 沿用上次选择的书名/格式，不再询问
+    manager.book_name_selected_after_download = true;
+    manager.format_selected_after_download = true;
+
+    let (chapters, directory_raw) = match manager.load_directory_cache() {
+        Some((directory, raw)) => {
+            let chapters: Vec<ChapterRef> = directory
+                .into_iter()
+                .filter(|(id, _)| {
+                    manager.downloaded.contains_key(id) || manager.paid_skipped.contains(id)
+                })
+                .map(|(id, title)| ChapterRef { id, title })
+                .collect();
+            (chapters, (!raw.is_null()).then_some(raw))
+        }
+        None => {
+            let mut chapters: Vec<ChapterRef> = manager
+                .downloaded
+                .iter()
+                .map(|(id, (title, _))| ChapterRef {
+                    id: id.clone(),
+                    title: title.clone(),
+                })
+                .collect();
+            chapters.sort_by(|a, b| match (a.id.parse::<u64>(), b.id.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                _ => a.id.cmp(&b.id),
+            });
+            warn!(target: "download", book_id, "缺少目录缓存，按章节 ID 排序重新生成（无分卷信息）");
+            (chapters, None)
+        }
+    };
+    if chapters.is_empty() {
+        return Err(anyhow!("《{}》没有已缓存的章节", book_name));
+    }
+
+    let mut reporter = make_reporter(config, &chapters, &[], progress);
+    reporter.snapshot.saved_chapters = chapters.len();
+    finalize_from_manager(
+        &mut manager,
+        &chapters,
+        directory_raw.as_ref(),
+        Some(&mut reporter),
+        cancel_flag.as_ref(),
+        &mut None,
+        &mut None,
+    )
+}
+
 pub(crate) fn collect_book_name_options(manager: &BookManager) -> Vec<BookNameOption> {
     let mut options: Vec<BookNameOption> = Vec::new();
 
@@ -1113,6 +1520,10 @@ pub(crate) fn collect_output_format_options() -> Vec<BookNameOption> {
             label: "pdf 格式".to_string(),
             value: "pdf".to_string(),
         },
+        BookNameOption {
+            label: "txt + epub 格式".to_string(),
+            value: "txt,epub".to_string(),
+        },
         BookNameOption {
             label: "散装文件".to_string(),
             value: "bulk_txt".to_string(),
@@ -1135,6 +1546,68 @@ fn extract_body_fragment(input: &str) -> String {
     input.to_string()
 }
 
+/// 将失败章节导出到书籍目录，便于之后只重下失败章节。
+fn export_failed_list(manager: &BookManager) {
+    let folder = manager.book_folder();
+    if !folder.exists() {
+        return;
+    }
+    match novel_updates::export_failed_chapter_list(folder, &manager.book_id) {
+        Ok(Some(path)) => info!(
+            target: "download",
+            path = %path.display(),
+            "已导出失败章节列表，可在更新界面按 r 仅重下失败章节"
+        ),
+        Ok(None) => {}
+        Err(e) => warn!(target: "download", error = %e, "导出失败章节列表失败"),
+    }
+}
+
+/// debug 模式下导出本次下载的请求 trace。
+fn export_request_trace(manager: &BookManager) {
+    let folder = manager.book_folder();
+    if !folder.exists() {
+        return;
+    }
+    match request_trace::export_to(folder) {
+        Ok(Some(path)) => info!(target: "download", path = %path.display(), "已导出请求 trace"),
+        Ok(None) => {}
+        Err(e) => warn!(target: "download", error = %e, "导出请求 trace 失败"),
+    }
+}
+
+/// 付费/未解锁章节：记为付费跳过，不计入失败也不进入重试。
+fn skip_paid_chapter(manager: &mut BookManager, chapter: &ChapterRef, result: &mut DownloadResult) {
+    info!(
+        target: "download",
+        chapter_id = %chapter.id,
+        "付费章节跳过：{} ({})",
+        chapter.title,
+        chapter.id
+    );
+    manager.save_paid_chapter(&chapter.id, &chapter.title);
+    result.paid_skipped += 1;
+}
+
+/// 结束报告：按失败原因聚合输出。
+fn log_failure_summary(result: &DownloadResult) {
+    if result.paid_skipped > 0 {
+        info!(target: "download", paid = result.paid_skipped, "付费/未解锁章节已跳过 {} 章", result.paid_skipped);
+    }
+    if result.failure_stats.is_empty() {
+        return;
+    }
+    warn!(
+        target: "download",
+        failed = result.failed,
+        "失败原因统计：{}",
+        result.failure_stats.summary()
+    );
+    if let Some(kind) = result.failure_stats.top() {
+        warn!(target: "download", "主要失败: {}，{}", kind.label(), kind.hint());
+    }
+}
+
 fn log_failed_chapter(chapter: &ChapterRef, reason: &str) {
     error!(
         target: "download",
@@ -1148,20 +1621,25 @@ fn log_failed_chapter(chapter: &ChapterRef, reason: &str) {
 }
 
 pub(crate) const MIN_DYNAMIC_GROUP_SIZE: usize = 15;
-pub(crate) const MAX_DYNAMIC_GROUP_SIZE: usize = 25;
 
-pub(crate) fn build_dynamic_chapter_groups(chapters: &[ChapterRef]) -> Vec<&[ChapterRef]> {
+/// 将章节均衡分组，每组不超过 `batch_size`；批量较大时每组尽量不少于 15 章。
+pub(crate) fn build_dynamic_chapter_groups(
+    chapters: &[ChapterRef],
+    batch_size: usize,
+) -> Vec<&[ChapterRef]> {
     if chapters.is_empty() {
         return Vec::new();
     }
 
+    let max_size = batch_size.clamp(1, MAX_BATCH_SIZE);
+    let min_size = MIN_DYNAMIC_GROUP_SIZE.min(max_size);
     let len = chapters.len();
-    if len <= MAX_DYNAMIC_GROUP_SIZE {
+    if len <= max_size {
         return vec![chapters];
     }
 
-    let min_groups = len.div_ceil(MAX_DYNAMIC_GROUP_SIZE);
-    let max_groups = len / MIN_DYNAMIC_GROUP_SIZE;
+    let min_groups = len.div_ceil(max_size);
+    let max_groups = len / min_size;
 
     let group_count = if min_groups <= max_groups {
         max_groups
@@ -1186,14 +1664,17 @@ pub(crate) fn build_dynamic_chapter_groups(chapters: &[ChapterRef]) -> Vec<&[Cha
     groups
 }
 
-pub(crate) fn dynamic_group_count(total: usize) -> usize {
-    build_dynamic_chapter_groups(&vec![
-        ChapterRef {
-            id: String::new(),
-            title: String::new(),
-        };
-        total
-    ])
+pub(crate) fn dynamic_group_count(total: usize, batch_size: usize) -> usize {
+    build_dynamic_chapter_groups(
+        &vec![
+            ChapterRef {
+                id: String::new(),
+                title: String::new(),
+            };
+            total
+        ],
+        batch_size,
+    )
     .len()
 }
 
@@ -1213,7 +1694,8 @@ fn attempt_deferred_batch(
     }
 
     let group: Vec<ChapterRef> = deferred.iter().map(|item| item.chapter.clone()).collect();
-    let outcome = match fetch_group_best_effort(client, &group, epub_mode, book_id) {
+    let strategy = TimeoutRetryStrategy::from_config(&config.timeout_retry_strategy);
+    let outcome = match fetch_group_best_effort(client, &group, epub_mode, book_id, strategy) {
         Ok(outcome) => outcome,
         Err(err) => {
             let reason = err.to_string();
@@ -1274,6 +1756,7 @@ fn retry_deferred_bisect_with<F>(
 where
     F: Fn(&[DeferredChapter]) -> DeferredBatchAttempt,
 {
+    wait_while_paused(cancel);
     if cancel
         .map(|flag| flag.load(Ordering::Relaxed))
         .unwrap_or(false)
@@ -1392,7 +1875,7 @@ fn retry_deferred_bisect(
 ) -> Result<Vec<DeferredRetryOutcome>> {
     retry_deferred_bisect_with(
         deferred,
-        MAX_DYNAMIC_GROUP_SIZE,
+        config.effective_batch_size(),
         DEFERRED_RETRY_SINGLE_FALLBACK_THRESHOLD,
         cancel,
         &|batch| attempt_deferred_batch(client, batch, config, epub_mode, book_id),
@@ -1405,6 +1888,7 @@ fn fetch_group_best_effort(
     group: &[ChapterRef],
     epub_mode: bool,
     book_id: Option<&str>,
+    strategy: TimeoutRetryStrategy,
 ) -> Result<GroupFetchOutcome> {
     let ids = group
         .iter()
@@ -1412,7 +1896,7 @@ fn fetch_group_best_effort(
         .collect::<Vec<_>>()
         .join(",");
 
-    let report = fetch_best_effort_with_cooldown_retry(client, &ids, epub_mode, book_id)?;
+    let report = fetch_best_effort_with_cooldown_retry(client, &ids, epub_mode, book_id, strategy)?;
 
     if should_escalate_full_group_retry(group.len(), &report) {
         let reason = report
@@ -1426,7 +1910,8 @@ fn fetch_group_best_effort(
             "检测到整组章节全部失败，立即切回整组换 IID 重试策略"
         );
 
-        let value = fetch_with_cooldown_retry(client, &ids, epub_mode, book_id)?;
+        let value = fetch_with_cooldown_retry(client, &ids, epub_mode, book_id, strategy)?;
+        account_bandwidth(&value);
         return Ok(GroupFetchOutcome {
             group: group.to_vec(),
             value,
@@ -1447,6 +1932,7 @@ fn fetch_group_best_effort(
         );
     }
 
+    account_bandwidth(&report.value);
     Ok(GroupFetchOutcome {
         group: group.to_vec(),
         value: report.value,
@@ -1454,24 +1940,84 @@ fn fetch_group_best_effort(
     })
 }
 
+/// 官方 API 的读取发生在外部 crate 内部，无法包装读取层；按响应体大小事后计入共享预算。
+#[cfg(feature = "official-api")]
+fn account_bandwidth(value: &Value) {
+    if bandwidth::is_limited() {
+        bandwidth::consume(value.to_string().len());
+    }
+}
+
 #[cfg(feature = "official-api")]
 fn fetch_best_effort_with_cooldown_retry(
     client: &FanqieClient,
     ids: &str,
     epub_mode: bool,
     book_id: Option<&str>,
+    strategy: TimeoutRetryStrategy,
 ) -> Result<ContentFetchReport> {
     let mut delay = std::time::Duration::from_millis(1100);
+    let mut last_msg: Option<String> = None;
     for attempt in 0..6 {
+        let started = std::time::Instant::now();
         match client.get_contents_best_effort(ids, epub_mode, book_id) {
-            Ok(v) => return Ok(v),
+            Ok(v) => {
+                request_trace::record(
+                    ids,
+                    request_trace::OFFICIAL_ENDPOINT,
+                    attempt,
+                    started.elapsed(),
+                    request_trace::TraceOutcome::Ok,
+                    None,
+                    v.error.clone(),
+                );
+                return Ok(v);
+            }
             Err(err) => {
                 let msg = err.to_string();
-                if msg.contains("Cooldown") || msg.contains("CooldownNotReached") {
+                let cooldown = msg.contains("Cooldown") || msg.contains("CooldownNotReached");
+                // 连接失败 / 读取超时按策略原地重试
+                let retry_wait = (!cooldown)
+                    .then(|| {
+                        strategy.official_retry_wait(FailurePhase::classify_message(&msg), delay)
+                    })
+                    .flatten();
+                request_trace::record(
+                    ids,
+                    request_trace::OFFICIAL_ENDPOINT,
+                    attempt,
+                    started.elapsed(),
+                    if cooldown {
+                        request_trace::TraceOutcome::Cooldown
+                    } else if retry_wait.is_some() {
+                        request_trace::TraceOutcome::Retryable
+                    } else {
+                        request_trace::TraceOutcome::Fatal
+                    },
+                    None,
+                    Some(msg.clone()),
+                );
+                if cooldown {
+                    last_msg = None;
                     std::thread::sleep(delay);
                     delay = std::cmp::min(delay * 2, std::time::Duration::from_secs(8));
                     continue;
                 }
+                if let Some(wait) = retry_wait {
+                    let kind = RequestErrorKind::classify_message(&msg);
+                    warn!(
+                        target: "download",
+                        kind = kind.label(),
+                        "{} 官方 API 请求失败，{}ms 后重试：{}",
+                        kind.prefix(),
+                        wait.as_millis(),
+                        msg
+                    );
+                    last_msg = Some(msg);
+                    std::thread::sleep(wait);
+                    delay = std::cmp::min(delay * 2, std::time::Duration::from_secs(8));
+                    continue;
+                }
                 if attempt == 0
                     && (msg.contains("tomato_novel_network_core") || msg.contains("Library"))
                 {
@@ -1480,12 +2026,27 @@ fn fetch_best_effort_with_cooldown_retry(
                         msg
                     ));
                 }
-                return Err(anyhow!(msg));
+                let kind = RequestErrorKind::classify_message(&msg);
+                warn!(
+                    target: "download",
+                    kind = kind.label(),
+                    "{} 官方 API 请求失败：{}",
+                    kind.prefix(),
+                    msg
+                );
+                return Err(anyhow!(kind.annotate(&msg)));
             }
         }
     }
 
-    Err(anyhow!("Cooldown exceeded retries"))
+    match last_msg {
+        Some(msg) => Err(anyhow!(
+            RequestErrorKind::classify_message(&msg).annotate(&msg)
+        )),
+        None => Err(anyhow!(
+            RequestErrorKind::RiskControl.annotate("Cooldown exceeded retries")
+        )),
+    }
 }
 
 #[cfg(feature = "official-api")]
@@ -1536,6 +2097,25 @@ mod tests {
     #[cfg(feature = "official-api")]
     use std::cell::RefCell;
 
+    #[test]
+    fn title_filter_drops_matching_chapters_and_ignores_bad_patterns() {
+        let chapters: Vec<ChapterRef> =
+            ["第1章 开局", "上架感言", "第2章 出发", "请假条（今日断更）"]
+                .iter()
+                .enumerate()
+                .map(|(i, t)| ChapterRef {
+                    id: i.to_string(),
+                    title: t.to_string(),
+                })
+                .collect();
+        let mut config = Config::default();
+        config.skip_title_patterns = vec!["上架感言|请假".to_string(), "(".to_string()];
+        let kept = apply_title_filter(&config, chapters.clone());
+        let ids: Vec<&str> = kept.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["0", "2"]);
+        assert_eq!(apply_title_filter(&Config::default(), chapters).len(), 4);
+    }
+
     #[test]
     fn merge_content_values_combines_data_entries() {
         let merged = merge_content_values(vec![
@@ -1645,7 +2225,7 @@ mod tests {
             })
             .collect();
 
-        let groups = build_dynamic_chapter_groups(&chapters);
+        let groups = build_dynamic_chapter_groups(&chapters, MAX_BATCH_SIZE);
         assert_eq!(
             groups.iter().map(|g| g.len()).sum::<usize>(),
             chapters.len()
@@ -1663,20 +2243,25 @@ mod tests {
             })
             .collect();
 
-        let groups = build_dynamic_chapter_groups(&chapters);
+        let groups = build_dynamic_chapter_groups(&chapters, MAX_BATCH_SIZE);
         assert_eq!(groups.len(), 1);
         assert_eq!(groups[0].len(), 14);
     }
 
     #[test]
     fn dynamic_group_count_matches_balanced_distribution() {
-        assert_eq!(dynamic_group_count(0), 0);
-        assert_eq!(dynamic_group_count(14), 1);
-        assert_eq!(dynamic_group_count(25), 1);
-        assert_eq!(dynamic_group_count(26), 2);
-        assert_eq!(dynamic_group_count(30), 2);
-        assert_eq!(dynamic_group_count(50), 3);
-        assert_eq!(dynamic_group_count(80), 5);
+        assert_eq!(dynamic_group_count(0, MAX_BATCH_SIZE), 0);
+        assert_eq!(dynamic_group_count(14, MAX_BATCH_SIZE), 1);
+        assert_eq!(dynamic_group_count(25, MAX_BATCH_SIZE), 1);
+        assert_eq!(dynamic_group_count(26, MAX_BATCH_SIZE), 2);
+        assert_eq!(dynamic_group_count(30, MAX_BATCH_SIZE), 2);
+        assert_eq!(dynamic_group_count(50, MAX_BATCH_SIZE), 3);
+        assert_eq!(dynamic_group_count(80, MAX_BATCH_SIZE), 5);
+        // 小批量时严格不超过配置值
+        assert_eq!(dynamic_group_count(25, 10), 3);
+        assert_eq!(dynamic_group_count(10, 10), 1);
+        assert_eq!(dynamic_group_count(7, 1), 7);
+        assert_eq!(dynamic_group_count(26, 99), 2);
     }
 
     #[cfg(feature = "official-api")]
@@ -6,14 +6,30 @@
 //! - `segment_pool`  — 段评并发下载工作池
 //! - `third_party`   — 第三方 API 地址解析 / 请求 / 重试
 //! - `plan`          — 下载计划准备与元数据搜索
+//! - `pause`         — 下载暂停/恢复标记
+//! - `deadline`      — 单本下载总超时看门狗
+//! - `network_wait`  — 断网等待与自动续跑
+//! - `trial`         — 试读单章，验证源可用性
+//! - `diagnose`      — 试运行诊断（解析/目录/endpoint/首章），不写文件
+//! - `content_check` — 章节字数异常检测（疑似缺失/截断）
+//! - `modified`      — 更新时检测正文被修订的已下载章节
+//! - `offline`       — 读取本地目录 JSON 与章节内容离线生成
 //! - `downloader`    — 下载主流程编排
 
+pub mod content_check;
+pub(crate) mod deadline;
+pub mod diagnose;
 pub mod downloader;
 pub mod models;
+pub mod modified;
+pub mod network_wait;
+pub mod offline;
+pub mod pause;
 pub mod plan;
 pub mod progress;
 pub(crate) mod segment_pool;
 pub(crate) mod third_party;
+pub mod trial;
 
 // ── 向后兼容重导出 ──────────────────────────────────────────────
 // 外部代码通过 `crate::download::downloader::Xxx` 引用的类型
@@ -4,7 +4,6 @@
 
 use serde::Serialize;
 use serde_json::Value;
-#[cfg(feature = "official-api")]
 use std::collections::HashSet;
 
 #[cfg(feature = "official-api")]
@@ -25,6 +24,180 @@ pub struct DownloadResult {
     pub success: u32,
     pub failed: u32,
     pub canceled: u32,
+    /// 付费/未解锁而跳过的章节数（不计入失败）。
+    pub paid_skipped: u32,
+    pub failure_stats: FailureStats,
+}
+
+impl DownloadResult {
+    /// 记录一次章节失败，并按原因归类；返回归入的分类。
+    pub fn record_failure(&mut self, reason: &str) -> FailureKind {
+        let kind = FailureKind::classify(reason);
+        self.failed += 1;
+        self.failure_stats.record(kind);
+        kind
+    }
+}
+
+/// 章节下载失败原因分类。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FailureKind {
+    Proxy,
+    Timeout,
+    Connect,
+    Http4xx,
+    Http5xx,
+    EmptyContent,
+    Decrypt,
+    Iid,
+    Other,
+}
+
+impl FailureKind {
+    pub const ALL: [FailureKind; 9] = [
+        FailureKind::Proxy,
+        FailureKind::Timeout,
+        FailureKind::Connect,
+        FailureKind::Http4xx,
+        FailureKind::Http5xx,
+        FailureKind::EmptyContent,
+        FailureKind::Decrypt,
+        FailureKind::Iid,
+        FailureKind::Other,
+    ];
+
+    /// 根据请求/解析层返回的错误文本归类（按优先级匹配关键字）。
+    pub fn classify(reason: &str) -> Self {
+        let lower = reason.to_ascii_lowercase();
+        let has = |keys: &[&str]| keys.iter().any(|k| lower.contains(k));
+
+        if has(&["proxy", "代理", "socks"]) {
+            return FailureKind::Proxy;
+        }
+        if has(&["timed out", "timeout", "超时"]) {
+            return FailureKind::Timeout;
+        }
+        if has(&["iid", "install_id", "device_id"]) {
+            return FailureKind::Iid;
+        }
+        if has(&["decrypt", "解密", "cipher", "base64"]) {
+            return FailureKind::Decrypt;
+        }
+        if let Some(code) = extract_http_status(&lower) {
+            return if code >= 500 {
+                FailureKind::Http5xx
+            } else {
+                FailureKind::Http4xx
+            };
+        }
+        if has(&[
+            "connect",
+            "connection",
+            "dns",
+            "resolve",
+            "连接",
+            "network",
+            "网络",
+        ]) {
+            return FailureKind::Connect;
+        }
+        if has(&["缺失", "为空", "empty"]) {
+            return FailureKind::EmptyContent;
+        }
+        FailureKind::Other
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FailureKind::Proxy => "代理错误",
+            FailureKind::Timeout => "请求超时",
+            FailureKind::Connect => "网络连接失败",
+            FailureKind::Http4xx => "HTTP 4xx",
+            FailureKind::Http5xx => "HTTP 5xx",
+            FailureKind::EmptyContent => "内容为空",
+            FailureKind::Decrypt => "解密失败",
+            FailureKind::Iid => "IID 失效",
+            FailureKind::Other => "其他错误",
+        }
+    }
+
+    /// 面向用户的处理建议。
+    pub fn hint(self) -> &'static str {
+        match self {
+            FailureKind::Proxy => "请检查代理",
+            FailureKind::Timeout => "可适当调大超时或降低并发",
+            FailureKind::Connect => "请检查网络连接",
+            FailureKind::Http4xx => "请求被拒绝，可稍后重试或检查 API 配置",
+            FailureKind::Http5xx => "服务端异常，请稍后重试",
+            FailureKind::EmptyContent => "可能为付费/下架章节，可稍后重试",
+            FailureKind::Decrypt => "请尝试更新程序",
+            FailureKind::Iid => "请稍后重试或更换 IID",
+            FailureKind::Other => "请查看日志了解详情",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// 从错误文本中提取 HTTP 状态码（如 "status 503"、"HTTP status client error (404 Not Found)"）。
+fn extract_http_status(lower: &str) -> Option<u16> {
+    if !["status", "http", "状态码"]
+        .iter()
+        .any(|k| lower.contains(k))
+    {
+        return None;
+    }
+    lower
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|tok| tok.len() == 3)
+        .filter_map(|tok| tok.parse::<u16>().ok())
+        .find(|code| (400..600).contains(code))
+}
+
+/// 按分类聚合的失败次数。
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FailureStats {
+    counts: [u32; FailureKind::ALL.len()],
+}
+
+impl FailureStats {
+    pub fn record(&mut self, kind: FailureKind) {
+        self.counts[kind.index()] += 1;
+    }
+
+    pub fn count(&self, kind: FailureKind) -> u32 {
+        self.counts[kind.index()]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|c| *c == 0)
+    }
+
+    /// 出现次数最多的失败分类。
+    pub fn top(&self) -> Option<FailureKind> {
+        FailureKind::ALL
+            .iter()
+            .copied()
+            .filter(|k| self.count(*k) > 0)
+            .max_by_key(|k| (self.count(*k), std::cmp::Reverse(k.index())))
+    }
+
+    /// 形如 "代理错误 3 章，请求超时 1 章"，按次数降序。
+    pub fn summary(&self) -> String {
+        let mut kinds: Vec<FailureKind> = FailureKind::ALL
+            .iter()
+            .copied()
+            .filter(|k| self.count(*k) > 0)
+            .collect();
+        kinds.sort_by_key(|k| std::cmp::Reverse(self.count(*k)));
+        kinds
+            .iter()
+            .map(|k| format!("{} {} 章", k.label(), self.count(*k)))
+            .collect::<Vec<_>>()
+            .join("，")
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -43,10 +216,14 @@ pub enum RetryFailed {
 pub struct DownloadFlowOptions {
     pub mode: DownloadMode,
     pub range: Option<ChapterRange>,
+    /// 任意勾选的章节 ID 集合；设置后优先于 `range`
+    pub selection: Option<HashSet<String>>,
     pub retry_failed: RetryFailed,
     pub stage_callback: Option<Box<dyn FnMut(DownloadResult) + Send>>,
     pub book_name_asker: Option<BookNameAsker>,
     pub format_asker: Option<FormatAsker>,
+    /// 开启 `detect_modified_chapters` 时，询问是否重新下载被修订的章节；未设置则只记日志
+    pub modified_asker: Option<ModifiedAsker>,
 }
 
 pub type BookNameAsker =
@@ -55,6 +232,8 @@ pub type BookNameAsker =
 pub type FormatAsker =
     Box<dyn FnMut(&crate::book_parser::book_manager::BookManager) -> Option<String> + Send>;
 
+pub type ModifiedAsker = Box<dyn FnMut(&[ChapterRef]) -> bool + Send>;
+
 #[derive(Debug, Clone, Default)]
 pub struct BookMeta {
     pub book_name: Option<String>,
@@ -121,12 +300,83 @@ pub struct DownloadPlan {
     pub _raw: Value,
 }
 
+impl DownloadPlan {
+    /// 目录章节数与元数据 `chapter_count` 对账；不符时返回 `(解析到, 元数据称)`。
+    pub fn catalog_mismatch(&self) -> Option<(usize, usize)> {
+        let claimed = self.meta.chapter_count.filter(|n| *n > 0)?;
+        let parsed = self.chapters.len();
+        (parsed != claimed).then_some((parsed, claimed))
+    }
+
+    /// 从目录原始数据解析分卷；无分卷或仅一个默认卷时为空。
+    pub fn volumes(&self) -> Vec<VolumeInfo> {
+        let ids: Vec<&str> = self.chapters.iter().map(|ch| ch.id.as_str()).collect();
+        crate::book_parser::finalize_utils::volume_chapter_spans(&ids, &self._raw)
+            .into_iter()
+            .map(|(title, start, end)| VolumeInfo { title, start, end })
+            .collect()
+    }
+
+    pub fn catalog_warning(&self) -> Option<String> {
+        self.catalog_mismatch().map(|(parsed, claimed)| {
+            format!("目录可能不完整（解析到 {parsed} 章，元数据称 {claimed} 章）")
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ChapterRange {
     pub start: usize,
     pub end: usize,
 }
 
+/// 目录中的一个分卷及其章节范围（1 起始闭区间，按目录顺序）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VolumeInfo {
+    pub title: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl VolumeInfo {
+    pub fn range(&self) -> ChapterRange {
+        ChapterRange {
+            start: self.start,
+            end: self.end,
+        }
+    }
+
+    pub fn chapter_count(&self) -> usize {
+        self.end + 1 - self.start
+    }
+}
+
+/// 识别 `vol:N` / `vol：N`（不区分大小写），返回 N 部分。
+pub fn strip_volume_prefix(input: &str) -> Option<&str> {
+    let head = input.get(..3)?;
+    if !head.eq_ignore_ascii_case("vol") {
+        return None;
+    }
+    let rest = &input[3..];
+    rest.strip_prefix(':').or_else(|| rest.strip_prefix('：'))
+}
+
+/// 按 `vol:N` 中的 N（1 起始）取对应分卷的章节范围。
+pub fn volume_range(volumes: &[VolumeInfo], n: &str) -> anyhow::Result<ChapterRange> {
+    if volumes.is_empty() {
+        return Err(anyhow::anyhow!("该书没有分卷信息"));
+    }
+    let n = n
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| anyhow::anyhow!("vol:N 中的 N 需为数字"))?;
+    let volume = n
+        .checked_sub(1)
+        .and_then(|i| volumes.get(i))
+        .ok_or_else(|| anyhow::anyhow!("卷号超出范围 (1-{})", volumes.len()))?;
+    Ok(volume.range())
+}
+
 #[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
 pub struct ProgressSnapshot {
     pub group_done: usize,
@@ -143,6 +393,9 @@ pub struct ProgressSnapshot {
     pub audiobook_skipped: usize,
     #[serde(default)]
     pub audiobook_failed: usize,
+    /// 当前出现次数最多的章节失败原因。
+    #[serde(default)]
+    pub top_failure: Option<FailureKind>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -243,3 +496,88 @@ pub(crate) fn merge_meta_prefer_hint_name(dir_meta: BookMeta, hint_meta: BookMet
             .or(hint_meta.cover_primary_color),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_failure_reasons() {
+        assert_eq!(
+            FailureKind::classify("error sending request: proxy connect failed"),
+            FailureKind::Proxy
+        );
+        assert_eq!(
+            FailureKind::classify("operation timed out"),
+            FailureKind::Timeout
+        );
+        assert_eq!(
+            FailureKind::classify("HTTP status server error (503 Service Unavailable)"),
+            FailureKind::Http5xx
+        );
+        assert_eq!(
+            FailureKind::classify("unexpected status 404"),
+            FailureKind::Http4xx
+        );
+        assert_eq!(
+            FailureKind::classify("章节内容缺失或为空"),
+            FailureKind::EmptyContent
+        );
+        assert_eq!(FailureKind::classify("???"), FailureKind::Other);
+    }
+
+    #[test]
+    fn catalog_mismatch_compares_meta_count() {
+        let mut plan = DownloadPlan {
+            book_id: "1".to_string(),
+            meta: BookMeta::default(),
+            chapters: vec![ChapterRef {
+                id: "10".to_string(),
+                title: "第一章".to_string(),
+            }],
+            _raw: Value::Null,
+        };
+        assert_eq!(plan.catalog_mismatch(), None);
+        plan.meta.chapter_count = Some(1);
+        assert_eq!(plan.catalog_mismatch(), None);
+        plan.meta.chapter_count = Some(3);
+        assert_eq!(plan.catalog_mismatch(), Some((1, 3)));
+    }
+
+    #[test]
+    fn volume_range_picks_nth_volume() {
+        let volumes = vec![
+            VolumeInfo {
+                title: "第一卷".to_string(),
+                start: 1,
+                end: 30,
+            },
+            VolumeInfo {
+                title: "第二卷".to_string(),
+                start: 31,
+                end: 80,
+            },
+        ];
+        assert_eq!(strip_volume_prefix("VOL：2"), Some("2"));
+        assert_eq!(strip_volume_prefix("last:2"), None);
+        let r = volume_range(&volumes, "2").unwrap();
+        assert_eq!((r.start, r.end), (31, 80));
+        assert!(volume_range(&volumes, "0").is_err());
+        assert!(volume_range(&volumes, "3").is_err());
+        assert!(volume_range(&[], "1").is_err());
+    }
+
+    #[test]
+    fn failure_stats_picks_most_frequent() {
+        let mut result = DownloadResult::default();
+        result.record_failure("operation timed out");
+        result.record_failure("proxy error");
+        result.record_failure("proxy error");
+        assert_eq!(result.failed, 3);
+        assert_eq!(result.failure_stats.top(), Some(FailureKind::Proxy));
+        assert_eq!(
+            result.failure_stats.summary(),
+            "代理错误 2 章，请求超时 1 章"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! 更新时检测被作者修订的章节：目录 item_version 变化的已下载章节重新拉取正文，与本地哈希对比。
+
+use serde_json::Value;
+use tracing::{info, warn};
+
+use super::models::ChapterRef;
+use super::trial::fetch_chapter;
+use crate::base_system::context::Config;
+use crate::book_parser::book_manager::{BookManager, chapter_content_hash};
+use crate::book_parser::segment_shared::extract_item_version_map;
+
+/// 目录版本号变化的已下载章节；旧目录缓存没有版本信息时返回空，避免整本重拉。
+pub fn version_changed_chapters(
+    manager: &BookManager,
+    old_raw: &Value,
+    new_raw: &Value,
+    chapters: &[ChapterRef],
+) -> Vec<ChapterRef> {
+    let old_versions = extract_item_version_map(old_raw);
+    if old_versions.is_empty() {
+        return Vec::new();
+    }
+    let new_versions = extract_item_version_map(new_raw);
+    chapters
+        .iter()
+        .filter(|ch| matches!(manager.downloaded.get(&ch.id), Some((_, Some(_)))))
+        .filter(|ch| {
+            matches!(
+                (old_versions.get(&ch.id), new_versions.get(&ch.id)),
+                (Some(old), Some(new)) if old != new
+            )
+        })
+        .cloned()
+        .collect()
+}
+
+/// 找出正文已被修改的章节（按目录顺序）；单章拉取失败只记日志，不算修改。
+pub fn detect_modified_chapters(
+    config: &Config,
+    manager: &BookManager,
+    new_raw: &Value,
+    chapters: &[ChapterRef],
+) -> Vec<ChapterRef> {
+    let Some((_, old_raw)) = manager.load_directory_cache() else {
+        return Vec::new();
+    };
+    let candidates = version_changed_chapters(manager, &old_raw, new_raw, chapters);
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+    info!(
+        target: "download",
+        candidates = candidates.len(),
+        "目录版本号变化，校验章节正文是否被修改"
+    );
+
+    candidates
+        .into_iter()
+        .filter(|ch| {
+            let Some(local) = manager.chapter_hash(&ch.id) else {
+                return false;
+            };
+            match fetch_chapter(config, &manager.book_id, ch) {
+                Ok(fetched) => chapter_content_hash(&fetched.content) != local,
+                Err(e) => {
+                    warn!(target: "download", chapter_id = %ch.id, "校验章节修改失败: {e}");
+                    false
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_downloaded_chapters_with_new_version_are_candidates() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.save_path = temp_dir.path().display().to_string();
+        let mut manager = BookManager::new(config, "1", "书").unwrap();
+        manager.save_chapter("a", "一", "正文");
+        manager.save_chapter("b", "二", "正文");
+        manager.save_error_chapter("c", "三");
+
+        let old = serde_json::json!({"item_data_list": [
+            {"item_id": "a", "item_version": "1"},
+            {"item_id": "b", "item_version": "1"},
+            {"item_id": "c", "item_version": "1"},
+        ]});
+        let new = serde_json::json!({"item_data_list": [
+            {"item_id": "a", "item_version": "1"},
+            {"item_id": "b", "item_version": "2"},
+            {"item_id": "c", "item_version": "2"},
+        ]});
+        let chapters: Vec<ChapterRef> = ["a", "b", "c"]
+            .iter()
+            .map(|id| ChapterRef {
+                id: id.to_string(),
+                title: id.to_string(),
+            })
+            .collect();
+
+        let changed = version_changed_chapters(&manager, &old, &new, &chapters);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].id, "b");
+        assert!(version_changed_chapters(&manager, &Value::Null, &new, &chapters).is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+//! 断网等待与自动续跑。
+//!
+//! 开启 `wait_for_network` 后，一组章节重试耗尽且错误为网络类（连接失败/超时/代理错误等）时，
+//! worker 不再把整组记为失败，而是阻塞等待网络恢复后重新拉取该组。
+//! 同一时间只有一个线程负责探测连通性，其余 worker 排队等待结果。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tracing::{info, warn};
+
+use super::pause::wait_while_paused;
+use crate::base_system::context::Config;
+
+const PROBE_INTERVAL: Duration = Duration::from_secs(10);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// 未配置第三方 endpoint 时的探测地址。
+const FALLBACK_PROBE_URL: &str = "https://fanqienovel.com";
+
+/// 同时充当探测互斥锁，记录最近一次网络恢复的时间。
+static LAST_RECOVERY: Mutex<Option<Instant>> = Mutex::new(None);
+static WAITING: AtomicBool = AtomicBool::new(false);
+
+/// 当前是否处于「等待网络恢复」状态。
+pub fn is_waiting_for_network() -> bool {
+    WAITING.load(Ordering::SeqCst)
+}
+
+/// 判断错误是否属于网络层故障（而非接口返回的业务错误）。
+pub(crate) fn is_network_error(err: &anyhow::Error) -> bool {
+    if err.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_connect() || e.is_timeout())
+    }) {
+        return true;
+    }
+    let text = format!("{err:#}").to_ascii_lowercase();
+    [
+        "proxyerror",
+        "proxy error",
+        "dns error",
+        "failed to lookup address",
+        "connection refused",
+        "connection reset",
+        "network is unreachable",
+        "no route to host",
+        "tcp connect error",
+        "error sending request",
+        "timed out",
+    ]
+    .iter()
+    .any(|marker| text.contains(marker))
+}
+
+fn probe_urls(cfg: &Config) -> Vec<String> {
+    let mut urls: Vec<String> = cfg
+        .api_endpoints
+        .iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .take(3)
+        .collect();
+    urls.push(FALLBACK_PROBE_URL.to_string());
+    urls
+}
+
+/// 任一探测地址返回 HTTP 响应（不论状态码）即视为网络可用。
+fn network_reachable(urls: &[String]) -> bool {
+    let Ok(client) = reqwest::blocking::Client::builder()
+        .timeout(PROBE_TIMEOUT)
+        .build()
+    else {
+        return false;
+    };
+    urls.iter().any(|url| client.head(url).send().is_ok())
+}
+
+fn cancelled(cancel: Option<&Arc<AtomicBool>>) -> bool {
+    cancel.is_some_and(|c| c.load(Ordering::Relaxed))
+}
+
+/// 网络故障后是否应重试：仅在确实经历断网并已恢复时返回 true。
+///
+/// `failed_since` 为本次请求开始时间；若其他 worker 已在此之后等到网络恢复，直接重试。
+/// 网络本身可达（故障出在 endpoint）或收到取消信号时返回 false，按原逻辑记为失败。
+fn wait_until_online(
+    cfg: &Config,
+    cancel: Option<&Arc<AtomicBool>>,
+    failed_since: Instant,
+) -> bool {
+    let mut last_recovery = LAST_RECOVERY.lock().unwrap_or_else(|e| e.into_inner());
+    if last_recovery.is_some_and(|t| t >= failed_since) {
+        return !cancelled(cancel);
+    }
+    let urls = probe_urls(cfg);
+    if network_reachable(&urls) {
+        return false;
+    }
+
+    WAITING.store(true, Ordering::SeqCst);
+    warn!(target: "download", "网络不可用，暂停下载，等待网络恢复后自动继续");
+    let started = Instant::now();
+    let online = loop {
+        let next_probe = Instant::now() + PROBE_INTERVAL;
+        while Instant::now() < next_probe {
+            if cancelled(cancel) {
+                break;
+            }
+            std::thread::sleep(CANCEL_POLL_INTERVAL);
+        }
+        wait_while_paused(cancel);
+        if cancelled(cancel) {
+            break false;
+        }
+        if network_reachable(&urls) {
+            break true;
+        }
+    };
+    WAITING.store(false, Ordering::SeqCst);
+    if online {
+        *last_recovery = Some(Instant::now());
+        info!(
+            target: "download",
+            waited_secs = started.elapsed().as_secs(),
+            "网络已恢复，继续下载未完成章节"
+        );
+    }
+    online
+}
+
+/// 执行拉取；开启 `wait_for_network` 且失败原因为网络错误时，等待恢复后重试。
+pub(crate) fn retry_after_network_recovery<T>(
+    cfg: &Config,
+    cancel: Option<&Arc<AtomicBool>>,
+    mut fetch: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    loop {
+        let started = Instant::now();
+        let res = fetch();
+        match &res {
+            Err(e) if cfg.wait_for_network && is_network_error(e) => {
+                if !wait_until_online(cfg, cancel, started) {
+                    return res;
+                }
+            }
+            _ => return res,
+        }
+    }
+}
@@ -0,0 +1,340 @@
+//! 离线生成：读取本地目录 JSON 与章节内容，跳过网络直接生成成书。
+//!
+//! 输入目录布局：
+//!
+//! ```text
+//! <dir>/
+//! ├── directory.json   目录（必需）：章节对象数组，或含 item_data_list / chapters /
+//! │                    items / catalog_data 数组的原始目录 JSON；章节对象需有
+//! │                    item_id（或 chapter_id / id）与 title
+//! ├── book.json        书籍信息（可选）：book_id / book_name / author / description /
+//! │                    tags（数组或 | 分隔）/ finished
+//! ├── chapters/        章节正文（可选）：<章节ID>.html / .xhtml / .txt，txt 按行分段
+//! ├── chapters.jsonl   章节正文（可选）：每行 {id,title,content}，与导出的 chapters.jsonl 相同
+//! └── cover.jpg        封面（可选）：cover.jpg / jpeg / png / webp
+//! ```
+//!
+//! 同一章节在 `chapters/` 与 `chapters.jsonl` 中都有时以 `chapters/` 为准；
+//! 找不到正文的章节跳过并记录警告。
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use anyhow::{Context, Result, anyhow};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use super::downloader::{finalize_from_manager, init_manager_from_plan};
+use super::models::{BookMeta, ChapterRef, DownloadPlan, ProgressSnapshot};
+use super::plan::{dedupe_chapters, parse_chapter_ref_from_value};
+use super::progress::make_reporter;
+use crate::base_system::book_paths::{
+    COVER_FILE_STEM, COVER_IMAGE_EXTENSIONS, canonical_cover_path,
+};
+use crate::base_system::context::Config;
+use crate::book_parser::html_utils::escape_html;
+
+pub const DIRECTORY_FILE: &str = "directory.json";
+pub const BOOK_INFO_FILE: &str = "book.json";
+pub const CHAPTERS_DIR: &str = "chapters";
+pub const CHAPTERS_JSONL: &str = "chapters.jsonl";
+
+/// 章节正文文件的扩展名，按优先级排列。
+const CHAPTER_EXTENSIONS: [&str; 3] = ["html", "xhtml", "txt"];
+
+/// 解析后的离线输入：下载计划（目录与元数据）及章节 ID -> 正文。
+pub struct OfflineInput {
+    pub plan: DownloadPlan,
+    pub contents: HashMap<String, String>,
+}
+
+/// 按约定布局读取离线输入目录。
+pub fn load_offline_input(dir: &Path) -> Result<OfflineInput> {
+    let directory_path = dir.join(DIRECTORY_FILE);
+    let raw: Value = read_json(&directory_path)?;
+    let book: Value = match dir.join(BOOK_INFO_FILE) {
+        p if p.is_file() => read_json(&p)?,
+        _ => Value::Null,
+    };
+
+    let book_id = pick_str(&book, "book_id")
+        .or_else(|| pick_str(&raw, "book_id"))
+        .or_else(|| dir.file_name().and_then(|s| s.to_str()).map(str::to_string))
+        .ok_or_else(|| anyhow!("无法确定 book_id，请在 {BOOK_INFO_FILE} 中指定"))?;
+
+    let chapters = dedupe_chapters(&book_id, directory_chapters(&raw));
+    if chapters.is_empty() {
+        return Err(anyhow!(
+            "{} 中没有解析到章节（需含 item_id/title）",
+            directory_path.display()
+        ));
+    }
+
+    let meta = BookMeta {
+        book_name: pick_str(&book, "book_name").or_else(|| pick_str(&raw, "book_name")),
+        author: pick_str(&book, "author"),
+        description: pick_str(&book, "description"),
+        tags: match book.get("tags") {
+            Some(Value::Array(arr)) => arr
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            Some(Value::String(s)) => s
+                .split('|')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        },
+        finished: book.get("finished").and_then(Value::as_bool),
+        chapter_count: Some(chapters.len()),
+        ..BookMeta::default()
+    };
+
+    let mut contents = read_chapters_jsonl(&dir.join(CHAPTERS_JSONL))?;
+    let chapters_dir = dir.join(CHAPTERS_DIR);
+    for ch in &chapters {
+        if let Some(content) = read_chapter_file(&chapters_dir, &ch.id)? {
+            contents.insert(ch.id.clone(), content);
+        }
+    }
+
+    Ok(OfflineInput {
+        plan: DownloadPlan {
+            book_id,
+            meta,
+            chapters,
+            _raw: raw,
+        },
+        contents,
+    })
+}
+
+/// 读取离线输入并生成成书；只输出找到正文的章节。
+pub fn generate_offline(
+    config: &Config,
+    dir: &Path,
+    progress: Option<Box<dyn FnMut(ProgressSnapshot) + Send>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> Result<DownloadPlan> {
+    let OfflineInput { plan, contents } = load_offline_input(dir)?;
+    info!(
+        target: "download",
+        book_id = %plan.book_id,
+        chapters = plan.chapters.len(),
+        contents = contents.len(),
+        "离线生成"
+    );
+    // 正文来自本地，但插图/封面仍可能联网拉取
+    crate::third_party::bandwidth::set_limit_kbps(config.max_bandwidth_kbps);
+
+    let mut manager = init_manager_from_plan(config, &plan)?;
+    // 离线输入即最终选择，不再询问书名/格式
+    manager.book_name_selected_after_download = true;
+    manager.format_selected_after_download = true;
+
+    let mut chapters: Vec<ChapterRef> = Vec::with_capacity(plan.chapters.len());
+    let mut missing = 0usize;
+    for ch in &plan.chapters {
+        match contents.get(&ch.id) {
+            Some(content) => {
+                manager.save_chapter(&ch.id, &ch.title, content);
+                chapters.push(ch.clone());
+            }
+            None => missing += 1,
+        }
+    }
+    if missing > 0 {
+        warn!(target: "download", missing, "离线输入中缺少部分章节正文，已跳过");
+    }
+    if chapters.is_empty() {
+        return Err(anyhow!(
+            "离线输入中没有任何章节正文（{CHAPTERS_DIR}/ 或 {CHAPTERS_JSONL}）"
+        ));
+    }
+
+    copy_offline_cover(dir, manager.book_folder());
+    manager.save_directory_cache(
+        &plan
+            .chapters
+            .iter()
+            .map(|ch| (ch.id.clone(), ch.title.clone()))
+            .collect::<Vec<_>>(),
+        &plan._raw,
+    );
+
+    let mut reporter = make_reporter(config, &chapters, &[], progress);
+    reporter.snapshot.saved_chapters = chapters.len();
+    finalize_from_manager(
+        &mut manager,
+        &chapters,
+        Some(&plan._raw),
+        Some(&mut reporter),
+        cancel_flag.as_ref(),
+        &mut None,
+        &mut None,
+    )?;
+    Ok(plan)
+}
+
+fn read_json(path: &Path) -> Result<Value> {
+    let raw = fs::read_to_string(path).with_context(|| format!("读取 {} 失败", path.display()))?;
+    serde_json::from_str(&raw).with_context(|| format!("解析 {} 失败", path.display()))
+}
+
+fn pick_str(v: &Value, key: &str) -> Option<String> {
+    match v.get(key)? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// 目录章节：顶层数组，或原始目录 JSON 中的第一个非空章节数组。
+fn directory_chapters(raw: &Value) -> Vec<ChapterRef> {
+    let items = match raw {
+        Value::Array(arr) => Some(arr),
+        _ => ["item_data_list", "chapters", "items", "catalog_data"]
+            .iter()
+            .filter_map(|k| raw.get(*k).and_then(Value::as_array))
+            .find(|arr| !arr.is_empty()),
+    };
+    items
+        .map(|arr| {
+            arr.iter()
+                .filter_map(parse_chapter_ref_from_value)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn read_chapters_jsonl(path: &Path) -> Result<HashMap<String, String>> {
+    let mut out = HashMap::new();
+    if !path.is_file() {
+        return Ok(out);
+    }
+    let raw = fs::read_to_string(path).with_context(|| format!("读取 {} 失败", path.display()))?;
+    for (idx, line) in raw.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let v: Value = serde_json::from_str(line)
+            .with_context(|| format!("{} 第 {} 行不是合法 JSON", path.display(), idx + 1))?;
+        if let (Some(id), Some(content)) =
+            (pick_str(&v, "id"), v.get("content").and_then(Value::as_str))
+        {
+            out.insert(id, content.to_string());
+        }
+    }
+    Ok(out)
+}
+
+fn read_chapter_file(chapters_dir: &Path, id: &str) -> Result<Option<String>> {
+    for ext in CHAPTER_EXTENSIONS {
+        let path = chapters_dir.join(format!("{id}.{ext}"));
+        if !path.is_file() {
+            continue;
+        }
+        let raw =
+            fs::read_to_string(&path).with_context(|| format!("读取 {} 失败", path.display()))?;
+        return Ok(Some(if ext == "txt" {
+            text_to_html(&raw)
+        } else {
+            html_body(&raw).to_string()
+        }));
+    }
+    Ok(None)
+}
+
+/// 纯文本按行分段为 `<p>`。
+fn text_to_html(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| format!("<p>{}</p>", escape_html(l)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 完整 HTML 文档只取 `<body>` 内部；片段原样返回。
+fn html_body(html: &str) -> &str {
+    let lower = html.to_ascii_lowercase();
+    let Some(open) = lower.find("<body") else {
+        return html;
+    };
+    let Some(start) = lower[open..].find('>').map(|i| open + i + 1) else {
+        return html;
+    };
+    let end = lower[start..]
+        .find("</body")
+        .map(|i| start + i)
+        .unwrap_or(html.len());
+    &html[start..end]
+}
+
+fn copy_offline_cover(dir: &Path, book_folder: &Path) {
+    for ext in COVER_IMAGE_EXTENSIONS {
+        let src = dir.join(format!("{COVER_FILE_STEM}.{ext}"));
+        if !src.is_file() {
+            continue;
+        }
+        let dst = canonical_cover_path(book_folder, ext);
+        if !dst.exists()
+            && let Err(e) = fs::copy(&src, &dst)
+        {
+            warn!(target: "download", error = ?e, "复制离线封面失败");
+        }
+        return;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_layout_with_files_overriding_jsonl() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        fs::write(
+            dir.join(DIRECTORY_FILE),
+            r#"{"item_data_list":[{"item_id":"1","title":"第一章"},{"item_id":"2","title":"第二章"},{"item_id":"3","title":"第三章"}]}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join(BOOK_INFO_FILE),
+            r#"{"book_id":"42","book_name":"离线书","tags":"玄幻|热血"}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join(CHAPTERS_JSONL),
+            "{\"id\":\"1\",\"title\":\"第一章\",\"content\":\"<p>旧</p>\"}\n{\"id\":\"2\",\"title\":\"第二章\",\"content\":\"<p>二</p>\"}\n",
+        )
+        .unwrap();
+        fs::create_dir(dir.join(CHAPTERS_DIR)).unwrap();
+        fs::write(dir.join(CHAPTERS_DIR).join("1.txt"), "一行\n\n<二行>\n").unwrap();
+
+        let input = load_offline_input(dir).unwrap();
+        assert_eq!(input.plan.book_id, "42");
+        assert_eq!(input.plan.meta.book_name.as_deref(), Some("离线书"));
+        assert_eq!(input.plan.meta.tags, vec!["玄幻", "热血"]);
+        assert_eq!(input.plan.chapters.len(), 3);
+        assert_eq!(input.contents["1"], "<p>一行</p>\n<p>&lt;二行&gt;</p>");
+        assert_eq!(input.contents["2"], "<p>二</p>");
+        assert!(!input.contents.contains_key("3"));
+    }
+
+    #[test]
+    fn html_body_extracts_document_body() {
+        assert_eq!(
+            html_body("<html><BODY class=\"x\"><p>正文</p></body></html>"),
+            "<p>正文</p>"
+        );
+        assert_eq!(html_body("<p>片段</p>"), "<p>片段</p>");
+    }
+}
@@ -0,0 +1,35 @@
+//! 下载暂停/恢复。
+//!
+//! 与取消不同，暂停不会销毁线程池：worker 在取下一组任务前检查暂停标记并阻塞等待，
+//! 恢复后继续剩余章节。同一时间只有一个前台下载任务，因此使用进程级标记。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+pub fn is_paused() -> bool {
+    PAUSED.load(Ordering::SeqCst)
+}
+
+pub fn set_paused(paused: bool) {
+    PAUSED.store(paused, Ordering::SeqCst);
+}
+
+/// 切换暂停状态，返回切换后的值（true = 已暂停）。
+pub fn toggle_paused() -> bool {
+    !PAUSED.fetch_xor(true, Ordering::SeqCst)
+}
+
+/// 暂停期间阻塞当前线程；收到取消信号时立即返回。
+pub(crate) fn wait_while_paused(cancel: Option<&Arc<AtomicBool>>) {
+    while is_paused() {
+        if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+            return;
+        }
+        std::thread::sleep(PAUSE_POLL_INTERVAL);
+    }
+}
@@ -3,7 +3,8 @@
 //! 负责从官方 API 或 Web 端拉取目录、章节列表，合并元数据，生成 `DownloadPlan`。
 
 #[cfg(feature = "official-api")]
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::time::Duration;
 
 use anyhow::{Context, Result, anyhow};
@@ -13,6 +14,7 @@ use tracing::{info, warn};
 use crate::base_system::book_paths;
 use crate::base_system::context::Config;
 use crate::base_system::json_extract;
+use crate::book_parser::image_utils;
 use crate::network_parser::network::{FanqieWebConfig, FanqieWebNetwork};
 
 use super::models::{
@@ -26,14 +28,32 @@ use super::third_party::resolve_api_urls;
 #[cfg(feature = "official-api")]
 use tomato_novel_official_api::{DirectoryClient, SearchClient};
 
+/// 预先拉取目录与元数据，便于 UI 展示预览/范围选择。
+pub fn prepare_download_plan(
+    config: &Config,
+    book_id: &str,
+    meta_hint: BookMeta,
+) -> Result<DownloadPlan> {
+    prepare_plan(config, book_id, meta_hint, true)
+}
+
+/// 与 [`prepare_download_plan`] 相同但不下载封面，供试运行诊断使用（不写任何文件）。
+pub fn prepare_download_plan_dry(
+    config: &Config,
+    book_id: &str,
+    meta_hint: BookMeta,
+) -> Result<DownloadPlan> {
+    prepare_plan(config, book_id, meta_hint, false)
+}
+
 // ── 下载计划准备（官方 API 版本）──────────────────────────────────
 
-/// 预先拉取目录与元数据，便于 UI 展示预览/范围选择。
 #[cfg(feature = "official-api")]
-pub fn prepare_download_plan(
+fn prepare_plan(
     config: &Config,
     book_id: &str,
     meta_hint: BookMeta,
+    with_cover: bool,
 ) -> Result<DownloadPlan> {
     info!(target: "download", book_id, "准备下载计划");
     let directory = DirectoryClient::new().context("init DirectoryClient")?;
@@ -41,7 +61,7 @@ pub fn prepare_download_plan(
     let api_url = dir_url.as_deref();
 
     // 并行回退：预先尝试 Web 目录/简介（失败不影响主流程）
-    let web_plan = prepare_download_plan_web(config, book_id, meta_hint.clone()).ok();
+    let web_plan = prepare_download_plan_web(config, book_id, meta_hint.clone(), with_cover).ok();
 
     // 首次获取目录和元数据。
     let mut dir = match directory.fetch_directory_with_cover(book_id, api_url, None) {
@@ -98,41 +118,51 @@ pub fn prepare_download_plan(
         completed_meta.book_name = Some(preferred_name);
     }
 
-    // 封面下载：仅使用 web 页面抓取的封面（稳定），不再依赖 API 提供的 cover_url。
-    {
-        let cover_dir =
-            book_paths::book_folder_path(config, book_id, completed_meta.book_name.as_deref());
+    // 封面下载：默认使用 web 页面抓取的封面；prefer_hd_cover 时优先 detail_cover_url 高清图。
+    if with_cover {
+        let root =
+            config.save_path_rule_root(completed_meta.category.as_deref(), &completed_meta.tags);
+        let cover_dir = book_paths::book_folder_path(
+            config,
+            book_id,
+            completed_meta.book_name.as_deref(),
+            root.as_deref(),
+        );
         download_web_cover(config, book_id, &completed_meta, &cover_dir);
     }
 
     if let Some(web_plan) = web_plan.as_ref() {
         dir.chapters = merge_chapters_with_web(dir.chapters, &web_plan.chapters);
     }
+    let chapters = dedupe_chapters(book_id, std::mem::take(&mut dir.chapters));
 
-    Ok(DownloadPlan {
+    let plan = DownloadPlan {
         book_id: dir.book_id.clone(),
         meta: completed_meta,
-        chapters: dir.chapters,
+        chapters,
         _raw: dir.raw,
-    })
+    };
+    warn_catalog_mismatch(&plan);
+    Ok(plan)
 }
 
 // ── 下载计划准备（非 official-api 版本）──────────────────────────
 
 /// no-official-api：使用 FanqieWebNetwork 拉目录 + 拉书本信息。
 #[cfg(not(feature = "official-api"))]
-pub fn prepare_download_plan(
+fn prepare_plan(
     config: &Config,
     book_id: &str,
     meta_hint: BookMeta,
+    with_cover: bool,
 ) -> Result<DownloadPlan> {
     info!(target: "download", book_id, "准备下载计划（no-official）");
-    prepare_download_plan_web(config, book_id, meta_hint)
+    prepare_download_plan_web(config, book_id, meta_hint, with_cover)
 }
 
 // ── Web 端回退 ──────────────────────────────────────────────────
 
-fn parse_chapter_ref_from_value(v: &Value) -> Option<ChapterRef> {
+pub(crate) fn parse_chapter_ref_from_value(v: &Value) -> Option<ChapterRef> {
     let maps = json_extract::collect_maps(v);
     let id = maps.iter().find_map(|m| {
         json_extract::pick_string(
@@ -171,6 +201,7 @@ fn prepare_download_plan_web(
     config: &Config,
     book_id: &str,
     meta_hint: BookMeta,
+    with_cover: bool,
 ) -> Result<DownloadPlan> {
     info!(target: "download", book_id, "准备下载计划（web fallback）");
 
@@ -227,9 +258,15 @@ fn prepare_download_plan_web(
     }
 
     // 封面下载：使用 web 页面抓取的封面
-    {
-        let cover_dir =
-            book_paths::book_folder_path(config, book_id, completed_meta.book_name.as_deref());
+    if with_cover {
+        let root =
+            config.save_path_rule_root(completed_meta.category.as_deref(), &completed_meta.tags);
+        let cover_dir = book_paths::book_folder_path(
+            config,
+            book_id,
+            completed_meta.book_name.as_deref(),
+            root.as_deref(),
+        );
         download_web_cover(config, book_id, &completed_meta, &cover_dir);
     }
 
@@ -240,16 +277,52 @@ fn prepare_download_plan_web(
     });
 
     // 章节顺序：web 接口一般已经是正确顺序；保险起见保持原顺序即可
-    Ok(DownloadPlan {
+    let plan = DownloadPlan {
         book_id: book_id.to_string(),
         meta: completed_meta,
-        chapters: std::mem::take(&mut chapters),
+        chapters: dedupe_chapters(book_id, std::mem::take(&mut chapters)),
         _raw: raw,
-    })
+    };
+    warn_catalog_mismatch(&plan);
+    Ok(plan)
+}
+
+/// 目录章节数与元数据不符时记录警告，尽早暴露目录解析问题。
+fn warn_catalog_mismatch(plan: &DownloadPlan) {
+    if let Some((parsed, claimed)) = plan.catalog_mismatch() {
+        warn!(
+            target: "download",
+            book_id = %plan.book_id,
+            parsed,
+            claimed,
+            "目录可能不完整（解析到 {parsed} 章，元数据称 {claimed} 章）"
+        );
+    }
 }
 
 // ── 章节合并 ──────────────────────────────────────────────────
 
+/// 按 chapter_id 去重（保留首次出现，保序），需在 `apply_range` 之前完成。
+pub(crate) fn dedupe_chapters(book_id: &str, chapters: Vec<ChapterRef>) -> Vec<ChapterRef> {
+    let before = chapters.len();
+    let mut seen: HashSet<String> = HashSet::with_capacity(before);
+    let out: Vec<ChapterRef> = chapters
+        .into_iter()
+        .filter(|ch| seen.insert(ch.id.clone()))
+        .collect();
+    let removed = before - out.len();
+    if removed > 0 {
+        warn!(
+            target: "download",
+            book_id,
+            removed,
+            "目录中存在重复 chapter_id，已去除 {} 个重复章节",
+            removed
+        );
+    }
+    out
+}
+
 #[cfg(feature = "official-api")]
 pub(crate) fn merge_chapters_with_web(
     official: Vec<ChapterRef>,
@@ -396,10 +469,64 @@ pub(crate) fn apply_range(chapters: &[ChapterRef], range: Option<ChapterRange>)
     }
 }
 
-// ── 封面下载（仅从 web 页面抓取）────────────────────────────────
+/// 按勾选集合过滤章节（保持目录顺序）；未勾选时回退到区间过滤。
+pub(crate) fn apply_selection(
+    chapters: &[ChapterRef],
+    range: Option<ChapterRange>,
+    selection: Option<&HashSet<String>>,
+) -> Vec<ChapterRef> {
+    match selection {
+        Some(ids) => chapters
+            .iter()
+            .filter(|ch| ids.contains(&ch.id))
+            .cloned()
+            .collect(),
+        None => apply_range(chapters, range),
+    }
+}
+
+/// 按 `skip_title_patterns` 剔除标题命中的章节（在范围/勾选之后调用），被跳过的标题写入日志。
+///
+/// 无法编译的正则记录警告后忽略。
+pub(crate) fn apply_title_filter(config: &Config, chapters: Vec<ChapterRef>) -> Vec<ChapterRef> {
+    let patterns: Vec<regex::Regex> = config
+        .skip_title_patterns
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .filter_map(|p| match regex::Regex::new(p) {
+            Ok(re) => Some(re),
+            Err(e) => {
+                warn!(target: "download", pattern = %p, error = %e, "skip_title_patterns 正则无效，已忽略");
+                None
+            }
+        })
+        .collect();
+    if patterns.is_empty() {
+        return chapters;
+    }
+    let (skipped, kept): (Vec<ChapterRef>, Vec<ChapterRef>) = chapters
+        .into_iter()
+        .partition(|ch| patterns.iter().any(|re| re.is_match(&ch.title)));
+    if !skipped.is_empty() {
+        let titles: Vec<&str> = skipped.iter().map(|ch| ch.title.as_str()).collect();
+        info!(
+            target: "download",
+            count = skipped.len(),
+            "按标题排除章节：{}",
+            titles.join("、")
+        );
+    }
+    kept
+}
+
+// ── 封面下载 ──────────────────────────────────────────────────
 
-/// 从番茄小说 web 页面抓取封面图片并保存到目标目录。
-/// 不再使用 API 提供的 cover_url（该路径不稳定，容易间歇性丢失）。
+/// 下载封面并保存到目标目录。
+///
+/// 默认只从番茄小说 web 页面抓取封面（API 的 cover_url 不稳定，容易间歇性丢失），存为 `cover.*`。
+/// 开启 `prefer_hd_cover` 时优先下载 `detail_cover_url`：原图存为 `cover_hd.*`，
+/// 压缩版存为 `cover.jpg` 供 EPUB 等格式使用；取不到高清图时回退到 web 封面。
 fn download_web_cover(
     config: &Config,
     book_id: &str,
@@ -409,14 +536,55 @@ fn download_web_cover(
     let book_name = meta.book_name.as_deref();
 
     // 检查并迁移旧版“书名.*”封面；新版统一保存为 cover.*。
-    if let Some(existing) = book_paths::migrate_legacy_cover_file(cover_dir, book_name) {
+    let existing = book_paths::migrate_legacy_cover_file(cover_dir, book_name);
+    let has_hd = book_paths::find_existing_hd_cover_file(cover_dir).is_some();
+    if let Some(existing) = existing.as_ref()
+        && (!config.prefer_hd_cover || has_hd)
+    {
         info!(target: "download", book_id, path = %existing.display(), "封面文件已存在，跳过下载");
         return;
     }
 
     let _ = std::fs::create_dir_all(cover_dir);
 
-    // 从 web 页面获取 html_img_cover_url
+    let mut web_info = None;
+    if config.prefer_hd_cover {
+        let hd_url = meta.detail_cover_url.clone().or_else(|| {
+            web_info
+                .get_or_insert_with(|| fetch_web_cover_urls(config, book_id))
+                .0
+                .clone()
+        });
+        if let Some(url) = hd_url
+            && save_hd_cover(config, book_id, &url, cover_dir)
+        {
+            return;
+        }
+        if existing.is_some() {
+            return;
+        }
+        warn!(target: "download", book_id, "高清封面获取失败，回退到 web 封面");
+    }
+
+    let (_, html_img_cover_url) = web_info.unwrap_or_else(|| fetch_web_cover_urls(config, book_id));
+    let Some(img_url) = html_img_cover_url else {
+        warn!(target: "download", book_id, "web 页面未提取到封面 URL，跳过封面下载");
+        return;
+    };
+    let Some((bytes, ext)) = fetch_cover_bytes(book_id, &img_url) else {
+        return;
+    };
+    let path = book_paths::canonical_cover_path(cover_dir, ext);
+    match std::fs::write(&path, &bytes) {
+        Ok(()) => info!(target: "download", book_id, path = %path.display(), "web 封面下载成功"),
+        Err(e) => {
+            warn!(target: "download", book_id, path = %path.display(), error = %e, "写入封面失败")
+        }
+    }
+}
+
+/// 从 web 页面取 (detail_cover_url, html_img_cover_url)，空串视为缺失。
+fn fetch_web_cover_urls(config: &Config, book_id: &str) -> (Option<String>, Option<String>) {
     let web_cfg = FanqieWebConfig {
         request_timeout: Duration::from_secs(config.request_timeout.max(1)),
         max_retries: 2,
@@ -426,18 +594,16 @@ fn download_web_cover(
         Ok(w) => w,
         Err(e) => {
             warn!(target: "download", book_id, error = %e, "初始化 FanqieWebNetwork 失败，跳过封面下载");
-            return;
-        }
-    };
-    let (_, _, _, _, _, _, html_img_cover_url, _, _) = web.get_book_info(book_id);
-    let img_url = match html_img_cover_url {
-        Some(ref u) if !u.trim().is_empty() => u.as_str(),
-        _ => {
-            warn!(target: "download", book_id, "web 页面未提取到封面 URL，跳过封面下载");
-            return;
+            return (None, None);
         }
     };
+    let (_, _, _, _, _, detail_cover_url, html_img_cover_url, _, _) = web.get_book_info(book_id);
+    let non_empty = |u: Option<String>| u.filter(|s| !s.trim().is_empty());
+    (non_empty(detail_cover_url), non_empty(html_img_cover_url))
+}
 
+/// 下载封面原图并按 magic bytes 嗅探扩展名；HEIC（EPUB 不支持）与重试耗尽时返回 None。
+fn fetch_cover_bytes(book_id: &str, url: &str) -> Option<(Vec<u8>, &'static str)> {
     let timeout = Duration::from_millis(10_000);
     let max_retries = 3u32;
 
@@ -447,31 +613,29 @@ fn download_web_cover(
             std::thread::sleep(Duration::from_millis(base_ms));
         }
 
-        let bytes = match crate::third_party::media_fetch::fetch_bytes(img_url, timeout) {
+        let bytes = match crate::third_party::media_fetch::fetch_bytes(url, timeout) {
             Some(b) if !b.is_empty() => b,
             _ => {
                 warn!(
                     target: "download",
                     book_id,
-                    url = img_url,
+                    url,
                     attempt = attempt + 1,
                     max_retries,
-                    "web 封面下载失败，重试中"
+                    "封面下载失败，重试中"
                 );
                 continue;
             }
         };
 
-        // 跳过 HEIC（EPUB 不支持）
         if bytes.len() >= 12
             && &bytes[4..8] == b"ftyp"
             && matches!(&bytes[8..12], b"heic" | b"heix" | b"mif1" | b"msf1")
         {
-            warn!(target: "download", book_id, "web 封面为 HEIC 格式，EPUB 不支持，跳过");
-            return;
+            warn!(target: "download", book_id, url, "封面为 HEIC 格式，EPUB 不支持，跳过");
+            return None;
         }
 
-        // 根据 magic bytes 嗅探格式
         let ext = if bytes.len() >= 8 && bytes[0] == 0x89 && &bytes[1..4] == b"PNG" {
             "png"
         } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
@@ -479,13 +643,45 @@ fn download_web_cover(
         } else {
             "jpg"
         };
+        return Some((bytes, ext));
+    }
 
-        let path = book_paths::canonical_cover_path(cover_dir, ext);
-        if std::fs::write(&path, &bytes).is_ok() {
-            info!(target: "download", book_id, path = %path.display(), "web 封面下载成功");
-            return;
-        }
+    warn!(target: "download", book_id, url, "封面下载失败（已重试 {} 次）", max_retries);
+    None
+}
+
+/// 保存高清原图为 `cover_hd.*`，并生成压缩版 `cover.jpg`（无法解码时直接复制原图）。
+fn save_hd_cover(config: &Config, book_id: &str, url: &str, cover_dir: &std::path::Path) -> bool {
+    let Some((bytes, ext)) = fetch_cover_bytes(book_id, url) else {
+        return false;
+    };
+    let hd_path = book_paths::hd_cover_path(cover_dir, ext);
+    if let Err(e) = std::fs::write(&hd_path, &bytes) {
+        warn!(target: "download", book_id, path = %hd_path.display(), error = %e, "写入高清封面失败");
+        return false;
     }
 
-    warn!(target: "download", book_id, "web 封面下载失败（已重试 {} 次）", max_retries);
+    let (cover_bytes, cover_ext) = match image_utils::compress_cover_jpeg(config, &bytes) {
+        Some(jpeg) => (jpeg, "jpg"),
+        None => (bytes, ext),
+    };
+    // 只保留一份 cover.*，避免导出时读到旧封面
+    for other in book_paths::COVER_IMAGE_EXTENSIONS {
+        if other != cover_ext {
+            let _ = std::fs::remove_file(book_paths::canonical_cover_path(cover_dir, other));
+        }
+    }
+    let cover_path = book_paths::canonical_cover_path(cover_dir, cover_ext);
+    if let Err(e) = std::fs::write(&cover_path, &cover_bytes) {
+        warn!(target: "download", book_id, path = %cover_path.display(), error = %e, "写入压缩版封面失败");
+        return false;
+    }
+    info!(
+        target: "download",
+        book_id,
+        hd = %hd_path.display(),
+        cover = %cover_path.display(),
+        "高清封面下载成功"
+    );
+    true
 }
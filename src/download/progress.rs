@@ -2,11 +2,13 @@
 
 #![cfg_attr(not(feature = "official-api"), allow(dead_code))]
 
+use std::time::{Duration, Instant};
+
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 
 use super::downloader::dynamic_group_count;
 use super::models::ChapterRef;
-use super::models::{ProgressSnapshot, SavePhase};
+use super::models::{FailureKind, ProgressSnapshot, SavePhase};
 use crate::base_system::context::Config;
 
 struct CliBars {
@@ -15,17 +17,122 @@ struct CliBars {
     save_bar: ProgressBar,
 }
 
+/// 百分比文本输出的步长。
+const TEXT_PROGRESS_STEP: usize = 10;
+
+/// 无 UI 回调、也不使用 CLI 进度条时（多 worker / 第三方模式）的纯文本进度。
+/// 每完成一组或百分比跨过一个步长时打印一行，避免刷屏。
+#[derive(Default)]
+struct TextProgress {
+    last_group: usize,
+    last_step: Option<usize>,
+    last_phase: Option<SavePhase>,
+}
+
+impl TextProgress {
+    fn line(&mut self, snap: &ProgressSnapshot) -> Option<String> {
+        if snap.chapter_total == 0 {
+            return None;
+        }
+        let percent = snap.saved_chapters.min(snap.chapter_total) * 100 / snap.chapter_total;
+        let step = percent / TEXT_PROGRESS_STEP;
+        let group_advanced = snap.group_done > self.last_group;
+        let step_advanced = self.last_step.is_none_or(|last| step > last);
+        let phase_changed = self.last_phase.is_some_and(|p| p != snap.save_phase);
+        if !(group_advanced || step_advanced || phase_changed) {
+            return None;
+        }
+        self.last_group = snap.group_done;
+        self.last_step = Some(step);
+        self.last_phase = Some(snap.save_phase);
+
+        let label = match snap.save_phase {
+            SavePhase::TextSave => "正文",
+            SavePhase::Audiobook => "有声书",
+        };
+        let mut line = format!(
+            "进度 {percent:>3}% | {label} {}/{} 章",
+            snap.saved_chapters, snap.chapter_total
+        );
+        if snap.group_total > 0 && snap.save_phase == SavePhase::TextSave {
+            line.push_str(&format!(" | 分组 {}/{}", snap.group_done, snap.group_total));
+        }
+        Some(line)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// 并发下载时 UI 回调的最小上报间隔。
+const UI_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+/// 间隔未到时，累计这么多次计数更新也强制上报一次。
+const UI_EMIT_BATCH: usize = 20;
+
+/// 多 worker 模式下章节乱序完成，逐章回调会让 UI 进度条抖动且挤占 channel，
+/// 这里按时间间隔或累计次数合并上报。
+#[derive(Default)]
+struct UiThrottle {
+    last: Option<Instant>,
+    pending: usize,
+}
+
+impl UiThrottle {
+    fn ready(&mut self, now: Instant) -> bool {
+        self.pending += 1;
+        let due = self
+            .last
+            .is_none_or(|last| now.duration_since(last) >= UI_EMIT_INTERVAL);
+        due || self.pending >= UI_EMIT_BATCH
+    }
+
+    fn mark(&mut self, now: Instant) {
+        self.last = Some(now);
+        self.pending = 0;
+    }
+}
+
 pub(crate) struct ProgressReporter {
     pub(crate) snapshot: ProgressSnapshot,
     pub(crate) cb: Option<Box<dyn FnMut(ProgressSnapshot) + Send>>, // optional UI callback
     cli: Option<CliBars>,
+    text: Option<TextProgress>,
+    /// 仅在有 UI 回调且并发下载时启用；串行模式逐章上报。
+    throttle: Option<UiThrottle>,
+    /// 分组计算使用的批量大小，重试时重新估算组数。
+    batch_size: usize,
 }
 
 impl ProgressReporter {
     pub(crate) fn emit(&mut self) {
+        if let Some(throttle) = self.throttle.as_mut() {
+            throttle.mark(Instant::now());
+        }
         if let Some(cb) = self.cb.as_mut() {
             cb(self.snapshot);
         }
+        if let Some(line) = self.text.as_mut().and_then(|t| t.line(&self.snapshot)) {
+            eprintln!("{line}");
+        }
+    }
+
+    /// 计数类更新走节流；`done` 表示该计数已到总数，立即上报保证 UI 停在最终值。
+    fn emit_counter(&mut self, done: bool) {
+        let skip = match self.throttle.as_mut() {
+            Some(throttle) => !done && !throttle.ready(Instant::now()),
+            None => false,
+        };
+        if !skip {
+            self.emit();
+        }
+    }
+
+    /// 把节流中积压的更新立即推给 UI。
+    pub(crate) fn flush(&mut self) {
+        if self.throttle.as_ref().is_some_and(|t| t.pending > 0) {
+            self.emit();
+        }
     }
 
     pub(crate) fn inc_group(&mut self) {
@@ -35,7 +142,7 @@ impl ProgressReporter {
 
     pub(crate) fn inc_saved(&mut self) {
         self.snapshot.saved_chapters += 1;
-        self.emit();
+        self.emit_counter(self.snapshot.saved_chapters >= self.snapshot.chapter_total);
     }
 
     pub(crate) fn set_save_phase(&mut self, phase: SavePhase) {
@@ -59,13 +166,20 @@ impl ProgressReporter {
         self.emit();
     }
 
+    pub(crate) fn set_top_failure(&mut self, kind: Option<FailureKind>) {
+        if self.snapshot.top_failure != kind {
+            self.snapshot.top_failure = kind;
+            self.emit();
+        }
+    }
+
     pub(crate) fn inc_save_progress(&mut self) {
         if self.snapshot.chapter_total == 0 {
             return;
         }
         self.snapshot.saved_chapters =
             (self.snapshot.saved_chapters + 1).min(self.snapshot.chapter_total);
-        self.emit();
+        self.emit_counter(self.snapshot.saved_chapters == self.snapshot.chapter_total);
     }
 
     pub(crate) fn inc_comment_fetch(&mut self) {
@@ -74,7 +188,7 @@ impl ProgressReporter {
         }
         self.snapshot.comment_fetch =
             (self.snapshot.comment_fetch + 1).min(self.snapshot.comment_total);
-        self.emit();
+        self.emit_counter(self.snapshot.comment_fetch == self.snapshot.comment_total);
     }
 
     pub(crate) fn inc_comment_saved(&mut self) {
@@ -83,15 +197,18 @@ impl ProgressReporter {
         }
         self.snapshot.comment_saved =
             (self.snapshot.comment_saved + 1).min(self.snapshot.comment_total);
-        self.emit();
+        self.emit_counter(self.snapshot.comment_saved == self.snapshot.comment_total);
     }
 
     pub(crate) fn reset_for_retry(&mut self, total: usize, pending_len: usize) {
         self.snapshot.group_done = 0;
-        self.snapshot.group_total = dynamic_group_count(pending_len);
+        self.snapshot.group_total = dynamic_group_count(pending_len, self.batch_size);
         self.snapshot.saved_chapters = total.saturating_sub(pending_len);
         self.snapshot.chapter_total = total;
         self.snapshot.save_phase = SavePhase::TextSave;
+        if let Some(text) = self.text.as_mut() {
+            text.reset();
+        }
         self.emit();
 
         if let Some(cli) = self.cli.as_ref() {
@@ -130,7 +247,7 @@ impl ProgressReporter {
 pub(crate) fn segment_enabled(cfg: &Config) -> bool {
     #[cfg(feature = "official-api")]
     {
-        cfg.enable_segment_comments && cfg.novel_format.eq_ignore_ascii_case("epub")
+        cfg.enable_segment_comments && cfg.wants_epub()
     }
     #[cfg(not(feature = "official-api"))]
     {
@@ -146,7 +263,8 @@ pub(crate) fn make_reporter(
     progress: Option<Box<dyn FnMut(ProgressSnapshot) + Send>>,
 ) -> ProgressReporter {
     let total = chosen.len();
-    let group_total = dynamic_group_count(pending.len());
+    let batch_size = config.effective_batch_size();
+    let group_total = dynamic_group_count(pending.len(), batch_size);
 
     let use_cli_bars = progress.is_none()
         && config.use_official_api
@@ -177,6 +295,9 @@ pub(crate) fn make_reporter(
     } else {
         None
     };
+    let throttle = (progress.is_some() && config.max_workers.max(1) > 1).then(UiThrottle::default);
+    let text =
+        (progress.is_none() && cli.is_none() && !pending.is_empty()).then(TextProgress::default);
 
     let mut reporter = ProgressReporter {
         snapshot: ProgressSnapshot {
@@ -191,10 +312,68 @@ pub(crate) fn make_reporter(
             audiobook_generated: 0,
             audiobook_skipped: 0,
             audiobook_failed: 0,
+            top_failure: None,
         },
         cb: progress,
         cli,
+        text,
+        throttle,
+        batch_size,
     };
     reporter.emit();
     reporter
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{TextProgress, UI_EMIT_BATCH, UI_EMIT_INTERVAL, UiThrottle};
+    use crate::download::models::{ProgressSnapshot, SavePhase};
+
+    fn snap(saved: usize, group_done: usize) -> ProgressSnapshot {
+        ProgressSnapshot {
+            group_done,
+            group_total: 4,
+            saved_chapters: saved,
+            chapter_total: 100,
+            save_phase: SavePhase::TextSave,
+            comment_fetch: 0,
+            comment_total: 0,
+            comment_saved: 0,
+            audiobook_generated: 0,
+            audiobook_skipped: 0,
+            audiobook_failed: 0,
+            top_failure: None,
+        }
+    }
+
+    #[test]
+    fn text_progress_prints_on_group_or_step() {
+        let mut text = TextProgress::default();
+        assert_eq!(
+            text.line(&snap(0, 0)).as_deref(),
+            Some("进度   0% | 正文 0/100 章 | 分组 0/4")
+        );
+        assert!(text.line(&snap(5, 0)).is_none());
+        assert!(text.line(&snap(12, 0)).is_some());
+        assert!(text.line(&snap(13, 1)).is_some());
+        assert!(text.line(&snap(14, 1)).is_none());
+    }
+
+    #[test]
+    fn ui_throttle_emits_by_interval_or_batch() {
+        let start = Instant::now();
+        let mut throttle = UiThrottle::default();
+        assert!(throttle.ready(start));
+        throttle.mark(start);
+
+        let soon = start + Duration::from_millis(10);
+        assert!(!throttle.ready(soon));
+        assert!(throttle.ready(start + UI_EMIT_INTERVAL));
+
+        throttle.mark(soon);
+        let hits = (0..UI_EMIT_BATCH).filter(|_| throttle.ready(soon)).count();
+        assert_eq!(hits, 1);
+    }
+}
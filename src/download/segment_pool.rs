@@ -23,13 +23,18 @@ pub(crate) use crate::book_parser::segment_shared::{
     SegmentCommentsChapterCache, SegmentCommentsParaCache,
 };
 #[cfg(feature = "official-api")]
-pub(crate) use crate::book_parser::segment_shared::{extract_para_counts_from_stats, write_atomic};
+pub(crate) use crate::book_parser::segment_shared::{
+    extract_para_counts_from_stats, segment_cache_is_stale, write_atomic,
+};
 
 #[cfg(feature = "official-api")]
 use tomato_novel_official_api::{CommentDownloadOptions, DirectoryClient, ReviewClient};
 
 #[cfg(feature = "official-api")]
-use std::sync::OnceLock;
+use std::sync::{Mutex, OnceLock};
+
+#[cfg(feature = "official-api")]
+use tracing::{debug, info};
 
 #[cfg(feature = "official-api")]
 #[derive(Debug, Clone, Copy)]
@@ -55,16 +60,14 @@ pub(crate) fn count_segment_comment_cache_files(seg_dir: &Path) -> usize {
 
 // ── 单章段评拉取 ──────────────────────────────────────────────────
 
-#[allow(clippy::too_many_arguments)]
+/// 第一步：仅请求轻量 stats，得到各段评论数（不含详情）。
 #[cfg(feature = "official-api")]
-fn fetch_segment_comments_for_chapter(
+fn fetch_segment_comment_counts(
     client: &ReviewClient,
-    _cfg: &Config,
     book_id: &str,
     chapter_id: &str,
     item_version: &str,
     top_n: usize,
-    status_dir: Option<&Path>,
     cancel: Option<&Arc<AtomicBool>>,
 ) -> Option<SegmentCommentsChapterCache> {
     if cancel.map(|c| c.load(Ordering::Relaxed)).unwrap_or(false) {
@@ -77,33 +80,43 @@ fn fetch_segment_comments_for_chapter(
 
     let mut paras: std::collections::BTreeMap<String, SegmentCommentsParaCache> =
         std::collections::BTreeMap::new();
-    let mut para_with_comments: Vec<i32> = Vec::new();
     for (k, v) in seg_counts {
-        let Some(idx) = k.parse::<i32>().ok() else {
+        if k.parse::<i32>().is_err() {
             continue;
-        };
-        let cnt = v.as_u64().unwrap_or(0);
+        }
         paras.insert(
-            k.clone(),
+            k,
             SegmentCommentsParaCache {
-                count: cnt,
+                count: v.as_u64().unwrap_or(0),
                 detail: None,
             },
         );
-        if cnt > 0 {
-            para_with_comments.push(idx);
-        }
     }
 
-    if para_with_comments.is_empty() {
-        return Some(SegmentCommentsChapterCache {
-            chapter_id: chapter_id.to_string(),
-            book_id: book_id.to_string(),
-            item_version: item_version.to_string(),
-            top_n,
-            paras,
-        });
-    }
+    Some(SegmentCommentsChapterCache {
+        chapter_id: chapter_id.to_string(),
+        book_id: book_id.to_string(),
+        item_version: item_version.to_string(),
+        top_n,
+        paras,
+        fetched_at: now_secs(),
+        details_complete: false,
+    })
+}
+
+/// 第二步：对有评论且尚无详情的段落逐个抓取详情；取消时返回 false。
+#[cfg(feature = "official-api")]
+fn fill_segment_comment_details(
+    client: &ReviewClient,
+    cache: &mut SegmentCommentsChapterCache,
+    cancel: Option<&Arc<AtomicBool>>,
+) -> bool {
+    let para_with_comments: Vec<i32> = cache
+        .paras
+        .iter()
+        .filter(|(_, p)| p.count > 0 && p.detail.is_none())
+        .filter_map(|(k, _)| k.parse::<i32>().ok())
+        .collect();
 
     // IMPORTANT: Do NOT spawn a per-paragraph thread pool here.
     // This function is called inside a chapter-level worker pool, and per-paragraph
@@ -111,19 +124,32 @@ fn fetch_segment_comments_for_chapter(
     // of concurrent requests, easily triggering IP 风控.
     //
     // Keep it sequential and rely on the outer pool for parallelism.
-    let _ = status_dir; // kept for API stability (media is handled by the ReviewClient options)
-    for para_idx in &para_with_comments {
+    for para_idx in para_with_comments {
         if cancel.map(|c| c.load(Ordering::Relaxed)).unwrap_or(false) {
-            return None;
+            return false;
         }
         let fetched = client
-            .fetch_para_comments(chapter_id, book_id, *para_idx, item_version, top_n, 2)
+            .fetch_para_comments(
+                &cache.chapter_id,
+                &cache.book_id,
+                para_idx,
+                &cache.item_version,
+                cache.top_n,
+                2,
+            )
             .or_else(|_| {
-                client.fetch_para_comments(chapter_id, book_id, *para_idx, item_version, top_n, 0)
+                client.fetch_para_comments(
+                    &cache.chapter_id,
+                    &cache.book_id,
+                    para_idx,
+                    &cache.item_version,
+                    cache.top_n,
+                    0,
+                )
             });
         if let Ok(Some(res)) = fetched
             && !res.response.reviews.is_empty()
-            && let Some(entry) = paras.get_mut(&para_idx.to_string())
+            && let Some(entry) = cache.paras.get_mut(&para_idx.to_string())
         {
             entry.detail = Some(res.response);
         } else {
@@ -131,14 +157,87 @@ fn fetch_segment_comments_for_chapter(
             std::thread::sleep(Duration::from_millis(200));
         }
     }
+    cache.details_complete = true;
+    true
+}
 
-    Some(SegmentCommentsChapterCache {
+#[cfg(feature = "official-api")]
+fn chapter_comment_total(cache: &SegmentCommentsChapterCache) -> u64 {
+    cache.paras.values().map(|p| p.count).sum()
+}
+
+#[cfg(feature = "official-api")]
+fn empty_chapter_cache(
+    book_id: &str,
+    chapter_id: &str,
+    item_version: &str,
+    top_n: usize,
+) -> SegmentCommentsChapterCache {
+    SegmentCommentsChapterCache {
         chapter_id: chapter_id.to_string(),
         book_id: book_id.to_string(),
         item_version: item_version.to_string(),
         top_n,
-        paras,
-    })
+        paras: std::collections::BTreeMap::new(),
+        fetched_at: 0,
+        details_complete: true,
+    }
+}
+
+#[cfg(feature = "official-api")]
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// 已有缓存需要重新抓取时返回旧缓存：只存了评论数（热门章节模式的冷门章节），
+/// 或开启刷新且已过期；其余情况（含读取失败）视为命中。
+#[cfg(feature = "official-api")]
+fn stale_chapter_cache(path: &Path, refresh_hours: u64) -> Option<SegmentCommentsChapterCache> {
+    let bytes = std::fs::read(path).ok()?;
+    let cache: SegmentCommentsChapterCache = serde_json::from_slice(&bytes).ok()?;
+    let expired =
+        refresh_hours != 0 && segment_cache_is_stale(cache.fetched_at, now_secs(), refresh_hours);
+    (!cache.details_complete || expired).then_some(cache)
+}
+
+/// 每个段评 worker 独立的 ReviewClient。
+#[cfg(feature = "official-api")]
+fn make_review_client(cfg: &Config, workers: usize, status_dir: &Path) -> Option<ReviewClient> {
+    // Treat cfg.media_download_workers as a global budget and distribute it across
+    // segment-comment workers to avoid multiplicative explosions.
+    let media_workers = {
+        let total = cfg.media_download_workers.max(1);
+        let per = total.div_ceil(workers);
+        per.clamp(1, 8)
+    };
+    let review_options = CommentDownloadOptions {
+        enable_comments: true,
+        download_avatars: cfg.wants_comment_avatars(),
+        download_images: cfg.wants_comment_images(),
+        media_workers,
+        status_dir: Some(status_dir.to_path_buf()),
+        media_timeout_secs: 8,
+        media_retries: 2,
+    };
+    ReviewClient::new(review_options).ok()
+}
+
+/// 落盘成功后才上报进度，避免"进度跑满但还在写"。
+#[cfg(feature = "official-api")]
+fn save_chapter_cache(
+    seg_dir: &Path,
+    cache: &SegmentCommentsChapterCache,
+    tx_evt: &channel::Sender<SegmentEvent>,
+) {
+    let out_path = seg_dir.join(format!("{}.json", cache.chapter_id));
+    if let Ok(bytes) = serde_json::to_vec(cache)
+        && write_atomic(&out_path, &bytes).is_ok()
+    {
+        let _ = tx_evt.send(SegmentEvent::Saved);
+    }
 }
 
 // ── SegmentCommentPool（official-api 版本）──────────────────────
@@ -146,8 +245,15 @@ fn fetch_segment_comments_for_chapter(
 #[cfg(feature = "official-api")]
 pub(crate) struct SegmentCommentPool {
     tx: Option<channel::Sender<String>>,
+    tx_evt: channel::Sender<SegmentEvent>,
     rx_evt: channel::Receiver<SegmentEvent>,
     handles: Vec<std::thread::JoinHandle<()>>,
+    cfg: Config,
+    status_dir: PathBuf,
+    workers: usize,
+    cancel: Option<Arc<AtomicBool>>,
+    /// 热门章节模式：第一轮只收集各章评论数，结束时再挑选前 N 章抓详情。
+    hot_pending: Option<Arc<Mutex<Vec<SegmentCommentsChapterCache>>>>,
 }
 
 #[cfg(feature = "official-api")]
@@ -174,6 +280,9 @@ impl SegmentCommentPool {
         let item_versions = Arc::new(item_versions);
         let seg_dir = status_dir.join("segment_comments");
         let _ = std::fs::create_dir_all(&seg_dir);
+        let hot_pending = cfg
+            .segment_comments_hot_chapters_only
+            .then(|| Arc::new(Mutex::new(Vec::new())));
 
         let mut handles = Vec::with_capacity(workers);
         for _ in 0..workers {
@@ -184,27 +293,11 @@ impl SegmentCommentPool {
             let status_dir = status_dir.clone();
             let item_versions = item_versions.clone();
             let cancel = cancel.clone();
+            let hot_pending = hot_pending.clone();
 
             handles.push(std::thread::spawn(move || {
-                // Treat cfg.media_download_workers as a global budget and distribute it across
-                // segment-comment workers to avoid multiplicative explosions.
-                let media_workers = {
-                    let total = cfg.media_download_workers.max(1);
-                    let per = total.div_ceil(workers);
-                    per.clamp(1, 8)
-                };
-                let review_options = CommentDownloadOptions {
-                    enable_comments: true,
-                    download_avatars: cfg.download_comment_avatars,
-                    download_images: cfg.download_comment_images,
-                    media_workers,
-                    status_dir: Some(status_dir.clone()),
-                    media_timeout_secs: 8,
-                    media_retries: 2,
-                };
-                let client = match ReviewClient::new(review_options) {
-                    Ok(c) => c,
-                    Err(_) => return,
+                let Some(client) = make_review_client(&cfg, workers, &status_dir) else {
+                    return;
                 };
 
                 let seg_dir = status_dir.join("segment_comments");
@@ -229,10 +322,18 @@ impl SegmentCommentPool {
                     };
 
                     let out_path = seg_dir.join(format!("{}.json", chapter_id));
-                    if out_path.exists() {
-                        let _ = tx_evt.send(SegmentEvent::Saved);
-                        continue;
-                    }
+                    let previous = if out_path.exists() {
+                        let stale =
+                            stale_chapter_cache(&out_path, cfg.segment_comments_refresh_hours);
+                        if stale.is_none() {
+                            let _ = tx_evt.send(SegmentEvent::Saved);
+                            continue;
+                        }
+                        stale
+                    } else {
+                        None
+                    };
+                    let refreshing = previous.is_some();
 
                     let item_version = item_versions
                         .get(&chapter_id)
@@ -257,38 +358,67 @@ impl SegmentCommentPool {
 
                     let top_n = cfg.segment_comments_top_n.max(1);
 
-                    let cache = fetch_segment_comments_for_chapter(
+                    let mut cache = fetch_segment_comment_counts(
                         &client,
-                        &cfg,
                         &book_id,
                         &chapter_id,
                         &item_version,
                         top_n,
-                        Some(&status_dir),
                         cancel.as_ref(),
-                    )
-                    .unwrap_or_else(|| SegmentCommentsChapterCache {
-                        chapter_id: chapter_id.clone(),
-                        book_id: book_id.clone(),
-                        item_version: item_version.clone(),
-                        top_n,
-                        paras: std::collections::BTreeMap::new(),
-                    });
-
-                    // Best-effort write; 仅在落盘成功后上报进度，避免"进度跑满但还在写"。
-                    if let Ok(bytes) = serde_json::to_vec(&cache)
-                        && write_atomic(&out_path, &bytes).is_ok()
+                    );
+                    if let Some(old) = previous {
+                        // 刷新时拉取评论数失败则保留旧缓存
+                        let Some(c) = cache.as_mut() else {
+                            let _ = tx_evt.send(SegmentEvent::Saved);
+                            continue;
+                        };
+                        let reused = c.reuse_unchanged_details(old);
+                        debug!(
+                            target: "segment",
+                            chapter_id = %chapter_id,
+                            reused,
+                            "段评缓存已过期或缺少详情，仅刷新评论数变化的段落"
+                        );
+                    }
+                    if let Some(pending) = &hot_pending {
+                        let cache = cache.unwrap_or_else(|| {
+                            empty_chapter_cache(&book_id, &chapter_id, &item_version, top_n)
+                        });
+                        pending
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .push(cache);
+                        continue;
+                    }
+                    if let Some(c) = cache.as_mut()
+                        && !fill_segment_comment_details(&client, c, cancel.as_ref())
                     {
-                        let _ = tx_evt.send(SegmentEvent::Saved);
+                        if refreshing {
+                            // 取消时不覆盖旧缓存
+                            continue;
+                        }
+                        cache = None;
                     }
+                    let cache = cache.unwrap_or_else(|| {
+                        empty_chapter_cache(&book_id, &chapter_id, &item_version, top_n)
+                    });
+
+                    // Best-effort write
+                    save_chapter_cache(&seg_dir, &cache, &tx_evt);
                 }
             }));
         }
 
         Some(Self {
             tx: Some(tx),
+            tx_evt,
             rx_evt,
             handles,
+            cfg,
+            status_dir,
+            workers,
+            cancel,
+            hot_pending,
         })
     }
 
@@ -314,8 +444,82 @@ impl SegmentCommentPool {
         for h in self.handles.drain(..) {
             let _ = h.join();
         }
+        if let Some(pending) = self.hot_pending.take() {
+            let pending = std::mem::take(&mut *pending.lock().unwrap_or_else(|e| e.into_inner()));
+            self.finish_hot_chapters(pending, progress);
+        }
         self.drain_progress(progress);
     }
+
+    /// 按评论总量排序，仅为前 N 章抓取详情；其余章节只保存各段评论数。
+    fn finish_hot_chapters(
+        &self,
+        mut pending: Vec<SegmentCommentsChapterCache>,
+        progress: &mut ProgressReporter,
+    ) {
+        let cancelled = self
+            .cancel
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::Relaxed));
+        if pending.is_empty() || cancelled {
+            return;
+        }
+        pending.sort_by_key(|c| std::cmp::Reverse(chapter_comment_total(c)));
+        let hot_n = self
+            .cfg
+            .segment_comments_hot_chapters
+            .max(1)
+            .min(pending.len());
+        let cold = pending.split_off(hot_n);
+        info!(
+            target: "segment",
+            hot = pending.len(),
+            skipped = cold.len(),
+            "热门章节模式：仅抓取评论最多的章节段评详情"
+        );
+
+        let seg_dir = self.status_dir.join("segment_comments");
+        for cache in &cold {
+            save_chapter_cache(&seg_dir, cache, &self.tx_evt);
+        }
+        self.drain_progress(progress);
+
+        let (tx, rx) = channel::unbounded::<SegmentCommentsChapterCache>();
+        for cache in pending {
+            let _ = tx.send(cache);
+        }
+        drop(tx);
+
+        let workers = self.workers;
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                let rx = rx.clone();
+                let tx_evt = self.tx_evt.clone();
+                let cfg = self.cfg.clone();
+                let status_dir = self.status_dir.clone();
+                let seg_dir = seg_dir.clone();
+                let cancel = self.cancel.clone();
+                std::thread::spawn(move || {
+                    let Some(client) = make_review_client(&cfg, workers, &status_dir) else {
+                        return;
+                    };
+                    for mut cache in rx.iter() {
+                        if !fill_segment_comment_details(&client, &mut cache, cancel.as_ref()) {
+                            return;
+                        }
+                        save_chapter_cache(&seg_dir, &cache, &tx_evt);
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            while !h.is_finished() {
+                self.drain_progress(progress);
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            let _ = h.join();
+        }
+    }
 }
 
 // ── SegmentCommentPool（非 official-api 占位版本）──────────────────
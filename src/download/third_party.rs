@@ -1,15 +1,22 @@
 //! 第三方 API 地址解析、请求、重试逻辑。
 
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::time::Duration;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use anyhow::{Result, anyhow};
+use tracing::{debug, warn};
 
 use super::models::ChapterRef;
 use crate::base_system::context::Config;
+use crate::base_system::json_extract::ResponseJsonError;
+use crate::base_system::request_error::{FailurePhase, RequestErrorKind, TimeoutRetryStrategy};
+use crate::base_system::request_trace::{self, TraceOutcome};
 use crate::book_parser::parser::ContentParser;
-use crate::third_party::content_client::ThirdPartyContentClient;
+use crate::third_party::content_client::{ThirdPartyContentClient, http_status_of};
 
 #[cfg(feature = "official-api")]
 fn normalize_base(base: &str) -> String {
@@ -70,6 +77,74 @@ pub(crate) fn resolve_api_urls(
     ))
 }
 
+/// 单个 endpoint 的实时请求统计，供 UI 看板展示。
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EndpointStat {
+    pub(crate) endpoint: String,
+    pub(crate) requests: u64,
+    pub(crate) success: u64,
+    pub(crate) failed: u64,
+    /// 已因返回空正文被移出地址池
+    pub(crate) disabled: bool,
+}
+
+impl EndpointStat {
+    /// 成功率（百分比），尚无请求时为 None。
+    pub(crate) fn success_rate(&self) -> Option<u64> {
+        (self.requests > 0).then(|| self.success * 100 / self.requests)
+    }
+}
+
+fn endpoint_stats() -> &'static Mutex<Vec<EndpointStat>> {
+    static STATS: OnceLock<Mutex<Vec<EndpointStat>>> = OnceLock::new();
+    STATS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn with_endpoint_stat(endpoint: &str, f: impl FnOnce(&mut EndpointStat)) {
+    let mut guard = endpoint_stats().lock().unwrap_or_else(|e| e.into_inner());
+    let idx = match guard.iter().position(|s| s.endpoint == endpoint) {
+        Some(idx) => idx,
+        None => {
+            guard.push(EndpointStat {
+                endpoint: endpoint.to_string(),
+                ..EndpointStat::default()
+            });
+            guard.len() - 1
+        }
+    };
+    f(&mut guard[idx]);
+}
+
+/// 开始新一轮下载时清空统计，并按地址池顺序预置各 endpoint。
+pub(crate) fn reset_endpoint_stats(endpoints: &[String]) {
+    let mut guard = endpoint_stats().lock().unwrap_or_else(|e| e.into_inner());
+    *guard = endpoints
+        .iter()
+        .map(|ep| EndpointStat {
+            endpoint: ep.clone(),
+            ..EndpointStat::default()
+        })
+        .collect();
+}
+
+pub(crate) fn endpoint_stats_snapshot() -> Vec<EndpointStat> {
+    endpoint_stats()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+fn record_endpoint_result(endpoint: &str, ok: bool) {
+    with_endpoint_stat(endpoint, |s| {
+        s.requests += 1;
+        if ok {
+            s.success += 1;
+        } else {
+            s.failed += 1;
+        }
+    });
+}
+
 pub(crate) fn ms_from_connect_timeout_secs(v: f64) -> Option<u64> {
     if v <= 0.0 {
         return None;
@@ -82,7 +157,17 @@ pub(crate) fn third_party_client_for_endpoint(
     cfg: &Config,
     endpoint: &str,
 ) -> Result<ThirdPartyContentClient> {
-    let timeout_ms = Some(cfg.request_timeout.saturating_mul(1000).max(100));
+    third_party_client_with_timeout_scale(cfg, endpoint, 1.0)
+}
+
+/// 读取超时后重试用：`request_timeout` 按倍率放宽，连接超时保持不变。
+fn third_party_client_with_timeout_scale(
+    cfg: &Config,
+    endpoint: &str,
+    scale: f64,
+) -> Result<ThirdPartyContentClient> {
+    let base_ms = cfg.request_timeout.saturating_mul(1000) as f64;
+    let timeout_ms = Some(((base_ms * scale.max(1.0)) as u64).max(100));
     let connect_timeout_ms = ms_from_connect_timeout_secs(cfg.min_connect_timeout);
     ThirdPartyContentClient::new(endpoint, timeout_ms, connect_timeout_ms)
 }
@@ -138,7 +223,27 @@ pub(crate) fn sleep_backoff(cfg: &Config, attempt: u32) {
     if wait > max_ms {
         wait = max_ms;
     }
-    std::thread::sleep(Duration::from_millis(wait));
+    std::thread::sleep(Duration::from_millis(with_jitter(
+        wait,
+        cfg.wait_jitter_ratio,
+    )));
+}
+
+/// 在 `base_ms` 上叠加 ±`ratio`% 的均匀随机抖动，避免请求时序过于规律。
+fn with_jitter(base_ms: u64, ratio: u8) -> u64 {
+    if ratio == 0 || base_ms == 0 {
+        return base_ms;
+    }
+    // RandomState 每次构造都带随机种子，足以满足抖动需求（避免引入 rand 依赖）
+    let seed = RandomState::new().build_hasher().finish();
+    let span = base_ms.saturating_mul(u64::from(ratio.min(100))) / 100;
+    let offset = seed % (span.saturating_mul(2) + 1);
+    (base_ms - span).saturating_add(offset).max(1)
+}
+
+/// 4xx 中除 408/429 外均视为"该源对该请求无效"，重试同一 endpoint 没有意义。
+pub(crate) fn is_non_retryable_client_error(status: u16) -> bool {
+    (400..500).contains(&status) && status != 408 && status != 429
 }
 
 pub(crate) fn fetch_group_third_party(
@@ -154,6 +259,14 @@ pub(crate) fn fetch_group_third_party(
         .map(|c| c.id.as_str())
         .collect::<Vec<_>>()
         .join(",");
+    // 对本组章节返回 4xx / 非 JSON 的 endpoint，本次调用内不再选用
+    let mut rejected: HashSet<String> = HashSet::new();
+    let mut last_status: Option<u16> = None;
+    let mut last_err: Option<anyhow::Error> = None;
+    let strategy = TimeoutRetryStrategy::from_config(&cfg.timeout_retry_strategy);
+    // 上次连接失败的 endpoint，地址池有其他候选时下一次先避开
+    let mut connect_failed: Option<String> = None;
+    let mut read_timeouts: u32 = 0;
 
     for attempt in 0..tries {
         let ep = {
@@ -161,28 +274,223 @@ pub(crate) fn fetch_group_third_party(
             if guard.is_empty() {
                 return Err(anyhow!("第三方 API 地址池已为空（全部判定无效）"));
             }
-            let idx = pick.fetch_add(1, Ordering::Relaxed) % guard.len();
-            guard[idx].clone()
+            let candidates: Vec<&String> =
+                guard.iter().filter(|ep| !rejected.contains(*ep)).collect();
+            if candidates.is_empty() {
+                return Err(final_error(
+                    last_status,
+                    last_err,
+                    &format!(
+                        "第三方 API 对该组章节均返回 HTTP status {}，已放弃重试",
+                        last_status.unwrap_or_default()
+                    ),
+                    "第三方 API 对该组章节均无有效响应",
+                ));
+            }
+            let preferred: Vec<&String> = candidates
+                .iter()
+                .copied()
+                .filter(|ep| connect_failed.as_ref() != Some(*ep))
+                .collect();
+            let pool = if preferred.is_empty() {
+                &candidates
+            } else {
+                &preferred
+            };
+            let idx = pick.fetch_add(1, Ordering::Relaxed) % pool.len();
+            pool[idx].clone()
         };
 
-        let client = third_party_client_for_endpoint(cfg, &ep)?;
+        let client = third_party_client_with_timeout_scale(
+            cfg,
+            &ep,
+            strategy.read_timeout_scale(read_timeouts),
+        )?;
+        let started = Instant::now();
         match client.get_contents_unthrottled(&ids, epub_mode) {
             Ok(v) => {
                 if !has_any_content_for_group(&v, group, cfg) {
+                    request_trace::record(
+                        &ids,
+                        &ep,
+                        attempt,
+                        started.elapsed(),
+                        TraceOutcome::EmptyContent,
+                        None,
+                        None,
+                    );
                     let mut guard = endpoints.lock().unwrap_or_else(|e| e.into_inner());
                     guard.retain(|x| x != &ep);
                     drop(guard);
+                    record_endpoint_result(&ep, false);
+                    with_endpoint_stat(&ep, |s| s.disabled = true);
                     sleep_backoff(cfg, attempt);
                     continue;
                 }
+                request_trace::record(
+                    &ids,
+                    &ep,
+                    attempt,
+                    started.elapsed(),
+                    TraceOutcome::Ok,
+                    None,
+                    None,
+                );
+                record_endpoint_result(&ep, true);
                 return Ok(v);
             }
-            Err(_) => {
-                sleep_backoff(cfg, attempt);
+            Err(err) => {
+                let elapsed = started.elapsed();
+                let status = http_status_of(&err);
+                record_endpoint_result(&ep, false);
+                if cfg.fast_switch_on_4xx
+                    && let Some(code) = status.filter(|c| is_non_retryable_client_error(*c))
+                {
+                    // 4xx：不退避，直接换下一个 endpoint
+                    request_trace::record(
+                        &ids,
+                        &ep,
+                        attempt,
+                        elapsed,
+                        TraceOutcome::ClientError,
+                        Some(code),
+                        Some(err.to_string()),
+                    );
+                    let kind =
+                        RequestErrorKind::from_status(code).unwrap_or(RequestErrorKind::Other);
+                    debug!(
+                        target: "download",
+                        endpoint = %ep,
+                        status = code,
+                        kind = kind.label(),
+                        "{} 第三方 API 返回客户端错误，切换 endpoint",
+                        kind.prefix()
+                    );
+                    rejected.insert(ep);
+                    last_status = Some(code);
+                    continue;
+                }
+                if err
+                    .downcast_ref::<ResponseJsonError>()
+                    .is_some_and(ResponseJsonError::should_switch_source)
+                {
+                    // 空响应 / 非 JSON：原地重试多半仍是同样结果，直接换源
+                    request_trace::record(
+                        &ids,
+                        &ep,
+                        attempt,
+                        elapsed,
+                        TraceOutcome::InvalidBody,
+                        status,
+                        Some(err.to_string()),
+                    );
+                    let kind = RequestErrorKind::classify(&err);
+                    warn!(
+                        target: "download",
+                        endpoint = %ep,
+                        error = %err,
+                        kind = kind.label(),
+                        "{} 第三方 API 响应无法解析，切换 endpoint",
+                        kind.prefix()
+                    );
+                    rejected.insert(ep);
+                    last_err = Some(err);
+                    continue;
+                }
+                // 5xx / 超时 / 网络错误：退避后重试；连接失败换端点，读取超时放宽超时
+                let kind = RequestErrorKind::classify(&err);
+                let phase = FailurePhase::classify(&err);
+                debug!(
+                    target: "download",
+                    endpoint = %ep,
+                    attempt = attempt + 1,
+                    error = %err,
+                    kind = kind.label(),
+                    phase = ?phase,
+                    "{} 第三方 API 请求失败，退避后重试",
+                    kind.prefix()
+                );
+                request_trace::record(
+                    &ids,
+                    &ep,
+                    attempt,
+                    elapsed,
+                    TraceOutcome::Retryable,
+                    status,
+                    Some(err.to_string()),
+                );
+                last_status = status.or(last_status);
+                last_err = Some(err);
+                match phase {
+                    FailurePhase::Connect => {
+                        connect_failed = Some(ep);
+                        match strategy.connect_retry_wait(cfg.min_wait_time) {
+                            Some(wait) => std::thread::sleep(wait),
+                            None => sleep_backoff(cfg, attempt),
+                        }
+                    }
+                    FailurePhase::Read => {
+                        read_timeouts += 1;
+                        sleep_backoff(cfg, attempt);
+                    }
+                    FailurePhase::Other => sleep_backoff(cfg, attempt),
+                }
                 continue;
             }
         }
     }
 
-    Err(anyhow!("第三方 API 请求重试耗尽"))
+    Err(final_error(
+        last_status,
+        last_err,
+        &format!(
+            "第三方 API 请求重试耗尽（HTTP status {}）",
+            last_status.unwrap_or_default()
+        ),
+        "第三方 API 请求重试耗尽",
+    ))
+}
+
+/// 组装最终失败信息：有状态码时用 `status_msg`，否则用 `msg` 包装底层错误，并附上归类与建议。
+fn final_error(
+    last_status: Option<u16>,
+    last_err: Option<anyhow::Error>,
+    status_msg: &str,
+    msg: &str,
+) -> anyhow::Error {
+    let kind = match (last_status, &last_err) {
+        (Some(status), _) => RequestErrorKind::from_status(status)
+            .or_else(|| last_err.as_ref().map(RequestErrorKind::classify))
+            .unwrap_or(RequestErrorKind::Other),
+        (None, Some(err)) => RequestErrorKind::classify(err),
+        (None, None) => RequestErrorKind::Other,
+    };
+    warn!(
+        target: "download",
+        kind = kind.label(),
+        "{} {}",
+        kind.prefix(),
+        if last_status.is_some() { status_msg } else { msg }
+    );
+    match (last_status, last_err) {
+        (Some(_), _) => anyhow!(kind.annotate(status_msg)),
+        // 保留底层错误，便于识别网络故障（见 `network_wait`）
+        (None, Some(err)) => err.context(kind.annotate(msg)),
+        (None, None) => anyhow!(kind.annotate(msg)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::with_jitter;
+
+    #[test]
+    fn jitter_stays_within_ratio() {
+        assert_eq!(with_jitter(1000, 0), 1000);
+        for _ in 0..200 {
+            let v = with_jitter(1000, 30);
+            assert!((700..=1300).contains(&v), "{v}");
+        }
+        assert!(with_jitter(1, 100) >= 1);
+    }
 }
@@ -0,0 +1,122 @@
+//! 试读：下载整本前先拉取单章，验证当前配置（官方/第三方 endpoint）能否拿到并解密正文。
+
+#[cfg(feature = "official-api")]
+use anyhow::Context;
+use anyhow::{Result, anyhow};
+
+use super::models::ChapterRef;
+use super::third_party::third_party_client_for_endpoint;
+use crate::base_system::context::Config;
+use crate::book_parser::parser::ContentParser;
+
+#[cfg(feature = "official-api")]
+use crate::base_system::cooldown_retry::fetch_with_cooldown_retry;
+#[cfg(feature = "official-api")]
+use crate::base_system::request_error::TimeoutRetryStrategy;
+#[cfg(feature = "official-api")]
+use tomato_novel_official_api::FanqieClient;
+
+/// 片段最多展示的字符数。
+const EXCERPT_CHARS: usize = 200;
+
+#[derive(Debug, Clone)]
+pub struct TrialRead {
+    pub title: String,
+    /// 正文来源：`官方 API` 或第三方 endpoint
+    pub source: String,
+    /// 纯文本字数
+    pub chars: usize,
+    pub excerpt: String,
+}
+
+/// 单章拉取结果：解析后的正文（未清洗）与来源。
+#[derive(Debug, Clone)]
+pub struct FetchedChapter {
+    pub title: String,
+    pub content: String,
+    /// 正文来源：`官方 API` 或第三方 endpoint
+    pub source: String,
+}
+
+/// 试读单章；第三方模式下按顺序尝试每个 endpoint，返回第一个拿到正文的结果。
+pub fn trial_read(config: &Config, book_id: &str, chapter: &ChapterRef) -> Result<TrialRead> {
+    let fetched = fetch_chapter(config, book_id, chapter)?;
+    let plain = ContentParser::clean_plain(&fetched.content, &fetched.title);
+    let plain = plain.trim();
+    if plain.is_empty() {
+        return Err(anyhow!("正文清洗后为空（可能解密失败）"));
+    }
+    let mut excerpt: String = plain.chars().take(EXCERPT_CHARS).collect();
+    let chars = plain.chars().filter(|c| !c.is_whitespace()).count();
+    if plain.chars().count() > EXCERPT_CHARS {
+        excerpt.push('…');
+    }
+    Ok(TrialRead {
+        title: fetched.title,
+        source: fetched.source,
+        chars,
+        excerpt,
+    })
+}
+
+/// 拉取单章正文，不写入任何缓存；第三方模式下依次尝试各 endpoint。
+pub fn fetch_chapter(
+    config: &Config,
+    book_id: &str,
+    chapter: &ChapterRef,
+) -> Result<FetchedChapter> {
+    #[cfg(feature = "official-api")]
+    if config.use_official_api {
+        let client = FanqieClient::new().context("init FanqieClient")?;
+        let strategy = TimeoutRetryStrategy::from_config(&config.timeout_retry_strategy);
+        let value =
+            fetch_with_cooldown_retry(&client, &chapter.id, false, Some(book_id), strategy)?;
+        return parse_chapter(config, &value, chapter, "官方 API".to_string());
+    }
+    let _ = book_id;
+
+    let endpoints: Vec<&str> = config
+        .api_endpoints
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if endpoints.is_empty() {
+        return Err(anyhow!("use_official_api=false 时，api_endpoints 不能为空"));
+    }
+
+    let mut errors = Vec::new();
+    for ep in endpoints {
+        let attempt = third_party_client_for_endpoint(config, ep)
+            .and_then(|client| client.get_contents_unthrottled(&chapter.id, false))
+            .and_then(|value| parse_chapter(config, &value, chapter, ep.to_string()));
+        match attempt {
+            Ok(fetched) => return Ok(fetched),
+            Err(e) => errors.push(format!("{ep}: {e}")),
+        }
+    }
+    Err(anyhow!("所有 endpoint 均拉取失败：\n{}", errors.join("\n")))
+}
+
+fn parse_chapter(
+    config: &Config,
+    value: &serde_json::Value,
+    chapter: &ChapterRef,
+    source: String,
+) -> Result<FetchedChapter> {
+    let parsed = ContentParser::extract_api_content(value, config);
+    let (content, title) = parsed
+        .get(&chapter.id)
+        .filter(|(content, _)| !content.trim().is_empty())
+        .ok_or_else(|| anyhow!("正文为空（可能解密失败或源不可用）"))?;
+    let title = if title.trim().is_empty() {
+        chapter.title.clone()
+    } else {
+        title.clone()
+    };
+    Ok(FetchedChapter {
+        title,
+        content: content.clone(),
+        source,
+    })
+}
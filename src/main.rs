@@ -27,6 +27,7 @@ use base_system::logging::{LogOptions, LogSystem};
 use tracing::info;
 #[cfg(feature = "official-api")]
 use tracing::warn;
+use ui::noui::json_output::{self, JsonEvent, OutputFormat};
 
 #[cfg(all(feature = "official-api", feature = "no-official-api"))]
 compile_error!(
@@ -38,6 +39,23 @@ use tomato_novel_official_api::prewarm_iid;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// 后台 IID 续期检查间隔。
+#[cfg(feature = "official-api")]
+const IID_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
+/// IID 探测失败后的首次重试间隔；连续失败时逐次翻倍，最长不超过续期间隔。
+#[cfg(feature = "official-api")]
+const IID_PROBE_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+/// 连续探测失败后的重试等待：5 分钟起逐次翻倍，封顶 `IID_REFRESH_INTERVAL`。
+#[cfg(feature = "official-api")]
+fn iid_probe_backoff(consecutive_failures: u32) -> std::time::Duration {
+    let shift = consecutive_failures.saturating_sub(1).min(8);
+    IID_PROBE_RETRY_INTERVAL
+        .saturating_mul(1 << shift)
+        .min(IID_REFRESH_INTERVAL)
+}
+
 #[derive(Debug, Parser)]
 #[command(name = "tomato-novel-downloader")]
 #[command(about = "Tomato Novel Downloader (Rust TUI)")]
@@ -85,6 +103,30 @@ struct Cli {
     /// 非交互模式下失败章节重试一次
     #[arg(long, default_value_t = false)]
     retry_failed: bool,
+
+    /// 在指定 book_id 的已下载章节中全文搜索（配合 --keyword）
+    #[arg(long)]
+    search_book: Option<String>,
+
+    /// 全文搜索关键词
+    #[arg(long)]
+    keyword: Option<String>,
+
+    /// 从本地目录离线生成成书（directory.json + chapters/ 或 chapters.jsonl，布局见文档）
+    #[arg(long)]
+    offline: Option<String>,
+
+    /// 试运行诊断：依次检查 book_id 解析、目录、endpoint 与首章正文，不下载、不写文件
+    #[arg(long, value_name = "BOOK_ID_OR_URL")]
+    dry_run: Option<String>,
+
+    /// 非交互模式（--update / --search-book / --offline / --dry-run）的输出格式；json 为 NDJSON 事件流，日志改写到 stderr
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+
+    /// 等价于 `--output-format json`
+    #[arg(long, default_value_t = false)]
+    json: bool,
 }
 
 fn main() -> Result<()> {
@@ -95,8 +137,10 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    let json = cli.json || cli.output_format == OutputFormat::Json;
     let data_dir = cli.data_dir.as_ref().map(std::path::Path::new);
-    let _log = init_logging(cli.debug, data_dir)?;
+    let _log = init_logging(cli.debug, data_dir, json)?;
+    base_system::request_trace::set_enabled(cli.debug);
 
     if cli.self_update {
         let _ = base_system::self_update::check_for_updates(VERSION, cli.self_update_yes);
@@ -118,13 +162,41 @@ fn main() -> Result<()> {
             // 注意：这里只应“预热/确保可用”，不得在每次启动时强制更换 IID。
             // `prewarm_iid()` 现在会优先复用本地文件缓存，仅在缓存缺失或过期时才注册新的 IID。
             match prewarm_iid() {
-                Ok(_) => info!(target: "startup", "IID 预热完成"),
+                Ok(_) => {
+                    info!(target: "startup", "IID 预热完成");
+                    prewarm_state::mark_prewarm_done();
+                }
                 Err(err) => {
                     prewarm_state::mark_prewarm_failed(err.to_string());
                     if let Some(message) = prewarm_state::prewarm_error() {
                         warn!(target: "startup", "{message}");
                     }
-                    return;
+                }
+            }
+
+            // 单 IID 后台续期，不是 IID 池：官方 API crate 目前只暴露 `prewarm_iid()`，
+            // 多 IID 轮换需它实现 `iid_pool::IidBackend`（注册/切换）后改由 `IidPool` 接管。
+            // 在此之前定期在后台调用一次，让过期后的更换发生在本线程里，而不是卡在某个下载批次中途。
+            // 健康探测只在下载开始时发起；探测失败后这里按指数退避续期并重新探测，
+            // 直到恢复，避免空闲时也周期性请求搜索接口。
+            loop {
+                let failures = prewarm_state::iid_health().consecutive_failures;
+                thread::sleep(if failures > 0 {
+                    iid_probe_backoff(failures)
+                } else {
+                    IID_REFRESH_INTERVAL
+                });
+                match prewarm_iid() {
+                    Ok(_) => {
+                        info!(target: "startup", "IID 后台续期检查完成");
+                        if prewarm_state::clear_prewarm_error() {
+                            info!(target: "startup", "IID 已恢复，清除启动预热错误");
+                        }
+                    }
+                    Err(err) => warn!(target: "startup", "IID 后台续期失败: {err}"),
+                }
+                if prewarm_state::iid_health().consecutive_failures > 0 {
+                    prewarm_state::run_iid_probe();
                 }
             }
         }
@@ -132,12 +204,33 @@ fn main() -> Result<()> {
         #[cfg(not(feature = "official-api"))]
         {
             info!(target: "startup", "no-official-api 构建：跳过 IID 预热");
+            prewarm_state::mark_prewarm_done();
         }
-        prewarm_state::mark_prewarm_done();
     });
 
     let mut config = load_config_from_data_dir(data_dir)?;
 
+    if let Some(book_id) = cli.search_book.as_deref() {
+        let Some(keyword) = cli.keyword.as_deref().filter(|k| !k.trim().is_empty()) else {
+            return Err(anyhow!("--search-book 需要配合 --keyword 指定搜索关键词"));
+        };
+        return report_json_error(
+            json,
+            ui::noui::search_downloaded_book(book_id, keyword, &config, json),
+        );
+    }
+
+    if let Some(input) = cli.dry_run.as_deref() {
+        return report_json_error(json, ui::noui::dry_run(input, &config, json));
+    }
+
+    if let Some(dir) = cli.offline.as_deref() {
+        return report_json_error(
+            json,
+            ui::noui::generate_offline(std::path::Path::new(dir), &config, json),
+        );
+    }
+
     // Handle command-line download/update modes
     if cli.download.is_some() || cli.update.is_some() {
         info!(target: "startup", "当前版本: v{}", VERSION);
@@ -149,11 +242,19 @@ fn main() -> Result<()> {
         }
 
         if let Some(book_id) = cli.update.as_deref() {
-            println!("更新指定书籍 book_id={}", book_id);
-            return ui::noui::update_existing_book_non_interactive(
-                book_id,
-                &config,
-                cli.retry_failed,
+            if json {
+                eprintln!("更新指定书籍 book_id={}", book_id);
+            } else {
+                println!("更新指定书籍 book_id={}", book_id);
+            }
+            return report_json_error(
+                json,
+                ui::noui::update_existing_book_non_interactive(
+                    book_id,
+                    &config,
+                    cli.retry_failed,
+                    json,
+                ),
             );
         }
     }
@@ -222,12 +323,24 @@ fn parse_bool_env(key: &str) -> bool {
         .unwrap_or(false)
 }
 
-fn init_logging(debug: bool, base_dir: Option<&std::path::Path>) -> Result<LogSystem> {
+/// JSON 输出模式下把失败也作为 `error` 事件写到 stdout，外部程序无需解析 stderr。
+fn report_json_error(json: bool, result: Result<()>) -> Result<()> {
+    if json && let Err(err) = &result {
+        json_output::emit(&JsonEvent::Error {
+            message: &format!("{err:#}"),
+        });
+    }
+    result
+}
+
+fn init_logging(debug: bool, base_dir: Option<&std::path::Path>, json: bool) -> Result<LogSystem> {
+    // JSON 模式：日志同时输出到 stderr，stdout 只保留 NDJSON 事件
     let opts = LogOptions {
         debug,
-        use_color: true,
+        use_color: !json,
         archive_on_exit: true,
-        console: false,
+        console: json,
+        console_stderr: json,
         broadcast_to_ui: true,
     };
     if let Some(base_dir) = base_dir {
@@ -2,8 +2,10 @@
 
 use reqwest::blocking::Client;
 use reqwest::header::{
-    ACCEPT, ACCEPT_ENCODING, CONNECTION, CONTENT_TYPE, HeaderMap, HeaderValue, REFERER, USER_AGENT,
+    ACCEPT, ACCEPT_ENCODING, CONNECTION, CONTENT_TYPE, ETAG, HeaderMap, HeaderName, HeaderValue,
+    IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, REFERER, USER_AGENT,
 };
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -11,6 +13,52 @@ use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::{debug, error, warn};
 
+use crate::base_system::json_extract::parse_json_or_diagnose;
+use crate::third_party::bandwidth::read_all_throttled;
+
+/// 目录响应的 ETag / Last-Modified，用于下次条件请求。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct DirValidators {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+}
+
+impl DirValidators {
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let get = |name: HeaderName| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let v = Self {
+            etag: get(ETAG),
+            last_modified: get(LAST_MODIFIED),
+        };
+        (v.etag.is_some() || v.last_modified.is_some()).then_some(v)
+    }
+
+    fn apply(&self, headers: &mut HeaderMap) {
+        let pairs = [
+            (IF_NONE_MATCH, self.etag.as_deref()),
+            (IF_MODIFIED_SINCE, self.last_modified.as_deref()),
+        ];
+        for (name, value) in pairs {
+            if let Some(value) = value.and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.insert(name, value);
+            }
+        }
+    }
+}
+
+/// 读取响应体并解析 JSON；空响应 / 风控页给出明确诊断。
+fn read_json(resp: reqwest::blocking::Response) -> anyhow::Result<Value> {
+    let body = read_all_throttled(resp)?;
+    Ok(parse_json_or_diagnose(&body)?)
+}
+
 // 编译一次复用的正则缓存
 fn re_next_data() -> &'static regex::Regex {
     static R: OnceLock<regex::Regex> = OnceLock::new();
@@ -228,9 +276,15 @@ impl FanqieWebNetwork {
         let mut backoff = 0.6f64;
         let mut last_error: Option<String> = None;
 
+        // 有本地目录缓存时带上条件请求头，服务端返回 304 则直接复用缓存
+        let mut validators = self.load_dir_validators(book_id);
+
         for attempt in 1..=retries {
             debug!("开始获取章节列表，URL: {}", api_url);
-            let headers = self.get_json_headers(book_id);
+            let mut headers = self.get_json_headers(book_id);
+            if let Some(v) = validators.as_ref() {
+                v.apply(&mut headers);
+            }
 
             if attempt == 1 {
                 // 屏蔽 Cookie（如果未来启用 cookies feature，这里也不会泄露）
@@ -268,6 +322,19 @@ impl FanqieWebNetwork {
 
             debug!("章节列表响应状态: {}", resp.status().as_u16());
 
+            if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+                if let Ok(Some(cached)) = self.load_dir_cache(book_id)
+                    && let Some(list) = Self::parse_chapter_data(&cached)
+                {
+                    debug!("章节目录未变更(304)，复用本地缓存: book_id={}", book_id);
+                    return Some(list);
+                }
+                last_error = Some("304 Not Modified 但本地缓存不可用".to_string());
+                self.drop_dir_validators(book_id);
+                validators = None;
+                continue;
+            }
+
             // 显式处理 403：可能为风控或限频
             if resp.status().as_u16() == 403 {
                 last_error = Some("403 Forbidden".to_string());
@@ -304,7 +371,8 @@ impl FanqieWebNetwork {
                 }
             };
 
-            let data: Value = match resp.json() {
+            let fresh_validators = DirValidators::from_headers(resp.headers());
+            let data: Value = match read_json(resp) {
                 Ok(v) => v,
                 Err(e) => {
                     last_error = Some(e.to_string());
@@ -314,9 +382,11 @@ impl FanqieWebNetwork {
                 }
             };
 
-            // 成功则缓存原始 JSON，便于下次回退
+            // 成功则缓存原始 JSON，便于下次回退，并记录条件请求所需的 ETag/Last-Modified
             if let Err(e) = self.save_dir_cache(book_id, &data) {
                 debug!("保存目录缓存失败(忽略): {}", e);
+            } else {
+                self.save_dir_validators(book_id, fresh_validators.as_ref());
             }
 
             if let Some(list) = Self::parse_chapter_data(&data) {
@@ -341,6 +411,67 @@ impl FanqieWebNetwork {
         }
     }
 
+    /// 拉取番茄网页分类榜单的一页（`new_book` 为新书榜，否则阅读榜），返回榜单条目原始 JSON。
+    pub(crate) fn fetch_category_rank(
+        &self,
+        gender: u8,
+        category_id: u32,
+        new_book: bool,
+        offset: usize,
+        limit: usize,
+    ) -> anyhow::Result<Vec<Value>> {
+        let rank_mold = if new_book { 1 } else { 2 };
+        let api_url = format!(
+            "https://fanqienovel.com/api/rank/category/list?app_id=2503&rank_list_type=3&offset={offset}&limit={limit}&category_id={category_id}&rank_version=&gender={gender}&rankMold={rank_mold}"
+        );
+
+        let retries = self.config.max_retries.max(1);
+        let mut backoff = 0.6f64;
+        let mut last_error = String::new();
+        for attempt in 1..=retries {
+            debug!("获取分类榜单，URL: {}", api_url);
+            let mut headers = self.get_json_headers("");
+            headers.insert(
+                REFERER,
+                HeaderValue::from_static("https://fanqienovel.com/rank"),
+            );
+            let data: Value = match self
+                .client
+                .get(&api_url)
+                .headers(headers)
+                .send()
+                .and_then(|r| r.error_for_status())
+                .map_err(anyhow::Error::from)
+                .and_then(read_json)
+            {
+                Ok(v) => v,
+                Err(e) => {
+                    last_error = e.to_string();
+                    warn!("获取分类榜单失败: {}", e);
+                    self.sleep_backoff(attempt, retries, &mut backoff, 0.3);
+                    continue;
+                }
+            };
+
+            let code = data.get("code").and_then(Value::as_i64).unwrap_or(0);
+            if code != 0 {
+                let msg = data
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown");
+                return Err(anyhow::anyhow!("榜单接口返回错误 code={code}: {msg}"));
+            }
+            let list = data
+                .get("data")
+                .and_then(|d| d.get("result_list").or_else(|| d.get("book_list")))
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            return Ok(list);
+        }
+        Err(anyhow::anyhow!("获取分类榜单失败: {last_error}"))
+    }
+
     fn throttle_directory(&self, min_gap: Duration) {
         if let Ok(mut last) = self.last_dir_fetch.lock() {
             let elapsed = last.elapsed();
@@ -375,6 +506,35 @@ impl FanqieWebNetwork {
         Ok(())
     }
 
+    fn validators_path(&self, book_id: &str) -> PathBuf {
+        self.config
+            .cache_dir
+            .join(format!("{book_id}.validators.json"))
+    }
+
+    /// 读取目录缓存对应的条件请求头；缓存正文不存在时视为无效。
+    fn load_dir_validators(&self, book_id: &str) -> Option<DirValidators> {
+        if !self.cache_path(book_id).exists() {
+            return None;
+        }
+        let bytes = fs::read(self.validators_path(book_id)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    fn save_dir_validators(&self, book_id: &str, validators: Option<&DirValidators>) {
+        let Some(validators) = validators else {
+            self.drop_dir_validators(book_id);
+            return;
+        };
+        if let Ok(bytes) = serde_json::to_vec(validators) {
+            let _ = fs::write(self.validators_path(book_id), bytes);
+        }
+    }
+
+    fn drop_dir_validators(&self, book_id: &str) {
+        let _ = fs::remove_file(self.validators_path(book_id));
+    }
+
     fn load_dir_cache(&self, book_id: &str) -> anyhow::Result<Option<Value>> {
         let path = self.cache_path(book_id);
         if !path.exists() {
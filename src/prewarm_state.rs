@@ -1,12 +1,67 @@
 //! 启动预热状态记录。
 //!
-//! 用于在启动时异步预热 IID，并让 UI 能显示“预热中/完成”。
+//! 用于在启动时异步预热 IID，并让 UI 能显示“预热中/完成”；
+//! 另记录 IID 健康探测的最近结果：探测在下载开始时按需发起，失败后由后台线程退避重试。
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+#[cfg(feature = "official-api")]
+use tracing::{info, warn};
+
+/// 下载开始时两次健康探测的最短间隔（秒），避免批量下载时每本都发请求。
+#[cfg(feature = "official-api")]
+const IID_PROBE_MIN_INTERVAL_SECS: u64 = 30 * 60;
+
+/// 健康探测使用的搜索词：结果稳定非空，单次请求开销小。
+#[cfg(feature = "official-api")]
+const IID_PROBE_KEYWORD: &str = "番茄";
+
+#[cfg(feature = "official-api")]
+static PROBE_RUNNING: AtomicBool = AtomicBool::new(false);
 
 static PREWARMING: AtomicBool = AtomicBool::new(false);
 static PREWARM_ERROR: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+static IID_HEALTH: Mutex<IidHealth> = Mutex::new(IidHealth {
+    state: IidHealthState::Unknown,
+    checked_at: None,
+    consecutive_failures: 0,
+    last_error: None,
+});
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IidHealthState {
+    /// 尚未探测（启动中或 no-official-api 构建）
+    Unknown,
+    Healthy,
+    /// 最近一次探测失败，下载可能整批失败
+    Failing,
+}
+
+/// 后台 IID 健康探测的最近结果。
+#[derive(Debug, Clone, Serialize)]
+pub struct IidHealth {
+    pub state: IidHealthState,
+    /// 最近一次探测的 Unix 时间戳（秒）
+    pub checked_at: Option<u64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+impl IidHealth {
+    /// 简短状态文本，供 TUI 标题栏等处展示。
+    pub fn label(&self) -> String {
+        match self.state {
+            IidHealthState::Unknown => "未探测".to_string(),
+            IidHealthState::Healthy => "正常".to_string(),
+            IidHealthState::Failing => format!("异常（连续 {} 次）", self.consecutive_failures),
+        }
+    }
+}
 
 #[allow(dead_code)]
 const IID_BLOCK_HINT: &str = "IID 注册需要访问 https://log.snssdk.com/service/2/device_register/。番茄把该域名用于设备注册/广告分发，因此它经常会被公司/校园网、DNS 过滤、代理规则或 AdGuard/uBlock 等反广告插件拦截。请把 log.snssdk.com 加入放行列表，或临时关闭相关拦截后重试。";
@@ -26,6 +81,14 @@ pub fn mark_prewarm_failed(err: impl Into<String>) {
     PREWARMING.store(false, Ordering::SeqCst);
 }
 
+/// 后台续期成功后清除启动预热留下的错误，UI 不再提示注册失败。
+#[allow(dead_code)]
+pub fn clear_prewarm_error() -> bool {
+    let had_error = prewarm_error().is_some();
+    set_prewarm_error(None);
+    had_error
+}
+
 pub fn is_prewarm_in_progress() -> bool {
     PREWARMING.load(Ordering::SeqCst)
 }
@@ -47,6 +110,82 @@ pub fn format_iid_register_failure(err: impl AsRef<str>) -> String {
     format!("IID 注册失败：{err}\n\n{IID_BLOCK_HINT}")
 }
 
+/// 记录一次 IID 健康探测结果。
+#[allow(dead_code)]
+pub fn record_iid_probe(result: Result<(), String>) {
+    let mut health = IID_HEALTH.lock().unwrap_or_else(|e| e.into_inner());
+    health.checked_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs());
+    match result {
+        Ok(()) => {
+            health.state = IidHealthState::Healthy;
+            health.consecutive_failures = 0;
+            health.last_error = None;
+        }
+        Err(err) => {
+            health.state = IidHealthState::Failing;
+            health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+            health.last_error = Some(err);
+        }
+    }
+}
+
+pub fn iid_health() -> IidHealth {
+    IID_HEALTH.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// 用一次轻量搜索探测当前 IID 是否仍有效，并记录结果。
+#[cfg(feature = "official-api")]
+pub fn run_iid_probe() {
+    use anyhow::Context as _;
+
+    let result = tomato_novel_official_api::SearchClient::new()
+        .context("init SearchClient")
+        .and_then(|client| {
+            client
+                .search_books(IID_PROBE_KEYWORD)
+                .context("search_books")
+        })
+        .and_then(|resp| {
+            if resp.books.is_empty() {
+                Err(anyhow::anyhow!("探测请求未返回任何结果"))
+            } else {
+                Ok(())
+            }
+        });
+    match result {
+        Ok(()) => {
+            info!(target: "startup", "IID 健康探测通过");
+            record_iid_probe(Ok(()));
+        }
+        Err(err) => {
+            warn!(target: "startup", "IID 健康探测失败: {err:#}");
+            record_iid_probe(Err(format!("{err:#}")));
+        }
+    }
+}
+
+/// 下载开始时在后台探测 IID；距上次探测不足最短间隔或已有探测进行中时跳过。
+#[cfg(feature = "official-api")]
+pub fn probe_iid_if_due() {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let due = iid_health()
+        .checked_at
+        .is_none_or(|at| now.saturating_sub(at) >= IID_PROBE_MIN_INTERVAL_SECS);
+    if !due || PROBE_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    std::thread::spawn(|| {
+        run_iid_probe();
+        PROBE_RUNNING.store(false, Ordering::SeqCst);
+    });
+}
+
 fn set_prewarm_error(err: Option<String>) {
     *PREWARM_ERROR
         .get_or_init(|| Mutex::new(None))
@@ -0,0 +1,89 @@
+//! 全局下载带宽限速。
+//!
+//! 所有下载线程共享同一份带宽预算（虚拟时间片调度）：每读取一段数据就预约
+//! 相应的传输时长，预约排在前面的线程先放行，从而限制的是总速率而非单线程速率。
+
+use std::io::Read;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// 单位 KB/s，0 表示不限速。
+static LIMIT_KBPS: AtomicU64 = AtomicU64::new(0);
+
+/// 允许的突发量：空闲一段时间后最多"预支"这么久的额度。
+const MAX_BURST: Duration = Duration::from_millis(500);
+
+fn next_free() -> &'static Mutex<Instant> {
+    static NEXT: OnceLock<Mutex<Instant>> = OnceLock::new();
+    NEXT.get_or_init(|| Mutex::new(Instant::now()))
+}
+
+pub(crate) fn set_limit_kbps(kbps: u64) {
+    LIMIT_KBPS.store(kbps, Ordering::Relaxed);
+}
+
+#[cfg_attr(not(feature = "official-api"), allow(dead_code))]
+pub(crate) fn is_limited() -> bool {
+    LIMIT_KBPS.load(Ordering::Relaxed) > 0
+}
+
+/// 记录已传输 `bytes` 字节，必要时阻塞当前线程以满足总速率限制。
+pub(crate) fn consume(bytes: usize) {
+    let kbps = LIMIT_KBPS.load(Ordering::Relaxed);
+    if kbps == 0 || bytes == 0 {
+        return;
+    }
+    let cost = Duration::from_secs_f64(bytes as f64 / (kbps as f64 * 1024.0));
+    let now = Instant::now();
+    let wait = {
+        let mut next = next_free().lock().unwrap_or_else(|e| e.into_inner());
+        let earliest = now.checked_sub(MAX_BURST).unwrap_or(now);
+        if *next < earliest {
+            *next = earliest;
+        }
+        *next += cost;
+        next.saturating_duration_since(now)
+    };
+    if !wait.is_zero() {
+        std::thread::sleep(wait);
+    }
+}
+
+/// 对任意 `Read` 做节流包装，读取量计入全局预算。
+pub(crate) struct ThrottledReader<R> {
+    inner: R,
+}
+
+impl<R: Read> ThrottledReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self { inner }
+    }
+}
+
+impl<R: Read> Read for ThrottledReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        consume(n);
+        Ok(n)
+    }
+}
+
+/// 读取完整响应体（经限速）。
+pub(crate) fn read_all_throttled<R: Read>(reader: R) -> std::io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    ThrottledReader::new(reader).read_to_end(&mut out)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttled_reader_passes_through_data() {
+        let data = vec![7u8; 4096];
+        let out = read_all_throttled(data.as_slice()).unwrap();
+        assert_eq!(out, data);
+    }
+}
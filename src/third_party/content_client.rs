@@ -4,6 +4,9 @@ use reqwest::header::{ACCEPT, ACCEPT_ENCODING, CONNECTION, HeaderMap, HeaderValu
 use serde_json::Value;
 use std::time::Duration;
 
+use super::bandwidth::read_all_throttled;
+use crate::base_system::json_extract::parse_json_or_diagnose;
+
 const AID: &str = "1967";
 
 fn normalize_base(base: &str) -> String {
@@ -46,6 +49,13 @@ fn join_params(params: &[(String, String)]) -> String {
     out
 }
 
+/// 从请求错误中取出 HTTP 状态码（`error_for_status` 产生的错误会携带）。
+pub(crate) fn http_status_of(err: &anyhow::Error) -> Option<u16> {
+    err.downcast_ref::<reqwest::Error>()
+        .and_then(|e| e.status())
+        .map(|s| s.as_u16())
+}
+
 /// 轻量第三方正文客户端：不依赖 Official-API。
 ///
 /// 约定：第三方服务应返回可直接解析的 JSON（尽量与 Official-API 解密后的结构兼容），
@@ -123,7 +133,8 @@ impl ThirdPartyContentClient {
 
         let resp = self.client.get(&url).send()?;
         let resp = resp.error_for_status()?;
-        let v: Value = resp.json()?;
+        let body = read_all_throttled(resp)?;
+        let v: Value = parse_json_or_diagnose(&body)?;
         Ok(v)
     }
 }
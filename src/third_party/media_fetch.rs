@@ -5,6 +5,8 @@ use reqwest::header::{
 use std::sync::OnceLock;
 use std::time::Duration;
 
+use super::bandwidth::read_all_throttled;
+
 /// 复用 HTTP Client，避免每次调用都重建导致连接池和 TLS 握手浪费。
 fn shared_client() -> &'static Client {
     static CLIENT: OnceLock<Client> = OnceLock::new();
@@ -38,6 +40,5 @@ pub(crate) fn fetch_bytes(url: &str, timeout: Duration) -> Option<Vec<u8>> {
     let client = shared_client();
     let resp = client.get(url).timeout(timeout).send().ok()?;
     let resp = resp.error_for_status().ok()?;
-    let bytes = resp.bytes().ok()?;
-    Some(bytes.to_vec())
+    read_all_throttled(resp).ok()
 }
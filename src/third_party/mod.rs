@@ -1,2 +1,3 @@
+pub(crate) mod bandwidth;
 pub(crate) mod content_client;
 pub(crate) mod media_fetch;
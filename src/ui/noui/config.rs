@@ -7,9 +7,10 @@ use std::path::Path;
 
 use anyhow::{Context, Result, anyhow};
 
-use crate::base_system::config::{ConfigSpec, write_with_comments};
+use crate::base_system::config::{ConfigSpec, reset_all, write_with_comments};
 use crate::base_system::context::{
-    Config, output_format_choices, output_format_label, output_format_value_from_label,
+    Config, RESET_PRESERVED_FIELDS, output_format_choices, output_format_label,
+    output_format_value_from_label,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -51,6 +52,7 @@ enum ConfigField {
     DownloadCommentAvatars,
     MediaDownloadWorkers,
     BlockedMediaDomains,
+    AllowedMediaDomains,
     ForceConvertImagesToJpeg,
     JpegRetryConvert,
     JpegQuality,
@@ -207,6 +209,11 @@ pub(super) fn show_config_menu(config: &mut Config) -> Result<()> {
             field: ConfigField::BlockedMediaDomains,
             ty: ConfigValueType::List,
         },
+        ConfigOption {
+            name: "图片域名白名单(逗号分隔，与黑名单互斥)",
+            field: ConfigField::AllowedMediaDomains,
+            ty: ConfigValueType::List,
+        },
         ConfigOption {
             name: "强制所有图片转JPEG",
             field: ConfigField::ForceConvertImagesToJpeg,
@@ -258,10 +265,8 @@ pub(super) fn show_config_menu(config: &mut Config) -> Result<()> {
         println!("\n=== 配置选项 ===");
         for (idx, opt) in OPTS.iter().enumerate() {
             let mut name = opt.name.to_string();
-            if matches!(opt.field, ConfigField::EnableSegmentComments)
-                && config.novel_format.eq_ignore_ascii_case("txt")
-            {
-                name.push_str("（TXT 不支持）");
+            if matches!(opt.field, ConfigField::EnableSegmentComments) && !config.wants_epub() {
+                name.push_str("（仅 EPUB 支持）");
             }
             println!(
                 "{}. {}: {}",
@@ -270,6 +275,7 @@ pub(super) fn show_config_menu(config: &mut Config) -> Result<()> {
                 config_value_display(config, opt.field)
             );
         }
+        println!("R. 全部恢复默认（保留保存路径等关键路径与凭据）");
         println!("0. 返回主菜单");
 
         let choice = super::read_line("\n请选择要修改的配置项编号: ")?;
@@ -277,6 +283,19 @@ pub(super) fn show_config_menu(config: &mut Config) -> Result<()> {
         if choice == "0" {
             break;
         }
+        if choice.eq_ignore_ascii_case("r") {
+            let confirm = super::read_line("确认将全部配置恢复默认？输入 yes 确认: ")?;
+            if !confirm.trim().eq_ignore_ascii_case("yes") {
+                println!("已取消恢复");
+                continue;
+            }
+            *config =
+                reset_all(config, RESET_PRESERVED_FIELDS).map_err(|e| anyhow!(e.to_string()))?;
+            write_with_comments(config, Path::new(<Config as ConfigSpec>::FILE_NAME))
+                .map_err(|e| anyhow!(e.to_string()))?;
+            println!("已将全部配置恢复默认");
+            continue;
+        }
         let Ok(idx) = choice.parse::<usize>() else {
             println!("请输入数字编号");
             continue;
@@ -328,9 +347,7 @@ pub(super) fn show_config_menu(config: &mut Config) -> Result<()> {
 fn config_value_display(config: &Config, field: ConfigField) -> String {
     match field {
         ConfigField::SavePath => config.save_path.clone(),
-        ConfigField::NovelFormat => {
-            output_format_label(config.current_output_format_choice()).to_string()
-        }
+        ConfigField::NovelFormat => output_format_label(&config.current_output_format_choice()),
         ConfigField::AutoClearDump => config.auto_clear_dump.to_string(),
         ConfigField::AllowOverwriteFiles => config.allow_overwrite_files.to_string(),
         ConfigField::PreferredBookNameField => {
@@ -358,6 +375,7 @@ fn config_value_display(config: &Config, field: ConfigField) -> String {
         ConfigField::DownloadCommentAvatars => config.download_comment_avatars.to_string(),
         ConfigField::MediaDownloadWorkers => config.media_download_workers.to_string(),
         ConfigField::BlockedMediaDomains => config.blocked_media_domains.join(","),
+        ConfigField::AllowedMediaDomains => config.allowed_media_domains.join(","),
         ConfigField::ForceConvertImagesToJpeg => config.force_convert_images_to_jpeg.to_string(),
         ConfigField::JpegRetryConvert => config.jpeg_retry_convert.to_string(),
         ConfigField::JpegQuality => config.jpeg_quality.to_string(),
@@ -417,7 +435,7 @@ fn set_bool(config: &mut Config, field: ConfigField, v: bool) -> Result<()> {
         ConfigField::EnableAudiobook => config.enable_audiobook = v,
         ConfigField::UseOfficialApi => config.use_official_api = v,
         ConfigField::EnableSegmentComments => {
-            if v && config.novel_format.eq_ignore_ascii_case("txt") {
+            if v && !config.wants_epub() {
                 config.novel_format = "epub".to_string();
                 println!("已自动将保存格式切换为 EPUB 以启用段评功能。");
             }
@@ -556,9 +574,9 @@ fn set_string(config: &mut Config, field: ConfigField, v: &str) -> Result<()> {
             config
                 .apply_output_format_choice(choice)
                 .map_err(anyhow::Error::msg)?;
-            if config.novel_format == "txt" && config.enable_segment_comments {
+            if !config.wants_epub() && config.enable_segment_comments {
                 config.enable_segment_comments = false;
-                println!("已自动关闭段评以兼容 TXT 格式。");
+                println!("已自动关闭段评（段评仅对 EPUB 生效）。");
             }
         }
         ConfigField::AudiobookVoice => config.audiobook_voice = v.to_string(),
@@ -601,7 +619,20 @@ fn set_string(config: &mut Config, field: ConfigField, v: &str) -> Result<()> {
 fn set_list(config: &mut Config, field: ConfigField, v: Vec<String>) -> Result<()> {
     match field {
         ConfigField::ApiEndpoints => config.api_endpoints = v,
-        ConfigField::BlockedMediaDomains => config.blocked_media_domains = v,
+        ConfigField::BlockedMediaDomains => {
+            let prev = std::mem::replace(&mut config.blocked_media_domains, v);
+            if let Err(msg) = config.validate_media_domain_filters() {
+                config.blocked_media_domains = prev;
+                return Err(anyhow!(msg));
+            }
+        }
+        ConfigField::AllowedMediaDomains => {
+            let prev = std::mem::replace(&mut config.allowed_media_domains, v);
+            if let Err(msg) = config.validate_media_domain_filters() {
+                config.allowed_media_domains = prev;
+                return Err(anyhow!(msg));
+            }
+        }
         _ => return Err(anyhow!("该字段不是 list")),
     }
     Ok(())
@@ -10,10 +10,14 @@ use crate::base_system::context::Config;
 use crate::download::downloader as dl;
 use crate::download::downloader::ChapterRef;
 
+use super::json_output::{self, JsonEvent, ProgressEmitter};
+
 #[derive(Debug, Clone, Copy)]
 struct DownloadOptions {
     interactive: bool,
     retry_failed_once: bool,
+    /// NDJSON 输出（仅非交互模式）
+    json: bool,
 }
 
 impl DownloadOptions {
@@ -21,13 +25,15 @@ impl DownloadOptions {
         Self {
             interactive: true,
             retry_failed_once: false,
+            json: false,
         }
     }
 
-    fn non_interactive(retry_failed_once: bool) -> Self {
+    fn non_interactive(retry_failed_once: bool, json: bool) -> Self {
         Self {
             interactive: false,
             retry_failed_once,
+            json,
         }
     }
 }
@@ -40,11 +46,12 @@ pub(super) fn download_book_non_interactive(
     book_id: &str,
     config: &Config,
     retry_failed_once: bool,
+    json: bool,
 ) -> Result<()> {
     download_book_with_options(
         book_id,
         config,
-        DownloadOptions::non_interactive(retry_failed_once),
+        DownloadOptions::non_interactive(retry_failed_once, json),
     )
 }
 
@@ -52,9 +59,10 @@ pub(super) fn update_existing_book_non_interactive(
     book_id: &str,
     config: &Config,
     retry_failed_once: bool,
+    json: bool,
 ) -> Result<()> {
     ensure_local_download_exists(config, book_id)?;
-    download_book_non_interactive(book_id, config, retry_failed_once)
+    download_book_non_interactive(book_id, config, retry_failed_once, json)
 }
 
 fn ensure_local_download_exists(config: &Config, book_id: &str) -> Result<()> {
@@ -82,6 +90,7 @@ fn download_book_with_options(
     options: DownloadOptions,
 ) -> Result<()> {
     let start_time = Instant::now();
+    let json = options.json;
 
     let plan = dl::prepare_download_plan(config, book_id, dl::BookMeta::default())
         .with_context(|| format!("准备下载计划失败: book_id={}", book_id))?;
@@ -93,25 +102,25 @@ fn download_book_with_options(
         .unwrap_or_else(|| plan.book_id.clone());
 
     // 打印书籍信息（对齐 old_main.py 的信息展示）
-    println!("\n书名: {}", book_name);
+    say!(json, "\n书名: {}", book_name);
     if let Some(author) = plan.meta.author.as_deref() {
-        println!("作者: {}", author);
+        say!(json, "作者: {}", author);
     }
     if let Some(finished) = plan.meta.finished {
-        println!("是否完结: {}", if finished { "完结" } else { "连载" });
+        say!(json, "是否完结: {}", if finished { "完结" } else { "连载" });
     }
     if let Some(count) = plan.meta.chapter_count {
-        println!("章节数: {}", count);
+        say!(json, "章节数: {}", count);
     }
     if !plan.meta.tags.is_empty() {
-        println!("标签: {}", plan.meta.tags.join("|"));
+        say!(json, "标签: {}", plan.meta.tags.join("|"));
     }
     if let Some(desc) = plan.meta.description.as_deref() {
         let mut short = desc.to_string();
         if short.chars().count() > 50 {
             short = short.chars().take(50).collect::<String>() + "...";
         }
-        println!("简介: {}", short);
+        say!(json, "简介: {}", short);
     }
 
     // 初始化 BookManager 并尝试加载历史状态
@@ -119,20 +128,52 @@ fn download_book_with_options(
     let resumed =
         manager.load_existing_status(&manager.book_id.clone(), &manager.book_name.clone());
     if resumed {
-        println!("\n已检测到历史下载记录，可继续下载或选择重新下载。\n");
+        say!(json, "\n已检测到历史下载记录，可继续下载或选择重新下载。\n");
     }
 
     // 若封面已经下载到状态目录，尝试 ASCII 预览
-    if let Some(cover) = find_cover_image(manager.book_folder()) {
+    if !json && let Some(cover) = find_cover_image(manager.book_folder()) {
         let _ = preview_cover_ascii(&cover);
     }
 
     let total = plan.chapters.len();
+    let volumes = plan.volumes();
     let (downloaded_ok, failed_count) = count_download_state(&manager, &plan.chapters);
-    println!(
+    say!(
+        json,
         "共发现 {} 章，下载失败 {} 章，已下载 {} 章",
-        total, failed_count, downloaded_ok
+        total,
+        failed_count,
+        downloaded_ok
     );
+    if json {
+        json_output::emit(&JsonEvent::Book {
+            book_id: &plan.book_id,
+            book_name: &book_name,
+            author: plan.meta.author.as_deref(),
+            chapter_total: total,
+            downloaded: downloaded_ok,
+            failed: failed_count,
+        });
+    }
+
+    if let Some(warning) = plan.catalog_warning() {
+        say!(json, "警告: {}", warning);
+        if json {
+            json_output::emit(&JsonEvent::Warning { message: &warning });
+        }
+        if config.block_on_incomplete_catalog {
+            let proceed = options.interactive
+                && super::read_line("是否仍然继续下载？[y/N]: ")?
+                    .trim()
+                    .eq_ignore_ascii_case("y");
+            if !proceed {
+                say!(json, "已按配置暂停下载（block_on_incomplete_catalog）\n");
+                let _ = manager.cleanup_status_folder();
+                return Ok(());
+            }
+        }
+    }
 
     let mut range: Option<dl::ChapterRange> = None;
     let mode = if options.interactive {
@@ -152,11 +193,11 @@ fn download_book_with_options(
         }
         DownloadMode::Full => {
             manager.downloaded.clear();
-            println!("将重新下载全部章节");
+            say!(json, "将重新下载全部章节");
         }
         DownloadMode::RangeIgnoreHistory | DownloadMode::RangeOrAll => {
             range = if options.interactive {
-                prompt_range(total)?
+                prompt_range(total, &volumes)?
             } else {
                 None
             };
@@ -167,9 +208,9 @@ fn download_book_with_options(
         DownloadMode::Resume | DownloadMode::FailedOnly => {}
     }
 
-    let chosen_chapters = dl::apply_range(&plan.chapters, range);
+    let chosen_chapters = dl::apply_title_filter(config, dl::apply_range(&plan.chapters, range));
     if chosen_chapters.is_empty() {
-        println!("范围无效或章节为空\n");
+        say!(json, "范围无效或章节为空\n");
         let _ = manager.cleanup_status_folder();
         return Ok(());
     }
@@ -180,7 +221,8 @@ fn download_book_with_options(
     };
 
     if matches!(mode, DownloadMode::Resume) {
-        println!(
+        say!(
+            json,
             "继续下载剩余章节: {} 章 (已完成 {})",
             pending.len(),
             chosen_chapters.len().saturating_sub(pending.len())
@@ -188,10 +230,13 @@ fn download_book_with_options(
     }
 
     if pending.is_empty() {
-        println!("没有需要下载的章节，将仅补齐段评缓存并执行收尾生成。\n");
+        say!(
+            json,
+            "没有需要下载的章节，将仅补齐段评缓存并执行收尾生成。\n"
+        );
     }
 
-    println!("\n开始下载...");
+    say!(json, "\n开始下载...");
     let save_dir = manager.default_save_dir();
 
     let retry_failed = if options.interactive {
@@ -213,7 +258,7 @@ fn download_book_with_options(
                 return false;
             }
             retried = true;
-            println!("\n重新下载失败章节: {} 章...", pending_len);
+            say!(json, "\n重新下载失败章节: {} 章...", pending_len);
             true
         }))
     } else {
@@ -234,28 +279,140 @@ fn download_book_with_options(
         dl::DownloadFlowOptions {
             mode: exec_mode,
             range,
+            selection: None,
             retry_failed,
-            stage_callback: Some(Box::new(|result| {
-                println!(
+            stage_callback: Some(Box::new(move |result| {
+                if json {
+                    json_output::emit(&JsonEvent::Stage {
+                        success: result.success,
+                        failed: result.failed,
+                        canceled: result.canceled,
+                        paid_skipped: result.paid_skipped,
+                        top_failure: result.failure_stats.top(),
+                    });
+                }
+                say!(
+                    json,
                     "\n下载完成（阶段）成功: {} 章 | 失败: {} 章 | 取消: {} 章",
-                    result.success, result.failed, result.canceled
+                    result.success,
+                    result.failed,
+                    result.canceled
                 );
+                if result.paid_skipped > 0 {
+                    say!(json, "付费/未解锁章节已跳过: {} 章", result.paid_skipped);
+                }
+                if let Some(kind) = result.failure_stats.top() {
+                    say!(
+                        json,
+                        "失败原因：{}（主要失败: {}，{}）",
+                        result.failure_stats.summary(),
+                        kind.label(),
+                        kind.hint()
+                    );
+                }
             })),
             book_name_asker: None,
             format_asker: None,
+            modified_asker: options.interactive.then(|| {
+                Box::new(|modified: &[dl::ChapterRef]| {
+                    println!("\n检测到 {} 章正文已被修改：", modified.len());
+                    for ch in modified {
+                        println!("  - {}", ch.title);
+                    }
+                    super::read_line(&format!("是否重新下载这 {} 章？[y/N]: ", modified.len()))
+                        .is_ok_and(|s| s.trim().eq_ignore_ascii_case("y"))
+                }) as dl::ModifiedAsker
+            }),
         },
-        None,
+        json.then(|| {
+            let mut emitter = ProgressEmitter::default();
+            Box::new(move |snap: dl::ProgressSnapshot| emitter.on_snapshot(&snap))
+                as Box<dyn FnMut(dl::ProgressSnapshot) + Send>
+        }),
         None,
     )?;
 
-    println!(
+    say!(
+        json,
         "\n下载完成！用时 {:.1} 秒",
         start_time.elapsed().as_secs_f32()
     );
-    println!("已保存到 {}", save_dir.display());
+    say!(json, "已保存到 {}", save_dir.display());
+    if json {
+        json_output::emit(&JsonEvent::Done {
+            book_id,
+            elapsed_secs: start_time.elapsed().as_secs_f64(),
+            save_dir: &save_dir.display().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// `--offline <DIR>`：读取本地目录 JSON 与章节内容直接生成成书。
+pub(super) fn generate_offline(dir: &Path, config: &Config, json: bool) -> Result<()> {
+    let start_time = Instant::now();
+    say!(json, "离线生成: {}", dir.display());
+    let plan = crate::download::offline::generate_offline(
+        config,
+        dir,
+        json.then(|| {
+            let mut emitter = ProgressEmitter::default();
+            Box::new(move |snap: dl::ProgressSnapshot| emitter.on_snapshot(&snap))
+                as Box<dyn FnMut(dl::ProgressSnapshot) + Send>
+        }),
+        None,
+    )
+    .with_context(|| format!("离线生成失败: {}", dir.display()))?;
+
+    let save_dir = config.default_save_dir();
+    say!(
+        json,
+        "离线生成完成！用时 {:.1} 秒",
+        start_time.elapsed().as_secs_f32()
+    );
+    say!(json, "已保存到 {}", save_dir.display());
+    if json {
+        json_output::emit(&JsonEvent::Done {
+            book_id: &plan.book_id,
+            elapsed_secs: start_time.elapsed().as_secs_f64(),
+            save_dir: &save_dir.display().to_string(),
+        });
+    }
     Ok(())
 }
 
+/// 试运行诊断：逐项输出检查结果与耗时，任一项失败时返回错误。
+pub(super) fn dry_run(input: &str, config: &Config, json: bool) -> Result<()> {
+    say!(json, "试运行诊断（不下载、不写文件）: {}", input.trim());
+    let steps = crate::download::diagnose::run_diagnose(config, input, |step| {
+        let elapsed_ms = step.elapsed.as_millis() as u64;
+        if json {
+            json_output::emit(&JsonEvent::Check {
+                name: step.name,
+                ok: step.ok,
+                detail: &step.detail,
+                elapsed_ms,
+            });
+        }
+        say!(
+            json,
+            "[{}] {}（{} ms）{}{}",
+            if step.ok { "通过" } else { "失败" },
+            step.name,
+            elapsed_ms,
+            if step.detail.is_empty() { "" } else { "：" },
+            step.detail
+        );
+    });
+    let failed: Vec<&str> = steps.iter().filter(|s| !s.ok).map(|s| s.name).collect();
+    if failed.is_empty() {
+        say!(json, "诊断通过，共 {} 项", steps.len());
+        Ok(())
+    } else {
+        Err(anyhow!("试运行诊断未通过：{}", failed.join("、")))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum DownloadMode {
     Resume,
@@ -288,14 +445,57 @@ fn select_download_mode(has_failed: bool) -> Result<DownloadMode> {
     Ok(mode)
 }
 
-fn prompt_range(total: usize) -> Result<Option<dl::ChapterRange>> {
-    let text = super::read_line("输入章节范围 形如 10~200 (留空表示全部): ")?;
+fn prompt_range(total: usize, volumes: &[dl::VolumeInfo]) -> Result<Option<dl::ChapterRange>> {
+    if !volumes.is_empty() {
+        println!("分卷：");
+        for (i, vol) in volumes.iter().enumerate() {
+            println!(
+                "  vol:{} {} ({}~{}，共 {} 章)",
+                i + 1,
+                vol.title,
+                vol.start,
+                vol.end,
+                vol.chapter_count()
+            );
+        }
+    }
+    let prompt = if volumes.is_empty() {
+        "输入章节范围 形如 10~200 或 last:20 (留空表示全部): "
+    } else {
+        "输入章节范围 形如 10~200、last:20 或 vol:2 (留空表示全部): "
+    };
+    let text = super::read_line(prompt)?;
     let text = text.trim();
     if text.is_empty() {
         return Ok(None);
     }
+    if let Some(n) = dl::strip_volume_prefix(text) {
+        return match dl::volume_range(volumes, n) {
+            Ok(range) => {
+                println!("已选择分卷: {}~{}", range.start, range.end);
+                Ok(Some(range))
+            }
+            Err(e) => {
+                println!("{e}，将使用全部章节");
+                Ok(None)
+            }
+        };
+    }
+    if let Some(n) = text
+        .strip_prefix("last:")
+        .or_else(|| text.strip_prefix("last："))
+    {
+        let Ok(n) = n.trim().parse::<usize>() else {
+            println!("last:N 解析失败，将使用全部章节");
+            return Ok(None);
+        };
+        let start = total.saturating_sub(n.max(1)) + 1;
+        let end = total.max(1);
+        println!("已选择最新 {} 章: {}~{}", end + 1 - start, start, end);
+        return Ok(Some(dl::ChapterRange { start, end }));
+    }
     let Some((a, b)) = text.split_once('~') else {
-        println!("范围格式错误，应为 a~b，将使用全部章节");
+        println!("范围格式错误，应为 a~b 或 last:N，将使用全部章节");
         return Ok(None);
     };
     let Ok(mut start) = a.trim().parse::<usize>() else {
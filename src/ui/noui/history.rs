@@ -19,7 +19,7 @@ pub(super) fn show_history_menu() -> Result<()> {
         } else {
             for (i, it) in items.iter().enumerate() {
                 println!(
-                    "{:>2}. [{}] 《{}》({}) | 作者: {} | {} | 状态: {}",
+                    "{:>2}. [{}] 《{}》({}) | 作者: {} | 范围: {} | {} | 状态: {}",
                     i + 1,
                     it.timestamp,
                     it.book_name,
@@ -29,6 +29,11 @@ pub(super) fn show_history_menu() -> Result<()> {
                     } else {
                         it.author.trim()
                     },
+                    if it.range.is_empty() {
+                        "全部"
+                    } else {
+                        it.range.as_str()
+                    },
                     it.progress,
                     it.status
                 );
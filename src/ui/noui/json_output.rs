@@ -0,0 +1,172 @@
+//! 非交互模式的机器可读输出（`--output-format json` / `--json`）。
+//!
+//! stdout 每行一个 JSON 对象（NDJSON），人类可读提示与日志改写到 stderr。
+//! 每条事件都带 `schema`（当前为 [`SCHEMA_VERSION`]）与 `event` 字段，字段只增不改：
+//!
+//! | event          | 字段 |
+//! |----------------|------|
+//! | `book`         | `book_id` `book_name` `author` `chapter_total` `downloaded` `failed` |
+//! | `warning`      | `message` |
+//! | `progress`     | `phase`(`text_save`/`audiobook`) `saved` `total` `group_done` `group_total` `comment_saved` `comment_total` |
+//! | `stage`        | `success` `failed` `canceled` `paid_skipped` `top_failure` |
+//! | `done`         | `book_id` `elapsed_secs` `save_dir` |
+//! | `search_hit`   | `book_id` `index` `chapter_id` `title` `count` `snippets` |
+//! | `search_done`  | `book_id` `keyword` `chapters` `hit_chapters` `matches` |
+//! | `check`        | `name` `ok` `detail` `elapsed_ms` |
+//! | `error`        | `message` |
+
+use std::io::Write;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::download::models::{FailureKind, ProgressSnapshot, SavePhase};
+
+pub(crate) const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// 人类可读文本
+    #[default]
+    Text,
+    /// NDJSON 事件流
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum JsonEvent<'a> {
+    Book {
+        book_id: &'a str,
+        book_name: &'a str,
+        author: Option<&'a str>,
+        chapter_total: usize,
+        downloaded: usize,
+        failed: usize,
+    },
+    Warning {
+        message: &'a str,
+    },
+    Progress {
+        phase: &'static str,
+        saved: usize,
+        total: usize,
+        group_done: usize,
+        group_total: usize,
+        comment_saved: usize,
+        comment_total: usize,
+    },
+    Stage {
+        success: u32,
+        failed: u32,
+        canceled: u32,
+        paid_skipped: u32,
+        top_failure: Option<FailureKind>,
+    },
+    Done {
+        book_id: &'a str,
+        elapsed_secs: f64,
+        save_dir: &'a str,
+    },
+    SearchHit {
+        book_id: &'a str,
+        index: usize,
+        chapter_id: &'a str,
+        title: &'a str,
+        count: usize,
+        snippets: &'a [String],
+    },
+    SearchDone {
+        book_id: &'a str,
+        keyword: &'a str,
+        chapters: usize,
+        hit_chapters: usize,
+        matches: usize,
+    },
+    Check {
+        name: &'a str,
+        ok: bool,
+        detail: &'a str,
+        elapsed_ms: u64,
+    },
+    Error {
+        message: &'a str,
+    },
+}
+
+#[derive(Serialize)]
+struct Envelope<'a> {
+    schema: u32,
+    #[serde(flatten)]
+    event: &'a JsonEvent<'a>,
+}
+
+/// 序列化为一行 JSON（不含换行）。
+pub(crate) fn to_line(event: &JsonEvent<'_>) -> String {
+    serde_json::to_string(&Envelope {
+        schema: SCHEMA_VERSION,
+        event,
+    })
+    .unwrap_or_default()
+}
+
+/// 输出一行事件到 stdout 并立即刷新，便于外部程序逐行读取。
+pub(crate) fn emit(event: &JsonEvent<'_>) {
+    let mut out = std::io::stdout().lock();
+    let _ = writeln!(out, "{}", to_line(event));
+    let _ = out.flush();
+}
+
+/// 进度事件：仅在计数变化时输出，避免刷屏。
+#[derive(Default)]
+pub(crate) struct ProgressEmitter {
+    last: Option<(usize, usize, usize, SavePhase)>,
+}
+
+impl ProgressEmitter {
+    pub(crate) fn on_snapshot(&mut self, snap: &ProgressSnapshot) {
+        let key = (
+            snap.saved_chapters,
+            snap.group_done,
+            snap.comment_saved,
+            snap.save_phase,
+        );
+        if self.last == Some(key) {
+            return;
+        }
+        self.last = Some(key);
+        emit(&JsonEvent::Progress {
+            phase: match snap.save_phase {
+                SavePhase::TextSave => "text_save",
+                SavePhase::Audiobook => "audiobook",
+            },
+            saved: snap.saved_chapters,
+            total: snap.chapter_total,
+            group_done: snap.group_done,
+            group_total: snap.group_total,
+            comment_saved: snap.comment_saved,
+            comment_total: snap.comment_total,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonEvent, to_line};
+
+    #[test]
+    fn events_carry_schema_and_snake_case_tag() {
+        let line = to_line(&JsonEvent::SearchDone {
+            book_id: "1",
+            keyword: "刀",
+            chapters: 10,
+            hit_chapters: 2,
+            matches: 3,
+        });
+        let v: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(v["schema"], 1);
+        assert_eq!(v["event"], "search_done");
+        assert_eq!(v["hit_chapters"], 2);
+        assert!(!line.contains('\n'));
+    }
+}
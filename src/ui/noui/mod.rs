@@ -13,10 +13,23 @@ use crossterm::terminal::{LeaveAlternateScreen, disable_raw_mode};
 use crate::base_system::context::Config;
 use crate::prewarm_state;
 
+/// 人类可读提示：JSON 输出模式下改写到 stderr，保证 stdout 只有 NDJSON。
+macro_rules! say {
+    ($json:expr, $($arg:tt)*) => {
+        if $json {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
 mod app_update;
 mod config;
 mod download;
 mod history;
+pub(crate) mod json_output;
+mod search;
 mod update;
 
 fn show_config_menu(config: &mut Config) -> Result<()> {
@@ -31,8 +44,26 @@ pub(crate) fn update_existing_book_non_interactive(
     book_id: &str,
     config: &Config,
     retry_failed: bool,
+    json: bool,
 ) -> Result<()> {
-    download::update_existing_book_non_interactive(book_id, config, retry_failed)
+    download::update_existing_book_non_interactive(book_id, config, retry_failed, json)
+}
+
+pub(crate) fn generate_offline(dir: &std::path::Path, config: &Config, json: bool) -> Result<()> {
+    download::generate_offline(dir, config, json)
+}
+
+pub(crate) fn dry_run(input: &str, config: &Config, json: bool) -> Result<()> {
+    download::dry_run(input, config, json)
+}
+
+pub(crate) fn search_downloaded_book(
+    book_id: &str,
+    keyword: &str,
+    config: &Config,
+    json: bool,
+) -> Result<()> {
+    search::search_downloaded_book(book_id, keyword, config, json)
 }
 
 pub fn run(config: &mut Config) -> Result<()> {
@@ -74,7 +105,7 @@ Fork From: https://github.com/Dlmily/Tomato-Novel-Downloader-Lite \n\
         }
 
         let prompt = format!(
-            "旧 CLI 已禁用新建下载；请输入命令（s配置 / h下载历史 / u更新小说 / c检查更新 / U程序自更新 / q退出，默认保存到 {}）：",
+            "旧 CLI 已禁用新建下载；请输入命令（s配置 / h下载历史 / u更新小说 / g全文搜索 / c检查更新 / U程序自更新 / q退出，默认保存到 {}）：",
             config.default_save_dir().display()
         );
         let input = read_line(&prompt)?;
@@ -106,6 +137,11 @@ Fork From: https://github.com/Dlmily/Tomato-Novel-Downloader-Lite \n\
             continue;
         }
 
+        if text.eq_ignore_ascii_case("g") {
+            search::search_menu(config)?;
+            continue;
+        }
+
         if text.eq_ignore_ascii_case("c") {
             app_update::check_update_menu()?;
             continue;
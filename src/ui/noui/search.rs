@@ -0,0 +1,115 @@
+//! noUI 已下载章节全文搜索。
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::base_system::context::Config;
+use crate::book_parser::chapter_search::ChapterIndex;
+
+use super::json_output::{self, JsonEvent};
+
+fn load_index(book_id: &str, config: &Config) -> Result<ChapterIndex> {
+    let folder = config
+        .find_existing_status_folder_by_book_id(book_id, None)
+        .with_context(|| format!("读取保存目录失败: {}", config.default_save_dir().display()))?
+        .ok_or_else(|| {
+            anyhow!(
+                "未在 {} 下找到 book_id={} 的下载记录",
+                config.default_save_dir().display(),
+                book_id
+            )
+        })?;
+    ChapterIndex::load(&folder)
+}
+
+fn print_hits(index: &ChapterIndex, keyword: &str) {
+    let hits = index.search(keyword);
+    if hits.is_empty() {
+        println!("在 {} 章中未找到「{}」", index.chapter_count(), keyword);
+        return;
+    }
+    let total: usize = hits.iter().map(|h| h.count).sum();
+    println!(
+        "「{}」共命中 {} 处，涉及 {} 章（已下载 {} 章）：",
+        keyword,
+        total,
+        hits.len(),
+        index.chapter_count()
+    );
+    for hit in &hits {
+        println!(
+            "\n第 {} 章 {}（{} 处）",
+            hit.index + 1,
+            hit.title,
+            hit.count
+        );
+        for s in &hit.snippets {
+            println!("    {}", s);
+        }
+        if hit.count > hit.snippets.len() {
+            println!("    …… 另有 {} 处", hit.count - hit.snippets.len());
+        }
+    }
+}
+
+/// 逐章输出 `search_hit` 事件，最后输出 `search_done` 汇总。
+fn emit_hits(book_id: &str, index: &ChapterIndex, keyword: &str) {
+    let hits = index.search(keyword);
+    for hit in &hits {
+        json_output::emit(&JsonEvent::SearchHit {
+            book_id,
+            index: hit.index,
+            chapter_id: &hit.chapter_id,
+            title: &hit.title,
+            count: hit.count,
+            snippets: &hit.snippets,
+        });
+    }
+    json_output::emit(&JsonEvent::SearchDone {
+        book_id,
+        keyword,
+        chapters: index.chapter_count(),
+        hit_chapters: hits.len(),
+        matches: hits.iter().map(|h| h.count).sum(),
+    });
+}
+
+/// 非交互：`--search-book <book_id> --keyword <关键词>`。
+pub(super) fn search_downloaded_book(
+    book_id: &str,
+    keyword: &str,
+    config: &Config,
+    json: bool,
+) -> Result<()> {
+    let index = load_index(book_id, config)?;
+    if json {
+        emit_hits(book_id, &index, keyword.trim());
+    } else {
+        print_hits(&index, keyword.trim());
+    }
+    Ok(())
+}
+
+/// 交互：选定书籍后可连续输入关键词搜索。
+pub(super) fn search_menu(config: &Config) -> Result<()> {
+    let book_id = super::read_line("请输入要搜索的 book_id（留空返回）: ")?;
+    let book_id = book_id.trim();
+    if book_id.is_empty() {
+        return Ok(());
+    }
+    let index = match load_index(book_id, config) {
+        Ok(i) => i,
+        Err(err) => {
+            println!("{}\n", err);
+            return Ok(());
+        }
+    };
+    println!("已载入 {} 章", index.chapter_count());
+    loop {
+        let keyword = super::read_line("\n搜索关键词（留空返回）: ")?;
+        let keyword = keyword.trim();
+        if keyword.is_empty() {
+            return Ok(());
+        }
+        print_hits(&index, keyword);
+    }
+}
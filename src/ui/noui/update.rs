@@ -1,7 +1,7 @@
 //! 无 UI 的更新检查与提示。
 
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::PathBuf;
 
 use crate::base_system::novel_updates;
 use anyhow::Result;
@@ -16,7 +16,8 @@ pub(super) struct UpdateEntry {
 
 pub(super) fn update_menu(config: &Config) -> Result<Option<String>> {
     let save_dir = config.default_save_dir();
-    if !save_dir.exists() {
+    let roots = config.save_roots();
+    if !roots.iter().any(|root| root.exists()) {
         println!(
             "没有可供更新的小说（保存目录不存在）：{}\n",
             save_dir.display()
@@ -24,7 +25,7 @@ pub(super) fn update_menu(config: &Config) -> Result<Option<String>> {
         return Ok(None);
     }
 
-    let (updates, no_updates) = scan_updates(config, &save_dir)?;
+    let (updates, no_updates) = scan_updates(config, &roots)?;
     if updates.is_empty() && no_updates.is_empty() {
         println!("没有可供更新的小说\n");
         return Ok(None);
@@ -100,9 +101,12 @@ fn select_from_list(list: &[UpdateEntry], title: &str) -> Result<Option<String>>
     }
 }
 
-fn scan_updates(_config: &Config, save_dir: &Path) -> Result<(Vec<UpdateEntry>, Vec<UpdateEntry>)> {
+fn scan_updates(
+    config: &Config,
+    roots: &[PathBuf],
+) -> Result<(Vec<UpdateEntry>, Vec<UpdateEntry>)> {
     println!("开始扫描更新（会边检查边显示结果）…");
-    let scan = novel_updates::scan_novel_updates_with_progress(save_dir, |progress| {
+    let scan = novel_updates::scan_novel_updates_with_progress(roots, |progress| {
         let row = progress.row;
         print!(
             "\r已检查 {}/{}，当前：《{}》({})      ",
@@ -118,6 +122,7 @@ fn scan_updates(_config: &Config, save_dir: &Path) -> Result<(Vec<UpdateEntry>,
         scan.updates.len(),
         scan.no_updates.len()
     );
+    prompt_merge_duplicates(config, &scan.duplicates)?;
 
     let to_entry = |it: novel_updates::NovelUpdateRow| UpdateEntry {
         book_id: it.book_id.clone(),
@@ -130,6 +135,43 @@ fn scan_updates(_config: &Config, save_dir: &Path) -> Result<(Vec<UpdateEntry>,
     ))
 }
 
+/// 同一 book_id 存在多个缓存文件夹时询问是否合并到 `<book_id>` 目录。
+fn prompt_merge_duplicates(
+    config: &Config,
+    duplicates: &[novel_updates::DuplicateBookFolders],
+) -> Result<()> {
+    if duplicates.is_empty() {
+        return Ok(());
+    }
+    println!("\n检测到 {} 本书存在多个缓存文件夹：", duplicates.len());
+    for dup in duplicates {
+        let names: Vec<String> = dup
+            .folders
+            .iter()
+            .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(str::to_string))
+            .collect();
+        println!(
+            "  《{}》({})：{}",
+            dup.book_name,
+            dup.book_id,
+            names.join(", ")
+        );
+    }
+    let ans = super::read_line("是否合并（保留最新状态并迁移已下载章节）？[y/N]：")?;
+    if !matches!(ans.trim().to_ascii_lowercase().as_str(), "y" | "yes") {
+        println!("已跳过合并\n");
+        return Ok(());
+    }
+    for dup in duplicates {
+        match novel_updates::merge_duplicate_book_folders(config, dup) {
+            Ok(path) => println!("已合并《{}》 -> {}", dup.book_name, path.display()),
+            Err(e) => println!("合并《{}》失败：{}", dup.book_name, e),
+        }
+    }
+    println!();
+    Ok(())
+}
+
 fn update_label(it: &novel_updates::NovelUpdateRow) -> String {
     let ignore_marker = if it.is_ignored { "[已忽略] " } else { "" };
     if it.new_count > 0 && it.local_failed > 0 {
@@ -3,6 +3,14 @@
 //! 展示项目信息，并提供打开链接等按钮。
 
 use super::*;
+use crate::base_system::download_stats::{DownloadStats, format_elapsed};
+
+/// 本页快捷键说明（`?` / F1 帮助浮层）。
+pub(super) const SHORTCUTS: &[(&str, &str)] = &[
+    ("↑ / ↓", "选择按钮"),
+    ("Enter", "执行选中按钮"),
+    ("q / Esc", "返回首页"),
+];
 
 pub(super) fn handle_event_about(app: &mut App, event: Event) -> Result<()> {
     match event {
@@ -119,6 +127,8 @@ pub(super) fn draw_about(frame: &mut ratatui::Frame, app: &mut App) {
         text.push_str("如果你为此付费，你被欺骗了。\n");
     }
 
+    push_download_stats(&mut text, &app.download_stats);
+
     text.push_str("\n===== 程序更新 =====\n");
     if cfg!(feature = "docker") {
         text.push_str("Docker 构建已禁用程序自更新，请通过重新拉取镜像进行升级。\n");
@@ -158,6 +168,49 @@ pub(super) fn draw_about(frame: &mut ratatui::Frame, app: &mut App) {
     super::render_log_box(frame, log_area, app);
 }
 
+fn push_download_stats(text: &mut String, stats: &DownloadStats) {
+    text.push_str("\n===== 下载统计 =====\n");
+    if stats.last_updated.is_empty() {
+        text.push_str("暂无统计（完成一次下载后生成）\n");
+        return;
+    }
+    text.push_str(&format!(
+        "累计: {} 本 / {} 章 / {} 字，耗时 {}\n",
+        stats.books,
+        stats.chapters,
+        stats.words,
+        format_elapsed(stats.elapsed_secs)
+    ));
+    text.push_str(&format!("统计起始: {}\n", stats.first_recorded));
+
+    let recent = stats.recent_daily(7);
+    if !recent.is_empty() {
+        text.push_str("近期趋势:\n");
+        for (day, t) in recent {
+            text.push_str(&format!(
+                "  {}  {} 本 / {} 章 / {} 字\n",
+                day, t.books, t.chapters, t.words
+            ));
+        }
+    }
+
+    let mut endpoints: Vec<_> = stats.endpoints.iter().collect();
+    endpoints.sort_by_key(|e| std::cmp::Reverse(e.1.requests));
+    if !endpoints.is_empty() {
+        text.push_str("Endpoint 历史成功率:\n");
+        for (ep, t) in endpoints {
+            let rate = t
+                .success_rate()
+                .map(|r| format!("{r}%"))
+                .unwrap_or_else(|| "-".to_string());
+            text.push_str(&format!(
+                "  {}  {} ({}/{})\n",
+                ep, rate, t.success, t.requests
+            ));
+        }
+    }
+}
+
 fn check_app_update(app: &mut App) -> Result<()> {
     app.status = "正在检查程序更新…".to_string();
     super::start_app_update_check(app);
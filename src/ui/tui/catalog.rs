@@ -0,0 +1,210 @@
+//! 预览弹窗中的完整章节目录：可滚动浏览并勾选任意章节下载。
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::layout::Alignment;
+use ratatui::prelude::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem};
+
+use super::preview::ChapterState;
+use super::{App, truncate};
+
+const PAGE_STEP: usize = 10;
+
+pub(super) fn open_catalog(app: &mut App) {
+    let Some(pending) = app.pending_download.as_ref() else {
+        return;
+    };
+    if pending.plan.chapters.is_empty() || app.download_cancel_flag.is_some() {
+        return;
+    }
+    app.preview_catalog_open = true;
+    if app.preview_catalog_state.selected().is_none() {
+        app.preview_catalog_state.select(Some(0));
+    }
+    app.status = "空格勾选/取消，a 全选/全不选，c 清空，Enter/Esc 返回".to_string();
+}
+
+/// 勾选的章节 ID 集合；未勾选时为 None（按范围输入下载）。
+pub(super) fn picked_chapter_ids(app: &App) -> Option<HashSet<String>> {
+    if app.preview_picked.is_empty() {
+        return None;
+    }
+    let pending = app.pending_download.as_ref()?;
+    Some(
+        app.preview_picked
+            .iter()
+            .filter_map(|&idx| pending.plan.chapters.get(idx))
+            .map(|ch| ch.id.clone())
+            .collect(),
+    )
+}
+
+fn chapter_total(app: &App) -> usize {
+    app.pending_download
+        .as_ref()
+        .map(|p| p.plan.chapters.len())
+        .unwrap_or(0)
+}
+
+fn move_cursor(app: &mut App, delta: isize) {
+    let total = chapter_total(app);
+    if total == 0 {
+        return;
+    }
+    let cur = app.preview_catalog_state.selected().unwrap_or(0) as isize;
+    let next = (cur + delta).clamp(0, total as isize - 1) as usize;
+    app.preview_catalog_state.select(Some(next));
+}
+
+fn toggle(app: &mut App, idx: usize) {
+    if idx >= chapter_total(app) {
+        return;
+    }
+    if !app.preview_picked.remove(&idx) {
+        app.preview_picked.insert(idx);
+    }
+}
+
+fn close_catalog(app: &mut App) {
+    app.preview_catalog_open = false;
+    app.status = if app.preview_picked.is_empty() {
+        "未勾选章节，将按范围输入下载".to_string()
+    } else {
+        format!(
+            "已勾选 {} 章，确定后仅下载勾选章节",
+            app.preview_picked.len()
+        )
+    };
+}
+
+pub(super) fn handle_event_catalog(app: &mut App, event: Event) -> Result<()> {
+    match event {
+        Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+            KeyCode::Esc | KeyCode::Enter => close_catalog(app),
+            KeyCode::Char('l') | KeyCode::Char('L')
+                if key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                close_catalog(app)
+            }
+            KeyCode::Up => move_cursor(app, -1),
+            KeyCode::Down => move_cursor(app, 1),
+            KeyCode::PageUp => move_cursor(app, -(PAGE_STEP as isize)),
+            KeyCode::PageDown => move_cursor(app, PAGE_STEP as isize),
+            KeyCode::Home => app.preview_catalog_state.select(Some(0)),
+            KeyCode::End => {
+                let total = chapter_total(app);
+                app.preview_catalog_state
+                    .select(Some(total.saturating_sub(1)));
+            }
+            KeyCode::Char(' ') => {
+                let idx = app.preview_catalog_state.selected().unwrap_or(0);
+                toggle(app, idx);
+                move_cursor(app, 1);
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                let total = chapter_total(app);
+                if app.preview_picked.len() == total {
+                    app.preview_picked.clear();
+                } else {
+                    app.preview_picked = (0..total).collect();
+                }
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => app.preview_picked.clear(),
+            _ => {}
+        },
+        Event::Mouse(me) => match me.kind {
+            MouseEventKind::ScrollUp => move_cursor(app, -3),
+            MouseEventKind::ScrollDown => move_cursor(app, 3),
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(area) = app.last_catalog_area
+                    && let Some(idx) = super::list_index_from_mouse_row(
+                        area,
+                        me.row,
+                        &app.preview_catalog_state,
+                        chapter_total(app),
+                    )
+                    && super::pos_in(area, me.column, me.row)
+                {
+                    app.preview_catalog_state.select(Some(idx));
+                    toggle(app, idx);
+                }
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+    Ok(())
+}
+
+pub(super) fn draw_catalog(frame: &mut ratatui::Frame, app: &mut App) {
+    let area = frame.size();
+    let Some(pending) = app.pending_download.as_ref() else {
+        return;
+    };
+    let width = area.width.min(90).max(40.min(area.width));
+    let height = area.height.saturating_sub(4).max(area.height.min(8));
+    let modal = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    let title_width = (width as usize).saturating_sub(24).max(10);
+    let items: Vec<ListItem> = pending
+        .plan
+        .chapters
+        .iter()
+        .enumerate()
+        .map(|(idx, ch)| {
+            let mark = if app.preview_picked.contains(&idx) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            let mut spans = vec![Span::raw(format!(
+                "{mark} {:>4}. {}",
+                idx + 1,
+                truncate(&ch.title, title_width)
+            ))];
+            match pending.chapter_states.get(idx) {
+                Some(ChapterState::Done) => {
+                    spans.push(Span::styled("  已下载", Style::default().fg(Color::Green)))
+                }
+                Some(ChapterState::Failed) => {
+                    spans.push(Span::styled("  失败", Style::default().fg(Color::Red)))
+                }
+                _ => {}
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let title = format!(
+        "章节目录 共 {} 章，已勾选 {} (空格勾选 a 全选 c 清空 Enter 返回)",
+        pending.plan.chapters.len(),
+        app.preview_picked.len()
+    );
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_alignment(Alignment::Center),
+        )
+        .highlight_style(
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    frame.render_widget(Clear, modal);
+    frame.render_stateful_widget(list, modal, &mut app.preview_catalog_state);
+    app.last_catalog_area = Some(modal);
+}
@@ -4,6 +4,17 @@ use super::*;
 
 use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState};
 
+/// 本页快捷键说明（`?` / F1 帮助浮层）。
+pub(super) const SHORTCUTS: &[(&str, &str)] = &[
+    ("← / → / Tab", "切换配置分类"),
+    ("↑ / ↓", "选择配置项"),
+    ("Enter", "编辑当前配置项 / 确认修改"),
+    ("/", "搜索配置项"),
+    ("r", "当前项恢复默认（再按一次确认）"),
+    ("R", "全部恢复默认（再按一次确认）"),
+    ("q / Esc / c / b", "返回首页"),
+];
+
 pub(super) fn handle_event_config(app: &mut App, event: Event) -> Result<()> {
     if app.segment_comments_confirm_open {
         match event {
@@ -101,6 +112,10 @@ pub(super) fn handle_event_config(app: &mut App, event: Event) -> Result<()> {
         return Ok(());
     }
 
+    if app.cfg_search.is_some() && app.cfg_editing.is_none() {
+        return handle_config_search(app, event);
+    }
+
     match event {
         Event::Key(key) if key.kind == KeyEventKind::Press => {
             if let Some((cat_idx, entry_idx)) = app.cfg_editing {
@@ -220,7 +235,12 @@ pub(super) fn handle_event_config(app: &mut App, event: Event) -> Result<()> {
                     _ => {}
                 }
             } else {
+                let reset_armed = app.cfg_reset_armed.take();
                 match key.code {
+                    KeyCode::Char('r') if app.cfg_focus == ConfigFocus::Entry => {
+                        super::handle_cfg_reset_key(app, reset_armed, false)
+                    }
+                    KeyCode::Char('R') => super::handle_cfg_reset_key(app, reset_armed, true),
                     KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('c') => {
                         app.view = View::Home;
                         app.status = "返回主菜单".to_string();
@@ -263,6 +283,12 @@ pub(super) fn handle_event_config(app: &mut App, event: Event) -> Result<()> {
                         app.view = View::Home;
                         app.status = "返回主菜单".to_string();
                     }
+                    KeyCode::Char('/') => {
+                        app.cfg_search = Some(String::new());
+                        app.cfg_search_state.select(Some(0));
+                        app.status =
+                            "输入关键词或拼音首字母搜索配置项，回车编辑，Esc 退出搜索".to_string();
+                    }
                     _ => {}
                 }
             }
@@ -275,6 +301,125 @@ pub(super) fn handle_event_config(app: &mut App, event: Event) -> Result<()> {
     Ok(())
 }
 
+/// 搜索模式：输入即过滤，上下选择结果，回车跳转到对应分类并开始编辑。
+fn handle_config_search(app: &mut App, event: Event) -> Result<()> {
+    let query = app.cfg_search.clone().unwrap_or_default();
+    let results = super::search_config_entries(&app.cfg_categories, &query);
+    match event {
+        Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+            KeyCode::Esc => {
+                app.cfg_search = None;
+                app.status = "已退出搜索".to_string();
+            }
+            KeyCode::Enter => {
+                let sel = app.cfg_search_state.selected().unwrap_or(0);
+                if let Some(&(cat_idx, entry_idx)) = results.get(sel) {
+                    jump_to_config_entry(app, cat_idx, entry_idx);
+                } else {
+                    app.status = "没有匹配的配置项".to_string();
+                }
+            }
+            KeyCode::Up => {
+                let cur = app.cfg_search_state.selected().unwrap_or(0);
+                app.cfg_search_state.select(Some(cur.saturating_sub(1)));
+            }
+            KeyCode::Down => {
+                let cur = app.cfg_search_state.selected().unwrap_or(0);
+                let next = (cur + 1).min(results.len().saturating_sub(1));
+                app.cfg_search_state.select(Some(next));
+            }
+            KeyCode::Backspace => {
+                if let Some(q) = app.cfg_search.as_mut() {
+                    q.pop();
+                }
+                app.cfg_search_state.select(Some(0));
+            }
+            KeyCode::Char(c) => {
+                if let Some(q) = app.cfg_search.as_mut() {
+                    q.push(c);
+                }
+                app.cfg_search_state.select(Some(0));
+            }
+            _ => {}
+        },
+        Event::Mouse(me) if matches!(me.kind, MouseEventKind::Down(MouseButton::Left)) => {
+            if let Some(layout) = app.last_config_layout
+                && super::pos_in(layout[2], me.column, me.row)
+                && let Some(idx) = super::list_index_from_mouse_row(
+                    layout[2],
+                    me.row,
+                    &app.cfg_search_state,
+                    results.len(),
+                )
+                && let Some(&(cat_idx, entry_idx)) = results.get(idx)
+            {
+                jump_to_config_entry(app, cat_idx, entry_idx);
+            }
+        }
+        Event::Mouse(me) if matches!(me.kind, MouseEventKind::ScrollUp) => {
+            let cur = app.cfg_search_state.selected().unwrap_or(0);
+            app.cfg_search_state.select(Some(cur.saturating_sub(1)));
+        }
+        Event::Mouse(me) if matches!(me.kind, MouseEventKind::ScrollDown) => {
+            let cur = app.cfg_search_state.selected().unwrap_or(0);
+            let next = (cur + 1).min(results.len().saturating_sub(1));
+            app.cfg_search_state.select(Some(next));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn jump_to_config_entry(app: &mut App, cat_idx: usize, entry_idx: usize) {
+    app.cfg_search = None;
+    app.cfg_cat_state.select(Some(cat_idx));
+    app.cfg_entry_state.select(Some(entry_idx));
+    app.cfg_focus = ConfigFocus::Entry;
+    super::start_cfg_edit(app);
+}
+
+fn draw_config_search(frame: &mut ratatui::Frame, app: &mut App, area: Rect, query: &str) {
+    let results = super::search_config_entries(&app.cfg_categories, query);
+    let items: Vec<ListItem> = if results.is_empty() {
+        vec![ListItem::new("没有匹配的配置项")]
+    } else {
+        results
+            .iter()
+            .map(|&(cat_idx, entry_idx)| {
+                let cat = &app.cfg_categories[cat_idx];
+                let entry = &cat.entries[entry_idx];
+                let val = super::current_cfg_value(app, entry.field);
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} / ", cat.title),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(format!("{}: {}", entry.title, val)),
+                ]))
+            })
+            .collect()
+    };
+    if let Some(sel) = app.cfg_search_state.selected()
+        && sel >= results.len()
+    {
+        app.cfg_search_state
+            .select(Some(results.len().saturating_sub(1)));
+    }
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("搜索: {query}_ (回车编辑, Esc 退出)")),
+        )
+        .highlight_style(
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+    frame.render_stateful_widget(list, area, &mut app.cfg_search_state);
+}
+
 pub(super) fn handle_mouse_config(app: &mut App, me: event::MouseEvent) -> Result<()> {
     let Some(layout) = app.last_config_layout else {
         return Ok(());
@@ -611,7 +756,9 @@ pub(super) fn draw_config(frame: &mut ratatui::Frame, app: &mut App) {
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::raw("  |  左右/Tab 切分类, 上下选项, 回车编辑, q 返回"),
+        Span::raw(
+            "  |  左右/Tab 切分类, 上下选项, 回车编辑, / 搜索, r/R 恢复默认(单项/全部), ? 帮助, q 返回",
+        ),
     ]);
 
     let header = Paragraph::new(header_line).block(
@@ -692,84 +839,88 @@ pub(super) fn draw_config(frame: &mut ratatui::Frame, app: &mut App) {
         frame.render_stateful_widget(sb, sb_area, &mut sb_state);
     }
 
-    let entries = super::current_cfg_entries(app);
-    let entry_items: Vec<ListItem> = if let Some(entries) = entries {
-        entries
-            .iter()
-            .enumerate()
-            .map(|(idx, entry)| {
-                let val = super::current_cfg_value(app, entry.field);
-                let mut spans = vec![Span::raw(format!("{}: {}", entry.title, val))];
-                if let Some((cat_i, entry_i)) = app.cfg_editing
-                    && Some(cat_i) == app.cfg_cat_state.selected()
-                    && entry_i == idx
-                {
-                    spans.push(Span::raw("  [编辑中] "));
-                    if !super::cfg_field_is_bool(entry.field) {
-                        spans.push(Span::styled(
-                            app.cfg_edit_buffer.clone(),
-                            Style::default().fg(Color::Yellow),
-                        ));
-                    }
-                }
-                ListItem::new(Line::from(spans))
-            })
-            .collect()
+    if let Some(query) = app.cfg_search.clone() {
+        draw_config_search(frame, app, body[1], &query);
     } else {
-        vec![ListItem::new("无可编辑配置")]
-    };
+        let entries = super::current_cfg_entries(app);
+        let entry_items: Vec<ListItem> = if let Some(entries) = entries {
+            entries
+                .iter()
+                .enumerate()
+                .map(|(idx, entry)| {
+                    let val = super::current_cfg_value(app, entry.field);
+                    let mut spans = vec![Span::raw(format!("{}: {}", entry.title, val))];
+                    if let Some((cat_i, entry_i)) = app.cfg_editing
+                        && Some(cat_i) == app.cfg_cat_state.selected()
+                        && entry_i == idx
+                    {
+                        spans.push(Span::raw("  [编辑中] "));
+                        if !super::cfg_field_is_bool(entry.field) {
+                            spans.push(Span::styled(
+                                app.cfg_edit_buffer.clone(),
+                                Style::default().fg(Color::Yellow),
+                            ));
+                        }
+                    }
+                    ListItem::new(Line::from(spans))
+                })
+                .collect()
+        } else {
+            vec![ListItem::new("无可编辑配置")]
+        };
 
-    let entry_highlight = if app.cfg_focus == ConfigFocus::Entry {
-        Style::default()
-            .fg(Color::LightCyan)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::LightCyan)
-    };
+        let entry_highlight = if app.cfg_focus == ConfigFocus::Entry {
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::LightCyan)
+        };
 
-    let entry_block = Block::default()
-        .borders(Borders::ALL)
-        .title("配置项 (上下选择, 回车编辑/保存)");
-    frame.render_widget(entry_block.clone(), body[1]);
-    let entry_inner = entry_block.inner(body[1]);
-    let entry_len = super::current_cfg_entries(app)
-        .map(|e| e.len())
-        .unwrap_or(0);
-    let need_entry_scrollbar =
-        entry_len > 0 && entry_inner.height > 0 && entry_len > entry_inner.height as usize;
-    let (entry_area, entry_sb) = if need_entry_scrollbar && entry_inner.width > 0 {
-        let w = entry_inner.width.saturating_sub(1).max(1);
-        (
-            Rect {
-                x: entry_inner.x,
-                y: entry_inner.y,
-                width: w,
-                height: entry_inner.height,
-            },
-            Some(Rect {
-                x: entry_inner.x.saturating_add(w),
-                y: entry_inner.y,
-                width: 1,
-                height: entry_inner.height,
-            }),
-        )
-    } else {
-        (entry_inner, None)
-    };
+        let entry_block = Block::default()
+            .borders(Borders::ALL)
+            .title("配置项 (上下选择, 回车编辑/保存)");
+        frame.render_widget(entry_block.clone(), body[1]);
+        let entry_inner = entry_block.inner(body[1]);
+        let entry_len = super::current_cfg_entries(app)
+            .map(|e| e.len())
+            .unwrap_or(0);
+        let need_entry_scrollbar =
+            entry_len > 0 && entry_inner.height > 0 && entry_len > entry_inner.height as usize;
+        let (entry_area, entry_sb) = if need_entry_scrollbar && entry_inner.width > 0 {
+            let w = entry_inner.width.saturating_sub(1).max(1);
+            (
+                Rect {
+                    x: entry_inner.x,
+                    y: entry_inner.y,
+                    width: w,
+                    height: entry_inner.height,
+                },
+                Some(Rect {
+                    x: entry_inner.x.saturating_add(w),
+                    y: entry_inner.y,
+                    width: 1,
+                    height: entry_inner.height,
+                }),
+            )
+        } else {
+            (entry_inner, None)
+        };
 
-    let entry_list = List::new(entry_items)
-        .highlight_style(entry_highlight)
-        .highlight_symbol(">> ");
-    frame.render_stateful_widget(entry_list, entry_area, &mut app.cfg_entry_state);
-    if let Some(sb_area) = entry_sb {
-        let pos = app
-            .cfg_entry_state
-            .selected()
-            .unwrap_or(0)
-            .min(entry_len.saturating_sub(1));
-        let mut sb_state = ScrollbarState::new(entry_len).position(pos);
-        let sb = Scrollbar::default().orientation(ScrollbarOrientation::VerticalRight);
-        frame.render_stateful_widget(sb, sb_area, &mut sb_state);
+        let entry_list = List::new(entry_items)
+            .highlight_style(entry_highlight)
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(entry_list, entry_area, &mut app.cfg_entry_state);
+        if let Some(sb_area) = entry_sb {
+            let pos = app
+                .cfg_entry_state
+                .selected()
+                .unwrap_or(0)
+                .min(entry_len.saturating_sub(1));
+            let mut sb_state = ScrollbarState::new(entry_len).position(pos);
+            let sb = Scrollbar::default().orientation(ScrollbarOrientation::VerticalRight);
+            frame.render_stateful_widget(sb, sb_area, &mut sb_state);
+        }
     }
 
     let footer = Layout::default()
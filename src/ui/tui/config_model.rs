@@ -6,21 +6,62 @@ use std::path::Path;
 
 use anyhow::{Result, anyhow};
 
-use crate::base_system::config::{ConfigSpec, write_with_comments};
+use crate::base_system::config::{ConfigSpec, reset_all, write_with_comments};
 use crate::base_system::context::{
-    Config, OUTPUT_FORMAT_BULK_TXT, OUTPUT_FORMAT_PDF, output_format_label,
-    output_format_value_from_label,
+    Config, MAX_BATCH_SIZE, OUTPUT_FORMAT_BULK_TXT, OUTPUT_FORMAT_PDF, OUTPUT_FORMAT_TXT_EPUB,
+    RESET_PRESERVED_FIELDS, output_format_label, output_format_value_from_label,
+    parse_output_formats,
 };
+use crate::base_system::request_error::TimeoutRetryStrategy;
+use crate::base_system::webdav;
+use crate::book_parser::epub_generator::EpubTheme;
+use crate::book_parser::finalize_utils::TxtSplitMode;
+use crate::book_parser::segment_utils::{SegmentCommentExportMode, SegmentCommentStyle};
+use crate::book_parser::text_convert::TextConversion;
 
 use super::App;
 
 #[derive(Debug, Clone, Copy)]
 pub(in crate::ui) enum ConfigField {
     SavePath,
+    SavePathRules,
     NovelFormat,
     AutoClearDump,
+    AutoClearImages,
+    AutoClearSegmentComments,
+    AutoClearJsonl,
     AutoOpenDownloadedFiles,
+    WriteOutputChecksum,
+    DownloadHistoryLimit,
+    WebdavUrl,
+    WebdavUsername,
+    WebdavPassword,
+    EnableNotifications,
     AllowOverwriteFiles,
+    EpubChapterNav,
+    EpubChapterHeader,
+    EpubIncludeImages,
+    EpubMergeShortChapters,
+    EpubShortChapterChars,
+    EpubMergeMaxChapters,
+    TxtChapterSeparator,
+    AutoParagraph,
+    StripDuplicateTitleLine,
+    TrimBlankParagraphs,
+    ReplacementsPath,
+    TextConversion,
+    TextConversionDescription,
+    ShowChapterTime,
+    ChapterTitleFormat,
+    BulkNameTemplate,
+    TxtSplitMode,
+    EpubUseTitleFormat,
+    SplitEpubByVolume,
+    EpubEmbedFontPath,
+    EpubPlaceholderCover,
+    PreferHdCover,
+    EpubTheme,
+    EpubHighlightDialogue,
     PreferredBookNameField,
     OldCli,
     FirstLineIndentEm,
@@ -28,11 +69,22 @@ pub(in crate::ui) enum ConfigField {
     UseOfficialApi,
     ApiEndpoints,
     MaxWorkers,
+    BatchSize,
     RequestTimeout,
     MaxRetries,
     MinConnectTimeout,
     MinWait,
     MaxWait,
+    WaitJitterRatio,
+    FastSwitchOn4xx,
+    TimeoutRetryStrategy,
+    MaxBandwidthKbps,
+    BlockOnIncompleteCatalog,
+    SkipTitlePatterns,
+    DetectModifiedChapters,
+    BookDownloadTimeoutSecs,
+    WaitForNetwork,
+    ChapterLengthAlertRatio,
     EnableAudiobook,
     AudiobookVoice,
     AudiobookRate,
@@ -46,10 +98,17 @@ pub(in crate::ui) enum ConfigField {
     AudiobookTtsModel,
     SegmentCommentsTopN,
     SegmentCommentsWorkers,
+    SegmentCommentsHotChaptersOnly,
+    SegmentCommentsHotChapters,
+    SegmentCommentsRefreshHours,
+    SegmentCommentsStyle,
+    SegmentCommentsExportMode,
+    ExportSegmentNotesHtml,
     DownloadCommentImages,
     DownloadCommentAvatars,
     MediaDownloadWorkers,
     BlockedMediaDomains,
+    AllowedMediaDomains,
     ForceConvertImagesToJpeg,
     JpegRetryConvert,
     JpegQuality,
@@ -160,6 +219,10 @@ pub(in crate::ui) const NOVEL_FORMAT_PRESETS: &[VoicePreset] = &[
         name: OUTPUT_FORMAT_PDF,
         label: "pdf 格式",
     },
+    VoicePreset {
+        name: OUTPUT_FORMAT_TXT_EPUB,
+        label: "txt + epub 格式",
+    },
     VoicePreset {
         name: OUTPUT_FORMAT_BULK_TXT,
         label: "散装文件",
@@ -170,12 +233,110 @@ pub(in crate::ui) const NOVEL_FORMAT_PRESETS: &[VoicePreset] = &[
     },
 ];
 
+pub(in crate::ui) const EPUB_THEME_PRESETS: &[VoicePreset] = &[
+    VoicePreset {
+        name: "default",
+        label: "默认",
+    },
+    VoicePreset {
+        name: "sepia",
+        label: "护眼米色",
+    },
+    VoicePreset {
+        name: "night",
+        label: "夜间深色",
+    },
+    VoicePreset {
+        name: "paper",
+        label: "纸质衬线",
+    },
+];
+
+pub(in crate::ui) const TEXT_CONVERSION_PRESETS: &[VoicePreset] = &[
+    VoicePreset {
+        name: "none",
+        label: "不转换",
+    },
+    VoicePreset {
+        name: "s2t",
+        label: "简体转繁体",
+    },
+    VoicePreset {
+        name: "t2s",
+        label: "繁体转简体",
+    },
+];
+
+pub(in crate::ui) const SEGMENT_COMMENTS_STYLE_PRESETS: &[VoicePreset] = &[
+    VoicePreset {
+        name: "chapter_end",
+        label: "章末汇总式",
+    },
+    VoicePreset {
+        name: "inline_footnote",
+        label: "行内脚注式",
+    },
+    VoicePreset {
+        name: "after_paragraph",
+        label: "段后块式",
+    },
+];
+
+pub(in crate::ui) const SEGMENT_COMMENTS_EXPORT_MODE_PRESETS: &[VoicePreset] = &[
+    VoicePreset {
+        name: "embed",
+        label: "嵌入正文",
+    },
+    VoicePreset {
+        name: "separate",
+        label: "单独导出",
+    },
+    VoicePreset {
+        name: "both",
+        label: "嵌入并导出",
+    },
+];
+
+pub(in crate::ui) const TXT_SPLIT_MODE_PRESETS: &[VoicePreset] = &[
+    VoicePreset {
+        name: "single",
+        label: "整本",
+    },
+    VoicePreset {
+        name: "per-chapter",
+        label: "按章",
+    },
+    VoicePreset {
+        name: "per-volume",
+        label: "按卷",
+    },
+];
+
+pub(in crate::ui) const TIMEOUT_RETRY_STRATEGY_PRESETS: &[VoicePreset] = &[
+    VoicePreset {
+        name: "conservative",
+        label: "保守",
+    },
+    VoicePreset {
+        name: "balanced",
+        label: "均衡",
+    },
+    VoicePreset {
+        name: "aggressive",
+        label: "激进",
+    },
+];
+
 pub(in crate::ui) fn cfg_field_is_combo(field: ConfigField) -> bool {
     matches!(
         field,
         ConfigField::AudiobookVoice
             | ConfigField::PreferredBookNameField
             | ConfigField::NovelFormat
+            | ConfigField::SegmentCommentsStyle
+            | ConfigField::SegmentCommentsExportMode
+            | ConfigField::TxtSplitMode
+            | ConfigField::TimeoutRetryStrategy
     )
 }
 
@@ -184,6 +345,12 @@ pub(in crate::ui) fn cfg_combo_presets(field: ConfigField) -> Option<&'static [V
         ConfigField::AudiobookVoice => Some(AUDIOBOOK_VOICE_PRESETS),
         ConfigField::PreferredBookNameField => Some(BOOK_NAME_FIELD_PRESETS),
         ConfigField::NovelFormat => Some(NOVEL_FORMAT_PRESETS),
+        ConfigField::SegmentCommentsStyle => Some(SEGMENT_COMMENTS_STYLE_PRESETS),
+        ConfigField::SegmentCommentsExportMode => Some(SEGMENT_COMMENTS_EXPORT_MODE_PRESETS),
+        ConfigField::TxtSplitMode => Some(TXT_SPLIT_MODE_PRESETS),
+        ConfigField::TimeoutRetryStrategy => Some(TIMEOUT_RETRY_STRATEGY_PRESETS),
+        ConfigField::EpubTheme => Some(EPUB_THEME_PRESETS),
+        ConfigField::TextConversion => Some(TEXT_CONVERSION_PRESETS),
         _ => None,
     }
 }
@@ -197,6 +364,10 @@ pub(in crate::ui) fn build_config_categories() -> Vec<ConfigCategory> {
                     title: "保存路径",
                     field: ConfigField::SavePath,
                 },
+                ConfigEntry {
+                    title: "分类保存路径规则",
+                    field: ConfigField::SavePathRules,
+                },
                 ConfigEntry {
                     title: "小说格式",
                     field: ConfigField::NovelFormat,
@@ -209,14 +380,146 @@ pub(in crate::ui) fn build_config_categories() -> Vec<ConfigCategory> {
                     title: "自动清理缓存",
                     field: ConfigField::AutoClearDump,
                 },
+                ConfigEntry {
+                    title: "成书后清理图片缓存",
+                    field: ConfigField::AutoClearImages,
+                },
+                ConfigEntry {
+                    title: "成书后清理段评缓存",
+                    field: ConfigField::AutoClearSegmentComments,
+                },
+                ConfigEntry {
+                    title: "成书后清理中间 jsonl",
+                    field: ConfigField::AutoClearJsonl,
+                },
                 ConfigEntry {
                     title: "下载完成后自动打开",
                     field: ConfigField::AutoOpenDownloadedFiles,
                 },
+                ConfigEntry {
+                    title: "写入 SHA-256 校验文件",
+                    field: ConfigField::WriteOutputChecksum,
+                },
+                ConfigEntry {
+                    title: "下载历史保留条数(0 不限)",
+                    field: ConfigField::DownloadHistoryLimit,
+                },
+                ConfigEntry {
+                    title: "WebDAV 上传地址",
+                    field: ConfigField::WebdavUrl,
+                },
+                ConfigEntry {
+                    title: "WebDAV 用户名",
+                    field: ConfigField::WebdavUsername,
+                },
+                ConfigEntry {
+                    title: "WebDAV 密码",
+                    field: ConfigField::WebdavPassword,
+                },
+                ConfigEntry {
+                    title: "完成后系统通知",
+                    field: ConfigField::EnableNotifications,
+                },
                 ConfigEntry {
                     title: "允许覆盖已存在文件",
                     field: ConfigField::AllowOverwriteFiles,
                 },
+                ConfigEntry {
+                    title: "EPUB 章节导航",
+                    field: ConfigField::EpubChapterNav,
+                },
+                ConfigEntry {
+                    title: "EPUB 章节页眉",
+                    field: ConfigField::EpubChapterHeader,
+                },
+                ConfigEntry {
+                    title: "EPUB 包含图片",
+                    field: ConfigField::EpubIncludeImages,
+                },
+                ConfigEntry {
+                    title: "EPUB 合并短章",
+                    field: ConfigField::EpubMergeShortChapters,
+                },
+                ConfigEntry {
+                    title: "短章字数阈值",
+                    field: ConfigField::EpubShortChapterChars,
+                },
+                ConfigEntry {
+                    title: "每文件最多合并章数",
+                    field: ConfigField::EpubMergeMaxChapters,
+                },
+                ConfigEntry {
+                    title: "TXT 章节分隔符",
+                    field: ConfigField::TxtChapterSeparator,
+                },
+                ConfigEntry {
+                    title: "智能分段",
+                    field: ConfigField::AutoParagraph,
+                },
+                ConfigEntry {
+                    title: "去除重复标题行",
+                    field: ConfigField::StripDuplicateTitleLine,
+                },
+                ConfigEntry {
+                    title: "规整空段落",
+                    field: ConfigField::TrimBlankParagraphs,
+                },
+                ConfigEntry {
+                    title: "正文替换表路径",
+                    field: ConfigField::ReplacementsPath,
+                },
+                ConfigEntry {
+                    title: "简繁转换",
+                    field: ConfigField::TextConversion,
+                },
+                ConfigEntry {
+                    title: "简繁转换含简介",
+                    field: ConfigField::TextConversionDescription,
+                },
+                ConfigEntry {
+                    title: "显示章节发布时间",
+                    field: ConfigField::ShowChapterTime,
+                },
+                ConfigEntry {
+                    title: "章节标题模板",
+                    field: ConfigField::ChapterTitleFormat,
+                },
+                ConfigEntry {
+                    title: "散装文件名模板",
+                    field: ConfigField::BulkNameTemplate,
+                },
+                ConfigEntry {
+                    title: "TXT 拆分方式",
+                    field: ConfigField::TxtSplitMode,
+                },
+                ConfigEntry {
+                    title: "EPUB 使用标题模板",
+                    field: ConfigField::EpubUseTitleFormat,
+                },
+                ConfigEntry {
+                    title: "EPUB 按卷拆分导出",
+                    field: ConfigField::SplitEpubByVolume,
+                },
+                ConfigEntry {
+                    title: "EPUB 内嵌字体路径",
+                    field: ConfigField::EpubEmbedFontPath,
+                },
+                ConfigEntry {
+                    title: "无封面时生成占位封面",
+                    field: ConfigField::EpubPlaceholderCover,
+                },
+                ConfigEntry {
+                    title: "优先高清封面",
+                    field: ConfigField::PreferHdCover,
+                },
+                ConfigEntry {
+                    title: "EPUB 主题",
+                    field: ConfigField::EpubTheme,
+                },
+                ConfigEntry {
+                    title: "EPUB 对话分色",
+                    field: ConfigField::EpubHighlightDialogue,
+                },
                 ConfigEntry {
                     title: "优先书名字段",
                     field: ConfigField::PreferredBookNameField,
@@ -234,6 +537,10 @@ pub(in crate::ui) fn build_config_categories() -> Vec<ConfigCategory> {
                     title: "最大线程数",
                     field: ConfigField::MaxWorkers,
                 },
+                ConfigEntry {
+                    title: "章节批量大小",
+                    field: ConfigField::BatchSize,
+                },
                 ConfigEntry {
                     title: "请求超时(s)",
                     field: ConfigField::RequestTimeout,
@@ -254,6 +561,46 @@ pub(in crate::ui) fn build_config_categories() -> Vec<ConfigCategory> {
                     title: "最大等待时间(ms)",
                     field: ConfigField::MaxWait,
                 },
+                ConfigEntry {
+                    title: "等待随机抖动(%)",
+                    field: ConfigField::WaitJitterRatio,
+                },
+                ConfigEntry {
+                    title: "4xx 立即切换 API",
+                    field: ConfigField::FastSwitchOn4xx,
+                },
+                ConfigEntry {
+                    title: "超时重试策略",
+                    field: ConfigField::TimeoutRetryStrategy,
+                },
+                ConfigEntry {
+                    title: "带宽上限(KB/s,0不限)",
+                    field: ConfigField::MaxBandwidthKbps,
+                },
+                ConfigEntry {
+                    title: "目录不完整时需确认",
+                    field: ConfigField::BlockOnIncompleteCatalog,
+                },
+                ConfigEntry {
+                    title: "排除章节标题(正则，分号分隔)",
+                    field: ConfigField::SkipTitlePatterns,
+                },
+                ConfigEntry {
+                    title: "更新时检测被修改的章节",
+                    field: ConfigField::DetectModifiedChapters,
+                },
+                ConfigEntry {
+                    title: "单本下载总超时(秒，0 不限)",
+                    field: ConfigField::BookDownloadTimeoutSecs,
+                },
+                ConfigEntry {
+                    title: "断网时等待恢复后续跑",
+                    field: ConfigField::WaitForNetwork,
+                },
+                ConfigEntry {
+                    title: "短章告警阈值(% 平均字数，0 关闭)",
+                    field: ConfigField::ChapterLengthAlertRatio,
+                },
             ],
         },
         ConfigCategory {
@@ -284,6 +631,30 @@ pub(in crate::ui) fn build_config_categories() -> Vec<ConfigCategory> {
                     title: "段评并发线程数",
                     field: ConfigField::SegmentCommentsWorkers,
                 },
+                ConfigEntry {
+                    title: "只抓热门章节段评",
+                    field: ConfigField::SegmentCommentsHotChaptersOnly,
+                },
+                ConfigEntry {
+                    title: "热门章节数(N)",
+                    field: ConfigField::SegmentCommentsHotChapters,
+                },
+                ConfigEntry {
+                    title: "段评刷新间隔(小时)",
+                    field: ConfigField::SegmentCommentsRefreshHours,
+                },
+                ConfigEntry {
+                    title: "段评呈现样式",
+                    field: ConfigField::SegmentCommentsStyle,
+                },
+                ConfigEntry {
+                    title: "段评输出方式",
+                    field: ConfigField::SegmentCommentsExportMode,
+                },
+                ConfigEntry {
+                    title: "导出段评旁注 HTML",
+                    field: ConfigField::ExportSegmentNotesHtml,
+                },
             ],
         },
         ConfigCategory {
@@ -305,6 +676,10 @@ pub(in crate::ui) fn build_config_categories() -> Vec<ConfigCategory> {
                     title: "阻止的图片域名",
                     field: ConfigField::BlockedMediaDomains,
                 },
+                ConfigEntry {
+                    title: "允许的图片域名(白名单)",
+                    field: ConfigField::AllowedMediaDomains,
+                },
                 ConfigEntry {
                     title: "强制转成 JPEG",
                     field: ConfigField::ForceConvertImagesToJpeg,
@@ -387,16 +762,131 @@ pub(in crate::ui) fn build_config_categories() -> Vec<ConfigCategory> {
     ]
 }
 
+/// 配置页分类名与标题用到的汉字的拼音首字母（多音字取配置语境下的读音）。
+///
+/// 新增标题用到表外汉字时需补充，否则该字不参与首字母匹配。
+const PINYIN_INITIALS: &[(char, &str)] = &[
+    ('a', "按"),
+    ('b', "不保包布并本板标版白表被败闭"),
+    ('c', "传出呈存寸尺成拆次测础程策超重除"),
+    ('d', "动单地多大对导带度待打抖断段的短第等逗"),
+    ('f', "分发复封方服符繁覆"),
+    ('g', "关告官改更格盖规隔高"),
+    ('h', "号合后含恢户换缓航行话"),
+    ('j', "介件即卷均基径接旧机校检简节警进间"),
+    ('k', "宽开空"),
+    ('l', "列历录理留略立类络落论路连量"),
+    ('m', "名媒密模每目眉码秒门面"),
+    ('n', "内能"),
+    ('p', "平批排旁片评跑"),
+    ('q', "切去启嵌强求清确请"),
+    ('r', "人入热认"),
+    ('s', "三上书使刷史声失式散数时生示缩色试说输速随首"),
+    ('t', "体图头替条统调通题"),
+    ('w', "位务完文无网"),
+    ('x', "下修像先写型小新显现系线续许限需"),
+    ('y', "与优允原域已有样用语阈音页验"),
+    (
+        'z',
+        "中主值再则制占只在址字总抓整智最止正注知章自装质转载阻",
+    ),
+];
+
+/// 文本的拼音首字母串：汉字取首字母，ASCII 字母数字转小写保留，其余忽略。
+fn pinyin_initials(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| {
+            if c.is_ascii_alphanumeric() {
+                return Some(c.to_ascii_lowercase());
+            }
+            PINYIN_INITIALS
+                .iter()
+                .find(|(_, chars)| chars.contains(c))
+                .map(|(initial, _)| *initial)
+        })
+        .collect()
+}
+
+/// 跨分类搜索配置项，返回 (分类下标, 配置项下标)。
+///
+/// 关键词按空白拆分，须全部命中分类名、配置项标题、字段名（忽略大小写与下划线）
+/// 或二者的拼音首字母（如 `shsj` 匹配“首行缩进”）。
+pub(in crate::ui) fn search_config_entries(
+    categories: &[ConfigCategory],
+    query: &str,
+) -> Vec<(usize, usize)> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|t| t.to_lowercase().replace('_', ""))
+        .collect();
+    let mut out = Vec::new();
+    for (cat_idx, category) in categories.iter().enumerate() {
+        for (entry_idx, entry) in category.entries.iter().enumerate() {
+            let hay = format!(
+                "{} {} {:?} {} {}",
+                category.title,
+                entry.title,
+                entry.field,
+                pinyin_initials(category.title),
+                pinyin_initials(entry.title)
+            )
+            .to_lowercase();
+            if terms.iter().all(|t| hay.contains(t.as_str())) {
+                out.push((cat_idx, entry_idx));
+            }
+        }
+    }
+    out
+}
+
 pub(in crate::ui) fn current_cfg_value(app: &App, field: ConfigField) -> String {
     match field {
         ConfigField::SavePath => app.config.save_path.clone(),
-        ConfigField::NovelFormat => {
-            output_format_label(app.config.current_output_format_choice()).to_string()
-        }
+        ConfigField::SavePathRules => app.config.save_path_rules.clone(),
+        ConfigField::NovelFormat => output_format_label(&app.config.current_output_format_choice()),
         ConfigField::FirstLineIndentEm => format!("{:.2}", app.config.first_line_indent_em),
         ConfigField::AutoClearDump => app.config.auto_clear_dump.to_string(),
+        ConfigField::AutoClearImages => app.config.auto_clear_images.to_string(),
+        ConfigField::AutoClearSegmentComments => app.config.auto_clear_segment_comments.to_string(),
+        ConfigField::AutoClearJsonl => app.config.auto_clear_jsonl.to_string(),
         ConfigField::AutoOpenDownloadedFiles => app.config.auto_open_downloaded_files.to_string(),
+        ConfigField::WriteOutputChecksum => app.config.write_output_checksum.to_string(),
+        ConfigField::DownloadHistoryLimit => app.config.download_history_limit.to_string(),
+        ConfigField::WebdavUrl => app.config.webdav_url.clone(),
+        ConfigField::WebdavUsername => app.config.webdav_username.clone(),
+        ConfigField::WebdavPassword => webdav::masked_password(&app.config.webdav_password),
+        ConfigField::EnableNotifications => app.config.enable_notifications.to_string(),
         ConfigField::AllowOverwriteFiles => app.config.allow_overwrite_files.to_string(),
+        ConfigField::EpubChapterNav => app.config.epub_chapter_nav.to_string(),
+        ConfigField::EpubChapterHeader => app.config.epub_chapter_header.to_string(),
+        ConfigField::EpubIncludeImages => app.config.epub_include_images.to_string(),
+        ConfigField::EpubMergeShortChapters => app.config.epub_merge_short_chapters.to_string(),
+        ConfigField::EpubShortChapterChars => app.config.epub_short_chapter_chars.to_string(),
+        ConfigField::EpubMergeMaxChapters => app.config.epub_merge_max_chapters.to_string(),
+        ConfigField::TxtChapterSeparator => app.config.txt_chapter_separator.clone(),
+        ConfigField::AutoParagraph => app.config.auto_paragraph.to_string(),
+        ConfigField::StripDuplicateTitleLine => app.config.strip_duplicate_title_line.to_string(),
+        ConfigField::TrimBlankParagraphs => app.config.trim_blank_paragraphs.to_string(),
+        ConfigField::ReplacementsPath => app.config.replacements_path.clone(),
+        ConfigField::TextConversion => TextConversion::from_config(&app.config.text_conversion)
+            .label()
+            .to_string(),
+        ConfigField::TextConversionDescription => {
+            app.config.text_conversion_description.to_string()
+        }
+        ConfigField::ShowChapterTime => app.config.show_chapter_time.to_string(),
+        ConfigField::ChapterTitleFormat => app.config.chapter_title_format.clone(),
+        ConfigField::BulkNameTemplate => app.config.bulk_name_template.clone(),
+        ConfigField::TxtSplitMode => TxtSplitMode::from_config(&app.config).label().to_string(),
+        ConfigField::EpubUseTitleFormat => app.config.epub_use_title_format.to_string(),
+        ConfigField::SplitEpubByVolume => app.config.split_epub_by_volume.to_string(),
+        ConfigField::EpubEmbedFontPath => app.config.epub_embed_font_path.clone(),
+        ConfigField::EpubPlaceholderCover => app.config.epub_placeholder_cover.to_string(),
+        ConfigField::PreferHdCover => app.config.prefer_hd_cover.to_string(),
+        ConfigField::EpubTheme => EpubTheme::from_config(&app.config.epub_theme)
+            .label()
+            .to_string(),
+        ConfigField::EpubHighlightDialogue => app.config.epub_highlight_dialogue.to_string(),
         ConfigField::PreferredBookNameField => {
             book_name_field_to_chinese(&app.config.preferred_book_name_field).to_string()
         }
@@ -405,11 +895,26 @@ pub(in crate::ui) fn current_cfg_value(app: &App, field: ConfigField) -> String
         ConfigField::UseOfficialApi => app.config.use_official_api.to_string(),
         ConfigField::ApiEndpoints => app.config.api_endpoints.join(","),
         ConfigField::MaxWorkers => app.config.max_workers.to_string(),
+        ConfigField::BatchSize => app.config.batch_size.to_string(),
         ConfigField::RequestTimeout => app.config.request_timeout.to_string(),
         ConfigField::MaxRetries => app.config.max_retries.to_string(),
         ConfigField::MinConnectTimeout => format!("{:.2}", app.config.min_connect_timeout),
         ConfigField::MinWait => app.config.min_wait_time.to_string(),
         ConfigField::MaxWait => app.config.max_wait_time.to_string(),
+        ConfigField::WaitJitterRatio => app.config.wait_jitter_ratio.to_string(),
+        ConfigField::FastSwitchOn4xx => app.config.fast_switch_on_4xx.to_string(),
+        ConfigField::TimeoutRetryStrategy => {
+            TimeoutRetryStrategy::from_config(&app.config.timeout_retry_strategy)
+                .label()
+                .to_string()
+        }
+        ConfigField::MaxBandwidthKbps => app.config.max_bandwidth_kbps.to_string(),
+        ConfigField::BlockOnIncompleteCatalog => app.config.block_on_incomplete_catalog.to_string(),
+        ConfigField::SkipTitlePatterns => app.config.skip_title_patterns.join("; "),
+        ConfigField::DetectModifiedChapters => app.config.detect_modified_chapters.to_string(),
+        ConfigField::BookDownloadTimeoutSecs => app.config.book_download_timeout_secs.to_string(),
+        ConfigField::WaitForNetwork => app.config.wait_for_network.to_string(),
+        ConfigField::ChapterLengthAlertRatio => app.config.chapter_length_alert_ratio.to_string(),
         ConfigField::EnableAudiobook => app.config.enable_audiobook.to_string(),
         ConfigField::AudiobookVoice => app.config.audiobook_voice.clone(),
         ConfigField::AudiobookRate => app.config.audiobook_rate.clone(),
@@ -423,10 +928,31 @@ pub(in crate::ui) fn current_cfg_value(app: &App, field: ConfigField) -> String
         ConfigField::AudiobookTtsModel => app.config.audiobook_tts_model.clone(),
         ConfigField::SegmentCommentsTopN => app.config.segment_comments_top_n.to_string(),
         ConfigField::SegmentCommentsWorkers => app.config.segment_comments_workers.to_string(),
+        ConfigField::SegmentCommentsHotChaptersOnly => {
+            app.config.segment_comments_hot_chapters_only.to_string()
+        }
+        ConfigField::SegmentCommentsHotChapters => {
+            app.config.segment_comments_hot_chapters.to_string()
+        }
+        ConfigField::SegmentCommentsRefreshHours => {
+            app.config.segment_comments_refresh_hours.to_string()
+        }
+        ConfigField::SegmentCommentsStyle => {
+            SegmentCommentStyle::from_config(&app.config.segment_comments_style)
+                .label()
+                .to_string()
+        }
+        ConfigField::SegmentCommentsExportMode => {
+            SegmentCommentExportMode::from_config(&app.config.segment_comments_export_mode)
+                .label()
+                .to_string()
+        }
+        ConfigField::ExportSegmentNotesHtml => app.config.export_segment_notes_html.to_string(),
         ConfigField::DownloadCommentImages => app.config.download_comment_images.to_string(),
         ConfigField::DownloadCommentAvatars => app.config.download_comment_avatars.to_string(),
         ConfigField::MediaDownloadWorkers => app.config.media_download_workers.to_string(),
         ConfigField::BlockedMediaDomains => app.config.blocked_media_domains.join(","),
+        ConfigField::AllowedMediaDomains => app.config.allowed_media_domains.join(","),
         ConfigField::ForceConvertImagesToJpeg => {
             app.config.force_convert_images_to_jpeg.to_string()
         }
@@ -445,6 +971,9 @@ pub(in crate::ui) fn cfg_field_is_bool(field: ConfigField) -> bool {
         ConfigField::AutoClearDump
             | ConfigField::AutoOpenDownloadedFiles
             | ConfigField::AllowOverwriteFiles
+            | ConfigField::EpubChapterNav
+            | ConfigField::AutoParagraph
+            | ConfigField::ShowChapterTime
             | ConfigField::OldCli
             | ConfigField::EnableSegmentComments
             | ConfigField::UseOfficialApi
@@ -455,6 +984,28 @@ pub(in crate::ui) fn cfg_field_is_bool(field: ConfigField) -> bool {
             | ConfigField::JpegRetryConvert
             | ConfigField::ConvertHeicToJpeg
             | ConfigField::KeepHeicOriginal
+            | ConfigField::EpubUseTitleFormat
+            | ConfigField::SplitEpubByVolume
+            | ConfigField::FastSwitchOn4xx
+            | ConfigField::EnableNotifications
+            | ConfigField::EpubMergeShortChapters
+            | ConfigField::BlockOnIncompleteCatalog
+            | ConfigField::EpubPlaceholderCover
+            | ConfigField::SegmentCommentsHotChaptersOnly
+            | ConfigField::WaitForNetwork
+            | ConfigField::AutoClearImages
+            | ConfigField::AutoClearSegmentComments
+            | ConfigField::AutoClearJsonl
+            | ConfigField::ExportSegmentNotesHtml
+            | ConfigField::EpubIncludeImages
+            | ConfigField::StripDuplicateTitleLine
+            | ConfigField::TrimBlankParagraphs
+            | ConfigField::EpubHighlightDialogue
+            | ConfigField::WriteOutputChecksum
+            | ConfigField::TextConversionDescription
+            | ConfigField::PreferHdCover
+            | ConfigField::EpubChapterHeader
+            | ConfigField::DetectModifiedChapters
     )
 }
 
@@ -463,6 +1014,9 @@ fn cfg_field_current_bool(app: &App, field: ConfigField) -> Option<bool> {
         ConfigField::AutoClearDump => app.config.auto_clear_dump,
         ConfigField::AutoOpenDownloadedFiles => app.config.auto_open_downloaded_files,
         ConfigField::AllowOverwriteFiles => app.config.allow_overwrite_files,
+        ConfigField::EpubChapterNav => app.config.epub_chapter_nav,
+        ConfigField::AutoParagraph => app.config.auto_paragraph,
+        ConfigField::ShowChapterTime => app.config.show_chapter_time,
         ConfigField::OldCli => app.config.old_cli,
         ConfigField::EnableSegmentComments => app.config.enable_segment_comments,
         ConfigField::UseOfficialApi => app.config.use_official_api,
@@ -473,6 +1027,30 @@ fn cfg_field_current_bool(app: &App, field: ConfigField) -> Option<bool> {
         ConfigField::JpegRetryConvert => app.config.jpeg_retry_convert,
         ConfigField::ConvertHeicToJpeg => app.config.convert_heic_to_jpeg,
         ConfigField::KeepHeicOriginal => app.config.keep_heic_original,
+        ConfigField::EpubUseTitleFormat => app.config.epub_use_title_format,
+        ConfigField::SplitEpubByVolume => app.config.split_epub_by_volume,
+        ConfigField::FastSwitchOn4xx => app.config.fast_switch_on_4xx,
+        ConfigField::EnableNotifications => app.config.enable_notifications,
+        ConfigField::EpubMergeShortChapters => app.config.epub_merge_short_chapters,
+        ConfigField::BlockOnIncompleteCatalog => app.config.block_on_incomplete_catalog,
+        ConfigField::EpubPlaceholderCover => app.config.epub_placeholder_cover,
+        ConfigField::SegmentCommentsHotChaptersOnly => {
+            app.config.segment_comments_hot_chapters_only
+        }
+        ConfigField::WaitForNetwork => app.config.wait_for_network,
+        ConfigField::AutoClearImages => app.config.auto_clear_images,
+        ConfigField::AutoClearSegmentComments => app.config.auto_clear_segment_comments,
+        ConfigField::AutoClearJsonl => app.config.auto_clear_jsonl,
+        ConfigField::ExportSegmentNotesHtml => app.config.export_segment_notes_html,
+        ConfigField::EpubIncludeImages => app.config.epub_include_images,
+        ConfigField::StripDuplicateTitleLine => app.config.strip_duplicate_title_line,
+        ConfigField::TrimBlankParagraphs => app.config.trim_blank_paragraphs,
+        ConfigField::EpubHighlightDialogue => app.config.epub_highlight_dialogue,
+        ConfigField::WriteOutputChecksum => app.config.write_output_checksum,
+        ConfigField::TextConversionDescription => app.config.text_conversion_description,
+        ConfigField::PreferHdCover => app.config.prefer_hd_cover,
+        ConfigField::EpubChapterHeader => app.config.epub_chapter_header,
+        ConfigField::DetectModifiedChapters => app.config.detect_modified_chapters,
         _ => return None,
     };
     Some(val)
@@ -532,30 +1110,32 @@ pub(in crate::ui) fn apply_cfg_edit(app: &mut App, cat_idx: usize, entry_idx: us
         ConfigField::SavePath => {
             app.config.save_path = raw.to_string();
         }
+        ConfigField::SavePathRules => {
+            app.config.save_path_rules = raw.to_string();
+        }
         ConfigField::NovelFormat => {
             let field_name = if let Some(english) = chinese_to_novel_format(raw) {
                 english
             } else {
                 let lower = raw.to_ascii_lowercase();
-                if lower == "txt"
-                    || lower == "epub"
-                    || lower == "pdf"
-                    || lower == OUTPUT_FORMAT_BULK_TXT
+                if lower == OUTPUT_FORMAT_BULK_TXT
                     || lower == "ask_after_download"
+                    || !parse_output_formats(&lower).is_empty()
                 {
                     lower
                 } else {
-                    app.status = "请选择：txt 格式、epub 格式、pdf 格式、散装文件 或 下载完后选择"
-                        .to_string();
+                    app.status =
+                        "请选择：txt/epub/pdf（可逗号组合，如 txt,epub）、散装文件 或 下载完后选择"
+                            .to_string();
                     return Ok(());
                 }
             };
             app.config
                 .apply_output_format_choice(&field_name)
                 .map_err(anyhow::Error::msg)?;
-            if app.config.novel_format == "txt" && app.config.enable_segment_comments {
+            if !app.config.wants_epub() && app.config.enable_segment_comments {
                 app.config.enable_segment_comments = false;
-                note = Some("已关闭段评以兼容 txt".to_string());
+                note = Some("已关闭段评（仅 epub 支持）".to_string());
             }
         }
         ConfigField::FirstLineIndentEm => {
@@ -570,14 +1150,167 @@ pub(in crate::ui) fn apply_cfg_edit(app: &mut App, cat_idx: usize, entry_idx: us
             let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
             app.config.auto_clear_dump = val;
         }
+        ConfigField::AutoClearImages => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.auto_clear_images = val;
+        }
+        ConfigField::AutoClearSegmentComments => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.auto_clear_segment_comments = val;
+        }
+        ConfigField::AutoClearJsonl => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.auto_clear_jsonl = val;
+        }
         ConfigField::AutoOpenDownloadedFiles => {
             let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
             app.config.auto_open_downloaded_files = val;
         }
+        ConfigField::WriteOutputChecksum => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.write_output_checksum = val;
+        }
+        ConfigField::DownloadHistoryLimit => {
+            let val: usize = raw.parse().map_err(|_| anyhow!("请输入非负整数"))?;
+            app.config.download_history_limit = val;
+        }
+        ConfigField::WebdavUrl => {
+            app.config.webdav_url = raw.trim().to_string();
+        }
+        ConfigField::WebdavUsername => {
+            app.config.webdav_username = raw.to_string();
+        }
+        ConfigField::WebdavPassword => {
+            // 未改动掩码时保留原密码
+            if raw != webdav::PASSWORD_MASK {
+                app.config.webdav_password = raw.to_string();
+            }
+        }
+        ConfigField::EnableNotifications => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.enable_notifications = val;
+        }
         ConfigField::AllowOverwriteFiles => {
             let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
             app.config.allow_overwrite_files = val;
         }
+        ConfigField::EpubChapterNav => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.epub_chapter_nav = val;
+        }
+        ConfigField::EpubChapterHeader => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.epub_chapter_header = val;
+        }
+        ConfigField::EpubIncludeImages => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.epub_include_images = val;
+        }
+        ConfigField::EpubMergeShortChapters => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.epub_merge_short_chapters = val;
+        }
+        ConfigField::EpubShortChapterChars => {
+            let val: usize = raw.parse().map_err(|_| anyhow!("请输入整数"))?;
+            app.config.epub_short_chapter_chars = val;
+        }
+        ConfigField::EpubMergeMaxChapters => {
+            let val: usize = raw.parse().map_err(|_| anyhow!("请输入整数"))?;
+            if val == 0 {
+                return Err(anyhow!("需大于 0"));
+            }
+            app.config.epub_merge_max_chapters = val;
+        }
+        ConfigField::TxtChapterSeparator => {
+            app.config.txt_chapter_separator = raw.to_string();
+        }
+        ConfigField::AutoParagraph => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.auto_paragraph = val;
+        }
+        ConfigField::StripDuplicateTitleLine => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.strip_duplicate_title_line = val;
+        }
+        ConfigField::TrimBlankParagraphs => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.trim_blank_paragraphs = val;
+        }
+        ConfigField::ReplacementsPath => {
+            app.config.replacements_path = raw.to_string();
+        }
+        ConfigField::TextConversion => {
+            let conversion = TextConversion::ALL
+                .into_iter()
+                .find(|c| raw.eq_ignore_ascii_case(c.as_str()) || raw == c.label());
+            let Some(conversion) = conversion else {
+                app.status = "请选择：不转换、简体转繁体 或 繁体转简体".to_string();
+                return Ok(());
+            };
+            app.config.text_conversion = conversion.as_str().to_string();
+        }
+        ConfigField::TextConversionDescription => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.text_conversion_description = val;
+        }
+        ConfigField::ShowChapterTime => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.show_chapter_time = val;
+        }
+        ConfigField::ChapterTitleFormat => {
+            app.config.chapter_title_format = raw.to_string();
+        }
+        ConfigField::BulkNameTemplate => {
+            let prev =
+                std::mem::replace(&mut app.config.bulk_name_template, raw.trim().to_string());
+            if let Err(msg) = app.config.validate_bulk_name_template() {
+                app.config.bulk_name_template = prev;
+                app.status = msg;
+                return Ok(());
+            }
+        }
+        ConfigField::TxtSplitMode => {
+            let Some(mode) = TxtSplitMode::parse(raw) else {
+                app.status = "请选择：整本、按章 或 按卷".to_string();
+                return Ok(());
+            };
+            app.config.txt_split_mode = mode.as_str().to_string();
+            // 散装开关与按章拆分保持一致，避免 bulk_files 覆盖新选择
+            app.config.bulk_files = mode == TxtSplitMode::PerChapter;
+        }
+        ConfigField::EpubUseTitleFormat => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.epub_use_title_format = val;
+        }
+        ConfigField::SplitEpubByVolume => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.split_epub_by_volume = val;
+        }
+        ConfigField::EpubEmbedFontPath => {
+            app.config.epub_embed_font_path = raw.to_string();
+        }
+        ConfigField::EpubPlaceholderCover => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.epub_placeholder_cover = val;
+        }
+        ConfigField::PreferHdCover => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.prefer_hd_cover = val;
+        }
+        ConfigField::EpubTheme => {
+            let theme = EpubTheme::ALL
+                .into_iter()
+                .find(|t| raw.eq_ignore_ascii_case(t.as_str()) || raw == t.label());
+            let Some(theme) = theme else {
+                app.status = "请选择：默认、护眼米色、夜间深色 或 纸质衬线".to_string();
+                return Ok(());
+            };
+            app.config.epub_theme = theme.as_str().to_string();
+        }
+        ConfigField::EpubHighlightDialogue => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.epub_highlight_dialogue = val;
+        }
         ConfigField::PreferredBookNameField => {
             // 尝试从中文转换，如果失败则尝试直接使用英文
             let field_name = if let Some(english) = chinese_to_book_name_field(raw) {
@@ -604,8 +1337,8 @@ pub(in crate::ui) fn apply_cfg_edit(app: &mut App, cat_idx: usize, entry_idx: us
         }
         ConfigField::EnableSegmentComments => {
             let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
-            if val && !app.config.novel_format.eq_ignore_ascii_case("epub") {
-                app.status = "段评仅支持 epub，请先将格式改为 epub".to_string();
+            if val && !app.config.wants_epub() {
+                app.status = "段评仅支持 epub，请先在格式中加入 epub".to_string();
                 return Ok(());
             }
             app.config.enable_segment_comments = val;
@@ -626,6 +1359,14 @@ pub(in crate::ui) fn apply_cfg_edit(app: &mut App, cat_idx: usize, entry_idx: us
             }
             app.config.max_workers = val;
         }
+        ConfigField::BatchSize => {
+            let val: usize = raw.parse().map_err(|_| anyhow!("请输入正整数"))?;
+            if !(1..=MAX_BATCH_SIZE).contains(&val) {
+                app.status = format!("章节批量大小需在 1-{} 之间", MAX_BATCH_SIZE);
+                return Ok(());
+            }
+            app.config.batch_size = val;
+        }
         ConfigField::RequestTimeout => {
             let val: u64 = raw.parse().map_err(|_| anyhow!("请输入秒数"))?;
             if val == 0 {
@@ -662,6 +1403,79 @@ pub(in crate::ui) fn apply_cfg_edit(app: &mut App, cat_idx: usize, entry_idx: us
             }
             app.config.max_wait_time = val;
         }
+        ConfigField::WaitJitterRatio => {
+            let val: u8 = raw
+                .parse()
+                .map_err(|_| anyhow!("请输入 0-100 之间的整数"))?;
+            if val > 100 {
+                app.status = "抖动比例需在 0-100 之间".to_string();
+                return Ok(());
+            }
+            app.config.wait_jitter_ratio = val;
+        }
+        ConfigField::FastSwitchOn4xx => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.fast_switch_on_4xx = val;
+        }
+        ConfigField::TimeoutRetryStrategy => {
+            let strategy = TimeoutRetryStrategy::ALL
+                .into_iter()
+                .find(|s| raw.eq_ignore_ascii_case(s.as_str()) || raw == s.label());
+            let Some(strategy) = strategy else {
+                app.status = "请选择：保守、均衡 或 激进".to_string();
+                return Ok(());
+            };
+            app.config.timeout_retry_strategy = strategy.as_str().to_string();
+        }
+        ConfigField::MaxBandwidthKbps => {
+            let val: u64 = raw
+                .parse()
+                .map_err(|_| anyhow!("请输入整数 KB/s（0 表示不限速）"))?;
+            app.config.max_bandwidth_kbps = val;
+        }
+        ConfigField::BlockOnIncompleteCatalog => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.block_on_incomplete_catalog = val;
+        }
+        ConfigField::SkipTitlePatterns => {
+            // 正则里常含逗号，这里只按分号/换行分隔
+            let patterns: Vec<String> = raw
+                .split([';', '\n'])
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect();
+            let prev = std::mem::replace(&mut app.config.skip_title_patterns, patterns);
+            if let Err(msg) = app.config.validate_skip_title_patterns() {
+                app.config.skip_title_patterns = prev;
+                app.status = msg;
+                return Ok(());
+            }
+        }
+        ConfigField::DetectModifiedChapters => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.detect_modified_chapters = val;
+        }
+        ConfigField::BookDownloadTimeoutSecs => {
+            let val: u64 = raw
+                .parse()
+                .map_err(|_| anyhow!("请输入整数秒数（0 表示不限）"))?;
+            app.config.book_download_timeout_secs = val;
+        }
+        ConfigField::WaitForNetwork => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.wait_for_network = val;
+        }
+        ConfigField::ChapterLengthAlertRatio => {
+            let val: u8 = raw
+                .parse()
+                .map_err(|_| anyhow!("请输入 0-100 之间的整数"))?;
+            if val > 100 {
+                app.status = "短章告警阈值需在 0-100 之间".to_string();
+                return Ok(());
+            }
+            app.config.chapter_length_alert_ratio = val;
+        }
         ConfigField::EnableAudiobook => {
             let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
             app.config.enable_audiobook = val;
@@ -722,6 +1536,48 @@ pub(in crate::ui) fn apply_cfg_edit(app: &mut App, cat_idx: usize, entry_idx: us
             }
             app.config.segment_comments_workers = val;
         }
+        ConfigField::SegmentCommentsHotChaptersOnly => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.segment_comments_hot_chapters_only = val;
+        }
+        ConfigField::SegmentCommentsHotChapters => {
+            let val: usize = raw.parse().map_err(|_| anyhow!("请输入正整数"))?;
+            if val == 0 {
+                app.status = "热门章节数需大于 0".to_string();
+                return Ok(());
+            }
+            app.config.segment_comments_hot_chapters = val;
+        }
+        ConfigField::SegmentCommentsRefreshHours => {
+            let val: u64 = raw
+                .parse()
+                .map_err(|_| anyhow!("请输入非负整数（0 表示永不刷新）"))?;
+            app.config.segment_comments_refresh_hours = val;
+        }
+        ConfigField::SegmentCommentsStyle => {
+            let style = SegmentCommentStyle::ALL
+                .into_iter()
+                .find(|s| raw.eq_ignore_ascii_case(s.as_str()) || raw == s.label());
+            let Some(style) = style else {
+                app.status = "请选择：章末汇总式、行内脚注式 或 段后块式".to_string();
+                return Ok(());
+            };
+            app.config.segment_comments_style = style.as_str().to_string();
+        }
+        ConfigField::SegmentCommentsExportMode => {
+            let mode = SegmentCommentExportMode::ALL
+                .into_iter()
+                .find(|m| raw.eq_ignore_ascii_case(m.as_str()) || raw == m.label());
+            let Some(mode) = mode else {
+                app.status = "请选择：嵌入正文、单独导出 或 嵌入并导出".to_string();
+                return Ok(());
+            };
+            app.config.segment_comments_export_mode = mode.as_str().to_string();
+        }
+        ConfigField::ExportSegmentNotesHtml => {
+            let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
+            app.config.export_segment_notes_html = val;
+        }
         ConfigField::DownloadCommentImages => {
             let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
             app.config.download_comment_images = val;
@@ -739,7 +1595,26 @@ pub(in crate::ui) fn apply_cfg_edit(app: &mut App, cat_idx: usize, entry_idx: us
             app.config.media_download_workers = val;
         }
         ConfigField::BlockedMediaDomains => {
-            app.config.blocked_media_domains = parse_string_list(raw);
+            let prev = std::mem::replace(
+                &mut app.config.blocked_media_domains,
+                parse_string_list(raw),
+            );
+            if let Err(msg) = app.config.validate_media_domain_filters() {
+                app.config.blocked_media_domains = prev;
+                app.status = msg;
+                return Ok(());
+            }
+        }
+        ConfigField::AllowedMediaDomains => {
+            let prev = std::mem::replace(
+                &mut app.config.allowed_media_domains,
+                parse_string_list(raw),
+            );
+            if let Err(msg) = app.config.validate_media_domain_filters() {
+                app.config.allowed_media_domains = prev;
+                app.status = msg;
+                return Ok(());
+            }
         }
         ConfigField::ForceConvertImagesToJpeg => {
             let val = parse_bool(raw).ok_or_else(|| anyhow!("请输入 true/false"))?;
@@ -786,6 +1661,84 @@ pub(in crate::ui) fn apply_cfg_edit(app: &mut App, cat_idx: usize, entry_idx: us
     Ok(())
 }
 
+/// 待确认的恢复默认操作。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(in crate::ui) enum ConfigReset {
+    Entry(usize, usize),
+    All,
+}
+
+/// `r` 恢复当前项、`R` 恢复全部；首次按键只提示，再次按同一键才执行。
+pub(in crate::ui) fn handle_cfg_reset_key(app: &mut App, armed: Option<ConfigReset>, all: bool) {
+    let target = if all {
+        ConfigReset::All
+    } else {
+        match (app.cfg_cat_state.selected(), app.cfg_entry_state.selected()) {
+            (Some(cat_idx), Some(entry_idx)) => ConfigReset::Entry(cat_idx, entry_idx),
+            _ => return,
+        }
+    };
+    if armed != Some(target) {
+        app.status = match target {
+            ConfigReset::All => {
+                "再次按 R 确认将全部配置恢复默认（保留保存路径等关键路径与凭据）".to_string()
+            }
+            ConfigReset::Entry(cat_idx, entry_idx) => {
+                let title = app
+                    .cfg_categories
+                    .get(cat_idx)
+                    .and_then(|c| c.entries.get(entry_idx))
+                    .map(|e| e.title)
+                    .unwrap_or("");
+                format!("再次按 r 确认将 [{title}] 恢复默认")
+            }
+        };
+        app.cfg_reset_armed = Some(target);
+        return;
+    }
+
+    let result = match target {
+        ConfigReset::All => reset_all_cfg(app),
+        ConfigReset::Entry(cat_idx, entry_idx) => reset_cfg_entry(app, cat_idx, entry_idx),
+    };
+    if let Err(err) = result {
+        app.status = format!("恢复默认失败: {err}");
+    }
+}
+
+/// 单项恢复默认：以默认配置的展示值走一遍编辑流程，复用校验与写回。
+fn reset_cfg_entry(app: &mut App, cat_idx: usize, entry_idx: usize) -> Result<()> {
+    let Some(entry) = app
+        .cfg_categories
+        .get(cat_idx)
+        .and_then(|c| c.entries.get(entry_idx))
+    else {
+        return Ok(());
+    };
+    let (field, title) = (entry.field, entry.title);
+    let current = std::mem::take(&mut app.config);
+    app.cfg_edit_buffer = current_cfg_value(app, field);
+    app.config = current;
+
+    let result = apply_cfg_edit(app, cat_idx, entry_idx);
+    app.cfg_edit_buffer.clear();
+    result?;
+    if app.status.starts_with("已保存") {
+        app.status = format!("已恢复默认: {title}");
+    }
+    Ok(())
+}
+
+fn reset_all_cfg(app: &mut App) -> Result<()> {
+    let config =
+        reset_all(&app.config, RESET_PRESERVED_FIELDS).map_err(|e| anyhow!(e.to_string()))?;
+    write_with_comments(&config, Path::new(Config::FILE_NAME))
+        .map_err(|e| anyhow!(e.to_string()))?;
+    app.config = config;
+    app.status = "已将全部配置恢复默认（保留保存路径等关键路径与凭据）".to_string();
+    Ok(())
+}
+
 fn parse_bool(input: &str) -> Option<bool> {
     match input.to_ascii_lowercase().as_str() {
         "true" | "1" | "yes" | "y" | "on" => Some(true),
@@ -829,3 +1782,26 @@ fn chinese_to_book_name_field(chinese: &str) -> Option<String> {
 fn chinese_to_novel_format(chinese: &str) -> Option<String> {
     output_format_value_from_label(chinese).map(ToString::to_string)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinyin_table_covers_config_titles() {
+        let is_cjk = |c: &char| ('\u{4e00}'..='\u{9fff}').contains(c);
+        let mut missing = Vec::new();
+        for category in build_config_categories() {
+            let titles =
+                std::iter::once(category.title).chain(category.entries.iter().map(|e| e.title));
+            for c in titles.flat_map(str::chars).filter(is_cjk) {
+                if !PINYIN_INITIALS.iter().any(|(_, chars)| chars.contains(c))
+                    && !missing.contains(&c)
+                {
+                    missing.push(c);
+                }
+            }
+        }
+        assert!(missing.is_empty(), "PINYIN_INITIALS 缺少: {missing:?}");
+    }
+}
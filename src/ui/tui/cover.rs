@@ -1,8 +1,26 @@
 //! TUI 封面/基础信息展示。
 
 use super::*;
+use crate::book_parser::placeholder_cover::parse_hex_color;
 use image::{DynamicImage, GenericImageView, imageops::FilterType};
 
+/// 强调色的最低亮度（0-255），低于此值时向白色混合，保证在深色终端上可读。
+const MIN_ACCENT_LUMA: u32 = 110;
+
+/// 书籍主色调（`cover_primary_color`）转为 TUI 强调色；缺失或无法解析时返回 None。
+pub(super) fn accent_color(hex: Option<&str>) -> Option<Color> {
+    let [r, g, b] = parse_hex_color(hex?)?;
+    let luma = (299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000;
+    if luma >= MIN_ACCENT_LUMA {
+        return Some(Color::Rgb(r, g, b));
+    }
+    let lift = |c: u8| c + ((255 - c) as u32 * (MIN_ACCENT_LUMA - luma) / (255 - luma)) as u8;
+    Some(Color::Rgb(lift(r), lift(g), lift(b)))
+}
+
+/// 本页快捷键说明（`?` / F1 帮助浮层）。
+pub(super) const SHORTCUTS: &[(&str, &str)] = &[("q / Esc / Enter", "关闭封面页")];
+
 pub(super) fn handle_event_cover(app: &mut App, event: Event) -> Result<()> {
     match event {
         Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
@@ -33,6 +51,12 @@ pub(super) fn show_cover(
 
     let cover_title = format!("《{}》 ({})", title, book_id);
     app.cover_title = cover_title;
+    app.cover_accent = app
+        .results
+        .iter()
+        .find(|b| b.book_id == book_id)
+        .and_then(|b| b.detail.as_ref())
+        .and_then(|d| accent_color(d.cover_primary_color.as_deref()));
 
     let candidates = cover_candidates(app, book_id, title, folder);
     let Some(path) = candidates.into_iter().find(|p| p.exists()) else {
@@ -68,8 +92,14 @@ pub(super) fn draw_cover(frame: &mut ratatui::Frame, app: &mut App) {
         app.cover_lines.iter().cloned().map(Line::from).collect()
     };
 
+    let mut block = Block::default().borders(Borders::ALL).title(title);
+    if let Some(accent) = app.cover_accent {
+        block = block
+            .border_style(Style::default().fg(accent))
+            .title_style(Style::default().fg(accent).add_modifier(Modifier::BOLD));
+    }
     let paragraph = Paragraph::new(lines)
-        .block(Block::default().borders(Borders::ALL).title(title))
+        .block(block)
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: false });
 
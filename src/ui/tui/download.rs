@@ -2,21 +2,30 @@
 //!
 //! 处理用户输入、启动下载任务、展示进度与状态。
 
+use std::collections::HashSet;
 use std::sync::{Arc, atomic::AtomicBool};
 use std::thread;
 
 use anyhow::Result;
+use crossterm::event::EnableMouseCapture;
+use crossterm::terminal::enable_raw_mode;
 use tracing::{debug, info, warn};
 
+use crate::base_system::file_manager::open_in_file_manager;
 use crate::download::downloader::{
     self, ChapterRange, DownloadFlowOptions, DownloadMode, ProgressSnapshot, RetryFailed, SavePhase,
 };
+use crate::download::pause;
 
 use super::{App, Focus, PendingDownload, View, WorkerMsg, start_spinner};
 
 pub(super) fn request_cancel_download(app: &mut App) {
     if let Some(flag) = app.download_cancel_flag.as_ref() {
         flag.store(true, std::sync::atomic::Ordering::SeqCst);
+        app.batch_queue.clear();
+        app.batch_current = None;
+        super::home::persist_batch_queue(app);
+        pause::set_paused(false);
         app.status = "已请求停止下载…".to_string();
         app.push_message("已发送停止信号，稍后结束当前任务");
     } else {
@@ -25,10 +34,27 @@ pub(super) fn request_cancel_download(app: &mut App) {
     app.stop_button_area = None;
 }
 
+pub(super) fn toggle_pause_download(app: &mut App) {
+    if app.download_cancel_flag.is_none() {
+        return;
+    }
+    if pause::toggle_paused() {
+        app.status = "已暂停（按 P 恢复）".to_string();
+        app.push_message("下载已暂停，正在进行的请求完成后停止取新任务");
+        info!(target: "ui", "下载已暂停");
+    } else {
+        app.status = "已恢复下载".to_string();
+        app.push_message("下载已恢复");
+        info!(target: "ui", "下载已恢复");
+    }
+}
+
 pub(super) fn start_download_task(
     app: &mut App,
     pending: PendingDownload,
     range: Option<ChapterRange>,
+    selection: Option<HashSet<String>>,
+    mode: DownloadMode,
 ) -> Result<()> {
     // keep pending info for preview overlay while download runs
     app.pending_download = Some(pending.clone());
@@ -40,7 +66,10 @@ pub(super) fn start_download_task(
     app.last_preview_desc_area = None;
     app.download_progress = Some(ProgressSnapshot {
         group_done: 0,
-        group_total: downloader::dynamic_group_count(pending.plan.chapters.len()),
+        group_total: downloader::dynamic_group_count(
+            pending.plan.chapters.len(),
+            app.config.effective_batch_size(),
+        ),
         saved_chapters: pending.downloaded_count,
         chapter_total: pending.plan.chapters.len(),
         save_phase: SavePhase::TextSave,
@@ -54,6 +83,7 @@ pub(super) fn start_download_task(
         audiobook_generated: 0,
         audiobook_skipped: 0,
         audiobook_failed: 0,
+        top_failure: None,
     });
     app.messages.clear();
     app.results.clear();
@@ -70,6 +100,14 @@ pub(super) fn start_download_task(
         .unwrap_or_else(|| book_id.clone());
 
     app.status = format!("开始下载: 《{}》 ({})", title, book_id);
+    app.last_output_dir = Some(
+        app.config
+            .save_path_rule_root(
+                pending.plan.meta.category.as_deref(),
+                &pending.plan.meta.tags,
+            )
+            .unwrap_or_else(|| app.config.default_save_dir()),
+    );
     info!(target: "ui", book_id = %book_id, "启动下载任务");
     debug!(
         target: "ui",
@@ -83,9 +121,16 @@ pub(super) fn start_download_task(
     start_spinner(app, format!("下载中: {book_id}"));
     let tx = app.worker_tx.clone();
     let progress_tx = app.worker_tx.clone();
-    let cfg = app.config.clone();
+    let mut cfg = app.config.clone();
+    // 批量队列项记录了入队时的输出格式（恢复队列时可能与当前配置不同）
+    if let Some(batch) = app.batch_current.as_ref()
+        && !batch.format.trim().is_empty()
+    {
+        cfg.novel_format = batch.format.clone();
+    }
     let cancel_flag = Arc::new(AtomicBool::new(false));
     app.download_cancel_flag = Some(cancel_flag.clone());
+    pause::set_paused(false);
     app.stop_button_area = None;
     thread::spawn(move || {
         let progress_cb = move |snap: ProgressSnapshot| {
@@ -114,13 +159,23 @@ pub(super) fn start_download_task(
             });
             resp_rx.recv().ok().flatten()
         };
+        let modified_ask_tx = tx.clone();
+        let modified_asker = move |modified: &[downloader::ChapterRef]| {
+            let (resp_tx, resp_rx) = std::sync::mpsc::channel();
+            let _ = modified_ask_tx.send(WorkerMsg::AskRedownloadModified {
+                titles: modified.iter().map(|ch| ch.title.clone()).collect(),
+                respond_to: resp_tx,
+            });
+            resp_rx.recv().unwrap_or(false)
+        };
         let result = downloader::download_with_plan_flow(
             &cfg,
             pending.plan,
             None,
             DownloadFlowOptions {
-                mode: DownloadMode::Resume,
+                mode,
                 range,
+                selection,
                 retry_failed: {
                     let mut retried = false;
                     RetryFailed::Decide(Box::new(move |_pending_len| {
@@ -134,6 +189,7 @@ pub(super) fn start_download_task(
                 stage_callback: None,
                 book_name_asker: Some(Box::new(book_name_asker)),
                 format_asker: Some(Box::new(format_asker)),
+                modified_asker: Some(Box::new(modified_asker)),
             },
             Some(Box::new(progress_cb)),
             Some(cancel_flag),
@@ -144,15 +200,75 @@ pub(super) fn start_download_task(
     Ok(())
 }
 
+/// 不联网，用本地缓存重新生成成书（更换格式/段评/封面配置后使用）。
+pub(super) fn start_regenerate_task(app: &mut App, book_id: String, book_name: String) {
+    app.download_progress = None;
+    app.status = format!("重新生成: 《{}》", book_name);
+    app.last_output_dir = Some(app.config.save_root_for_book(&book_id));
+    info!(target: "ui", book_id = %book_id, format = %app.config.novel_format, "启动重新生成任务");
+
+    start_spinner(app, format!("重新生成中: {book_name}"));
+    let tx = app.worker_tx.clone();
+    let progress_tx = app.worker_tx.clone();
+    let cfg = app.config.clone();
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    app.download_cancel_flag = Some(cancel_flag.clone());
+    thread::spawn(move || {
+        let progress_cb = move |snap: ProgressSnapshot| {
+            let _ = progress_tx.send(WorkerMsg::DownloadProgress(snap));
+        };
+        let result = downloader::regenerate_from_cache(
+            &cfg,
+            &book_id,
+            &book_name,
+            Some(Box::new(progress_cb)),
+            Some(cancel_flag),
+        );
+        let _ = tx.send(WorkerMsg::DownloadDone { book_id, result });
+    });
+}
+
 pub(super) fn apply_download_progress(app: &mut App, snap: ProgressSnapshot) {
+    let prev_failure = app.download_progress.and_then(|p| p.top_failure);
+    if let Some(kind) = snap.top_failure
+        && prev_failure != Some(kind)
+    {
+        app.status = format!("主要失败: {}，{}", kind.label(), kind.hint());
+    }
     app.download_progress = Some(snap);
 }
 
+/// 用系统文件管理器打开最近一次下载的输出目录。
+pub(super) fn open_last_output_dir(app: &mut App) {
+    let Some(dir) = app.last_output_dir.clone() else {
+        app.status = "暂无可打开的下载目录".to_string();
+        return;
+    };
+    match open_in_file_manager(&dir) {
+        Ok(()) => app.status = format!("已打开文件夹: {}", dir.display()),
+        Err(e) => app.status = format!("打开文件夹失败: {e}（{}）", dir.display()),
+    }
+    // 外部程序可能改动终端模式，这里恢复 raw mode 与鼠标捕获
+    let _ = enable_raw_mode();
+    let mut out = std::io::stdout();
+    let _ = crossterm::execute!(&mut out, EnableMouseCapture);
+}
+
 pub(super) fn apply_download_done(app: &mut App, book_id: String, result: Result<()>) {
+    pause::set_paused(false);
     match result {
         Ok(()) => {
-            app.status = format!("下载完成: {book_id}");
+            let top_failure = app.download_progress.and_then(|p| p.top_failure);
+            app.status = match top_failure {
+                Some(kind) => format!(
+                    "下载完成: {book_id}（主要失败: {}，{}；Ctrl+O 打开文件夹）",
+                    kind.label(),
+                    kind.hint()
+                ),
+                None => format!("下载完成: {book_id}（Ctrl+O 打开文件夹）"),
+            };
             app.push_message("下载完成");
+            app.history_names = super::home::load_history_names();
             info!(target: "ui", book_id = %book_id, "下载完成");
             app.pending_download = None;
             app.preview_range.clear();
@@ -180,4 +296,7 @@ pub(super) fn apply_download_done(app: &mut App, book_id: String, result: Result
             app.stop_button_area = None;
         }
     }
+    if let Err(e) = super::home::continue_batch(app) {
+        app.status = format!("批量下载失败: {e}");
+    }
 }
@@ -0,0 +1,130 @@
+//! 快捷键帮助浮层。
+//!
+//! 任意页面按 `?` 或 F1 打开，列出当前视图可用的按键，按任意键关闭。
+
+use crossterm::event::{Event, KeyCode, KeyEventKind};
+use ratatui::layout::Alignment;
+use ratatui::prelude::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use super::{App, Focus, PreviewFocus, View};
+
+/// 所有页面通用的快捷键。
+const GLOBAL_SHORTCUTS: &[(&str, &str)] = &[("Ctrl+L", "打开日志查看器"), ("? / F1", "显示本帮助")];
+
+fn view_title(view: View) -> &'static str {
+    match view {
+        View::Home => "首页",
+        View::Config => "配置",
+        View::Update => "更新",
+        View::History => "下载历史",
+        View::About => "关于",
+        View::Cover => "封面",
+        View::Preview => "预览",
+    }
+}
+
+fn view_shortcuts(view: View) -> &'static [(&'static str, &'static str)] {
+    match view {
+        View::Home => super::home::SHORTCUTS,
+        View::Config => super::config::SHORTCUTS,
+        View::Update => super::update::SHORTCUTS,
+        View::History => super::history::SHORTCUTS,
+        View::About => super::about::SHORTCUTS,
+        View::Cover => super::cover::SHORTCUTS,
+        View::Preview => super::preview::SHORTCUTS,
+    }
+}
+
+/// 当前是否在输入文本（此时 `?` 作为普通字符输入，只有 F1 打开帮助）。
+fn is_typing(app: &App) -> bool {
+    match app.view {
+        View::Home => app.focus == Focus::Input,
+        View::Config => {
+            app.cfg_editing.is_some()
+                || app.cfg_search.is_some()
+                || app.segment_comments_confirm_open
+        }
+        View::Preview => !app.preview_catalog_open && app.preview_focus == PreviewFocus::Range,
+        _ => false,
+    }
+}
+
+/// 是否为打开帮助浮层的按键（`?` / F1）。
+pub(super) fn is_open_key(app: &App, evt: &Event) -> bool {
+    let Event::Key(key) = evt else {
+        return false;
+    };
+    if key.kind != KeyEventKind::Press {
+        return false;
+    }
+    match key.code {
+        KeyCode::F(1) => true,
+        KeyCode::Char('?') => !is_typing(app),
+        _ => false,
+    }
+}
+
+pub(super) fn render_help_overlay(frame: &mut ratatui::Frame, app: &App) {
+    if !app.help_overlay_open {
+        return;
+    }
+
+    let rows = view_shortcuts(app.view);
+    let area = frame.size();
+    let width = area.width.min(72);
+    let height = area
+        .height
+        .min((rows.len() + GLOBAL_SHORTCUTS.len() + 6) as u16);
+    if width == 0 || height == 0 {
+        return;
+    }
+    let overlay = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let inner = Rect {
+        x: overlay.x.saturating_add(2),
+        y: overlay.y.saturating_add(1),
+        width: overlay.width.saturating_sub(4).max(1),
+        height: overlay.height.saturating_sub(2).max(1),
+    };
+
+    let key_width = rows
+        .iter()
+        .chain(GLOBAL_SHORTCUTS)
+        .map(|(k, _)| k.chars().count())
+        .max()
+        .unwrap_or(0);
+    let key_style = Style::default()
+        .fg(Color::LightCyan)
+        .add_modifier(Modifier::BOLD);
+    let row_line = |(k, desc): &(&str, &str)| {
+        Line::from(vec![
+            Span::styled(format!("{k:<key_width$}"), key_style),
+            Span::raw("  "),
+            Span::raw(desc.to_string()),
+        ])
+    };
+
+    let mut lines: Vec<Line> = rows.iter().map(row_line).collect();
+    lines.push(Line::from(""));
+    lines.extend(GLOBAL_SHORTCUTS.iter().map(row_line));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "按任意键关闭",
+        Style::default().fg(Color::Cyan),
+    )));
+
+    frame.render_widget(Clear, overlay);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("快捷键 · {}", view_title(app.view)))
+        .title_alignment(Alignment::Center);
+    frame.render_widget(block, overlay);
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), inner);
+}
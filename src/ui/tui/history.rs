@@ -11,6 +11,14 @@ pub(super) fn show_history_menu(app: &mut App) -> Result<()> {
     Ok(())
 }
 
+/// 本页快捷键说明（`?` / F1 帮助浮层）。
+pub(super) const SHORTCUTS: &[(&str, &str)] = &[
+    ("↑ / ↓", "滚动历史记录"),
+    ("Enter", "重新下载/更新选中的书（沿用记录的范围）"),
+    ("r", "刷新历史记录"),
+    ("b / Esc", "返回首页"),
+];
+
 pub(super) fn handle_event_history(app: &mut App, event: Event) -> Result<()> {
     match event {
         Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
@@ -21,6 +29,7 @@ pub(super) fn handle_event_history(app: &mut App, event: Event) -> Result<()> {
             KeyCode::Char('r') => {
                 refresh_history(app);
             }
+            KeyCode::Enter => redownload_selected(app)?,
             KeyCode::Up => select_prev(app),
             KeyCode::Down => select_next(app),
             _ => {}
@@ -31,6 +40,29 @@ pub(super) fn handle_event_history(app: &mut App, event: Event) -> Result<()> {
     Ok(())
 }
 
+/// 打开选中记录的预览；记录的是连续范围时预填到范围输入框，确认后按续传下载（即更新）。
+fn redownload_selected(app: &mut App) -> Result<()> {
+    let Some(rec) = app
+        .history_state
+        .selected()
+        .and_then(|idx| app.history_entries.get(idx))
+        .cloned()
+    else {
+        return Ok(());
+    };
+    app.preview_range = if rec.range.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        rec.range.clone()
+    } else {
+        String::new()
+    };
+    app.status = format!("重新下载: 《{}》", rec.book_name);
+    let hint = BookMeta {
+        book_name: Some(rec.book_name.clone()).filter(|n| !n.trim().is_empty()),
+        ..BookMeta::default()
+    };
+    super::start_preview_task(app, rec.book_id, hint)
+}
+
 fn refresh_history(app: &mut App) {
     app.history_entries = read_download_history(200, None);
     if app.history_entries.is_empty() {
@@ -114,7 +146,7 @@ pub(super) fn draw_history(frame: &mut ratatui::Frame, app: &mut App) {
         .constraints([
             Constraint::Length(3),
             Constraint::Min(10),
-            Constraint::Length(8),
+            Constraint::Length(9),
         ])
         .split(main);
 
@@ -131,7 +163,7 @@ pub(super) fn draw_history(frame: &mut ratatui::Frame, app: &mut App) {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::raw("  |  ↑↓ 选择  r 刷新  b 返回"),
+        Span::raw("  |  ↑↓ 选择  Enter 重新下载  r 刷新  b 返回"),
     ]))
     .block(Block::default().borders(Borders::ALL).title("History"));
     frame.render_widget(header, layout[0]);
@@ -258,6 +290,14 @@ fn selected_details(
             }
         )),
         Line::from(format!("Book ID: {}", it.book_id)),
+        Line::from(format!(
+            "范围: {}",
+            if it.range.is_empty() {
+                "全部"
+            } else {
+                it.range.as_str()
+            }
+        )),
         Line::from(format!("进度: {}", it.progress)),
         Line::from(format!("状态: {}", it.status)),
     ]
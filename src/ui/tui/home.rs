@@ -7,7 +7,29 @@ use std::path::Path;
 
 use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState};
 
+use crate::base_system::book_library::BROWSE_SYNTAX_HINT;
 use crate::base_system::config::{ConfigSpec, write_with_comments};
+use crate::base_system::download_history::read_download_history;
+use crate::base_system::favorites::{self, FavoriteBook};
+use crate::base_system::search_query::SEARCH_SYNTAX_HINT;
+use crate::prewarm_state::IidHealthState;
+
+/// 本页快捷键说明（`?` / F1 帮助浮层）。
+pub(super) const SHORTCUTS: &[(&str, &str)] = &[
+    ("Tab", "在输入框 / 菜单 / 结果列表间切换焦点"),
+    ("Enter", "搜索或打开输入的书籍；在结果中下载选中书籍"),
+    ("→", "补全输入框中的搜索语法"),
+    ("↑ / ↓", "在菜单或结果列表中移动"),
+    ("Esc", "清空结果并回到输入框"),
+    ("Ctrl+V", "粘贴剪贴板内容"),
+    ("Ctrl+O", "打开最近一次下载的输出文件夹"),
+    ("c / u / h / a", "配置 / 更新 / 历史 / 关于（非输入框焦点）"),
+    ("f", "结果中收藏选中书籍；菜单中打开收藏"),
+    ("p", "查看选中书籍的封面与简介"),
+    ("Space / d", "标记书籍 / 批量下载已标记的书籍"),
+    ("[ / ]", "分类榜单上一页 / 下一页"),
+    ("q", "退出（非输入框焦点）"),
+];
 
 pub(super) fn handle_event_home(app: &mut App, event: Event) -> Result<()> {
     match event {
@@ -52,6 +74,20 @@ pub(super) fn handle_event_home(app: &mut App, event: Event) -> Result<()> {
                     super::switch_view(app, MenuAction::About)?;
                 }
             }
+            KeyCode::Char('f') => {
+                if app.focus == Focus::Input {
+                    app.input.push('f');
+                } else if app.focus == Focus::Results {
+                    toggle_selected_favorite(app);
+                } else {
+                    super::switch_view(app, MenuAction::Favorites)?;
+                }
+            }
+            KeyCode::Right if app.focus == Focus::Input => {
+                if let Some(done) = input_completion(app).map(str::to_string) {
+                    app.input = done;
+                }
+            }
             KeyCode::Esc => {
                 app.focus = Focus::Input;
                 app.results.clear();
@@ -67,6 +103,9 @@ pub(super) fn handle_event_home(app: &mut App, event: Event) -> Result<()> {
                     app.input.pop();
                 }
             }
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                super::download::open_last_output_dir(app);
+            }
             KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 #[cfg(feature = "clipboard")]
                 {
@@ -104,6 +143,10 @@ pub(super) fn handle_event_home(app: &mut App, event: Event) -> Result<()> {
                     super::cover::show_cover(app, &item.book_id, &item.title, None)?;
                 }
             }
+            KeyCode::Char(' ') if app.focus == Focus::Results => toggle_batch_mark(app),
+            KeyCode::Char('d') if app.focus == Focus::Results => start_batch_download(app)?,
+            KeyCode::Char('[') if app.focus == Focus::Results => turn_browse_page(app, false),
+            KeyCode::Char(']') if app.focus == Focus::Results => turn_browse_page(app, true),
             KeyCode::Char(c)
                 if !key.modifiers.contains(KeyModifiers::CONTROL)
                     && !key.modifiers.contains(KeyModifiers::ALT) =>
@@ -256,6 +299,86 @@ pub(super) fn handle_mouse_home(app: &mut App, me: event::MouseEvent) -> Result<
     Ok(())
 }
 
+/// 读取下载历史中的书名（去重，最近的在前），供搜索框补全。
+pub(super) fn load_history_names() -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for rec in read_download_history(200, None) {
+        let name = rec.book_name.trim();
+        if !name.is_empty() && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+fn input_completion(app: &App) -> Option<&str> {
+    if app.pending_download.is_some() {
+        return None;
+    }
+    favorites::completion_candidate(
+        &app.input,
+        &app.favorites,
+        app.history_names.iter().map(String::as_str),
+    )
+}
+
+/// 主菜单“收藏”入口：把收藏列表放进结果区，Enter 即可预览并检测更新。
+pub(super) fn show_favorites(app: &mut App) {
+    if app.favorites.is_empty() {
+        app.status = "收藏列表为空：在搜索结果中按 f 收藏".to_string();
+        return;
+    }
+    app.results = app
+        .favorites
+        .iter()
+        .map(|f| SearchItem {
+            title: f.book_name.clone(),
+            author: f.author.clone(),
+            book_id: f.book_id.clone(),
+            detail: None,
+        })
+        .collect();
+    app.list_state.select(Some(0));
+    app.focus = Focus::Results;
+    app.status = format!(
+        "收藏 {} 本：Enter 预览/检测更新，f 取消收藏",
+        app.favorites.len()
+    );
+}
+
+/// 搜索结果中已收藏的书置顶（保持原有相对顺序）。
+pub(super) fn pin_favorites(app: &mut App) {
+    let favs = &app.favorites;
+    app.results
+        .sort_by_key(|item| !favorites::is_favorite(favs, &item.book_id));
+}
+
+fn toggle_selected_favorite(app: &mut App) {
+    let Some(item) = app
+        .list_state
+        .selected()
+        .and_then(|idx| app.results.get(idx))
+        .cloned()
+    else {
+        return;
+    };
+    let book = FavoriteBook::new(
+        item.book_id.clone(),
+        item.title.clone(),
+        item.author.clone(),
+    );
+    let added = favorites::toggle_favorite(&mut app.favorites, book);
+    if let Err(e) = favorites::save_favorites(&app.favorites) {
+        app.status = format!("保存收藏失败: {e}");
+        return;
+    }
+    app.status = if added {
+        format!("已收藏《{}》", item.title)
+    } else {
+        format!("已取消收藏《{}》", item.title)
+    };
+}
+
 fn cycle_focus(app: &mut App) {
     app.focus = match app.focus {
         Focus::Input => Focus::Menu,
@@ -318,7 +441,7 @@ pub(super) fn process_input(app: &mut App) -> Result<()> {
     }
 
     if let Some(pending) = app.pending_download.clone() {
-        match parse_range_input(text, pending.plan.chapters.len()) {
+        match parse_range_input(text, pending.plan.chapters.len(), &pending.volumes) {
             Ok(range) => {
                 super::start_download_task(app, pending, range)?;
                 app.input.clear();
@@ -335,6 +458,17 @@ pub(super) fn process_input(app: &mut App) -> Result<()> {
         return Ok(());
     }
 
+    if let Some(browse) = crate::base_system::book_library::parse_browse_query(text) {
+        match browse {
+            Ok(query) => {
+                app.input.clear();
+                super::start_browse_task(app, query);
+            }
+            Err(msg) => app.status = msg,
+        }
+        return Ok(());
+    }
+
     if let Some(book_id) = parse_book_id(text) {
         app.focus = Focus::Input;
         app.status = format!("准备下载书籍 {book_id} …");
@@ -344,8 +478,7 @@ pub(super) fn process_input(app: &mut App) -> Result<()> {
         app.list_state.select(None);
     } else if crate::base_system::book_id::is_short_link(text) {
         app.focus = Focus::Input;
-        app.status = "正在解析短链接…".to_string();
-        super::start_preview_task(app, text.to_string(), BookMeta::default())?;
+        super::start_share_link_task(app, text.to_string());
         app.input.clear();
         app.results.clear();
         app.list_state.select(None);
@@ -356,6 +489,128 @@ pub(super) fn process_input(app: &mut App) -> Result<()> {
     Ok(())
 }
 
+/// 结果列表中标记/取消标记当前书籍，供批量下载。
+fn toggle_batch_mark(app: &mut App) {
+    let Some(item) = app
+        .list_state
+        .selected()
+        .and_then(|idx| app.results.get(idx))
+    else {
+        return;
+    };
+    if !app.batch_marked.remove(&item.book_id) {
+        app.batch_marked.insert(item.book_id.clone());
+    }
+    app.status = format!("已标记 {} 本，按 d 批量下载", app.batch_marked.len());
+    app.select_next();
+}
+
+/// 依次下载已标记的书籍（未标记时下载当前选中项）。
+fn start_batch_download(app: &mut App) -> Result<()> {
+    let mut queue: Vec<SearchItem> = app
+        .results
+        .iter()
+        .filter(|item| app.batch_marked.contains(&item.book_id))
+        .cloned()
+        .collect();
+    if queue.is_empty()
+        && let Some(item) = app
+            .list_state
+            .selected()
+            .and_then(|idx| app.results.get(idx))
+    {
+        queue.push(item.clone());
+    }
+    if queue.is_empty() {
+        return Ok(());
+    }
+    info!(target: "ui", count = queue.len(), "开始批量下载");
+    app.batch_marked.clear();
+    let format = app.config.novel_format.clone();
+    app.batch_queue = queue
+        .into_iter()
+        .map(|item| BatchItem {
+            item,
+            range: String::new(),
+            format: format.clone(),
+        })
+        .collect();
+    continue_batch(app)
+}
+
+/// 取出批量队列中的下一本，预览就绪后直接开始下载。
+///
+/// 上一本（若有）此时已结束，持久化的队列只保留当前一本与剩余项。
+pub(super) fn continue_batch(app: &mut App) -> Result<()> {
+    app.batch_current = app.batch_queue.pop_front();
+    persist_batch_queue(app);
+    let Some(book) = app.batch_current.as_ref().map(|b| b.item.clone()) else {
+        return Ok(());
+    };
+    let remaining = app.batch_queue.len();
+    app.focus = Focus::Input;
+    app.batch_auto_download = true;
+    app.push_message(format!(
+        "批量下载：《{}》（剩余 {} 本）",
+        book.title, remaining
+    ));
+    let hint = book_meta_from_item(&book);
+    super::start_preview_task(app, book.book_id.clone(), hint)
+}
+
+/// 将当前一本与剩余队列写入 queue.json；队列为空时删除文件。
+pub(super) fn persist_batch_queue(app: &App) {
+    let list: Vec<QueuedBook> = app
+        .batch_current
+        .iter()
+        .chain(app.batch_queue.iter())
+        .map(|b| QueuedBook {
+            book_id: b.item.book_id.clone(),
+            book_name: b.item.title.clone(),
+            author: b.item.author.clone(),
+            range: b.range.clone(),
+            format: b.format.clone(),
+        })
+        .collect();
+    if let Err(e) = download_queue::save_queue(&list) {
+        warn!(target: "ui", error = %e, "保存下载队列失败");
+    }
+}
+
+/// 恢复启动时发现的未完成队列；各本按断点续传从已下载进度继续。
+pub(super) fn resume_saved_queue(app: &mut App, items: Vec<QueuedBook>) -> Result<()> {
+    info!(target: "ui", count = items.len(), "恢复上次未完成的下载队列");
+    app.batch_queue = items
+        .into_iter()
+        .map(|q| BatchItem {
+            item: SearchItem {
+                title: q.book_name,
+                author: q.author,
+                book_id: q.book_id,
+                detail: None,
+            },
+            range: q.range,
+            format: q.format,
+        })
+        .collect();
+    continue_batch(app)
+}
+
+fn turn_browse_page(app: &mut App, forward: bool) {
+    let Some(query) = app.browse_query else {
+        return;
+    };
+    let page = if forward {
+        query.page + 1
+    } else if query.page > 1 {
+        query.page - 1
+    } else {
+        app.status = "已是第一页".to_string();
+        return;
+    };
+    super::start_browse_task(app, query.with_page(page));
+}
+
 fn download_selected(app: &mut App) -> Result<()> {
     let Some(idx) = app.list_state.selected() else {
         return Ok(());
@@ -497,7 +752,7 @@ pub(super) fn draw_home(frame: &mut ratatui::Frame, app: &mut App) {
         .constraints([
             Constraint::Length(3),
             Constraint::Length(3),
-            Constraint::Length(8),
+            Constraint::Length(9),
             Constraint::Length(12),
             Constraint::Min(6),
         ])
@@ -509,6 +764,12 @@ pub(super) fn draw_home(frame: &mut ratatui::Frame, app: &mut App) {
     }
 
     let header_line = {
+        let iid_health = prewarm_state::iid_health();
+        let iid_style = match iid_health.state {
+            IidHealthState::Healthy => Style::default().fg(Color::Green),
+            IidHealthState::Failing => Style::default().fg(Color::Red),
+            IidHealthState::Unknown => Style::default().fg(Color::DarkGray),
+        };
         #[cfg(feature = "official-api")]
         let notice = "  |  本程序完全免费，若发现收费渠道，请勿上当受骗！";
         #[cfg(not(feature = "official-api"))]
@@ -526,6 +787,8 @@ pub(super) fn draw_home(frame: &mut ratatui::Frame, app: &mut App) {
                 app.config.default_save_dir().display().to_string(),
                 Style::default().fg(Color::Green),
             ),
+            Span::raw("  |  IID: "),
+            Span::styled(iid_health.label(), iid_style),
             Span::styled(
                 notice,
                 #[cfg(feature = "official-api")]
@@ -548,13 +811,22 @@ pub(super) fn draw_home(frame: &mut ratatui::Frame, app: &mut App) {
     } else {
         Style::default()
     };
-    let input = Paragraph::new(format!("> {}", app.input))
-        .style(input_style)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("输入书名/ID/链接 (Enter 确认, Tab 切换)"),
-        );
+    let mut input_spans = vec![Span::styled(format!("> {}", app.input), input_style)];
+    if app.focus == Focus::Input
+        && let Some(done) = input_completion(app)
+    {
+        let rest = done.strip_prefix(app.input.trim()).unwrap_or_default();
+        input_spans.push(Span::styled(
+            rest.to_string(),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let input = Paragraph::new(Line::from(input_spans)).block(
+        Block::default().borders(Borders::ALL).title(format!(
+            "输入书名/ID/链接 (Enter 确认, Tab 切换, → 补全; {}; {})",
+            SEARCH_SYNTAX_HINT, BROWSE_SYNTAX_HINT
+        )),
+    );
     frame.render_widget(input, layout[1]);
 
     let menu_items: Vec<ListItem> = MENU_ITEMS
@@ -614,7 +886,17 @@ pub(super) fn draw_home(frame: &mut ratatui::Frame, app: &mut App) {
         app.results
             .iter()
             .map(|b| {
-                let label = format!("{} | {} | {}", b.title, b.book_id, b.author);
+                let star = if favorites::is_favorite(&app.favorites, &b.book_id) {
+                    "★ "
+                } else {
+                    ""
+                };
+                let mark = if app.batch_marked.contains(&b.book_id) {
+                    "[x] "
+                } else {
+                    ""
+                };
+                let label = format!("{mark}{star}{} | {} | {}", b.title, b.book_id, b.author);
                 ListItem::new(label)
             })
             .collect()
@@ -622,7 +904,7 @@ pub(super) fn draw_home(frame: &mut ratatui::Frame, app: &mut App) {
 
     let results_block = Block::default()
         .borders(Borders::ALL)
-        .title("搜索结果 (上下选择, Enter 下载)");
+        .title("搜索结果 (上下选择, Enter 下载, f 收藏, Space 标记, d 批量下载)");
     frame.render_widget(results_block.clone(), layout[3]);
     let results_inner = results_block.inner(layout[3]);
 
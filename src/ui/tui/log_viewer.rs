@@ -0,0 +1,249 @@
+//! 日志查看弹窗。
+//!
+//! 任意页面按 Ctrl+L 打开，可滚动浏览保留的历史日志，并按关键词过滤、高亮匹配内容。
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseEventKind};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::prelude::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+};
+
+use super::{App, style_log_line};
+
+const PAGE_STEP: usize = 10;
+
+/// 是否为打开日志查看器的快捷键（Ctrl+L）。
+pub(super) fn is_open_key(evt: &Event) -> bool {
+    matches!(
+        evt,
+        Event::Key(key)
+            if key.kind == KeyEventKind::Press
+                && key.modifiers.contains(KeyModifiers::CONTROL)
+                && matches!(key.code, KeyCode::Char('l') | KeyCode::Char('L'))
+    )
+}
+
+pub(super) fn open_log_viewer(app: &mut App) {
+    app.log_viewer_open = true;
+    app.log_scroll = 0;
+    app.log_filter_editing = false;
+}
+
+/// 按关键词过滤日志（ASCII 不区分大小写），空关键词返回全部。
+fn filtered_logs<'a>(logs: &'a [String], filter: &str) -> Vec<&'a str> {
+    let needle = filter.trim().to_ascii_lowercase();
+    logs.iter()
+        .map(String::as_str)
+        .filter(|l| needle.is_empty() || l.to_ascii_lowercase().contains(&needle))
+        .collect()
+}
+
+pub(super) fn handle_log_viewer_event(app: &mut App, evt: Event) -> Result<()> {
+    match evt {
+        Event::Key(key) if key.kind == KeyEventKind::Press => {
+            if app.log_filter_editing {
+                match key.code {
+                    KeyCode::Enter | KeyCode::Esc => app.log_filter_editing = false,
+                    KeyCode::Backspace => {
+                        app.log_filter.pop();
+                        app.log_scroll = 0;
+                    }
+                    KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.log_filter.push(c);
+                        app.log_scroll = 0;
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => app.log_viewer_open = false,
+                KeyCode::Char('l') | KeyCode::Char('L')
+                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    app.log_viewer_open = false;
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    app.log_scroll = app.log_scroll.saturating_add(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    app.log_scroll = app.log_scroll.saturating_sub(1);
+                }
+                KeyCode::PageUp => app.log_scroll = app.log_scroll.saturating_add(PAGE_STEP),
+                KeyCode::PageDown => app.log_scroll = app.log_scroll.saturating_sub(PAGE_STEP),
+                // 滚动量在渲染时按实际行数收敛
+                KeyCode::Home => app.log_scroll = usize::MAX,
+                KeyCode::End => app.log_scroll = 0,
+                KeyCode::Char('/') => app.log_filter_editing = true,
+                KeyCode::Char('c') => {
+                    app.log_filter.clear();
+                    app.log_scroll = 0;
+                }
+                _ => {}
+            }
+        }
+        Event::Mouse(me) => match me.kind {
+            MouseEventKind::ScrollUp => app.log_scroll = app.log_scroll.saturating_add(3),
+            MouseEventKind::ScrollDown => app.log_scroll = app.log_scroll.saturating_sub(3),
+            _ => {}
+        },
+        Event::Paste(s) if app.log_filter_editing => {
+            app.log_filter.push_str(s.trim());
+            app.log_scroll = 0;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 在已着色的日志行上高亮关键词（保留原有配色，匹配部分反色）。
+fn highlight_line(line: Line<'static>, needle: &str) -> Line<'static> {
+    if needle.is_empty() {
+        return line;
+    }
+    let mut spans = Vec::new();
+    for span in line.spans {
+        let text = span.content.to_string();
+        let lower = text.to_ascii_lowercase();
+        let mut last = 0usize;
+        for (pos, m) in lower.match_indices(needle) {
+            if pos > last {
+                spans.push(Span::styled(text[last..pos].to_string(), span.style));
+            }
+            spans.push(Span::styled(
+                text[pos..pos + m.len()].to_string(),
+                span.style
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            last = pos + m.len();
+        }
+        if last < text.len() {
+            spans.push(Span::styled(text[last..].to_string(), span.style));
+        }
+    }
+    Line::from(spans)
+}
+
+pub(super) fn render_log_viewer(frame: &mut ratatui::Frame, app: &mut App) {
+    let area = frame.size();
+    let w = (area.width as f32 * 0.90) as u16;
+    let h = (area.height as f32 * 0.85) as u16;
+    let modal = Rect {
+        x: area.x + area.width.saturating_sub(w) / 2,
+        y: area.y + area.height.saturating_sub(h) / 2,
+        width: w.max(30).min(area.width),
+        height: h.max(10).min(area.height),
+    };
+
+    let matched = filtered_logs(&app.logs, &app.log_filter);
+    let title = if app.log_filter.trim().is_empty() {
+        format!("日志查看 (共 {} 行)", app.logs.len())
+    } else {
+        format!(
+            "日志查看 (共 {} 行，匹配 {} 行)",
+            app.logs.len(),
+            matched.len()
+        )
+    };
+
+    frame.render_widget(Clear, modal);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Cyan)),
+        modal,
+    );
+
+    let inner = Rect {
+        x: modal.x + 1,
+        y: modal.y + 1,
+        width: modal.width.saturating_sub(2),
+        height: modal.height.saturating_sub(2),
+    };
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let list_area = Rect {
+        width: parts[0].width.saturating_sub(1).max(1),
+        ..parts[0]
+    };
+    let visible = list_area.height.max(1) as usize;
+    let max_scroll = matched.len().saturating_sub(visible);
+    app.log_scroll = app.log_scroll.min(max_scroll);
+    let end = matched.len() - app.log_scroll;
+    let start = end.saturating_sub(visible);
+
+    let needle = app.log_filter.trim().to_ascii_lowercase();
+    let lines: Vec<Line> = if matched.is_empty() {
+        vec![Line::from(Span::styled(
+            if app.logs.is_empty() {
+                "暂无日志"
+            } else {
+                "没有匹配的日志"
+            },
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        matched[start..end]
+            .iter()
+            .map(|l| highlight_line(style_log_line(l), &needle))
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines), list_area);
+
+    if max_scroll > 0 {
+        let sb_area = Rect {
+            x: list_area.x + list_area.width,
+            width: 1,
+            ..parts[0]
+        };
+        let mut state = ScrollbarState::new(max_scroll).position(max_scroll - app.log_scroll);
+        frame.render_stateful_widget(
+            Scrollbar::default().orientation(ScrollbarOrientation::VerticalRight),
+            sb_area,
+            &mut state,
+        );
+    }
+
+    let filter_style = if app.log_filter_editing {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    let filter_box = Paragraph::new(format!("> {}", app.log_filter))
+        .style(filter_style)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("过滤关键词 (如 ERROR / book_id)"),
+        );
+    frame.render_widget(filter_box, parts[1]);
+
+    let hint = if app.log_filter_editing {
+        "输入关键词，Enter/Esc 结束输入"
+    } else {
+        "↑↓/PgUp/PgDn/滚轮 滚动  Home/End 最早/最新  / 过滤  c 清除过滤  Esc 关闭"
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            hint,
+            Style::default().fg(Color::DarkGray),
+        ))),
+        parts[2],
+    );
+}
@@ -2,6 +2,7 @@
 //!
 //! 负责终端初始化（raw mode / mouse capture）、事件循环、页面切换与全局状态管理。
 
+use std::collections::{HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::{
@@ -29,36 +30,44 @@ use ratatui::prelude::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap};
-#[cfg(feature = "official-api")]
 use serde_json::Value;
 #[cfg(feature = "official-api")]
 use tomato_novel_official_api::SearchClient;
 use tracing::{info, warn};
 
 mod about;
+mod catalog;
 mod clipboard;
 mod config;
 mod config_model;
 mod cover;
 mod download;
+mod help;
 mod history;
 mod home;
+mod log_viewer;
 mod preview;
 mod update;
 
 use history::show_history_menu;
 use update::show_update_menu;
 
+use crate::base_system::book_library::{self, BrowseQuery};
 use crate::base_system::context::Config;
-#[cfg(feature = "official-api")]
+use crate::base_system::download_queue::{self, QueuedBook};
 use crate::base_system::json_extract;
 use crate::base_system::logging::take_broadcast_rx;
-use crate::download::downloader::{BookMeta, ChapterRange, DownloadPlan, ProgressSnapshot};
+#[cfg(feature = "official-api")]
+use crate::base_system::search_query::SearchQuery;
+use crate::download::downloader::{
+    BookMeta, ChapterRange, DownloadMode, DownloadPlan, ProgressSnapshot, VolumeInfo,
+};
 use crate::prewarm_state;
 
 pub(super) use config_model::{
     AUDIOBOOK_VOICE_PRESETS, ConfigCategory, ConfigEntry, apply_cfg_edit, build_config_categories,
-    cfg_combo_presets, cfg_field_is_bool, cfg_field_is_combo, current_cfg_value, start_cfg_edit,
+    cfg_combo_presets, cfg_field_is_bool, cfg_field_is_combo, current_cfg_value,
+    handle_cfg_reset_key, search_config_entries, start_cfg_edit,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -85,6 +94,7 @@ pub(super) enum MenuAction {
     Config,
     Update,
     History,
+    Favorites,
     About,
     Quit,
 }
@@ -115,6 +125,8 @@ struct UpdateEntry {
     label: String,
     _new_count: usize,
     _has_update: bool,
+    tags: Vec<String>,
+    category: String,
 }
 
 #[derive(Debug)]
@@ -133,6 +145,10 @@ enum WorkerMsg {
         options: Vec<crate::download::downloader::BookNameOption>,
         respond_to: std::sync::mpsc::Sender<Option<String>>,
     },
+    AskRedownloadModified {
+        titles: Vec<String>,
+        respond_to: std::sync::mpsc::Sender<bool>,
+    },
     PreviewReady(Box<Result<PendingDownload>>),
     UpdateScanProgress {
         entry: UpdateEntry,
@@ -142,6 +158,11 @@ enum WorkerMsg {
     },
     UpdateScanned(Result<(Vec<UpdateEntry>, Vec<UpdateEntry>)>),
     AppUpdateChecked(Result<crate::base_system::app_update::UpdateCheckReport>),
+    TrialReadDone(Result<crate::download::trial::TrialRead>),
+    ShareLinkResolved {
+        input: String,
+        book_id: Option<String>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -152,6 +173,14 @@ struct SearchItem {
     detail: Option<BookDetail>,
 }
 
+/// 批量下载队列项：搜索结果作为预览提示，另记录恢复所需的范围与格式。
+#[derive(Clone, Debug)]
+struct BatchItem {
+    item: SearchItem,
+    range: String,
+    format: String,
+}
+
 #[derive(Clone, Debug, Default)]
 struct BookDetail {
     description: Option<String>,
@@ -172,7 +201,6 @@ struct BookDetail {
     cover_primary_color: Option<String>,
 }
 
-#[cfg(feature = "official-api")]
 impl BookDetail {
     fn has_data(&self) -> bool {
         self.description.is_some()
@@ -198,6 +226,12 @@ impl BookDetail {
 pub(super) struct PendingDownload {
     plan: DownloadPlan,
     downloaded_count: usize,
+    /// 与 `plan.chapters` 一一对应的本地下载状态，用于预览状态条。
+    chapter_states: Vec<preview::ChapterState>,
+    /// 与 `plan.chapters` 一一对应的逐章字数（源未提供时为 None）。
+    chapter_words: Vec<Option<usize>>,
+    /// 目录分卷（供 `vol:N` 范围选择与预览展示）。
+    volumes: Vec<VolumeInfo>,
 }
 
 pub(super) struct App {
@@ -227,6 +261,11 @@ pub(super) struct App {
     cfg_bool_state: ListState,
     cfg_combo_state: ListState,
     cfg_combo_focus: ConfigComboFocus,
+    /// 配置搜索关键词；Some 表示处于搜索模式
+    cfg_search: Option<String>,
+    cfg_search_state: ListState,
+    /// 已按过一次、等待再次确认的"恢复默认"操作
+    cfg_reset_armed: Option<config_model::ConfigReset>,
     last_config_layout: Option<[Rect; 3]>,
     last_config_button: Option<Rect>,
     last_config_bool_area: Option<Rect>,
@@ -244,12 +283,16 @@ pub(super) struct App {
     update_no_updates: Vec<UpdateEntry>,
     update_state: ListState,
     show_no_update: bool,
+    /// 更新列表按分类/标签过滤，None 表示不过滤。
+    update_filter: Option<String>,
     last_update_layout: Option<[Rect; 3]>,
     last_update_exit_button: Option<Rect>,
 
     // about state
     about_btn_state: ListState,
     last_about_buttons: Option<Rect>,
+    /// 进入关于页时读取的全局下载统计
+    download_stats: crate::base_system::download_stats::DownloadStats,
 
     // app update (program update)
     app_update_report: Option<crate::base_system::app_update::UpdateCheckReport>,
@@ -261,11 +304,17 @@ pub(super) struct App {
     // cover state
     cover_lines: Vec<String>,
     cover_title: String,
+    /// 封面页强调色（取自书籍主色调）
+    cover_accent: Option<Color>,
     _previous_view_cover: View,
 
     // home layout
     last_home_layout: Option<[Rect; 5]>,
 
+    // favorites / search completion
+    favorites: Vec<crate::base_system::favorites::FavoriteBook>,
+    history_names: Vec<String>,
+
     // history
     history_entries: Vec<crate::base_system::download_history::DownloadHistoryRecord>,
     history_state: ListState,
@@ -299,6 +348,12 @@ pub(super) struct App {
     preview_buttons: ListState,
     preview_range: String,
     preview_modal_open: bool,
+    /// 完整章节目录浮层是否打开
+    preview_catalog_open: bool,
+    preview_catalog_state: ListState,
+    /// 目录中勾选的章节（`plan.chapters` 下标）；非空时优先于范围输入
+    preview_picked: HashSet<usize>,
+    last_catalog_area: Option<Rect>,
 
     // preview layout cache (for mouse)
     last_preview_layout: Option<[Rect; 2]>,
@@ -317,6 +372,8 @@ pub(super) struct App {
     // download cancel
     download_cancel_flag: Option<Arc<AtomicBool>>,
     stop_button_area: Option<Rect>,
+    /// 最近一次下载/重新生成的输出目录（首页 Ctrl+O 打开）
+    last_output_dir: Option<PathBuf>,
 
     // book name modal (post-download)
     book_name_modal_open: bool,
@@ -331,6 +388,37 @@ pub(super) struct App {
     format_modal_options: Vec<crate::download::downloader::BookNameOption>,
     format_modal_sender: Option<std::sync::mpsc::Sender<Option<String>>>,
     last_format_modal_list: Option<Rect>,
+
+    // modified chapters modal (update)
+    modified_modal_titles: Vec<String>,
+    modified_modal_sender: Option<std::sync::mpsc::Sender<bool>>,
+
+    // log viewer (Ctrl+L)
+    log_viewer_open: bool,
+    /// 快捷键帮助浮层（? / F1）。
+    help_overlay_open: bool,
+    /// 距最新一行向上滚动的行数，0 表示贴底。
+    log_scroll: usize,
+    log_filter: String,
+    log_filter_editing: bool,
+
+    /// 下一次预览就绪后直接以“仅重下失败章节”模式开始下载。
+    retry_failed_only: bool,
+    /// 目录章节数不符且开启 `block_on_incomplete_catalog` 时，用户已确认继续下载。
+    catalog_mismatch_confirmed: bool,
+
+    /// 当前结果列表对应的分类榜单（用于翻页），普通搜索时为 None。
+    browse_query: Option<BrowseQuery>,
+    /// 结果列表中标记待批量下载的 book_id。
+    batch_marked: HashSet<String>,
+    /// 批量下载队列（依次预览并直接下载），变化时同步到 queue.json。
+    batch_queue: VecDeque<BatchItem>,
+    /// 批量下载中正在处理的一本。
+    batch_current: Option<BatchItem>,
+    /// 启动时发现的上次未完成队列，等待用户确认是否恢复。
+    queue_resume_prompt: Vec<QueuedBook>,
+    /// 下一次预览就绪后直接开始下载（批量下载中）。
+    batch_auto_download: bool,
 }
 #[derive(Clone, Debug, Default)]
 struct PreviewModalLayout {
@@ -378,7 +466,7 @@ impl App {
         Self {
             input: String::new(),
             focus: Focus::Input,
-            status: "输入书名/ID/链接，Enter 确认，Tab 切换焦点，q 退出".to_string(),
+            status: "输入书名/ID/链接，Enter 确认，Tab 切换焦点，F1 查看快捷键，q 退出".to_string(),
             messages: Vec::new(),
             logs: Vec::new(),
             results: Vec::new(),
@@ -400,6 +488,9 @@ impl App {
             cfg_bool_state,
             cfg_combo_state,
             cfg_combo_focus: ConfigComboFocus::List,
+            cfg_search: None,
+            cfg_search_state: ListState::default(),
+            cfg_reset_armed: None,
             last_config_layout: None,
             last_config_button: None,
             last_config_bool_area: None,
@@ -413,17 +504,22 @@ impl App {
             update_no_updates: Vec::new(),
             update_state,
             show_no_update: false,
+            update_filter: None,
             last_update_layout: None,
             last_update_exit_button: None,
             about_btn_state,
             last_about_buttons: None,
+            download_stats: Default::default(),
             app_update_report: None,
             self_update_requested: false,
             self_update_auto_yes: false,
             cover_lines: Vec::new(),
             cover_title: String::new(),
+            cover_accent: None,
             _previous_view_cover: View::Home,
             last_home_layout: None,
+            favorites: crate::base_system::favorites::load_favorites(),
+            history_names: home::load_history_names(),
             history_entries: Vec::new(),
             history_state,
             last_history_layout: None,
@@ -444,6 +540,10 @@ impl App {
             preview_buttons,
             preview_range: String::new(),
             preview_modal_open: false,
+            preview_catalog_open: false,
+            preview_catalog_state: ListState::default(),
+            preview_picked: HashSet::new(),
+            last_catalog_area: None,
             last_preview_layout: None,
             last_preview_modal: None,
             preview_desc_scroll: 0,
@@ -454,6 +554,7 @@ impl App {
             download_progress: None,
             download_cancel_flag: None,
             stop_button_area: None,
+            last_output_dir: None,
 
             book_name_modal_open: false,
             book_name_modal_state,
@@ -470,6 +571,25 @@ impl App {
             format_modal_options: Vec::new(),
             format_modal_sender: None,
             last_format_modal_list: None,
+
+            modified_modal_titles: Vec::new(),
+            modified_modal_sender: None,
+
+            log_viewer_open: false,
+            help_overlay_open: false,
+            log_scroll: 0,
+            log_filter: String::new(),
+            log_filter_editing: false,
+
+            retry_failed_only: false,
+            catalog_mismatch_confirmed: false,
+
+            browse_query: None,
+            batch_marked: HashSet::new(),
+            batch_queue: VecDeque::new(),
+            batch_current: None,
+            queue_resume_prompt: Vec::new(),
+            batch_auto_download: false,
         }
     }
 
@@ -557,6 +677,7 @@ fn run_loop(
     worker_rx: Receiver<WorkerMsg>,
 ) -> Result<TuiExit> {
     let mut app = App::new(config, worker_tx, worker_rx);
+    app.queue_resume_prompt = download_queue::load_queue();
 
     // 每次启动检查程序更新（异步，不阻塞 UI）。
     start_app_update_check(&mut app);
@@ -572,6 +693,8 @@ fn run_loop(
             draw_ui(f, &mut app);
             render_prewarm_overlay(f, &app);
             render_iid_error_overlay(f, &app);
+            render_queue_resume_overlay(f, &app);
+            help::render_help_overlay(f, &app);
         })?;
 
         if !handle_event(&mut app)? {
@@ -617,6 +740,14 @@ fn draw_ui(frame: &mut ratatui::Frame, app: &mut App) {
     if app.format_modal_open {
         render_format_modal(frame, app);
     }
+
+    if app.modified_modal_sender.is_some() {
+        render_modified_modal(frame, app);
+    }
+
+    if app.log_viewer_open {
+        log_viewer::render_log_viewer(frame, app);
+    }
 }
 
 fn handle_event(app: &mut App) -> Result<bool> {
@@ -634,6 +765,43 @@ fn handle_event(app: &mut App) -> Result<bool> {
         }
         return Ok(!app.should_quit);
     }
+    if !app.queue_resume_prompt.is_empty() {
+        if let Event::Key(key) = evt
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    let items = std::mem::take(&mut app.queue_resume_prompt);
+                    home::resume_saved_queue(app, items)?;
+                }
+                KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                    app.queue_resume_prompt.clear();
+                    home::persist_batch_queue(app);
+                    app.status = "已放弃上次未完成的下载队列".to_string();
+                }
+                _ => {}
+            }
+        }
+        return Ok(!app.should_quit);
+    }
+
+    if app.help_overlay_open {
+        if let Event::Key(key) = evt
+            && key.kind == KeyEventKind::Press
+        {
+            app.help_overlay_open = false;
+        }
+        return Ok(!app.should_quit);
+    }
+
+    if app.log_viewer_open {
+        log_viewer::handle_log_viewer_event(app, evt)?;
+        return Ok(!app.should_quit);
+    }
+    if log_viewer::is_open_key(&evt) {
+        log_viewer::open_log_viewer(app);
+        return Ok(!app.should_quit);
+    }
 
     if app.book_name_modal_open {
         handle_book_name_modal_event(app, evt)?;
@@ -643,6 +811,14 @@ fn handle_event(app: &mut App) -> Result<bool> {
         handle_format_modal_event(app, evt)?;
         return Ok(!app.should_quit);
     }
+    if app.modified_modal_sender.is_some() {
+        handle_modified_modal_event(app, evt);
+        return Ok(!app.should_quit);
+    }
+    if help::is_open_key(app, &evt) {
+        app.help_overlay_open = true;
+        return Ok(!app.should_quit);
+    }
     match app.view {
         View::Home => home::handle_event_home(app, evt)?,
         View::Config => config::handle_event_config(app, evt)?,
@@ -847,10 +1023,80 @@ fn render_format_modal(frame: &mut ratatui::Frame, app: &mut App) {
     app.last_format_modal_list = Some(parts[1]);
 }
 
+/// 更新时发现被修订的章节：Y 重新下载，N/Esc 保留本地版本。
+fn handle_modified_modal_event(app: &mut App, event: Event) {
+    let Event::Key(key) = event else {
+        return;
+    };
+    if key.kind != KeyEventKind::Press {
+        return;
+    }
+    let redownload = match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => true,
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => false,
+        _ => return,
+    };
+    if let Some(tx) = app.modified_modal_sender.take() {
+        let _ = tx.send(redownload);
+    }
+    app.status = if redownload {
+        format!(
+            "将重新下载 {} 章被修改的章节",
+            app.modified_modal_titles.len()
+        )
+    } else {
+        "已保留本地版本".to_string()
+    };
+    app.modified_modal_titles.clear();
+}
+
+fn render_modified_modal(frame: &mut ratatui::Frame, app: &mut App) {
+    const MAX_LISTED: usize = 8;
+    let area = frame.size();
+    let w = (area.width as f32 * 0.60) as u16;
+    let listed = app.modified_modal_titles.len().min(MAX_LISTED) as u16;
+    let h: u16 = listed + 5;
+    let modal = Rect {
+        x: area.x + area.width.saturating_sub(w) / 2,
+        y: area.y + area.height.saturating_sub(h) / 2,
+        width: w.max(30).min(area.width.saturating_sub(2)),
+        height: h.min(area.height.saturating_sub(2)).max(6),
+    };
+
+    frame.render_widget(Clear, modal);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "检测到 {} 章正文被修改",
+            app.modified_modal_titles.len()
+        ))
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let mut lines: Vec<Line> = app
+        .modified_modal_titles
+        .iter()
+        .take(MAX_LISTED)
+        .map(|t| Line::from(format!("· {t}")))
+        .collect();
+    if app.modified_modal_titles.len() > MAX_LISTED {
+        lines.push(Line::from(format!(
+            "…等 {} 章",
+            app.modified_modal_titles.len()
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from("Y 重新下载 / N 或 Esc 保留本地版本"));
+    let body = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(body, modal);
+}
+
 #[cfg(feature = "official-api")]
 fn search_books(query: &str) -> Result<Vec<SearchItem>> {
+    let query = SearchQuery::parse(query);
     let client = SearchClient::new().context("init SearchClient")?;
-    let resp = client.search_books(query).context("search_books")?;
+    let resp = client
+        .search_books(&query.keyword)
+        .context("search_books")?;
     let mut results = Vec::new();
     for book in resp.books {
         let title = book.title.unwrap_or_default();
@@ -869,7 +1115,7 @@ fn search_books(query: &str) -> Result<Vec<SearchItem>> {
             detail,
         });
     }
-    Ok(results)
+    Ok(query.apply(results, |it| it.title.as_str(), |it| it.author.as_str()))
 }
 
 #[cfg(not(feature = "official-api"))]
@@ -877,7 +1123,6 @@ fn search_books(_query: &str) -> Result<Vec<SearchItem>> {
     anyhow::bail!("当前构建未启用 official-api feature，搜索功能不可用")
 }
 
-#[cfg(feature = "official-api")]
 fn detail_from_search(raw: &Value) -> BookDetail {
     let maps = json_extract::collect_maps(raw);
 
@@ -1009,8 +1254,12 @@ pub(super) fn parse_book_id(input: &str) -> Option<String> {
     crate::base_system::book_id::parse_book_id(input)
 }
 
-pub(super) fn parse_range_input(input: &str, total: usize) -> Result<Option<ChapterRange>> {
-    preview::parse_range_input(input, total)
+pub(super) fn parse_range_input(
+    input: &str,
+    total: usize,
+    volumes: &[VolumeInfo],
+) -> Result<Option<ChapterRange>> {
+    preview::parse_range_input(input, total, volumes)
 }
 
 const MENU_ITEMS: &[(&str, MenuAction)] = &[
@@ -1018,6 +1267,7 @@ const MENU_ITEMS: &[(&str, MenuAction)] = &[
     ("配置", MenuAction::Config),
     ("更新", MenuAction::Update),
     ("历史", MenuAction::History),
+    ("收藏", MenuAction::Favorites),
     ("关于", MenuAction::About),
     ("退出", MenuAction::Quit),
 ];
@@ -1205,9 +1455,11 @@ fn render_log_box(frame: &mut ratatui::Frame, area: Rect, app: &App) {
         );
     }
 
-    let log = Paragraph::new(lines)
-        .wrap(Wrap { trim: true })
-        .block(Block::default().borders(Borders::ALL).title("日志"));
+    let log = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("日志 (Ctrl+L 查看/搜索)"),
+    );
     frame.render_widget(log, area);
 }
 
@@ -1312,6 +1564,71 @@ fn render_iid_error_overlay(frame: &mut ratatui::Frame, app: &App) {
     frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), inner);
 }
 
+fn render_queue_resume_overlay(frame: &mut ratatui::Frame, app: &App) {
+    if app.queue_resume_prompt.is_empty() {
+        return;
+    }
+
+    let area = frame.size();
+    let shown = app.queue_resume_prompt.len().min(6);
+    let width = area.width.min(70);
+    let height = area.height.min(7 + shown as u16 + 1);
+    if width == 0 || height == 0 {
+        return;
+    }
+    let overlay = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let inner = Rect {
+        x: overlay.x.saturating_add(2),
+        y: overlay.y.saturating_add(1),
+        width: overlay.width.saturating_sub(4).max(1),
+        height: overlay.height.saturating_sub(2).max(1),
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "检测到上次未完成的下载队列（{} 本）",
+                app.queue_resume_prompt.len()
+            ),
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    for q in app.queue_resume_prompt.iter().take(shown) {
+        lines.push(Line::from(format!(
+            "《{}》 ({})",
+            truncate(&q.book_name, 40),
+            q.book_id
+        )));
+    }
+    if app.queue_resume_prompt.len() > shown {
+        lines.push(Line::from(format!(
+            "…… 另有 {} 本",
+            app.queue_resume_prompt.len() - shown
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Enter/y 恢复（从已下载进度继续），Esc/n 放弃",
+        Style::default().fg(Color::Cyan),
+    )));
+
+    frame.render_widget(Clear, overlay);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("恢复下载队列")
+        .title_alignment(Alignment::Center);
+    frame.render_widget(block, overlay);
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), inner);
+}
+
 fn render_book_name_modal(frame: &mut ratatui::Frame, app: &mut App) {
     let area = frame.size();
     let w = (area.width as f32 * 0.70) as u16;
@@ -1496,9 +1813,11 @@ pub(super) fn switch_view(app: &mut App, action: MenuAction) -> Result<()> {
         }
         MenuAction::Update => show_update_menu(app)?,
         MenuAction::History => show_history_menu(app)?,
+        MenuAction::Favorites => home::show_favorites(app),
         MenuAction::About => {
             app.view = View::About;
             app.status = "关于".to_string();
+            app.download_stats = crate::base_system::download_stats::load_download_stats();
         }
         MenuAction::Quit => app.should_quit = true,
     }
@@ -1516,6 +1835,7 @@ pub(super) fn trigger_menu_action(app: &mut App) -> Result<()> {
 
 pub(super) fn start_search_task(app: &mut App, query: String) -> Result<()> {
     info!(target: "ui", "开始搜索: {query}");
+    app.browse_query = None;
     start_spinner(app, "搜索中…");
     let tx = app.worker_tx.clone();
     thread::spawn(move || {
@@ -1525,6 +1845,42 @@ pub(super) fn start_search_task(app: &mut App, query: String) -> Result<()> {
     Ok(())
 }
 
+/// 后台跟随短链接跳转；解析失败时回退为按口令中的书名搜索。
+pub(super) fn start_share_link_task(app: &mut App, input: String) {
+    info!(target: "ui", "解析分享链接: {input}");
+    start_spinner(app, "正在解析短链接…");
+    let tx = app.worker_tx.clone();
+    thread::spawn(move || {
+        let book_id = crate::base_system::book_id::resolve_book_id(&input);
+        let _ = tx.send(WorkerMsg::ShareLinkResolved { input, book_id });
+    });
+}
+
+/// 按分类拉取榜单，结果复用搜索结果列表。
+pub(super) fn start_browse_task(app: &mut App, query: BrowseQuery) {
+    info!(target: "ui", category = query.category.name, page = query.page, "浏览分类榜单");
+    app.browse_query = Some(query);
+    start_spinner(app, format!("加载榜单: {}", query.label()));
+    let tx = app.worker_tx.clone();
+    thread::spawn(move || {
+        let result = book_library::fetch_rank_page(&query).map(|books| {
+            books
+                .into_iter()
+                .map(|book| {
+                    let detail = detail_from_search(&book.raw);
+                    SearchItem {
+                        title: book.title,
+                        author: book.author,
+                        book_id: book.book_id,
+                        detail: detail.has_data().then_some(detail),
+                    }
+                })
+                .collect()
+        });
+        let _ = tx.send(WorkerMsg::SearchDone(result));
+    });
+}
+
 pub(super) fn start_preview_task(app: &mut App, book_id: String, hint: BookMeta) -> Result<()> {
     preview::start_preview_task(app, book_id, hint)
 }
@@ -1534,26 +1890,52 @@ pub(super) fn start_download_task(
     pending: PendingDownload,
     range: Option<ChapterRange>,
 ) -> Result<()> {
-    download::start_download_task(app, pending, range)
+    download::start_download_task(app, pending, range, None, DownloadMode::Resume)
 }
 
 fn poll_worker(app: &mut App) -> Result<()> {
     while let Ok(msg) = app.worker_rx.try_recv() {
         stop_spinner(app);
         match msg {
+            WorkerMsg::ShareLinkResolved { input, book_id } => match book_id {
+                Some(book_id) => {
+                    app.status = format!("准备下载书籍 {book_id} …");
+                    start_preview_task(app, book_id, BookMeta::default())?;
+                }
+                None => match crate::base_system::book_id::share_search_keyword(&input) {
+                    Some(keyword) => {
+                        app.push_message(format!("短链接解析失败，改为搜索：{keyword}"));
+                        start_search_task(app, keyword)?;
+                    }
+                    None => app.status = "短链接解析失败，请改用书名搜索".to_string(),
+                },
+            },
             WorkerMsg::SearchDone(res) => match res {
                 Ok(results) => {
                     if results.is_empty() {
-                        app.status = "未找到匹配书籍".to_string();
+                        app.status = match app.browse_query {
+                            Some(q) => format!("{}：没有更多书籍", q.label()),
+                            None => "未找到匹配书籍".to_string(),
+                        };
                         app.results.clear();
                         app.list_state.select(None);
                         app.focus = Focus::Input;
+                    } else if let Some(q) = app.browse_query {
+                        app.status = format!(
+                            "{}：{} 本，Enter 预览，Space 标记，d 批量下载，[ ] 翻页",
+                            q.label(),
+                            results.len()
+                        );
+                        app.results = results;
+                        app.list_state.select(Some(0));
+                        app.focus = Focus::Results;
                     } else {
                         app.status = format!(
                             "找到 {} 本书，使用上下键选择，Enter 预览/下载。",
                             results.len()
                         );
                         app.results = results;
+                        home::pin_favorites(app);
                         app.list_state.select(Some(0));
                         app.focus = Focus::Results;
                     }
@@ -1597,6 +1979,11 @@ fn poll_worker(app: &mut App) -> Result<()> {
                     app.status = "请选择输出格式（下载已完成）".to_string();
                 }
             }
+            WorkerMsg::AskRedownloadModified { titles, respond_to } => {
+                app.modified_modal_titles = titles;
+                app.modified_modal_sender = Some(respond_to);
+                app.status = "检测到正文被修改的章节，是否重新下载？".to_string();
+            }
             WorkerMsg::UpdateScanProgress {
                 entry,
                 is_update,
@@ -1643,6 +2030,10 @@ fn poll_worker(app: &mut App) -> Result<()> {
                         let has = app.update_entries.len();
                         let none = app.update_no_updates.len();
                         app.status = format!("扫描完成：有更新 {has} 本，无更新 {none} 本");
+                        if let Some(hint) = update::duplicate_folders_hint(&app.config) {
+                            app.status.push('；');
+                            app.status.push_str(&hint);
+                        }
                         info!(target: "ui", updates = has, no_updates = none, "扫描完成");
                         app.view = View::Update;
                     }
@@ -1658,6 +2049,7 @@ fn poll_worker(app: &mut App) -> Result<()> {
                 download::apply_download_done(app, book_id, result);
             }
             WorkerMsg::DownloadProgress(snap) => download::apply_download_progress(app, snap),
+            WorkerMsg::TrialReadDone(res) => preview::apply_trial_read(app, res),
             WorkerMsg::AppUpdateChecked(res) => match res {
                 Ok(report) => {
                     let notify = crate::base_system::app_update::should_notify_startup(&report);
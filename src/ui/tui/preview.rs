@@ -17,15 +17,36 @@ use ratatui::widgets::{
 use tracing::{info, warn};
 
 use crate::base_system::context::safe_fs_name;
-use crate::download::downloader::{self, BookMeta, ChapterRange, ProgressSnapshot, SavePhase};
+use crate::download::downloader::{
+    self, BookMeta, ChapterRange, DownloadMode, ProgressSnapshot, SavePhase, VolumeInfo,
+};
+use crate::download::third_party::{self, EndpointStat};
+use crate::download::{network_wait, pause};
 
-use super::download::{request_cancel_download, start_download_task};
+use super::cover::accent_color;
+use super::download::{request_cancel_download, start_download_task, toggle_pause_download};
 use super::{
     App, Focus, PendingDownload, PreviewFocus, PreviewModalLayout, View, WorkerMsg,
-    format_word_count, render_log_box, start_spinner, truncate, upsert_result_detail_from_plan,
+    format_word_count, render_log_box, start_spinner, stop_spinner, truncate,
+    upsert_result_detail_from_plan,
 };
 
+/// 本页快捷键说明（`?` / F1 帮助浮层）。
+pub(super) const SHORTCUTS: &[(&str, &str)] = &[
+    ("Tab", "在章节范围输入与按钮间切换"),
+    ("Enter", "确认下载 / 执行选中按钮"),
+    ("↑ / ↓ / PgUp / PgDn", "滚动预览内容"),
+    ("Home / End", "跳到顶部 / 底部"),
+    ("Ctrl+T", "试读"),
+    ("p", "暂停 / 继续下载（下载中）"),
+    ("s", "停止下载"),
+    ("Esc", "取消预览"),
+];
+
 pub(super) fn handle_event_preview(app: &mut App, event: Event) -> Result<()> {
+    if app.preview_catalog_open {
+        return super::catalog::handle_event_catalog(app, event);
+    }
     match event {
         Event::Paste(s) => {
             if app.preview_focus == PreviewFocus::Range {
@@ -39,6 +60,19 @@ pub(super) fn handle_event_preview(app: &mut App, event: Event) -> Result<()> {
             KeyCode::Char('s') | KeyCode::Char('S') => {
                 request_cancel_download(app);
             }
+            KeyCode::Char('p') | KeyCode::Char('P') if app.download_cancel_flag.is_some() => {
+                toggle_pause_download(app);
+            }
+            KeyCode::Char('t') | KeyCode::Char('T')
+                if key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                start_trial_read(app);
+            }
+            KeyCode::Char('l') | KeyCode::Char('L')
+                if key.modifiers.contains(KeyModifiers::CONTROL) && app.preview_modal_open =>
+            {
+                super::catalog::open_catalog(app);
+            }
             KeyCode::Tab => {
                 app.preview_focus = match app.preview_focus {
                     PreviewFocus::Range => PreviewFocus::Buttons,
@@ -190,15 +224,71 @@ pub(super) fn handle_mouse_preview(app: &mut App, me: event::MouseEvent) -> Resu
     Ok(())
 }
 
-pub(super) fn parse_range_input(input: &str, total: usize) -> Result<Option<ChapterRange>> {
+const MAX_ENDPOINT_ROWS: usize = 4;
+
+fn endpoint_stat_line(stat: &EndpointStat) -> Paragraph<'static> {
+    let name = stat
+        .endpoint
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let (rate, color) = match stat.success_rate() {
+        _ if stat.disabled => ("已停用".to_string(), Color::DarkGray),
+        None => ("--".to_string(), Color::Gray),
+        Some(r) if r >= 80 => (format!("{r}%"), Color::Green),
+        Some(r) if r >= 50 => (format!("{r}%"), Color::Yellow),
+        Some(r) => (format!("{r}%"), Color::Red),
+    };
+    Paragraph::new(Line::from(vec![
+        Span::raw(format!("{:<28} ", truncate(name, 26))),
+        Span::raw(format!(
+            "请求 {} 成功 {} 失败 {} ",
+            stat.requests, stat.success, stat.failed
+        )),
+        Span::styled(
+            rate,
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+    ]))
+}
+
+pub(super) fn parse_range_input(
+    input: &str,
+    total: usize,
+    volumes: &[VolumeInfo],
+) -> Result<Option<ChapterRange>> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
         return Ok(None);
     }
 
+    // `vol:N`：第 N 卷的全部章节
+    if let Some(n) = downloader::strip_volume_prefix(trimmed) {
+        return downloader::volume_range(volumes, n).map(Some);
+    }
+
+    // `last:N`：最新 N 章，N 超过总章数时即全部
+    if let Some(n) = strip_last_prefix(trimmed) {
+        let n = n
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| anyhow!("last:N 中的 N 需为数字"))?;
+        if n == 0 {
+            return Err(anyhow!("last:N 中的 N 需大于 0"));
+        }
+        if total == 0 {
+            return Err(anyhow!("目录为空"));
+        }
+        return Ok(Some(ChapterRange {
+            start: total.saturating_sub(n) + 1,
+            end: total,
+        }));
+    }
+
     let parts: Vec<&str> = trimmed.split('-').collect();
     if parts.len() > 2 {
-        return Err(anyhow!("格式应为 start-end，例如 1-10"));
+        return Err(anyhow!(
+            "格式应为 start-end、last:N 或 vol:N，例如 1-10、last:20、vol:2"
+        ));
     }
 
     let start_part = parts.first().copied().unwrap_or("").trim();
@@ -235,6 +325,16 @@ pub(super) fn parse_range_input(input: &str, total: usize) -> Result<Option<Chap
     }))
 }
 
+/// 识别 `last:N` / `last：N`（不区分大小写），返回 N 部分。
+fn strip_last_prefix(input: &str) -> Option<&str> {
+    let head = input.get(..4)?;
+    if !head.eq_ignore_ascii_case("last") {
+        return None;
+    }
+    let rest = &input[4..];
+    rest.strip_prefix(':').or_else(|| rest.strip_prefix('：'))
+}
+
 pub(super) fn start_preview_task(app: &mut App, book_id: String, hint: BookMeta) -> Result<()> {
     app.pending_download = None;
     app.messages.clear();
@@ -256,17 +356,40 @@ pub(super) fn start_preview_task(app: &mut App, book_id: String, hint: BookMeta)
         // Resolve short links in the background thread to avoid blocking the UI.
         let book_id = crate::base_system::book_id::resolve_book_id(&book_id).unwrap_or(book_id);
         let result = downloader::prepare_download_plan(&cfg, &book_id, hint).map(|plan| {
+            let root = cfg.save_path_rule_root(plan.meta.category.as_deref(), &plan.meta.tags);
             let folder = crate::base_system::book_paths::book_folder_path(
                 &cfg,
                 &plan.book_id,
                 plan.meta.book_name.as_deref(),
+                root.as_deref(),
             );
             let downloaded =
                 crate::base_system::novel_updates::read_downloaded_ok_count(&folder, &plan.book_id)
                     .unwrap_or(0);
+            let status =
+                crate::base_system::novel_updates::read_chapter_status_map(&folder, &plan.book_id);
+            let chapter_states = plan
+                .chapters
+                .iter()
+                .map(|ch| match status.get(&ch.id) {
+                    Some(true) => ChapterState::Done,
+                    Some(false) => ChapterState::Failed,
+                    None => ChapterState::Missing,
+                })
+                .collect();
+            let words = crate::base_system::json_extract::chapter_word_counts(&plan._raw);
+            let chapter_words = plan
+                .chapters
+                .iter()
+                .map(|ch| words.get(&ch.id).copied())
+                .collect();
+            let volumes = plan.volumes();
             PendingDownload {
                 plan,
                 downloaded_count: downloaded,
+                chapter_states,
+                chapter_words,
+                volumes,
             }
         });
         let _ = tx.send(WorkerMsg::PreviewReady(Box::new(result)));
@@ -280,12 +403,22 @@ pub(super) fn confirm_preview(app: &mut App) -> Result<()> {
         None => return Ok(()),
     };
 
+    if app.config.block_on_incomplete_catalog
+        && !app.catalog_mismatch_confirmed
+        && let Some(warning) = pending.plan.catalog_warning()
+    {
+        app.catalog_mismatch_confirmed = true;
+        app.status = format!("{warning}，再次确认开始下载");
+        return Ok(());
+    }
+
+    let selection = super::catalog::picked_chapter_ids(app);
     let total = pending.plan.chapters.len();
     let input = app.preview_range.trim();
-    let range = if input.is_empty() {
+    let range = if selection.is_some() || input.is_empty() {
         None
     } else {
-        match parse_range_input(input, total) {
+        match parse_range_input(input, total, &pending.volumes) {
             Ok(r) => r,
             Err(err) => {
                 app.status = format!("范围无效: {err}");
@@ -295,12 +428,13 @@ pub(super) fn confirm_preview(app: &mut App) -> Result<()> {
     };
 
     app.preview_range.clear();
+    app.preview_picked.clear();
     app.preview_buttons.select(Some(0));
     app.view = View::Preview;
     app.focus = Focus::Input;
     app.input.clear();
 
-    start_download_task(app, pending, range)
+    start_download_task(app, pending, range, selection, DownloadMode::Resume)
 }
 
 pub(super) fn cancel_preview(app: &mut App) {
@@ -309,6 +443,8 @@ pub(super) fn cancel_preview(app: &mut App) {
 
     app.pending_download = None;
     app.preview_range.clear();
+    app.preview_picked.clear();
+    app.preview_catalog_open = false;
     app.preview_buttons.select(Some(0));
     app.preview_modal_open = false;
     app.download_progress = None;
@@ -337,10 +473,15 @@ pub(super) fn cleanup_preview_cover_artifacts(app: &mut App) {
 
     // NOTE: preview currently downloads cover into the same book folder name as status folder.
     // Only delete when the folder contains *only* cover-like files and no status.json.
+    let meta = &pending.plan.meta;
+    let root = app
+        .config
+        .save_path_rule_root(meta.category.as_deref(), &meta.tags);
     let dir = crate::base_system::book_paths::book_folder_path(
         &app.config,
         &pending.plan.book_id,
         Some(book_name),
+        root.as_deref(),
     );
     if !dir.exists() {
         return;
@@ -442,7 +583,16 @@ pub(super) fn preview_scroll_to_bottom(app: &mut App) {
 
 pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
     let area = frame.size();
-    let progress_height: u16 = 7;
+    // 第三方多源下载时的 endpoint 看板，最多展示前几个
+    let endpoint_stats: Vec<EndpointStat> = if app.download_progress.is_some() {
+        third_party::endpoint_stats_snapshot()
+            .into_iter()
+            .take(MAX_ENDPOINT_ROWS)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let progress_height: u16 = 7 + endpoint_stats.len() as u16;
     let log_height = area.height.saturating_sub(progress_height);
 
     let log_area = Rect {
@@ -482,9 +632,16 @@ pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
             .filter(|s| !s.is_empty())
             .unwrap_or("暂无简介");
 
-        let desc_block = Block::default()
+        let accent = app
+            .pending_download
+            .as_ref()
+            .and_then(|p| accent_color(p.plan.meta.cover_primary_color.as_deref()));
+        let mut desc_block = Block::default()
             .borders(Borders::ALL)
             .title("简介 (↑↓/滚轮)");
+        if let Some(accent) = accent {
+            desc_block = desc_block.border_style(Style::default().fg(accent));
+        }
         frame.render_widget(desc_block.clone(), desc_area);
         let inner = desc_block.inner(desc_area);
 
@@ -600,7 +757,7 @@ pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
     app.stop_button_area = None;
     if !items.is_empty() && inner_area.height > 0 {
         let mut constraints = Vec::new();
-        for _ in &items {
+        for _ in 0..items.len() + endpoint_stats.len() {
             constraints.push(Constraint::Length(1));
         }
         // extra line for stop button
@@ -626,11 +783,22 @@ pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
             }
         }
 
+        let offset = rows.len().saturating_sub(1 + endpoint_stats.len());
+        for (idx, stat) in endpoint_stats.iter().enumerate() {
+            if let Some(area) = rows.get(offset + idx) {
+                frame.render_widget(endpoint_stat_line(stat), *area);
+            }
+        }
+
         if let Some(btn_area) = rows.last() {
-            let txt = if app.download_cancel_flag.is_some() {
-                "[ 停止下载 ] (S/点击)"
-            } else {
+            let txt = if app.download_cancel_flag.is_none() {
                 ""
+            } else if pause::is_paused() {
+                "[ 停止下载 ] (S/点击)  已暂停，按 P 恢复"
+            } else if network_wait::is_waiting_for_network() {
+                "[ 停止下载 ] (S/点击)  网络不可用，等待恢复后自动继续…"
+            } else {
+                "[ 停止下载 ] (S/点击)  [ 暂停 ] (P)"
             };
             let para = Paragraph::new(txt)
                 .alignment(Alignment::Center)
@@ -668,7 +836,7 @@ pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
 
         let pending = app.pending_download.as_ref();
         let fallback_meta = BookMeta::default();
-        let (title, original_title, author, total, downloaded, meta) = pending
+        let (title, original_title, author, total, downloaded, meta, states, volumes) = pending
             .map(|p| {
                 (
                     p.plan
@@ -681,9 +849,20 @@ pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
                     p.plan.chapters.len(),
                     p.downloaded_count,
                     &p.plan.meta,
+                    p.chapter_states.as_slice(),
+                    p.volumes.as_slice(),
                 )
             })
-            .unwrap_or(("预览".to_string(), None, None, 0, 0, &fallback_meta));
+            .unwrap_or((
+                "预览".to_string(),
+                None,
+                None,
+                0,
+                0,
+                &fallback_meta,
+                &[][..],
+                &[][..],
+            ));
 
         let mut title_line = format!("《{}》", title);
         if let Some(orig) = original_title.as_ref()
@@ -709,6 +888,15 @@ pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
         meta_lines.push(Line::from(row1_s.clone()));
         info_plain_lines.push(row1_s);
 
+        if !states.is_empty() && states.iter().any(|s| *s != ChapterState::Missing) {
+            let bar_width = (inner.width.saturating_sub(4) as usize).clamp(10, 72);
+            let (bar_line, bar_plain) = chapter_status_bar(states, bar_width);
+            meta_lines.push(bar_line);
+            info_plain_lines.push(bar_plain);
+            meta_lines.push(chapter_status_legend());
+            info_plain_lines.push("█已下载 ▄部分 █失败 ░未下载".to_string());
+        }
+
         if let Some(desc) = meta.description.as_ref() {
             if !desc.is_empty() {
                 let desc = desc.trim();
@@ -771,11 +959,26 @@ pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
             info_plain_lines.push(row4_s);
         }
 
+        for (i, vol) in volumes.iter().enumerate() {
+            let vol_s = format!(
+                "vol:{} {} ({}-{}，{} 章)",
+                i + 1,
+                truncate(&vol.title, 40),
+                vol.start,
+                vol.end,
+                vol.chapter_count()
+            );
+            meta_lines.push(Line::from(vol_s.clone()));
+            info_plain_lines.push(vol_s);
+        }
+
+        // 有书籍主色调时用于弹窗边框与书名，否则沿用默认配色
+        let accent = accent_color(meta.cover_primary_color.as_deref());
         let mut info_lines = Vec::new();
         info_lines.push(Line::from(Span::styled(
             title_line.clone(),
             Style::default()
-                .fg(Color::LightCyan)
+                .fg(accent.unwrap_or(Color::LightCyan))
                 .add_modifier(Modifier::BOLD),
         )));
         info_lines.extend(meta_lines);
@@ -796,13 +999,20 @@ pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
         } else {
             Style::default()
         };
+        let range_title = match pending {
+            Some(_) if !app.preview_picked.is_empty() => format!(
+                "下载范围 · 已在目录勾选 {} 章（优先于范围输入，Ctrl+L 修改）",
+                app.preview_picked.len()
+            ),
+            Some(p) => format!(
+                "下载范围 (空=全部，last:N=最新N章，vol:N=第N卷){}",
+                range_estimate_text(p, &app.preview_range)
+            ),
+            None => "下载范围 (空=全部，last:N=最新N章，vol:N=第N卷)".to_string(),
+        };
         let range_line = Paragraph::new(format!("> {}", app.preview_range))
             .style(range_style)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("下载范围 (空=全部)"),
-            );
+            .block(Block::default().borders(Borders::ALL).title(range_title));
 
         let buttons = ["确定", "取消"];
         let button_items: Vec<ListItem> = buttons.iter().map(|b| ListItem::new(*b)).collect();
@@ -812,7 +1022,11 @@ pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
             Style::default()
         };
         let button_list = List::new(button_items)
-            .block(Block::default().borders(Borders::ALL).title("操作"))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("操作 (Ctrl+T 试读第 1 章, Ctrl+L 章节目录)"),
+            )
             .highlight_style(button_style.add_modifier(Modifier::BOLD))
             .highlight_symbol(">> ");
 
@@ -857,13 +1071,16 @@ pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
         app.preview_modal_scroll = app.preview_modal_scroll.min(app.preview_modal_scroll_max);
 
         frame.render_widget(Clear, modal);
-        frame.render_widget(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("预览与下载 (↑↓/滚轮)")
-                .title_alignment(Alignment::Center),
-            modal,
-        );
+        let mut modal_block = Block::default()
+            .borders(Borders::ALL)
+            .title("预览与下载 (↑↓/滚轮)")
+            .title_alignment(Alignment::Center);
+        if let Some(accent) = accent {
+            modal_block = modal_block
+                .border_style(Style::default().fg(accent))
+                .title_style(Style::default().fg(accent).add_modifier(Modifier::BOLD));
+        }
+        frame.render_widget(modal_block, modal);
 
         let info_para = Paragraph::new(info_lines)
             .wrap(Wrap { trim: true })
@@ -883,9 +1100,106 @@ pub(super) fn draw_preview(frame: &mut ratatui::Frame, app: &mut App) {
             range: chunks[1],
             buttons: chunks[2],
         });
+        if app.preview_catalog_open {
+            super::catalog::draw_catalog(frame, app);
+        }
     }
 }
 
+/// 随范围输入实时刷新的章数/字数估算，追加在范围输入框标题后。
+fn range_estimate_text(pending: &PendingDownload, input: &str) -> String {
+    let total = pending.plan.chapters.len();
+    let range = match parse_range_input(input, total, &pending.volumes) {
+        Ok(r) => r,
+        Err(_) => return " · 范围无效".to_string(),
+    };
+    let count = range.map(|r| r.end + 1 - r.start).unwrap_or(total);
+    match estimate_range_words(pending.plan.meta.word_count, &pending.chapter_words, range) {
+        Some((words, true)) => format!(" · 选中 {} 章，共 {}", count, format_word_count(words)),
+        Some((words, false)) => format!(" · 选中 {} 章，约 {}", count, format_word_count(words)),
+        None => format!(" · 选中 {} 章", count),
+    }
+}
+
+/// 估算所选范围的字数，返回 (字数, 是否精确)。
+///
+/// 逐章字数齐全时直接求和；否则缺失章节按全书字数/章数（或已知章节均值）等比估算。
+fn estimate_range_words(
+    total_words: Option<usize>,
+    chapter_words: &[Option<usize>],
+    range: Option<ChapterRange>,
+) -> Option<(usize, bool)> {
+    let len = chapter_words.len();
+    if len == 0 {
+        return None;
+    }
+    let (start, end) = range
+        .map(|r| (r.start.saturating_sub(1), r.end.min(len)))
+        .unwrap_or((0, len));
+    let selected = chapter_words.get(start..end)?;
+
+    let known: Vec<usize> = chapter_words.iter().flatten().copied().collect();
+    let selected_known: usize = selected.iter().flatten().sum();
+    let missing = selected.iter().filter(|w| w.is_none()).count();
+    if missing == 0 {
+        return Some((selected_known, true));
+    }
+
+    let avg = match total_words {
+        Some(words) if words > 0 => words / len,
+        _ if !known.is_empty() => known.iter().sum::<usize>() / known.len(),
+        _ => return None,
+    };
+    Some((selected_known + missing * avg, false))
+}
+
+/// 单章本地下载状态。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ChapterState {
+    Done,
+    Failed,
+    Missing,
+}
+
+/// 将章节状态压缩为宽度不超过 `width` 的色块条；章节多于宽度时每块聚合若干章。
+fn chapter_status_bar(states: &[ChapterState], width: usize) -> (Line<'static>, String) {
+    let cells = states.len().min(width.max(1));
+    let mut spans = Vec::with_capacity(cells);
+    let mut plain = String::with_capacity(cells * 3);
+    for i in 0..cells {
+        let start = i * states.len() / cells;
+        let end = ((i + 1) * states.len() / cells).max(start + 1);
+        let chunk = &states[start..end];
+        let done = chunk.iter().filter(|s| **s == ChapterState::Done).count();
+        let failed = chunk.contains(&ChapterState::Failed);
+        let (ch, color) = if failed {
+            ("█", Color::Red)
+        } else if done == chunk.len() {
+            ("█", Color::Green)
+        } else if done > 0 {
+            ("▄", Color::Yellow)
+        } else {
+            ("░", Color::DarkGray)
+        };
+        plain.push_str(ch);
+        spans.push(Span::styled(ch, Style::default().fg(color)));
+    }
+    (Line::from(spans), plain)
+}
+
+fn chapter_status_legend() -> Line<'static> {
+    Line::from(vec![
+        Span::styled("█", Style::default().fg(Color::Green)),
+        Span::raw("已下载 "),
+        Span::styled("▄", Style::default().fg(Color::Yellow)),
+        Span::raw("部分 "),
+        Span::styled("█", Style::default().fg(Color::Red)),
+        Span::raw("失败 "),
+        Span::styled("░", Style::default().fg(Color::DarkGray)),
+        Span::raw("未下载"),
+    ])
+}
+
 pub(super) fn apply_preview_ready(app: &mut App, pending: PendingDownload) {
     let title = pending
         .plan
@@ -900,16 +1214,56 @@ pub(super) fn apply_preview_ready(app: &mut App, pending: PendingDownload) {
     // This keeps home preview info complete without extra API calls.
     upsert_result_detail_from_plan(app, &pending.plan.book_id, &pending.plan.meta);
 
+    app.catalog_mismatch_confirmed = false;
+    let catalog_warning = pending.plan.catalog_warning();
+    if let Some(warning) = catalog_warning.as_deref() {
+        warn!(target: "ui", book_id = %pending.plan.book_id, "{warning}");
+    }
+    if catalog_warning.is_some() && app.config.block_on_incomplete_catalog {
+        // 需要用户确认，取消自动开始下载，停留在预览页。
+        app.batch_auto_download = false;
+        app.retry_failed_only = false;
+    }
+
+    if std::mem::take(&mut app.batch_auto_download) {
+        app.status = format!("批量下载: 《{}》", title);
+        let range = match app.batch_current.as_ref().map(|b| b.range.trim()) {
+            Some(raw) if !raw.is_empty() => match parse_range_input(raw, total, &pending.volumes) {
+                Ok(range) => range,
+                Err(e) => {
+                    warn!(target: "ui", range = raw, "队列中的下载范围无效，改为全部下载: {e}");
+                    None
+                }
+            },
+            _ => None,
+        };
+        if let Err(e) = start_download_task(app, pending, range, None, DownloadMode::Resume) {
+            app.status = format!("批量下载失败: {e}");
+        }
+        return;
+    }
+
+    if std::mem::take(&mut app.retry_failed_only) {
+        app.status = format!("开始重下《{}》的失败章节", title);
+        if let Err(e) = start_download_task(app, pending, None, None, DownloadMode::FailedOnly) {
+            app.status = format!("重下失败章节失败: {e}");
+        }
+        return;
+    }
+
     app.pending_download = Some(pending);
     app.view = View::Preview;
     app.preview_focus = PreviewFocus::Range;
     app.preview_buttons.select(Some(0));
     app.preview_range.clear();
+    app.preview_picked.clear();
+    app.preview_catalog_open = false;
+    app.preview_catalog_state.select(Some(0));
     app.preview_modal_open = true;
     app.input.clear();
     app.download_progress = Some(ProgressSnapshot {
         group_done: 0,
-        group_total: downloader::dynamic_group_count(total),
+        group_total: downloader::dynamic_group_count(total, app.config.effective_batch_size()),
         saved_chapters: downloaded,
         chapter_total: total,
         save_phase: SavePhase::TextSave,
@@ -923,13 +1277,67 @@ pub(super) fn apply_preview_ready(app: &mut App, pending: PendingDownload) {
         audiobook_generated: 0,
         audiobook_skipped: 0,
         audiobook_failed: 0,
+        top_failure: None,
     });
-    app.status = format!("预览: 《{}》 共 {} 章，已下载 {}", title, total, downloaded);
+    app.status = match catalog_warning {
+        Some(warning) => format!("预览: 《{}》 {}", title, warning),
+        None => format!("预览: 《{}》 共 {} 章，已下载 {}", title, total, downloaded),
+    };
+}
+
+/// 试读所选书的第 1 章，验证当前配置能否拿到正文。
+fn start_trial_read(app: &mut App) {
+    if app.spinner_active || app.download_cancel_flag.is_some() {
+        return;
+    }
+    let Some(pending) = app.pending_download.as_ref() else {
+        return;
+    };
+    let Some(chapter) = pending.plan.chapters.first().cloned() else {
+        app.status = "目录为空，无法试读".to_string();
+        return;
+    };
+    let book_id = pending.plan.book_id.clone();
+    info!(target: "ui", book_id = %book_id, chapter_id = %chapter.id, "试读第 1 章");
+    start_spinner(app, format!("试读: {}", chapter.title));
+    let tx = app.worker_tx.clone();
+    let cfg = app.config.clone();
+    thread::spawn(move || {
+        let result = crate::download::trial::trial_read(&cfg, &book_id, &chapter);
+        let _ = tx.send(WorkerMsg::TrialReadDone(result));
+    });
+}
+
+pub(super) fn apply_trial_read(app: &mut App, res: Result<crate::download::trial::TrialRead>) {
+    stop_spinner(app);
+    match res {
+        Ok(trial) => {
+            app.status = format!(
+                "试读成功: {} ({} 字，来源 {})",
+                trial.title, trial.chars, trial.source
+            );
+            // 预览页的日志框会显示这些行，作为正文片段
+            info!(target: "ui", "试读《{}》来源 {}：", trial.title, trial.source);
+            for line in trial.excerpt.lines().filter(|l| !l.trim().is_empty()) {
+                info!(target: "ui", "  {}", line.trim());
+            }
+        }
+        Err(err) => {
+            app.status = format!("试读失败: {}", err.to_string().lines().next().unwrap_or(""));
+            app.push_message(format!("试读失败: {err}"));
+            warn!(target: "ui", "试读失败: {err}");
+        }
+    }
 }
 
 pub(super) fn apply_preview_error(app: &mut App, err: anyhow::Error) {
+    app.retry_failed_only = false;
+    app.batch_auto_download = false;
     app.status = format!("加载目录失败: {err}");
     app.push_message(format!("加载目录失败: {err}"));
     super::maybe_show_iid_failure(app, err.to_string());
     warn!(target: "ui", "加载目录失败: {err}");
+    if let Err(e) = super::home::continue_batch(app) {
+        app.status = format!("批量下载失败: {e}");
+    }
 }
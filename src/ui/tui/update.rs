@@ -7,6 +7,21 @@ use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState};
 
 use crate::base_system::novel_updates;
 
+/// 本页快捷键说明（`?` / F1 帮助浮层）。
+pub(super) const SHORTCUTS: &[(&str, &str)] = &[
+    ("↑ / ↓", "选择书籍"),
+    ("Enter", "更新选中书籍"),
+    ("n", "切换显示有更新 / 无更新的书籍"),
+    ("t", "按分类 / 标签循环过滤"),
+    ("r", "重下选中书籍的失败章节"),
+    ("g", "重新生成选中书籍的成书"),
+    ("i", "切换选中书籍的忽略更新状态"),
+    ("f", "收藏选中书籍"),
+    ("p", "查看选中书籍的封面与简介"),
+    ("m", "合并重复的书籍目录"),
+    ("q / Esc / b", "返回首页"),
+];
+
 pub(super) fn handle_event_update(app: &mut App, event: Event) -> Result<()> {
     match event {
         Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
@@ -53,16 +68,58 @@ pub(super) fn handle_event_update(app: &mut App, event: Event) -> Result<()> {
                     )?;
                 }
             }
+            KeyCode::Char('m') => merge_duplicate_folders(app)?,
+            KeyCode::Char('g') => {
+                if let Some(entry) = current_update_entry(app) {
+                    super::download::start_regenerate_task(
+                        app,
+                        entry.book_id.clone(),
+                        entry.book_name.clone(),
+                    );
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(entry) = current_update_entry(app) {
+                    let failed = novel_updates::read_failed_chapters(&entry.folder, &entry.book_id);
+                    if failed.is_empty() {
+                        app.status = format!("《{}》没有失败章节", entry.book_name);
+                        return Ok(());
+                    }
+                    app.status = format!(
+                        "重下失败章节: 《{}》共 {} 章",
+                        entry.book_name,
+                        failed.len()
+                    );
+                    app.retry_failed_only = true;
+                    let hint = BookMeta {
+                        book_name: Some(entry.book_name.clone()),
+                        ..BookMeta::default()
+                    };
+                    super::start_preview_task(app, entry.book_id.clone(), hint)?;
+                }
+            }
+            KeyCode::Char('f') => {
+                if let Some(entry) = current_update_entry(app) {
+                    let book = crate::base_system::favorites::FavoriteBook::new(
+                        entry.book_id.clone(),
+                        entry.book_name.clone(),
+                        String::new(),
+                    );
+                    let added =
+                        crate::base_system::favorites::toggle_favorite(&mut app.favorites, book);
+                    app.status = match crate::base_system::favorites::save_favorites(&app.favorites)
+                    {
+                        Err(e) => format!("保存收藏失败: {e}"),
+                        Ok(()) if added => format!("已收藏《{}》", entry.book_name),
+                        Ok(()) => format!("已取消收藏《{}》", entry.book_name),
+                    };
+                }
+            }
             KeyCode::Char('n') => {
                 app.show_no_update = !app.show_no_update;
-                if (app.show_no_update && !app.update_no_updates.is_empty())
-                    || (!app.show_no_update && !app.update_entries.is_empty())
-                {
-                    app.update_state.select(Some(0));
-                } else {
-                    app.update_state.select(None);
-                }
+                reset_update_selection(app);
             }
+            KeyCode::Char('t') => cycle_update_filter(app),
             KeyCode::Up => {
                 select_prev_update(app);
             }
@@ -88,6 +145,41 @@ pub(super) fn handle_event_update(app: &mut App, event: Event) -> Result<()> {
     Ok(())
 }
 
+/// 合并同一 book_id 的多个缓存文件夹，完成后重新扫描。
+fn merge_duplicate_folders(app: &mut App) -> Result<()> {
+    let duplicates = novel_updates::find_duplicate_book_folders(&app.config.save_roots())?;
+    if duplicates.is_empty() {
+        app.status = "没有需要合并的重复文件夹".to_string();
+        return Ok(());
+    }
+    let mut merged = 0usize;
+    for dup in &duplicates {
+        match novel_updates::merge_duplicate_book_folders(&app.config, dup) {
+            Ok(_) => merged += 1,
+            Err(e) => app.push_message(format!("合并《{}》失败: {}", dup.book_name, e)),
+        }
+    }
+    info!(target: "ui", merged, total = duplicates.len(), "合并重复缓存文件夹");
+    show_update_menu(app)?;
+    app.status = format!(
+        "已合并 {merged}/{} 本书的重复文件夹，重新扫描中…",
+        duplicates.len()
+    );
+    Ok(())
+}
+
+/// 扫描完成后附加的重复文件夹提示（仅本地目录扫描，开销很小）。
+pub(super) fn duplicate_folders_hint(config: &Config) -> Option<String> {
+    let dups = novel_updates::find_duplicate_book_folders(&config.save_roots()).ok()?;
+    if dups.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "检测到 {} 本书存在重复缓存文件夹（按 m 合并）",
+        dups.len()
+    ))
+}
+
 fn exit_update_view(app: &mut App) -> Result<()> {
     app.view = View::Home;
     app.status = "返回主菜单".to_string();
@@ -110,11 +202,7 @@ pub(super) fn handle_mouse_update(app: &mut App, me: event::MouseEvent) -> Resul
         match me.kind {
             MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
                 if pos_in(list_area, me.column, me.row) {
-                    let list = if app.show_no_update {
-                        &app.update_no_updates
-                    } else {
-                        &app.update_entries
-                    };
+                    let list = visible_updates(app);
                     if list.is_empty() {
                         return Ok(());
                     }
@@ -132,11 +220,7 @@ pub(super) fn handle_mouse_update(app: &mut App, me: event::MouseEvent) -> Resul
             }
             MouseEventKind::Down(MouseButton::Left) => {
                 if pos_in(list_area, me.column, me.row) {
-                    let list = if app.show_no_update {
-                        &app.update_no_updates
-                    } else {
-                        &app.update_entries
-                    };
+                    let list = visible_updates(app);
                     if let Some(idx) = super::list_index_from_mouse_row(
                         list_area,
                         me.row,
@@ -156,11 +240,7 @@ pub(super) fn handle_mouse_update(app: &mut App, me: event::MouseEvent) -> Resul
             }
             MouseEventKind::Moved => {
                 if pos_in(list_area, me.column, me.row) {
-                    let list = if app.show_no_update {
-                        &app.update_no_updates
-                    } else {
-                        &app.update_entries
-                    };
+                    let list = visible_updates(app);
                     if let Some(idx) = super::list_index_from_mouse_row(
                         list_area,
                         me.row,
@@ -178,11 +258,7 @@ pub(super) fn handle_mouse_update(app: &mut App, me: event::MouseEvent) -> Resul
 }
 
 fn select_next_update(app: &mut App) {
-    let list = if app.show_no_update {
-        &app.update_no_updates
-    } else {
-        &app.update_entries
-    };
+    let list = visible_updates(app);
     if list.is_empty() {
         app.update_state.select(None);
         return;
@@ -196,11 +272,7 @@ fn select_next_update(app: &mut App) {
 }
 
 fn select_prev_update(app: &mut App) {
-    let list = if app.show_no_update {
-        &app.update_no_updates
-    } else {
-        &app.update_entries
-    };
+    let list = visible_updates(app);
     if list.is_empty() {
         app.update_state.select(None);
         return;
@@ -214,13 +286,76 @@ fn select_prev_update(app: &mut App) {
 }
 
 fn current_update_entry(app: &App) -> Option<UpdateEntry> {
+    let list = visible_updates(app);
+    let entry = *list.get(app.update_state.selected()?)?;
+    Some(entry.clone())
+}
+
+/// 当前列表（有更新/无更新）中符合标签过滤的条目。
+fn visible_updates(app: &App) -> Vec<&UpdateEntry> {
     let list = if app.show_no_update {
         &app.update_no_updates
     } else {
         &app.update_entries
     };
-    let idx = app.update_state.selected()?;
-    list.get(idx).cloned()
+    list.iter()
+        .filter(|e| {
+            app.update_filter.as_deref().is_none_or(|label| {
+                novel_updates::matches_tag_or_category(&e.tags, &e.category, label)
+            })
+        })
+        .collect()
+}
+
+fn reset_update_selection(app: &mut App) {
+    let has_items = !visible_updates(app).is_empty();
+    app.update_state
+        .select(if has_items { Some(0) } else { None });
+}
+
+/// 扫描结果中出现过的分类与标签（分类在前），供过滤循环切换。
+fn update_filter_labels(app: &App) -> Vec<String> {
+    let entries = app.update_entries.iter().chain(&app.update_no_updates);
+    let mut categories = std::collections::BTreeSet::new();
+    let mut tags = std::collections::BTreeSet::new();
+    for e in entries {
+        if !e.category.is_empty() {
+            categories.insert(e.category.clone());
+        }
+        tags.extend(e.tags.iter().cloned());
+    }
+    let tags: Vec<String> = tags
+        .into_iter()
+        .filter(|t| !categories.contains(t))
+        .collect();
+    categories.into_iter().chain(tags).collect()
+}
+
+/// 按“全部 → 各分类/标签 → 全部”循环切换过滤条件。
+fn cycle_update_filter(app: &mut App) {
+    let labels = update_filter_labels(app);
+    if labels.is_empty() {
+        app.update_filter = None;
+        app.status = "本地书籍没有记录分类/标签，无法过滤".to_string();
+        return;
+    }
+    let next = match app.update_filter.as_deref() {
+        None => Some(0),
+        Some(cur) => labels
+            .iter()
+            .position(|l| l == cur)
+            .map(|i| i + 1)
+            .filter(|&i| i < labels.len()),
+    };
+    app.update_filter = next.map(|i| labels[i].clone());
+    reset_update_selection(app);
+    app.status = match &app.update_filter {
+        Some(label) => format!(
+            "过滤：{label}（{} 本，t 切换下一项）",
+            visible_updates(app).len()
+        ),
+        None => "已取消过滤，显示全部书籍".to_string(),
+    };
 }
 
 pub(super) fn show_update_menu(app: &mut App) -> Result<()> {
@@ -229,6 +364,7 @@ pub(super) fn show_update_menu(app: &mut App) -> Result<()> {
     app.update_no_updates.clear();
     app.update_state.select(None);
     app.show_no_update = false;
+    app.update_filter = None;
     app.view = View::Update;
     super::start_spinner(app, "扫描本地小说…");
 
@@ -257,8 +393,7 @@ fn scan_updates<F>(config: &Config, on_progress: F) -> Result<(Vec<UpdateEntry>,
 where
     F: FnMut(novel_updates::NovelUpdateProgress),
 {
-    let save_dir = config.default_save_dir();
-    let scan = novel_updates::scan_novel_updates_with_progress(&save_dir, on_progress)?;
+    let scan = novel_updates::scan_novel_updates_with_progress(&config.save_roots(), on_progress)?;
 
     Ok((
         scan.updates
@@ -303,6 +438,8 @@ fn update_entry_from_row(it: novel_updates::NovelUpdateRow) -> UpdateEntry {
         label,
         _new_count: it.new_count,
         _has_update: it.has_update,
+        tags: it.tags,
+        category: it.category,
     }
 }
 
@@ -329,17 +466,15 @@ pub(super) fn draw_update(frame: &mut ratatui::Frame, app: &mut App) {
                 .fg(Color::Green)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::raw("  |  上下选择，Enter 下载，i 忽略/取消忽略，n 切换无更新，b 或右下角返回"),
+        Span::raw(
+            "  |  上下选择，Enter 下载，i 忽略/取消忽略，r 仅重下失败章节，g 重新生成，n 切换无更新，t 按分类/标签过滤，m 合并重复文件夹，f 收藏，b 或右下角返回",
+        ),
     ]);
     let header =
         Paragraph::new(header_line).block(Block::default().borders(Borders::ALL).title("更新检测"));
     frame.render_widget(header, layout[0]);
 
-    let list = if app.show_no_update {
-        &app.update_no_updates
-    } else {
-        &app.update_entries
-    };
+    let list = visible_updates(app);
     let items: Vec<ListItem> = if list.is_empty() {
         vec![ListItem::new("没有可展示的项目")]
     } else {
@@ -352,11 +487,16 @@ pub(super) fn draw_update(frame: &mut ratatui::Frame, app: &mut App) {
     } else {
         "有更新书籍"
     };
+    let list_title = match &app.update_filter {
+        Some(label) => format!("{list_title} · 过滤: {label}"),
+        None => list_title.to_string(),
+    };
+    let list_len = list.len();
     let list_block = Block::default().borders(Borders::ALL).title(list_title);
     frame.render_widget(list_block.clone(), layout[1]);
     let inner = list_block.inner(layout[1]);
 
-    let need_scrollbar = !list.is_empty() && inner.height > 0 && list.len() > inner.height as usize;
+    let need_scrollbar = list_len > 0 && inner.height > 0 && list_len > inner.height as usize;
     let (list_area, sb_area) = if need_scrollbar && inner.width > 0 {
         let list_w = inner.width.saturating_sub(1).max(1);
         (
@@ -391,8 +531,8 @@ pub(super) fn draw_update(frame: &mut ratatui::Frame, app: &mut App) {
             .update_state
             .selected()
             .unwrap_or(0)
-            .min(list.len().saturating_sub(1));
-        let mut sb_state = ScrollbarState::new(list.len()).position(pos);
+            .min(list_len.saturating_sub(1));
+        let mut sb_state = ScrollbarState::new(list_len).position(pos);
         let sb = Scrollbar::default().orientation(ScrollbarOrientation::VerticalRight);
         frame.render_stateful_widget(sb, sb_area, &mut sb_state);
     }
@@ -32,6 +32,7 @@ pub fn run(
     };
 
     let library_root = config.default_save_dir();
+    let library_roots = config.save_roots();
 
     let auth = password
         .or_else(|| std::env::var("TOMATO_WEB_PASSWORD").ok())
@@ -54,6 +55,7 @@ pub fn run(
         config.clone(),
         config_path,
         library_root,
+        library_roots,
         auth,
     ))
 }
@@ -129,6 +131,7 @@ async fn run_async(
     config: Config,
     config_path: PathBuf,
     library_root: PathBuf,
+    library_roots: Vec<PathBuf>,
     auth: Option<AuthState>,
 ) -> Result<()> {
     let state = AppState {
@@ -137,10 +140,12 @@ async fn run_async(
         config: Arc::new(std::sync::Mutex::new(config)),
         config_path: Arc::new(config_path),
         library_root: Arc::new(library_root),
+        library_roots: Arc::new(library_roots),
         jobs: Arc::new(JobStore::default()),
         self_update: Arc::new(state::SelfUpdateStore::default()),
         library_scan: Arc::new(LibraryScanStore::default()),
         update_scan: Arc::new(state::UpdateScanStore::default()),
+        logs: Arc::new(state::LogHub::default()),
         auth,
         // 最多允许 2 个并发的上游 API 请求（search / preview），
         // 单用户正常使用完全够用，SaaS 滥用场景下无法并发服务多用户。
@@ -148,6 +153,8 @@ async fn run_async(
         api_semaphore: Arc::new(tokio::sync::Semaphore::new(2)),
     };
 
+    state.logs.spawn_pump();
+
     routes::library::spawn_library_scan(
         state.library_root.as_ref().clone(),
         String::new(),
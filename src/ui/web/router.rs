@@ -37,7 +37,19 @@ pub(crate) fn build_router(state: AppState) -> Router {
             "/api/config/full",
             get(routes::auth::get_config_full).post(routes::auth::set_config_full),
         )
+        .route("/api/config/reset", post(routes::auth::reset_config))
         .route("/api/library", get(routes::library::api_library))
+        .route("/library", get(routes::books::library_page))
+        .route("/api/books", get(routes::books::api_books))
+        .route(
+            "/api/books/:book_id",
+            delete(routes::books::api_book_delete),
+        )
+        .route("/api/books/:book_id/toc", get(routes::books::api_book_toc))
+        .route(
+            "/api/books/:book_id/cover",
+            get(routes::books::api_book_cover),
+        )
         .route("/download/*path", get(routes::download::download_file))
         .route("/download-zip/*path", get(routes::download::download_zip))
         .route("/api/search", get(routes::search::api_search))
@@ -69,7 +81,8 @@ pub(crate) fn build_router(state: AppState) -> Router {
             post(routes::jobs::submit_format_choice),
         )
         .route("/api/updates", get(routes::updates::api_updates))
-        .route("/api/history", get(routes::history::api_history));
+        .route("/api/history", get(routes::history::api_history))
+        .route("/logs/stream", get(routes::logs::logs_stream));
 
     protected
         .layer(from_fn_with_state(state.clone(), auth_and_log_mw))
@@ -93,7 +106,10 @@ async fn auth_and_log_mw(
     // If lock mode enabled, require password for any non-asset route,
     // except the login endpoint and landing page.
     if let Some(auth) = &state.auth {
-        let allow = path == "/" || path.starts_with("/assets/") || path == "/api/login";
+        let allow = path == "/"
+            || path == "/library"
+            || path.starts_with("/assets/")
+            || path == "/api/login";
 
         if !allow {
             let provided_header = req
@@ -11,8 +11,11 @@ use serde_json::{Value, json};
 use serde_yaml;
 use tracing::{error, info, warn};
 
-use crate::base_system::config::{generate_yaml_with_comments, write_with_comments};
-use crate::base_system::context::Config;
+use crate::base_system::config::{
+    generate_yaml_with_comments, reset_all, reset_field, write_with_comments,
+};
+use crate::base_system::context::{Config, MAX_BATCH_SIZE, RESET_PRESERVED_FIELDS};
+use crate::base_system::webdav;
 use crate::ui::web::state::AppState;
 use crate::ui::web::state::LoginLimitDecision;
 
@@ -113,7 +116,7 @@ pub(crate) async fn get_config(State(state): State<AppState>) -> Json<WebConfigV
         .unwrap_or_else(|e| e.into_inner())
         .clone();
     Json(WebConfigView {
-        novel_format: cfg.current_output_format_choice().to_string(),
+        novel_format: cfg.current_output_format_choice(),
         bulk_files: cfg.bulk_files,
         enable_audiobook: cfg.enable_audiobook,
         audiobook_format: cfg.audiobook_format,
@@ -170,7 +173,7 @@ pub(crate) async fn set_config(
 
         if touched_output_mode {
             g.normalize_output_format_fields();
-            if g.enable_segment_comments && g.novel_format != "epub" {
+            if g.enable_segment_comments && !g.wants_epub() {
                 g.enable_segment_comments = false;
             }
         }
@@ -255,12 +258,14 @@ pub(crate) async fn set_config_raw(
     Ok(Json(json!({"ok": true})))
 }
 
+/// 完整配置（WebDAV 密码以掩码返回）。
 pub(crate) async fn get_config_full(State(state): State<AppState>) -> Json<Config> {
-    let cfg = state
+    let mut cfg = state
         .config
         .lock()
         .unwrap_or_else(|e| e.into_inner())
         .clone();
+    cfg.webdav_password = webdav::masked_password(&cfg.webdav_password);
     Json(cfg)
 }
 
@@ -268,14 +273,18 @@ pub(crate) async fn set_config_full(
     State(state): State<AppState>,
     Json(mut cfg): Json<Config>,
 ) -> Result<Json<Value>, StatusCode> {
-    normalize_config(&mut cfg);
-    validate_config(&cfg).map_err(|_| StatusCode::BAD_REQUEST)?;
-
     let old_cfg = state
         .config
         .lock()
         .unwrap_or_else(|e| e.into_inner())
         .clone();
+    // 前端回传掩码表示未修改密码
+    if cfg.webdav_password == webdav::PASSWORD_MASK {
+        cfg.webdav_password = old_cfg.webdav_password.clone();
+    }
+    normalize_config(&mut cfg);
+    validate_config(&cfg).map_err(|_| StatusCode::BAD_REQUEST)?;
+
     let path = state.config_path.as_path();
     if let Err(e) = write_with_comments(&cfg, path) {
         let mut g = state.config.lock().unwrap_or_else(|e| e.into_inner());
@@ -290,6 +299,54 @@ pub(crate) async fn set_config_full(
     Ok(Json(json!({"ok": true})))
 }
 
+#[derive(Debug, Deserialize)]
+pub(crate) struct WebConfigResetReq {
+    /// 要恢复的字段；缺省表示全部恢复（保留关键路径与凭据）
+    #[serde(default)]
+    pub(crate) field: Option<String>,
+}
+
+pub(crate) async fn reset_config(
+    State(state): State<AppState>,
+    Json(req): Json<WebConfigResetReq>,
+) -> Result<Json<Value>, StatusCode> {
+    let old_cfg = state
+        .config
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+
+    let mut cfg = match req.field.as_deref().map(str::trim) {
+        Some(field) if !field.is_empty() => {
+            let mut cfg = reset_field(&old_cfg, field).map_err(|_| StatusCode::BAD_REQUEST)?;
+            // 网页里的"小说格式"同时对应这两个开关
+            if field == "novel_format" {
+                for extra in ["bulk_files", "ask_format_after_download"] {
+                    cfg = reset_field(&cfg, extra).map_err(|_| StatusCode::BAD_REQUEST)?;
+                }
+            }
+            cfg
+        }
+        _ => reset_all(&old_cfg, RESET_PRESERVED_FIELDS)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+    };
+    normalize_config(&mut cfg);
+
+    let path = state.config_path.as_path();
+    if let Err(e) = write_with_comments(&cfg, path) {
+        tracing::error!(target: "web_config", path = %path.display(), err = %e, "failed to persist config.yml");
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    info!(target: "web_config", field = ?req.field, "配置已恢复默认");
+
+    let mut g = state.config.lock().unwrap_or_else(|e| e.into_inner());
+    *g = cfg;
+
+    Ok(Json(
+        json!({"ok": true, "preserved": RESET_PRESERVED_FIELDS}),
+    ))
+}
+
 fn normalize_config(cfg: &mut Config) {
     cfg.normalize_output_format_fields();
     cfg.audiobook_format = cfg.audiobook_format.trim().to_ascii_lowercase();
@@ -298,10 +355,12 @@ fn normalize_config(cfg: &mut Config) {
 }
 
 fn validate_config(cfg: &Config) -> Result<(), String> {
-    if cfg.novel_format != "txt" && cfg.novel_format != "epub" && cfg.novel_format != "pdf" {
-        return Err("novel_format must be txt, epub, or pdf".to_string());
+    if crate::base_system::context::parse_output_formats(&cfg.novel_format).is_empty() {
+        return Err(
+            "novel_format must be txt, epub, pdf or a comma list like txt,epub".to_string(),
+        );
     }
-    if cfg.enable_segment_comments && cfg.novel_format != "epub" {
+    if cfg.enable_segment_comments && !cfg.wants_epub() {
         return Err("segment comments require epub".to_string());
     }
     if cfg.audiobook_format != "mp3" && cfg.audiobook_format != "wav" {
@@ -310,6 +369,9 @@ fn validate_config(cfg: &Config) -> Result<(), String> {
     if cfg.max_workers == 0 {
         return Err("max_workers must be > 0".to_string());
     }
+    if !(1..=MAX_BATCH_SIZE).contains(&cfg.batch_size) {
+        return Err(format!("batch_size must be 1-{}", MAX_BATCH_SIZE));
+    }
     if cfg.request_timeout == 0 {
         return Err("request_timeout must be > 0".to_string());
     }
@@ -319,6 +381,12 @@ fn validate_config(cfg: &Config) -> Result<(), String> {
     if cfg.min_wait_time > cfg.max_wait_time {
         return Err("min_wait_time cannot exceed max_wait_time".to_string());
     }
+    if cfg.wait_jitter_ratio > 100 {
+        return Err("wait_jitter_ratio must be 0-100".to_string());
+    }
+    if cfg.epub_merge_max_chapters == 0 {
+        return Err("epub_merge_max_chapters must be > 0".to_string());
+    }
     if cfg.audiobook_concurrency == 0 {
         return Err("audiobook_concurrency must be > 0".to_string());
     }
@@ -328,15 +396,60 @@ fn validate_config(cfg: &Config) -> Result<(), String> {
     if cfg.segment_comments_workers == 0 {
         return Err("segment_comments_workers must be > 0".to_string());
     }
+    if cfg.segment_comments_hot_chapters == 0 {
+        return Err("segment_comments_hot_chapters must be > 0".to_string());
+    }
     if cfg.media_download_workers == 0 {
         return Err("media_download_workers must be > 0".to_string());
     }
+    cfg.validate_media_domain_filters()?;
+    cfg.validate_bulk_name_template()?;
+    cfg.validate_skip_title_patterns()?;
     if cfg.jpeg_quality > 100 {
         return Err("jpeg_quality must be 0-100".to_string());
     }
+    if cfg.chapter_length_alert_ratio > 100 {
+        return Err("chapter_length_alert_ratio must be 0-100".to_string());
+    }
+    let webdav_url = cfg.webdav_url.trim();
+    if !webdav_url.is_empty()
+        && !webdav_url.starts_with("http://")
+        && !webdav_url.starts_with("https://")
+    {
+        return Err("webdav_url must start with http:// or https://".to_string());
+    }
     if cfg.first_line_indent_em < 0.0 {
         return Err("first_line_indent_em must be >= 0".to_string());
     }
+    match cfg.epub_theme.as_str() {
+        "default" | "sepia" | "night" | "paper" => {}
+        _ => return Err("epub_theme must be default/sepia/night/paper".to_string()),
+    }
+    match cfg.segment_comments_style.as_str() {
+        "chapter_end" | "inline_footnote" | "after_paragraph" => {}
+        _ => {
+            return Err(
+                "segment_comments_style must be chapter_end/inline_footnote/after_paragraph"
+                    .to_string(),
+            );
+        }
+    }
+    match cfg.segment_comments_export_mode.as_str() {
+        "embed" | "separate" | "both" => {}
+        _ => return Err("segment_comments_export_mode must be embed/separate/both".to_string()),
+    }
+    match cfg.timeout_retry_strategy.as_str() {
+        "conservative" | "balanced" | "aggressive" => {}
+        _ => {
+            return Err(
+                "timeout_retry_strategy must be conservative/balanced/aggressive".to_string(),
+            );
+        }
+    }
+    match cfg.txt_split_mode.as_str() {
+        "single" | "per-chapter" | "per-volume" => {}
+        _ => return Err("txt_split_mode must be single/per-chapter/per-volume".to_string()),
+    }
     match cfg.preferred_book_name_field.as_str() {
         "" | "book_name" | "original_book_name" | "book_short_name" | "ask_after_download" => {}
         _ => {
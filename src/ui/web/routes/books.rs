@@ -0,0 +1,297 @@
+//! 书库页：按书列出 `library_roots`（默认保存目录与规则目录）下已下载的小说，支持目录预览、下载成书与删除。
+//!
+//! 书籍信息来自各书缓存目录的 status.json（与更新扫描共用读取逻辑），不访问网络；
+//! 成书文件按导出时的命名规则（`<书名>.<格式>`）在保存目录中查找；TXT 与散装目录
+//! 以书籍信息里的 `book_id=` 校验归属，同名的其它书的成书不会被列出或删除。
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path as FsPath, PathBuf};
+
+use axum::Json;
+use axum::body::Body;
+use axum::extract::{Path, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{Redirect, Response};
+use serde_json::{Value, json};
+use tracing::{info, warn};
+
+use crate::base_system::book_paths::find_existing_cover_file;
+use crate::base_system::context::safe_fs_name;
+use crate::base_system::novel_updates::{self, LocalBookEntry};
+use crate::ui::web::state::{AppState, JobState};
+
+/// 成书可能的格式；`txt` 目录为散装模式输出。
+const OUTPUT_FORMATS: [&str; 3] = ["epub", "txt", "pdf"];
+
+/// 散装 TXT 目录中的书籍信息文件（含 `book_id=`）。
+const BULK_INFO_FILE: &str = "0000_书籍信息.txt";
+
+/// 校验归属时只读取 TXT 开头的书籍信息块。
+const OWNER_PROBE_BYTES: usize = 4096;
+
+/// `/library` 直达书库页。
+pub(crate) async fn library_page() -> Redirect {
+    Redirect::to("/#books")
+}
+
+pub(crate) async fn api_books(State(state): State<AppState>) -> Result<Json<Value>, StatusCode> {
+    let root = state.library_root.as_ref().clone();
+    let roots = state.library_roots.clone();
+    let books = tokio::task::spawn_blocking(move || {
+        novel_updates::list_local_books(&roots).map(|books| {
+            books
+                .iter()
+                .map(|b| book_json(&roots, b))
+                .collect::<Vec<_>>()
+        })
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .map_err(|e| {
+        warn!(target: "web", error = %e, "扫描书库失败");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(json!({
+        "root": root.to_string_lossy(),
+        "items": books,
+    })))
+}
+
+pub(crate) async fn api_book_toc(
+    State(state): State<AppState>,
+    Path(book_id): Path<String>,
+) -> Result<Json<Value>, StatusCode> {
+    let roots = state.library_roots.clone();
+    let (book, toc) = tokio::task::spawn_blocking(move || {
+        let book = find_book(&roots, &book_id)?;
+        let toc = novel_updates::read_status_toc(&book.folder, &book.book_id);
+        Ok::<_, StatusCode>((book, toc))
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)??;
+
+    let chapters: Vec<Value> = toc
+        .into_iter()
+        .map(|(id, title, ok)| json!({ "id": id, "title": title, "ok": ok }))
+        .collect();
+    Ok(Json(json!({
+        "book_id": book.book_id,
+        "book_name": book.book_name,
+        "chapters": chapters,
+    })))
+}
+
+pub(crate) async fn api_book_cover(
+    State(state): State<AppState>,
+    Path(book_id): Path<String>,
+) -> Result<Response, StatusCode> {
+    let roots = state.library_roots.clone();
+    let (path, bytes) = tokio::task::spawn_blocking(move || {
+        let book = find_book(&roots, &book_id)?;
+        let path = find_existing_cover_file(&book.folder, Some(book.book_name.as_str()))
+            .ok_or(StatusCode::NOT_FOUND)?;
+        let bytes = std::fs::read(&path).map_err(|_| StatusCode::NOT_FOUND)?;
+        Ok::<_, StatusCode>((path, bytes))
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)??;
+
+    let mime = match path
+        .extension()
+        .and_then(|s| s.to_str())
+        .map(|s| s.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("png") => "image/png",
+        Some("webp") => "image/webp",
+        _ => "image/jpeg",
+    };
+    let mut resp = Response::new(Body::from(bytes));
+    resp.headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(mime));
+    resp.headers_mut()
+        .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    Ok(resp)
+}
+
+/// 删除一本书：缓存目录（含 status.json、封面、段评等）与保存目录中的成书文件。
+///
+/// 同一根目录下还有其它书与本书同名时，无法确认归属的成书（EPUB/PDF）保留不删，列入 `skipped`。
+pub(crate) async fn api_book_delete(
+    State(state): State<AppState>,
+    Path(book_id): Path<String>,
+) -> Result<Json<Value>, StatusCode> {
+    let busy = state.jobs.list().iter().any(|job| {
+        job.book_id == book_id && matches!(job.state, JobState::Queued | JobState::Running)
+    });
+    if busy {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let roots = state.library_roots.clone();
+    let (book, removed, skipped) = tokio::task::spawn_blocking(move || {
+        let books = novel_updates::list_local_books(&roots)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let book = find_book_in(&books, &book_id)?;
+        let name_shared = books.iter().any(|other| {
+            other.book_id != book.book_id
+                && book_root(other) == book_root(&book)
+                && output_base_name(&other.book_name) == output_base_name(&book.book_name)
+        });
+        let mut removed = Vec::new();
+        let mut skipped = Vec::new();
+        for (_, path) in book_output_files(book_root(&book), &book) {
+            if name_shared && output_owner_matches(&path, &book.book_id) != Some(true) {
+                skipped.push(path.display().to_string());
+                continue;
+            }
+            let result = if path.is_dir() {
+                std::fs::remove_dir_all(&path)
+            } else {
+                std::fs::remove_file(&path)
+            };
+            match result {
+                Ok(()) => removed.push(path.display().to_string()),
+                Err(e) => {
+                    warn!(target: "web", path = %path.display(), error = %e, "删除成书文件失败")
+                }
+            }
+        }
+        std::fs::remove_dir_all(&book.folder).map_err(|e| {
+            warn!(target: "web", path = %book.folder.display(), error = %e, "删除书籍缓存目录失败");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        removed.push(book.folder.display().to_string());
+        Ok::<_, StatusCode>((book, removed, skipped))
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)??;
+
+    info!(target: "web", book_id = %book.book_id, book_name = %book.book_name, "已从书库删除");
+    Ok(Json(json!({
+        "book_id": book.book_id,
+        "removed": removed,
+        "skipped": skipped,
+    })))
+}
+
+fn find_book(roots: &[PathBuf], book_id: &str) -> Result<LocalBookEntry, StatusCode> {
+    let books =
+        novel_updates::list_local_books(roots).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    find_book_in(&books, book_id)
+}
+
+fn find_book_in(books: &[LocalBookEntry], book_id: &str) -> Result<LocalBookEntry, StatusCode> {
+    let book_id = book_id.trim();
+    if book_id.is_empty() || !book_id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    books
+        .iter()
+        .find(|b| b.book_id == book_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// 书籍所在的保存根目录（缓存目录的上一级）；成书与缓存目录同处一个根目录。
+fn book_root(book: &LocalBookEntry) -> &FsPath {
+    book.folder.parent().unwrap_or(&book.folder)
+}
+
+/// 成书文件名（不含扩展名），命名规则同导出。
+fn output_base_name(book_name: &str) -> String {
+    let name = if book_name.is_empty() {
+        "book"
+    } else {
+        book_name
+    };
+    safe_fs_name(name, "_", 120)
+}
+
+/// 成书中记录的 `book_id=` 是否为该书：TXT 读开头的书籍信息，散装目录读 `0000_书籍信息.txt`；
+/// EPUB/PDF 等无法判断时返回 None。
+fn output_owner_matches(path: &FsPath, book_id: &str) -> Option<bool> {
+    let info_file = if path.is_dir() {
+        path.join(BULK_INFO_FILE)
+    } else if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("txt"))
+    {
+        path.to_path_buf()
+    } else {
+        return None;
+    };
+    let mut head = Vec::with_capacity(OWNER_PROBE_BYTES);
+    File::open(&info_file)
+        .ok()?
+        .take(OWNER_PROBE_BYTES as u64)
+        .read_to_end(&mut head)
+        .ok()?;
+    let head = String::from_utf8_lossy(&head);
+    let recorded = head
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("book_id="))?;
+    Some(recorded.trim() == book_id)
+}
+
+/// 保存目录中属于该书的成书文件：(格式, 路径)。
+///
+/// 记录了其它 `book_id` 的 TXT 不计入；散装目录必须带本书的书籍信息才计入，避免误删同名目录。
+fn book_output_files(root: &FsPath, book: &LocalBookEntry) -> Vec<(&'static str, PathBuf)> {
+    let safe_book = output_base_name(&book.book_name);
+    let mut files: Vec<(&'static str, PathBuf)> = OUTPUT_FORMATS
+        .iter()
+        .map(|fmt| (*fmt, root.join(format!("{safe_book}.{fmt}"))))
+        .filter(|(_, path)| path.is_file())
+        .filter(|(_, path)| output_owner_matches(path, &book.book_id) != Some(false))
+        .collect();
+    let bulk_dir = root.join(&safe_book);
+    if bulk_dir.is_dir() && output_owner_matches(&bulk_dir, &book.book_id) == Some(true) {
+        files.push(("txt", bulk_dir));
+    }
+    files
+}
+
+fn book_json(roots: &[PathBuf], book: &LocalBookEntry) -> Value {
+    let root = book_root(book);
+    // 下载链接按根目录下标区分；不在列表中的（理论上不会出现）退回默认目录
+    let root_idx = roots.iter().position(|r| r == root).unwrap_or(0);
+    let outputs: Vec<Value> = book_output_files(root, book)
+        .into_iter()
+        .map(|(format, path)| {
+            let rel_path = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let is_dir = path.is_dir();
+            let size = if is_dir {
+                0
+            } else {
+                std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+            };
+            json!({
+                "format": format,
+                "kind": if is_dir { "dir" } else { "file" },
+                "rel_path": rel_path,
+                "root": root_idx,
+                "size": size,
+            })
+        })
+        .collect();
+    let has_cover = find_existing_cover_file(&book.folder, Some(book.book_name.as_str())).is_some();
+
+    json!({
+        "book_id": book.book_id,
+        "book_name": book.book_name,
+        "author": book.author,
+        "chapter_total": book.local_total,
+        "chapter_ok": book.local_total.saturating_sub(book.local_failed),
+        "chapter_failed": book.local_failed,
+        "is_ignored": book.is_ignored,
+        "has_cover": has_cover,
+        "outputs": outputs,
+    })
+}
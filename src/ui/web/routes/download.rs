@@ -3,15 +3,34 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use axum::body::Body;
-use axum::extract::{Path as AxumPath, State};
+use axum::extract::{Path as AxumPath, Query, State};
 use axum::http::{StatusCode, header};
 use axum::response::Response;
 use tokio_util::io::ReaderStream;
 use zip::ZipWriter;
 use zip::write::FileOptions;
 
+use serde::Deserialize;
+
 use crate::ui::web::state::AppState;
 
+#[derive(Debug, Deserialize)]
+pub(crate) struct DownloadQuery {
+    /// `library_roots` 下标；缺省为默认保存目录，书库页按规则分流的书会带上。
+    pub(crate) root: Option<usize>,
+}
+
+fn download_base(state: &AppState, q: &DownloadQuery) -> Result<std::path::PathBuf, StatusCode> {
+    match q.root {
+        None | Some(0) => Ok(state.library_root.as_ref().clone()),
+        Some(idx) => state
+            .library_roots
+            .get(idx)
+            .cloned()
+            .ok_or(StatusCode::NOT_FOUND),
+    }
+}
+
 fn make_content_disposition(filename: &str) -> Option<header::HeaderValue> {
     // RFC 5987 filename* for UTF-8 names, plus ASCII fallback for legacy clients.
     fn is_unreserved(b: u8) -> bool {
@@ -49,9 +68,10 @@ fn make_content_disposition(filename: &str) -> Option<header::HeaderValue> {
 pub(crate) async fn download_file(
     State(state): State<AppState>,
     AxumPath(path): AxumPath<String>,
+    Query(q): Query<DownloadQuery>,
 ) -> Result<Response, StatusCode> {
-    let base = state.library_root.as_ref();
-    let (_target, target_canon) = resolve_target(base, &path)?;
+    let base = download_base(&state, &q)?;
+    let (_target, target_canon) = resolve_target(&base, &path)?;
 
     let meta = std::fs::metadata(&target_canon).map_err(|_| StatusCode::NOT_FOUND)?;
     if !meta.is_file() {
@@ -120,9 +140,10 @@ fn resolve_target(
 pub(crate) async fn download_zip(
     State(state): State<AppState>,
     AxumPath(path): AxumPath<String>,
+    Query(q): Query<DownloadQuery>,
 ) -> Result<Response, StatusCode> {
-    let base = state.library_root.as_ref();
-    let (target, target_canon) = resolve_target(base, &path)?;
+    let base = download_base(&state, &q)?;
+    let (target, target_canon) = resolve_target(&base, &path)?;
 
     let meta = std::fs::metadata(&target_canon).map_err(|_| StatusCode::NOT_FOUND)?;
     if !meta.is_dir() {
@@ -5,6 +5,7 @@ use serde::Deserialize;
 use serde_json::{Value, json};
 
 use crate::base_system::download_history::read_download_history;
+use crate::base_system::download_stats::load_download_stats;
 use crate::ui::web::state::AppState;
 
 #[derive(Debug, Deserialize)]
@@ -25,5 +26,6 @@ pub(crate) async fn api_history(
         "items": items,
         "limit": limit,
         "keyword": q.q,
+        "stats": load_download_stats(),
     })))
 }
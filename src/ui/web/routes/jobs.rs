@@ -114,6 +114,10 @@ pub(crate) async fn create_job(
             plan.meta.book_name.clone(),
             plan.meta.author.clone(),
         );
+        let output_dir = cfg
+            .save_path_rule_root(plan.meta.category.as_deref(), &plan.meta.tags)
+            .unwrap_or_else(|| cfg.default_save_dir());
+        jobs.set_output_dir(handle.id, output_dir.display().to_string());
 
         let id = handle.id;
         let jobs_cb = jobs.clone();
@@ -156,6 +160,7 @@ pub(crate) async fn create_job(
             dl::DownloadFlowOptions {
                 mode: dl::DownloadMode::Resume,
                 range,
+                selection: None,
                 retry_failed: {
                     let mut retried = false;
                     dl::RetryFailed::Decide(Box::new(move |_pending_len| {
@@ -169,6 +174,7 @@ pub(crate) async fn create_job(
                 stage_callback: None,
                 book_name_asker: Some(Box::new(book_name_asker)),
                 format_asker: Some(Box::new(format_asker)),
+                modified_asker: None,
             },
             Some(Box::new(move |snap| jobs_cb.set_progress(id, snap))),
             Some(handle.cancel.clone()),
@@ -0,0 +1,112 @@
+//! 实时日志推送（SSE）。
+//!
+//! `GET /logs/stream?target=download,book_manager&job=3`：先补发最近保留的日志，再持续推送新行。
+//! 浏览器断线重连时会带上 `Last-Event-ID`，只补发该 id 之后的行，避免重复。
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use serde::Deserialize;
+use tokio::sync::mpsc::Receiver;
+
+use crate::ui::web::state::{AppState, LogLine};
+
+/// 未指定 target 时默认屏蔽的访问日志（每个请求一条，且 SSE 连接本身也会产生）。
+const DEFAULT_EXCLUDED_TARGETS: &[&str] = &["web_access"];
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct LogStreamQuery {
+    /// 逗号分隔的 target 白名单
+    pub(crate) target: Option<String>,
+    /// 只看某个下载任务（按书籍 ID / 书名匹配日志内容）
+    pub(crate) job: Option<u64>,
+    /// 只补发该 id 之后的日志（`Last-Event-ID` 优先）
+    pub(crate) after: Option<u64>,
+}
+
+struct LogFilter {
+    targets: Vec<String>,
+    keywords: Vec<String>,
+}
+
+impl LogFilter {
+    fn matches(&self, line: &LogLine) -> bool {
+        let target_ok = if self.targets.is_empty() {
+            !DEFAULT_EXCLUDED_TARGETS.contains(&line.target.as_str())
+        } else {
+            self.targets.contains(&line.target)
+        };
+        target_ok
+            && (self.keywords.is_empty() || self.keywords.iter().any(|k| line.text.contains(k)))
+    }
+}
+
+struct LogStream {
+    pending: VecDeque<LogLine>,
+    rx: Receiver<LogLine>,
+    filter: LogFilter,
+}
+
+impl futures_core::Stream for LogStream {
+    type Item = Result<Event, axum::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let line = match this.pending.pop_front() {
+                Some(line) => line,
+                None => match this.rx.poll_recv(cx) {
+                    Poll::Ready(Some(line)) => line,
+                    Poll::Ready(None) => return Poll::Ready(None),
+                    Poll::Pending => return Poll::Pending,
+                },
+            };
+            if this.filter.matches(&line) {
+                let event = Event::default().id(line.id.to_string()).json_data(&line);
+                return Poll::Ready(Some(event));
+            }
+        }
+    }
+}
+
+pub(crate) async fn logs_stream(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(q): Query<LogStreamQuery>,
+) -> Sse<impl futures_core::Stream<Item = Result<Event, axum::Error>>> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+
+    let targets = q
+        .target
+        .as_deref()
+        .unwrap_or("")
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let keywords = q
+        .job
+        .and_then(|id| state.jobs.get(id))
+        .map(|job| {
+            std::iter::once(job.book_id)
+                .chain(job.title)
+                .filter(|k| !k.trim().is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let (replay, rx) = state.logs.subscribe(last_event_id.or(q.after));
+    let stream = LogStream {
+        pending: replay.into(),
+        rx,
+        filter: LogFilter { targets, keywords },
+    };
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
@@ -1,10 +1,12 @@
 pub(crate) mod app_update;
 pub(crate) mod auth;
+pub(crate) mod books;
 pub(crate) mod download;
 pub(crate) mod history;
 pub(crate) mod index;
 pub(crate) mod jobs;
 pub(crate) mod library;
+pub(crate) mod logs;
 pub(crate) mod preview;
 pub(crate) mod search;
 pub(crate) mod status;
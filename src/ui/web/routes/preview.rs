@@ -8,7 +8,7 @@ use std::path::{Path as FsPath, PathBuf};
 use tracing::{debug, info, warn};
 
 use crate::base_system::book_id::resolve_book_id;
-use crate::base_system::book_paths::book_folder_path;
+use crate::base_system::book_paths::{self, book_folder_path};
 use crate::base_system::context::safe_fs_name;
 use crate::base_system::file_cleaner::is_empty_dir;
 use crate::book_parser::image_utils::ensure_cached_image;
@@ -227,6 +227,9 @@ pub(crate) async fn api_preview(
         "category": meta.category,
         "first_chapter_title": meta.first_chapter_title,
         "last_chapter_title": meta.last_chapter_title,
+        "catalog_warning": plan.catalog_warning(),
+        "volumes": plan.volumes(),
+        "catalog_confirm_required": cfg.block_on_incomplete_catalog,
     })))
 }
 
@@ -367,8 +370,14 @@ pub(crate) async fn api_preview_cleanup(
     // 在 blocking 线程执行文件系统清理
     let cfg_for_cleanup = cfg.clone();
     let bid_for_cleanup = book_id.clone();
+    let root = cfg.save_path_rule_root(plan.meta.category.as_deref(), &plan.meta.tags);
     tokio::task::spawn_blocking(move || {
-        cleanup_preview_cover_dir(&cfg_for_cleanup, &bid_for_cleanup, &book_name);
+        cleanup_preview_cover_dir(
+            &cfg_for_cleanup,
+            &bid_for_cleanup,
+            &book_name,
+            root.as_deref(),
+        );
     })
     .await
     .ok();
@@ -381,8 +390,9 @@ fn cleanup_preview_cover_dir(
     cfg: &crate::base_system::context::Config,
     book_id: &str,
     book_name: &str,
+    root: Option<&std::path::Path>,
 ) {
-    let dir = book_folder_path(cfg, book_id, Some(book_name));
+    let dir = book_folder_path(cfg, book_id, Some(book_name), root);
     if !dir.exists() {
         return;
     }
@@ -424,7 +434,9 @@ fn cleanup_preview_cover_dir(
         if !is_img {
             return false;
         }
-        stem == safe_name || stem.eq_ignore_ascii_case("cover")
+        stem == safe_name
+            || stem.eq_ignore_ascii_case(book_paths::COVER_FILE_STEM)
+            || stem.eq_ignore_ascii_case(book_paths::HD_COVER_FILE_STEM)
     };
 
     // 如果存在非封面文件，中止清理
@@ -7,6 +7,8 @@ use serde_json::{Value, json};
 #[cfg(feature = "official-api")]
 use tomato_novel_official_api::SearchClient;
 
+#[cfg(feature = "official-api")]
+use crate::base_system::search_query::SearchQuery as ParsedQuery;
 use crate::ui::web::state::AppState;
 
 #[derive(Debug, Deserialize)]
@@ -43,16 +45,22 @@ pub(crate) async fn api_search(
                 api_error(StatusCode::SERVICE_UNAVAILABLE, "上游 API 并发限制已关闭")
             })?;
 
+        let query = ParsedQuery::parse(&keyword);
+        let search_keyword = query.keyword.clone();
         let resp = tokio::task::spawn_blocking(move || {
             let client = SearchClient::new()?;
-            client.search_books(&keyword)
+            client.search_books(&search_keyword)
         })
         .await
         .map_err(|_| api_error(StatusCode::INTERNAL_SERVER_ERROR, "搜索任务执行失败"))?
         .map_err(|err| api_error(StatusCode::BAD_GATEWAY, format!("搜索失败: {err}")))?;
 
-        let items: Vec<Value> = resp
-            .books
+        let books = query.apply(
+            resp.books,
+            |b| b.title.as_deref().unwrap_or(""),
+            |b| b.author.as_deref().unwrap_or(""),
+        );
+        let items: Vec<Value> = books
             .into_iter()
             .map(|b| {
                 json!({
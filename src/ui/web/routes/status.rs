@@ -15,6 +15,7 @@ pub(crate) async fn api_status(State(state): State<AppState>) -> Json<Value> {
         "docker_build": cfg!(feature = "docker"),
         "prewarm_in_progress": crate::prewarm_state::is_prewarm_in_progress(),
         "prewarm_error": crate::prewarm_state::prewarm_error(),
+        "iid_health": crate::prewarm_state::iid_health(),
         "save_dir": state.library_root.to_string_lossy(),
         "bind_addr": bind_addr,
         "bind_addrs": bind_addrs,
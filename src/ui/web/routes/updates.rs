@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::PathBuf;
 use std::thread;
 
 use crate::base_system::novel_updates;
@@ -26,13 +26,13 @@ pub(crate) async fn api_updates(
         .lock()
         .unwrap_or_else(|e| e.into_inner())
         .clone();
-    let save_dir = cfg.default_save_dir();
-    let save_dir_display = save_dir.display().to_string();
+    let save_dir_display = cfg.default_save_dir().display().to_string();
+    let roots = cfg.save_roots();
 
     if q.start.unwrap_or(true) && state.update_scan.try_start(save_dir_display.clone()) {
         let store = state.update_scan.clone();
         thread::spawn(move || {
-            if let Err(err) = scan_updates(&save_dir, store.clone()) {
+            if let Err(err) = scan_updates(&roots, store.clone()) {
                 store.finish_failed(err.to_string());
             }
         });
@@ -52,8 +52,8 @@ pub(crate) async fn api_updates(
     })))
 }
 
-fn scan_updates(save_dir: &Path, store: std::sync::Arc<UpdateScanStore>) -> Result<()> {
-    let scan = novel_updates::scan_novel_updates_with_progress(save_dir, |progress| {
+fn scan_updates(roots: &[PathBuf], store: std::sync::Arc<UpdateScanStore>) -> Result<()> {
+    let scan = novel_updates::scan_novel_updates_with_progress(roots, |progress| {
         store.push_progress(
             row_from_update(progress.row),
             progress.scanned,
@@ -61,8 +61,12 @@ fn scan_updates(save_dir: &Path, store: std::sync::Arc<UpdateScanStore>) -> Resu
         );
     })?;
 
+    let save_dir = roots
+        .first()
+        .map(|r| r.display().to_string())
+        .unwrap_or_default();
     store.finish(
-        save_dir.display().to_string(),
+        save_dir,
         scan.updates.into_iter().map(row_from_update).collect(),
         scan.no_updates.into_iter().map(row_from_update).collect(),
     );
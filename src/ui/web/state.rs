@@ -8,6 +8,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::Serialize;
 use sha2::{Digest, Sha256};
+use tokio::sync::mpsc::error::TrySendError;
 use uuid::Uuid;
 
 use crate::base_system::context::Config;
@@ -28,10 +29,13 @@ pub(crate) struct AppState {
     pub(crate) config: Arc<Mutex<Config>>, // allow runtime updates via Web UI
     pub(crate) config_path: Arc<PathBuf>,
     pub(crate) library_root: Arc<PathBuf>,
+    /// 书库扫描的全部根目录（`library_root` 在前，其后为 `save_path_rules` 规则目录）。
+    pub(crate) library_roots: Arc<Vec<PathBuf>>,
     pub(crate) jobs: Arc<JobStore>,
     pub(crate) self_update: Arc<SelfUpdateStore>,
     pub(crate) library_scan: Arc<LibraryScanStore>,
     pub(crate) update_scan: Arc<UpdateScanStore>,
+    pub(crate) logs: Arc<LogHub>,
     pub(crate) auth: Option<AuthState>,
     /// 限制同时访问上游 API（search / preview）的并发数，防止 WebUI 被用作多用户 API 代理。
     /// 仅在启用 official-api feature 时有意义，其他 feature 下置 None。
@@ -374,6 +378,95 @@ impl UpdateScanStore {
     }
 }
 
+/// 推送给浏览器的一条日志。
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct LogLine {
+    pub(crate) id: u64,
+    pub(crate) level: String,
+    pub(crate) target: String,
+    pub(crate) text: String,
+}
+
+/// 保留的最近日志行数，供新连接 / 断线重连补发。
+const LOG_BACKLOG: usize = 500;
+/// 单个订阅者的待发队列上限；浏览器消费过慢时丢弃新行，重连后按 id 补发。
+const LOG_SUBSCRIBER_BUFFER: usize = 256;
+
+/// Web 端日志中转：独占 `take_broadcast_rx` 的广播通道，保留最近日志并转发给 SSE 订阅者。
+#[derive(Debug, Default)]
+pub(crate) struct LogHub {
+    inner: Mutex<LogHubInner>,
+}
+
+#[derive(Debug, Default)]
+struct LogHubInner {
+    next_id: u64,
+    backlog: VecDeque<LogLine>,
+    subscribers: Vec<tokio::sync::mpsc::Sender<LogLine>>,
+}
+
+impl LogHub {
+    /// 启动后台线程持续读取日志广播；日志系统未开启广播时不做任何事。
+    pub(crate) fn spawn_pump(self: &Arc<Self>) {
+        let Some(rx) = crate::base_system::logging::take_broadcast_rx() else {
+            return;
+        };
+        let hub = Arc::clone(self);
+        std::thread::spawn(move || {
+            for raw in rx.iter() {
+                hub.push(&raw);
+            }
+        });
+    }
+
+    pub(crate) fn push(&self, raw: &str) {
+        let text = raw.trim_end_matches(['\r', '\n']);
+        if text.trim().is_empty() {
+            return;
+        }
+        let (level, target) = parse_log_line(text);
+        let mut g = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        g.next_id += 1;
+        let line = LogLine {
+            id: g.next_id,
+            level: level.to_string(),
+            target: target.to_string(),
+            text: text.to_string(),
+        };
+        g.subscribers
+            .retain(|tx| !matches!(tx.try_send(line.clone()), Err(TrySendError::Closed(_))));
+        g.backlog.push_back(line);
+        if g.backlog.len() > LOG_BACKLOG {
+            g.backlog.pop_front();
+        }
+    }
+
+    /// 返回 `after` 之后仍保留的日志（缺省为全部保留行）以及后续实时日志的接收端。
+    pub(crate) fn subscribe(
+        &self,
+        after: Option<u64>,
+    ) -> (Vec<LogLine>, tokio::sync::mpsc::Receiver<LogLine>) {
+        let (tx, rx) = tokio::sync::mpsc::channel(LOG_SUBSCRIBER_BUFFER);
+        let mut g = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let replay = g
+            .backlog
+            .iter()
+            .filter(|l| after.is_none_or(|id| l.id > id))
+            .cloned()
+            .collect();
+        g.subscribers.push(tx);
+        (replay, rx)
+    }
+}
+
+/// 从 fmt 输出（`时间 LEVEL target: 消息`）中取出级别与 target；无法识别时返回空串。
+fn parse_log_line(text: &str) -> (&str, &str) {
+    let mut parts = text.split_whitespace().skip(1);
+    let level = parts.next().unwrap_or("");
+    let target = parts.next().and_then(|t| t.strip_suffix(':')).unwrap_or("");
+    (level, target)
+}
+
 #[derive(Clone)]
 pub(crate) struct AuthState {
     pub(crate) password_sha256: [u8; 32],
@@ -636,6 +729,8 @@ pub(crate) struct JobInfo {
     pub(crate) message: Option<String>,
     pub(crate) book_name_options: Option<Vec<BookNameOption>>,
     pub(crate) format_options: Option<Vec<BookNameOption>>,
+    /// 成书所在目录（服务端完整路径，前端展示供复制）
+    pub(crate) output_dir: Option<String>,
     pub(crate) created_ms: u64,
     pub(crate) updated_ms: u64,
 }
@@ -676,6 +771,7 @@ impl JobStore {
             message: None,
             book_name_options: None,
             format_options: None,
+            output_dir: None,
             created_ms: now,
             updated_ms: now,
         };
@@ -705,6 +801,11 @@ impl JobStore {
         v
     }
 
+    pub(crate) fn get(&self, id: u64) -> Option<JobInfo> {
+        let g = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        g.get(&id).map(|e| e.info.clone())
+    }
+
     pub(crate) fn prune_done_older_than(&self, retention_ms: u64) {
         let cutoff = now_ms().saturating_sub(retention_ms);
         let mut g = self.inner.lock().unwrap_or_else(|e| e.into_inner());
@@ -735,6 +836,12 @@ impl JobStore {
         });
     }
 
+    pub(crate) fn set_output_dir(&self, id: u64, dir: String) {
+        self.update(id, |j| {
+            j.output_dir = Some(dir);
+        });
+    }
+
     pub(crate) fn set_progress(&self, id: u64, snap: ProgressSnapshot) {
         self.update(id, |j| {
             j.progress = Some(snap);
@@ -893,3 +1000,28 @@ fn now_secs() -> u64 {
         .unwrap_or_default()
         .as_secs()
 }
+
+#[cfg(test)]
+mod log_hub_tests {
+    use super::*;
+
+    #[test]
+    fn log_hub_parses_target_and_replays_after_id() {
+        assert_eq!(
+            parse_log_line("2026-01-01T00:00:00.000000Z  WARN download: 封面下载失败 book_id=1"),
+            ("WARN", "download")
+        );
+        assert_eq!(parse_log_line("纯文本"), ("", ""));
+
+        let hub = LogHub::default();
+        hub.push("t INFO download: 一\n");
+        hub.push("t INFO web_access: ok");
+        let (replay, mut rx) = hub.subscribe(Some(1));
+        assert_eq!(replay.len(), 1);
+        assert_eq!(replay[0].target, "web_access");
+
+        hub.push("t ERROR book_manager: 三");
+        let live = rx.try_recv().unwrap();
+        assert_eq!((live.id, live.level.as_str()), (3, "ERROR"));
+    }
+}
@@ -191,7 +191,7 @@ function parseBookId(input) {
   if (!trimmed) return '';
   if (/^[0-9]+$/.test(trimmed)) return trimmed;
 
-  const urlMatch = trimmed.match(/https?:\/\/\S+/i);
+  const urlMatch = trimmed.match(/https?:\/\/[!-~]+/i);
   const target = urlMatch ? urlMatch[0] : trimmed;
 
   const qs = target.match(/(?:^|[?&#])(?:book_id|bookId)=([0-9]+)/i);
@@ -200,15 +200,22 @@ function parseBookId(input) {
   const page = target.match(/\/page\/([0-9]+)/i);
   if (page && page[1]) return page[1];
 
+  // App 分享口令：显式书号，或链接之外的 15~20 位数字串。
+  const shareId = trimmed.match(/(?:书号|书籍\s*ID|book_?id)\s*[:：=#]?\s*([0-9]{6,})/i);
+  if (shareId && shareId[1]) return shareId[1];
+  const longDigits = trimmed.replace(/https?:\/\/[!-~]+/gi, ' ').match(/(?:^|[^0-9])([0-9]{15,20})(?:[^0-9]|$)/);
+  if (longDigits && longDigits[1]) return longDigits[1];
+
   // Short link (e.g. https://changdunovel.com/t/E_HDbOHpMJA/) – return the URL so
   // the server can follow the redirect and extract the book ID.
-  // Restrict to known share-link hosts to prevent forwarding arbitrary URLs.
-  const allowedShortLinkHosts = new Set(['changdunovel.com', 'www.changdunovel.com', 'fanqienovel.com', 'www.fanqienovel.com', 'fqnovel.com', 'www.fqnovel.com']);
+  // Restrict to known share-link hosts (and their subdomains) to prevent forwarding arbitrary URLs.
+  const allowedShortLinkHosts = ['changdunovel.com', 'fanqienovel.com', 'fqnovel.com'];
   try {
     const parsed = new URL(target);
+    const host = parsed.hostname.toLowerCase();
     if (
       (parsed.protocol === 'http:' || parsed.protocol === 'https:') &&
-      allowedShortLinkHosts.has(parsed.hostname.toLowerCase()) &&
+      allowedShortLinkHosts.some(h => host === h || host.endsWith(`.${h}`)) &&
       /^\/t\/[A-Za-z0-9_-]+\/?$/.test(parsed.pathname)
     ) {
       return target;
@@ -220,6 +227,14 @@ function parseBookId(input) {
   return '';
 }
 
+// 短链接解析失败时的回退搜索词：优先《书名》，否则为去掉链接后的文本。
+function shareSearchKeyword(input) {
+  const text = (input ?? '').toString();
+  const title = text.match(/《([^《》]+)》/);
+  if (title && title[1].trim()) return title[1].trim();
+  return text.replace(/https?:\/\/[!-~]+/gi, ' ').replace(/\s+/g, ' ').trim();
+}
+
 function isLikelyHeicUrl(url) {
   const s = (url || '').toString().toLowerCase();
   if (!s) return false;
@@ -427,6 +442,22 @@ let pendingBookNameOptions = [];
 let pendingFormatJobId = null;
 let pendingFormatOptions = [];
 
+function renderIidHealth(health) {
+  const el = document.getElementById('iidHealth');
+  if (!el) return;
+  const h = health || {};
+  const checked = h.checked_at ? ` · ${new Date(h.checked_at * 1000).toLocaleTimeString()} 探测` : '';
+  if (h.state === 'healthy') {
+    el.textContent = `正常${checked}`;
+  } else if (h.state === 'failing') {
+    el.textContent = `异常（连续 ${h.consecutive_failures || 1} 次）${checked}`;
+  } else {
+    el.textContent = '未探测';
+  }
+  el.classList.toggle('error', h.state === 'failing');
+  el.title = h.last_error || '';
+}
+
 async function refreshStatus() {
   const data = await j('/api/status');
   document.getElementById('version').textContent = data.version || '';
@@ -434,6 +465,7 @@ async function refreshStatus() {
   document.getElementById('prewarm').textContent = prewarmError
     ? 'failed'
     : (data.prewarm_in_progress ? 'warming' : 'ready');
+  renderIidHealth(data.iid_health);
   document.getElementById('saveDir').textContent = data.save_dir || '';
   document.getElementById('bind').textContent = data.bind_addr || '';
   document.getElementById('locked').textContent = data.locked ? 'locked' : 'unlocked';
@@ -501,17 +533,65 @@ const FULL_CONFIG_SCHEMA = [
     title: '基础与格式',
     fields: [
       { key: 'save_path', label: '保存路径', type: 'text' },
+      { key: 'save_path_rules', label: '分类保存路径规则', type: 'text', placeholder: '分类=路径;标签=路径，未命中用保存路径' },
       { key: 'novel_format', label: '小说格式', type: 'select', options: [
         { value: 'txt', label: 'txt' },
         { value: 'epub', label: 'epub' },
         { value: 'pdf', label: 'pdf' },
+        { value: 'txt,epub', label: 'txt + epub' },
         { value: 'bulk_txt', label: '散装文件' },
         { value: 'ask_after_download', label: '下载后选择' }
       ] },
       { key: 'first_line_indent_em', label: '首行缩进(em)', type: 'number', parse: 'float', step: '0.1', min: '0' },
       { key: 'auto_clear_dump', label: '自动清理缓存', type: 'bool' },
+      { key: 'auto_clear_images', label: '成书后清理图片缓存', type: 'bool' },
+      { key: 'auto_clear_segment_comments', label: '成书后清理段评缓存', type: 'bool' },
+      { key: 'auto_clear_jsonl', label: '成书后清理中间 jsonl', type: 'bool' },
       { key: 'auto_open_downloaded_files', label: '下载完成后自动打开', type: 'bool' },
+      { key: 'write_output_checksum', label: '写入 SHA-256 校验文件', type: 'bool' },
+      { key: 'download_history_limit', label: '下载历史保留条数(0 不限)', type: 'number', parse: 'int', min: '0' },
+      { key: 'webdav_url', label: 'WebDAV 上传地址（留空不上传）', type: 'text' },
+      { key: 'webdav_username', label: 'WebDAV 用户名', type: 'text' },
+      { key: 'webdav_password', label: 'WebDAV 密码', type: 'password' },
+      { key: 'enable_notifications', label: '完成后系统通知', type: 'bool' },
       { key: 'allow_overwrite_files', label: '允许覆盖已存在文件', type: 'bool' },
+      { key: 'epub_chapter_nav', label: 'EPUB 章节导航', type: 'bool' },
+      { key: 'epub_chapter_header', label: 'EPUB 章节页眉与序号', type: 'bool' },
+      { key: 'epub_include_images', label: 'EPUB 包含图片(关闭为纯文字版)', type: 'bool' },
+      { key: 'epub_merge_short_chapters', label: 'EPUB 合并短章', type: 'bool' },
+      { key: 'epub_short_chapter_chars', label: '短章字数阈值', type: 'number', parse: 'int', min: '0' },
+      { key: 'epub_merge_max_chapters', label: '每文件最多合并章数', type: 'number', parse: 'int', min: '1' },
+      { key: 'txt_chapter_separator', label: 'TXT 章节分隔符', type: 'text' },
+      { key: 'auto_paragraph', label: '智能分段', type: 'bool' },
+      { key: 'strip_duplicate_title_line', label: '去除重复标题行', type: 'bool' },
+      { key: 'trim_blank_paragraphs', label: '规整空段落', type: 'bool' },
+      { key: 'replacements_path', label: '正文替换表路径', type: 'text', placeholder: '留空使用 replacements.json' },
+      { key: 'text_conversion', label: '简繁转换', type: 'select', options: [
+        { value: 'none', label: '不转换' },
+        { value: 's2t', label: '简体转繁体' },
+        { value: 't2s', label: '繁体转简体' }
+      ] },
+      { key: 'text_conversion_description', label: '简繁转换含简介', type: 'bool' },
+      { key: 'show_chapter_time', label: '显示章节发布时间', type: 'bool' },
+      { key: 'chapter_title_format', label: '章节标题模板', type: 'text' },
+      { key: 'bulk_name_template', label: '散装文件名模板', type: 'text', placeholder: '{index_padded}_{title}' },
+      { key: 'txt_split_mode', label: 'TXT 拆分方式', type: 'select', options: [
+        { value: 'single', label: '整本' },
+        { value: 'per-chapter', label: '按章（散装）' },
+        { value: 'per-volume', label: '按卷（无多卷时整本）' }
+      ] },
+      { key: 'epub_use_title_format', label: 'EPUB 使用标题模板', type: 'bool' },
+      { key: 'split_epub_by_volume', label: 'EPUB 按卷拆分导出', type: 'bool' },
+      { key: 'epub_embed_font_path', label: 'EPUB 内嵌字体路径', type: 'text' },
+      { key: 'epub_placeholder_cover', label: '无封面时生成占位封面', type: 'bool' },
+      { key: 'prefer_hd_cover', label: '优先高清封面（另存 cover_hd）', type: 'bool' },
+      { key: 'epub_theme', label: 'EPUB 主题', type: 'select', options: [
+        { value: 'default', label: '默认' },
+        { value: 'sepia', label: '护眼米色' },
+        { value: 'night', label: '夜间深色' },
+        { value: 'paper', label: '纸质衬线' }
+      ] },
+      { key: 'epub_highlight_dialogue', label: 'EPUB 对话分色', type: 'bool' },
       { key: 'preferred_book_name_field', label: '优先书名字段', type: 'select', options: [
         { value: 'book_name', label: '默认书名' },
         { value: 'original_book_name', label: '原始书名' },
@@ -525,11 +605,26 @@ const FULL_CONFIG_SCHEMA = [
     title: '网络与调度',
     fields: [
       { key: 'max_workers', label: '最大线程数', type: 'number', parse: 'int', min: '1' },
+      { key: 'batch_size', label: '章节批量大小', type: 'number', parse: 'int', min: '1', max: '25' },
       { key: 'request_timeout', label: '请求超时(s)', type: 'number', parse: 'int', min: '1' },
       { key: 'max_retries', label: '最大重试次数', type: 'number', parse: 'int', min: '0' },
       { key: 'min_connect_timeout', label: '最小连接超时(s)', type: 'number', parse: 'float', step: '0.1', min: '0' },
       { key: 'min_wait_time', label: '最小等待时间(ms)', type: 'number', parse: 'int', min: '0' },
       { key: 'max_wait_time', label: '最大等待时间(ms)', type: 'number', parse: 'int', min: '0' },
+      { key: 'wait_jitter_ratio', label: '等待随机抖动(%，0 关闭)', type: 'number', parse: 'int', min: '0', max: '100' },
+      { key: 'fast_switch_on_4xx', label: '4xx 立即切换 API', type: 'bool' },
+      { key: 'timeout_retry_strategy', label: '超时重试策略', type: 'select', options: [
+        { value: 'conservative', label: '保守（连接失败照常退避）' },
+        { value: 'balanced', label: '均衡（连接失败快速换源，读取超时放宽）' },
+        { value: 'aggressive', label: '激进（连接失败立即换源）' }
+      ] },
+      { key: 'max_bandwidth_kbps', label: '带宽上限(KB/s，0 不限)', type: 'number', parse: 'int', min: '0' },
+      { key: 'block_on_incomplete_catalog', label: '目录不完整时需确认', type: 'bool' },
+      { key: 'skip_title_patterns', label: '按标题排除章节（正则）', type: 'list', placeholder: '每行一个正则，如 上架感言|请假条' },
+      { key: 'detect_modified_chapters', label: '更新时检测被修改的章节', type: 'bool' },
+      { key: 'book_download_timeout_secs', label: '单本下载总超时(秒，0 不限)', type: 'number', parse: 'int', min: '0' },
+      { key: 'wait_for_network', label: '断网时等待恢复后续跑', type: 'bool' },
+      { key: 'chapter_length_alert_ratio', label: '短章告警阈值(% 平均字数，0 关闭)', type: 'number', parse: 'int', min: '0', max: '100' },
     ]
   },
   {
@@ -544,7 +639,21 @@ const FULL_CONFIG_SCHEMA = [
     fields: [
       { key: 'enable_segment_comments', label: '启用段评', type: 'bool' },
       { key: 'segment_comments_top_n', label: '每段评论数上限', type: 'number', parse: 'int', min: '1' },
+      { key: 'segment_comments_style', label: '段评呈现样式', type: 'select', options: [
+        { value: 'chapter_end', label: '章末汇总式' },
+        { value: 'inline_footnote', label: '行内脚注式' },
+        { value: 'after_paragraph', label: '段后块式' }
+      ] },
+      { key: 'segment_comments_export_mode', label: '段评输出方式', type: 'select', options: [
+        { value: 'embed', label: '嵌入正文' },
+        { value: 'separate', label: '单独导出（Markdown + JSON）' },
+        { value: 'both', label: '嵌入并导出' }
+      ] },
       { key: 'segment_comments_workers', label: '段评并发线程数', type: 'number', parse: 'int', min: '1' },
+      { key: 'segment_comments_hot_chapters_only', label: '只抓热门章节段评', type: 'bool' },
+      { key: 'segment_comments_hot_chapters', label: '热门章节数(N)', type: 'number', parse: 'int', min: '1' },
+      { key: 'segment_comments_refresh_hours', label: '段评刷新间隔(小时，0=不刷新)', type: 'number', parse: 'int', min: '0' },
+      { key: 'export_segment_notes_html', label: '导出段评旁注 HTML', type: 'bool' },
     ]
   },
   {
@@ -553,7 +662,8 @@ const FULL_CONFIG_SCHEMA = [
       { key: 'download_comment_images', label: '下载评论图片', type: 'bool' },
       { key: 'download_comment_avatars', label: '下载评论头像', type: 'bool' },
       { key: 'media_download_workers', label: '媒体下载线程数', type: 'number', parse: 'int', min: '1' },
-      { key: 'blocked_media_domains', label: '阻止的图片域名', type: 'list', placeholder: '每行一个域名' },
+      { key: 'blocked_media_domains', label: '阻止的图片域名', type: 'list', placeholder: '每行一个域名，可带路径前缀' },
+      { key: 'allowed_media_domains', label: '允许的图片域名（白名单，与阻止列表互斥）', type: 'list', placeholder: '每行一个域名，留空不限制' },
       { key: 'force_convert_images_to_jpeg', label: '强制转成 JPEG', type: 'bool' },
       { key: 'jpeg_retry_convert', label: '失败重试再转 JPEG', type: 'bool' },
       { key: 'jpeg_quality', label: 'JPEG 质量(0-100)', type: 'number', parse: 'int', min: '0', max: '100' },
@@ -683,7 +793,7 @@ function renderFullConfigForm(cfg) {
         input.value = (cfg[field.key] ?? '').toString();
       } else {
         input = document.createElement('input');
-        input.type = 'text';
+        input.type = field.type === 'password' ? 'password' : 'text';
         input.value = (cfg[field.key] ?? '').toString();
         if (field.placeholder) input.placeholder = field.placeholder;
       }
@@ -695,11 +805,37 @@ function renderFullConfigForm(cfg) {
       }
 
       row.appendChild(input);
+
+      const reset = document.createElement('button');
+      reset.type = 'button';
+      reset.className = 'sm';
+      reset.textContent = '默认';
+      reset.title = '恢复此项默认值';
+      reset.addEventListener('click', () => {
+        if (!confirm(`确定将「${field.label}」恢复默认值吗？`)) return;
+        resetConfig(field.key).catch(err => alert(err));
+      });
+      row.appendChild(reset);
       sec.appendChild(row);
     }
   }
 }
 
+// 恢复默认值并写回配置文件；不传 key 时全部恢复（保留关键路径与凭据）。
+async function resetConfig(key) {
+  const msg = document.getElementById('cfgFullMsg');
+  if (msg) msg.textContent = '恢复中…';
+  await j('/api/config/reset', {
+    method: 'POST',
+    headers: { 'content-type': 'application/json' },
+    body: JSON.stringify(key ? { field: key } : {})
+  });
+  await loadFullConfigPanel();
+  await refreshConfig();
+  await refreshRawConfig();
+  if (msg) msg.textContent = '已恢复默认';
+}
+
 async function loadFullConfigPanel() {
   const msg = document.getElementById('cfgFullMsg');
   if (msg) msg.textContent = '加载中…';
@@ -847,6 +983,86 @@ async function refreshLibrary(start = true) {
   }
 }
 
+// ── Books ──────────────────────────────────────────────────────────
+
+async function refreshBooks() {
+  const hint = document.getElementById('booksHint');
+  const tbody = document.getElementById('booksBody');
+  if (!tbody) return;
+
+  const data = await j('/api/books');
+  const kwInput = document.getElementById('booksKeyword');
+  const kw = kwInput ? kwInput.value.trim().toLowerCase() : '';
+  const items = (data.items || []).filter(it => !kw
+    || (it.book_name || '').toLowerCase().includes(kw)
+    || (it.author || '').toLowerCase().includes(kw)
+    || (it.book_id || '').includes(kw));
+  if (hint) hint.textContent = `共 ${items.length} 本`;
+
+  tbody.innerHTML = '';
+  for (const it of items) {
+    const bookId = it.book_id || '';
+    const cover = it.has_cover
+      ? `<img class="bookCover" loading="lazy" src="/api/books/${encodeURIComponent(bookId)}/cover" alt="" />`
+      : '';
+    const outputs = (it.outputs || []).map(o => {
+      const rel = encodePathSegments(o.rel_path || '');
+      const q = Number(o.root || 0) > 0 ? `?root=${Number(o.root)}` : '';
+      return o.kind === 'dir'
+        ? `<a href="/download-zip/${rel}${q}">${esc(o.format)}（散装）</a>`
+        : `<a href="/download/${rel}${q}" title="${esc(fmtBytes(o.size))}">${esc(o.format)}</a>`;
+    }).join(' ');
+    const failed = Number(it.chapter_failed || 0);
+    const chapters = `${Number(it.chapter_ok || 0)}/${Number(it.chapter_total || 0)}`
+      + (failed > 0 ? ` <span class="badge danger">失败 ${failed}</span>` : '');
+    const tr = document.createElement('tr');
+    tr.innerHTML = `
+      <td>${cover}</td>
+      <td>${esc(it.book_name || '')} <code>${esc(bookId)}</code>${it.is_ignored ? ' <span class="badge">忽略更新</span>' : ''}</td>
+      <td>${esc(it.author || '')}</td>
+      <td>${chapters}</td>
+      <td>${outputs || '<span class="k">未生成</span>'}</td>
+      <td>
+        <button data-bookid="${esc(bookId)}" class="toggleBookToc sm">目录</button>
+        <button data-bookid="${esc(bookId)}" class="startDownload sm primary">更新</button>
+        <button data-bookid="${esc(bookId)}" data-title="${esc(it.book_name || '')}" class="deleteBook sm warning">删除</button>
+      </td>
+    `;
+    tbody.appendChild(tr);
+  }
+
+  if (items.length === 0) {
+    tbody.innerHTML = `<tr class="empty-row"><td colspan="6">${kw ? '没有匹配的书' : '书库为空，先下载一本书吧'}</td></tr>`;
+  }
+}
+
+async function toggleBookToc(btn, bookId) {
+  const row = btn.closest('tr');
+  if (!row) return;
+  const next = row.nextElementSibling;
+  if (next && next.classList.contains('bookToc')) {
+    next.remove();
+    return;
+  }
+  const data = await j(`/api/books/${encodeURIComponent(bookId)}/toc`);
+  const chapters = data.chapters || [];
+  const list = chapters.map(ch => `<li class="${ch.ok ? '' : 'failed'}">${esc(ch.title || ch.id)}</li>`).join('');
+  const tr = document.createElement('tr');
+  tr.className = 'bookToc';
+  tr.innerHTML = `<td colspan="6">${list ? `<ol>${list}</ol>` : '<span class="k">暂无章节记录</span>'}</td>`;
+  row.after(tr);
+}
+
+async function deleteBook(bookId, title) {
+  if (!confirm(`确认删除《${title || bookId}》的缓存与成书文件吗？此操作不可恢复。`)) return;
+  const data = await j(`/api/books/${encodeURIComponent(bookId)}`, { method: 'DELETE' });
+  const skipped = (data && data.skipped) || [];
+  if (skipped.length) {
+    alert(`存在同名书籍，以下无法确认归属的成书文件未删除：\n${skipped.join('\n')}`);
+  }
+  await refreshBooks();
+}
+
 // ── Search ─────────────────────────────────────────────────────────
 
 async function doSearch(q) {
@@ -957,10 +1173,22 @@ async function openPreview(bookId) {
         span.textContent = p;
         stats.appendChild(span);
       });
+      if (preview.catalog_warning) {
+        const warn = document.createElement('span');
+        warn.className = 'error';
+        warn.textContent = `⚠ ${preview.catalog_warning}`;
+        stats.appendChild(warn);
+      }
     }
 
     if (desc) desc.textContent = preview.description || '暂无简介';
 
+    if (rangeHint && preview.volumes && preview.volumes.length > 0) {
+      rangeHint.textContent = '分卷：' + preview.volumes
+        .map((v, i) => `vol:${i + 1} ${v.title} (${v.start}-${v.end})`)
+        .join('；');
+    }
+
     if (tags) {
       if (preview.tags && preview.tags.length > 0) {
         tags.innerHTML = '';
@@ -1016,7 +1244,7 @@ async function openPreview(bookId) {
     }
 
     if (rangeHint && preview.chapter_count) {
-      rangeHint.textContent = `例如: 1-10 下载第1到第10章，1-${preview.chapter_count} 下载全部`;
+      rangeHint.textContent = `例如: 1-10 下载第1到第10章，last:20 下载最新20章，1-${preview.chapter_count} 下载全部`;
     }
   } catch (err) {
     if (loading) loading.textContent = `加载失败: ${err}`;
@@ -1032,6 +1260,14 @@ async function confirmPreview() {
   const rangeHint = document.getElementById('previewRangeHint');
   const rangeText = rangeInput ? rangeInput.value.trim() : '';
 
+  if (
+    currentPreviewData.catalog_warning &&
+    currentPreviewData.catalog_confirm_required &&
+    !confirm(`${currentPreviewData.catalog_warning}，仍要下载吗？`)
+  ) {
+    return;
+  }
+
   let rangeStart = null;
   let rangeEnd = null;
 
@@ -1041,8 +1277,29 @@ async function confirmPreview() {
       if (rangeHint) { rangeHint.textContent = '章节数未知，无法使用范围下载'; rangeHint.classList.add('error'); }
       return;
     }
+    const last = rangeText.match(/^last\s*[:：]\s*(\d+)$/i);
+    const vol = rangeText.match(/^vol\s*[:：]\s*(\d+)$/i);
     const parts = rangeText.split('-').map(p => p.trim());
-    if (parts.length === 2) {
+    if (vol) {
+      const volumes = currentPreviewData.volumes || [];
+      const v = volumes[parseInt(vol[1], 10) - 1];
+      if (!v) {
+        const msg = volumes.length ? `卷号超出范围 (1-${volumes.length})` : '该书没有分卷信息';
+        if (rangeHint) { rangeHint.textContent = msg; rangeHint.classList.add('error'); }
+        return;
+      }
+      rangeStart = v.start;
+      rangeEnd = v.end;
+    } else if (last) {
+      const n = parseInt(last[1], 10);
+      if (n < 1) {
+        if (rangeHint) { rangeHint.textContent = 'last:N 中的 N 需大于 0'; rangeHint.classList.add('error'); }
+        return;
+      }
+      // N 超过总章数时即全部
+      rangeStart = Math.max(1, total - n + 1);
+      rangeEnd = total;
+    } else if (parts.length === 2) {
       const start = parts[0] === '' ? 1 : parseInt(parts[0], 10);
       const end = parts[1] === '' ? total : parseInt(parts[1], 10);
       if (isNaN(start) || isNaN(end) || start < 1 || end < 1 || start > end || end > total) {
@@ -1052,7 +1309,7 @@ async function confirmPreview() {
       rangeStart = start;
       rangeEnd = end;
     } else {
-      if (rangeHint) { rangeHint.textContent = '格式应为 start-end，例如 1-10'; rangeHint.classList.add('error'); }
+      if (rangeHint) { rangeHint.textContent = '格式应为 start-end、last:N 或 vol:N，例如 1-10、last:20、vol:2'; rangeHint.classList.add('error'); }
       return;
     }
   }
@@ -1161,9 +1418,14 @@ async function refreshJobs() {
         btnHtml = `<button data-jobid="${esc(it.id)}" class="cancelJob sm">取消</button>`;
     }
 
+    // 完成后显示成书目录的完整路径，便于复制到文件管理器
+    const pathHtml = (vState === 'done' || vState === 'partial') && it.output_dir
+      ? `<div class="job-path"><code>${esc(it.output_dir)}</code> <button data-path="${esc(it.output_dir)}" class="copyPath sm">复制路径</button></div>`
+      : '';
+
     tr.innerHTML = `
       <td><span class="badge">${esc(it.id)}</span></td>
-      <td>${esc(title)}</td>
+      <td>${esc(title)}${pathHtml}</td>
       <td>${stateHtml}</td>
       <td>${esc(progressText)}</td>
       <td>${btnHtml}</td>
@@ -1173,6 +1435,63 @@ async function refreshJobs() {
   if ((data.items || []).length === 0) {
     tbody.innerHTML = '<tr class="empty-row"><td colspan="5">暂无任务（已完成超过 2 小时会自动隐藏）</td></tr>';
   }
+  syncLogJobOptions(data.items || []);
+}
+
+// ── Live logs ──────────────────────────────────────────────────────
+
+const LOG_MAX_LINES = 500;
+let logSource = null;
+
+function logStreamUrl() {
+  const qs = new URLSearchParams();
+  const target = document.getElementById('logTarget')?.value || '';
+  const job = document.getElementById('logJob')?.value || '';
+  if (target) qs.set('target', target);
+  if (job) qs.set('job', job);
+  const q = qs.toString();
+  return q ? `/logs/stream?${q}` : '/logs/stream';
+}
+
+// 切换过滤条件时重新订阅；断线由 EventSource 自动重连并带上 Last-Event-ID 续传。
+function connectLogStream() {
+  const box = document.getElementById('logStream');
+  const hint = document.getElementById('logHint');
+  if (!box || typeof EventSource === 'undefined') return;
+  if (logSource) logSource.close();
+  box.textContent = '';
+  logSource = new EventSource(logStreamUrl());
+  logSource.onopen = () => { if (hint) hint.textContent = '已连接'; };
+  logSource.onerror = () => { if (hint) hint.textContent = '连接中断，正在重连…'; };
+  logSource.onmessage = (ev) => {
+    let line;
+    try { line = JSON.parse(ev.data); } catch (_) { return; }
+    const row = document.createElement('div');
+    const level = (line.level || '').toLowerCase();
+    if (level === 'warn' || level === 'error' || level === 'debug') row.className = `log-${level}`;
+    row.textContent = line.text || '';
+    box.appendChild(row);
+    while (box.childElementCount > LOG_MAX_LINES) box.firstElementChild.remove();
+    if (document.getElementById('logAutoScroll')?.checked) box.scrollTop = box.scrollHeight;
+  };
+}
+
+function syncLogJobOptions(items) {
+  const sel = document.getElementById('logJob');
+  if (!sel) return;
+  const current = sel.value;
+  const ids = new Set();
+  sel.innerHTML = '<option value="">全部任务</option>';
+  for (const it of items) {
+    const opt = document.createElement('option');
+    opt.value = String(it.id);
+    opt.textContent = `#${it.id} ${it.title || it.book_id || ''}`;
+    sel.appendChild(opt);
+    ids.add(String(it.id));
+  }
+  sel.value = ids.has(current) ? current : '';
+  // 所选任务已被清理时回到全部任务
+  if (current && !ids.has(current)) connectLogStream();
 }
 
 // ── History ───────────────────────────────────────────────────────
@@ -1205,7 +1524,7 @@ async function refreshHistory() {
       <td>${esc(it.book_name || '')}</td>
       <td>${esc(it.author || '')}</td>
       <td><code>${esc(it.book_id || '')}</code></td>
-      <td>${esc(it.progress || '')}</td>
+      <td>${esc(it.progress || '')}${it.range ? ` · 范围 ${esc(it.range)}` : ''}</td>
       <td>${badge}</td>
     `;
     body.appendChild(tr);
@@ -1215,6 +1534,41 @@ async function refreshHistory() {
     body.innerHTML = '<tr class="empty-row"><td colspan="6">暂无历史记录</td></tr>';
   }
   if (hint) hint.textContent = `共 ${items.length} 条`;
+  renderDownloadStats(data.stats);
+}
+
+function formatElapsed(secs) {
+  const s = Number(secs) || 0;
+  const h = Math.floor(s / 3600);
+  const m = Math.floor((s % 3600) / 60);
+  if (h > 0) return `${h} 小时 ${m} 分`;
+  if (m > 0) return `${m} 分 ${s % 60} 秒`;
+  return `${s} 秒`;
+}
+
+function renderDownloadStats(stats) {
+  const box = document.getElementById('historyStats');
+  if (!box) return;
+  if (!stats || !stats.last_updated) {
+    box.textContent = '暂无下载统计（完成一次下载后生成）';
+    return;
+  }
+  const lines = [
+    `累计：${stats.books} 本 / ${stats.chapters} 章 / ${stats.words} 字，耗时 ${formatElapsed(stats.elapsed_secs)}（自 ${stats.first_recorded}）`,
+  ];
+  const days = Object.entries(stats.daily || {}).slice(-7);
+  if (days.length) {
+    lines.push('近期趋势：' + days.map(([d, t]) => `${d} ${t.chapters} 章`).join('，'));
+  }
+  const eps = Object.entries(stats.endpoints || {})
+    .sort((a, b) => (b[1].requests || 0) - (a[1].requests || 0));
+  if (eps.length) {
+    lines.push('Endpoint 历史成功率：' + eps.map(([ep, t]) => {
+      const rate = t.requests > 0 ? `${Math.floor(t.success * 100 / t.requests)}%` : '-';
+      return `${ep} ${rate}`;
+    }).join('，'));
+  }
+  box.innerHTML = lines.map(esc).join('<br>');
 }
 
 // ── Updates ────────────────────────────────────────────────────────
@@ -1508,6 +1862,12 @@ function wire() {
           const out = document.getElementById('searchResults');
           if (out) out.innerHTML = '<tr class="empty-row"><td colspan="4">已加入任务队列，可在"任务"页查看进度</td></tr>';
         } catch (err) {
+          const keyword = /^https?:/i.test(bookId) ? shareSearchKeyword(q) : '';
+          if (keyword) {
+            if (hint) hint.textContent = `短链接解析失败，改为搜索：${keyword}`;
+            try { await doSearch(keyword); } catch (err2) { alert(err2); }
+            return;
+          }
           if (hint) hint.textContent = '创建任务失败';
           alert(err);
         }
@@ -1556,6 +1916,19 @@ function wire() {
     }
   });
 
+  const booksRefresh = document.getElementById('booksRefresh');
+  if (booksRefresh) booksRefresh.addEventListener('click', async () => {
+    try { await refreshBooks(); } catch (err) { alert(err); }
+  });
+
+  const booksKeyword = document.getElementById('booksKeyword');
+  if (booksKeyword) booksKeyword.addEventListener('keydown', async (e) => {
+    if (e.key === 'Enter') {
+      e.preventDefault();
+      try { await refreshBooks(); } catch (err) { alert(err); }
+    }
+  });
+
   const historyRefresh = document.getElementById('historyRefresh');
   if (historyRefresh) historyRefresh.addEventListener('click', async () => {
     try { await refreshHistory(); } catch (err) { alert(err); }
@@ -1600,6 +1973,19 @@ function wire() {
     }
   });
 
+  const cfgFullReset = document.getElementById('cfgFullReset');
+  if (cfgFullReset) cfgFullReset.addEventListener('click', async () => {
+    if (!confirm('确定将全部配置恢复默认吗？保存路径等关键路径与凭据会保留。')) return;
+    if (!confirm('此操作会覆盖 config.yml，再次确认？')) return;
+    try {
+      await resetConfig();
+    } catch (err) {
+      const msg = document.getElementById('cfgFullMsg');
+      if (msg) msg.textContent = '恢复失败';
+      alert(err);
+    }
+  });
+
   // -- YAML Config --
   const cfgRawReload = document.getElementById('cfgRawReload');
   if (cfgRawReload) cfgRawReload.addEventListener('click', async () => {
@@ -1654,6 +2040,15 @@ function wire() {
         await refreshJobs();
       } catch (err) { alert(err); }
     }
+    if (t.classList.contains('copyPath')) {
+      const path = t.getAttribute('data-path') || '';
+      try {
+        await navigator.clipboard.writeText(path);
+        t.textContent = '已复制';
+      } catch (_) {
+        window.prompt('复制以下路径：', path);
+      }
+    }
     if (t.classList.contains('configJob')) {
       const jobId = t.getAttribute('data-jobid');
       const kind = t.getAttribute('data-kind') || '';
@@ -1671,6 +2066,15 @@ function wire() {
       await refreshLibrary();
       highlightLibraryItem(title);
     }
+    if (t.classList.contains('toggleBookToc')) {
+      const bookId = t.getAttribute('data-bookid');
+      try { await toggleBookToc(t, bookId); } catch (err) { alert(err); }
+    }
+    if (t.classList.contains('deleteBook')) {
+      const bookId = t.getAttribute('data-bookid');
+      const title = t.getAttribute('data-title') || '';
+      try { await deleteBook(bookId, title); } catch (err) { alert(err); }
+    }
     if (t.classList.contains('openDir')) {
       const p = (t.getAttribute('data-path') || '').toString();
       libraryPath = p;
@@ -1745,6 +2149,16 @@ function wire() {
   if (iidWarningClose) iidWarningClose.addEventListener('click', () => showIidWarningModal(false));
   const iidWarningOk = document.getElementById('iidWarningOk');
   if (iidWarningOk) iidWarningOk.addEventListener('click', () => showIidWarningModal(false));
+
+  const logTarget = document.getElementById('logTarget');
+  if (logTarget) logTarget.addEventListener('change', connectLogStream);
+  const logJob = document.getElementById('logJob');
+  if (logJob) logJob.addEventListener('change', connectLogStream);
+  const logClear = document.getElementById('logClear');
+  if (logClear) logClear.addEventListener('click', () => {
+    const box = document.getElementById('logStream');
+    if (box) box.textContent = '';
+  });
 }
 
 function highlightLibraryItem(title) {
@@ -1771,11 +2185,13 @@ async function boot() {
   await Promise.allSettled([
     refreshJobs(),
     refreshHistory(),
+    refreshBooks(),
     refreshLibrary(false),
   ]);
   refreshUpdates().catch(() => {});
   if (!isDockerBuild) refreshAppUpdate(false).catch(() => {});
   pollSelfUpdateStatus().catch(() => {});
+  connectLogStream();
   setInterval(() => refreshJobs().catch(() => {}), 1500);
   setInterval(() => refreshStatus().catch(() => {}), 5000);
   if (!isDockerBuild) {